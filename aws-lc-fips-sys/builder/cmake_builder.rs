@@ -129,6 +129,10 @@ impl CmakeBuilder {
         }
         cmake_cfg.define("FIPS", "1");
 
+        if cfg!(feature = "cpu-jitter-entropy") {
+            cmake_cfg.define("ENABLE_FIPS_ENTROPY_CPU_JITTER", "ON");
+        }
+
         if cfg!(feature = "asan") {
             env::set_var("CC", "clang");
             env::set_var("CXX", "clang++");
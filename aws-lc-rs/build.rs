@@ -8,6 +8,25 @@ fn main() {
         "`fips` and `non-fips` are mutually exclusive crate features."
     );
 
+    // These features only expose algorithms that are not approved for use in FIPS mode.
+    let non_approved_features = [
+        ("legacy-digests", cfg!(feature = "legacy-digests")),
+        ("legacy-dsa", cfg!(feature = "legacy-dsa")),
+        ("legacy-pem", cfg!(feature = "legacy-pem")),
+        (
+            "legacy-pkcs1-encryption",
+            cfg!(feature = "legacy-pkcs1-encryption"),
+        ),
+    ];
+    if cfg!(feature = "fips") {
+        for (name, enabled) in non_approved_features {
+            assert!(
+                !enabled,
+                "`fips` and `{name}` are mutually exclusive crate features: `{name}` only provides algorithms that are not FIPS-approved."
+            );
+        }
+    }
+
     // This appears asymmetric, but it reflects the `cfg` statements in lib.rs that
     // require `aws-lc-sys` to be present when "fips" is not enabled.
     // if `fips` is enabled, then use that
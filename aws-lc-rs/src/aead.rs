@@ -120,10 +120,13 @@ use core::{fmt::Debug, ops::RangeFrom};
 
 mod aead_ctx;
 mod aes_gcm;
+#[cfg(not(feature = "strict-fips"))]
 mod chacha;
+#[cfg(not(feature = "strict-fips"))]
 pub mod chacha20_poly1305_openssh;
 mod nonce;
 pub mod nonce_sequence;
+#[cfg(not(feature = "strict-fips"))]
 mod poly1305;
 pub mod quic;
 mod rand_nonce;
@@ -132,13 +135,15 @@ mod unbound_key;
 
 pub use self::{
     aes_gcm::{AES_128_GCM, AES_128_GCM_SIV, AES_256_GCM, AES_256_GCM_SIV},
-    chacha::CHACHA20_POLY1305,
     nonce::{Nonce, NONCE_LEN},
     rand_nonce::RandomizedNonceKey,
     tls::{TlsProtocolId, TlsRecordOpeningKey, TlsRecordSealingKey},
     unbound_key::UnboundKey,
 };
 
+#[cfg(not(feature = "strict-fips"))]
+pub use self::chacha::CHACHA20_POLY1305;
+
 /// A sequences of unique nonces.
 ///
 /// A given `NonceSequence` must never return the same `Nonce` twice from
@@ -763,6 +768,7 @@ enum AlgorithmID {
     AES_256_GCM,
     AES_128_GCM_SIV,
     AES_256_GCM_SIV,
+    #[cfg(not(feature = "strict-fips"))]
     CHACHA20_POLY1305,
 }
 
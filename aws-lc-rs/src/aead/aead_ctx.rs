@@ -4,6 +4,7 @@
 use core::mem::size_of;
 use core::ptr::null_mut;
 
+#[cfg(not(feature = "strict-fips"))]
 use crate::cipher::chacha;
 
 use crate::cipher::aes::{AES_128_KEY_LEN, AES_256_KEY_LEN};
@@ -15,9 +16,12 @@ use aws_lc::{
     EVP_aead_aes_128_gcm_randnonce, EVP_aead_aes_128_gcm_siv, EVP_aead_aes_128_gcm_tls12,
     EVP_aead_aes_128_gcm_tls13, EVP_aead_aes_256_gcm, EVP_aead_aes_256_gcm_randnonce,
     EVP_aead_aes_256_gcm_siv, EVP_aead_aes_256_gcm_tls12, EVP_aead_aes_256_gcm_tls13,
-    EVP_aead_chacha20_poly1305, OPENSSL_malloc, EVP_AEAD_CTX,
+    OPENSSL_malloc, EVP_AEAD_CTX,
 };
 
+#[cfg(not(feature = "strict-fips"))]
+use aws_lc::EVP_aead_chacha20_poly1305;
+
 pub(crate) enum AeadDirection {
     Open,
     Seal,
@@ -53,6 +57,7 @@ pub(crate) enum AeadCtx {
     AES_128_GCM_TLS13(LcPtr<EVP_AEAD_CTX>),
     AES_256_GCM_TLS13(LcPtr<EVP_AEAD_CTX>),
 
+    #[cfg(not(feature = "strict-fips"))]
     CHACHA20_POLY1305(LcPtr<EVP_AEAD_CTX>),
 }
 
@@ -174,6 +179,7 @@ impl AeadCtx {
         )?))
     }
 
+    #[cfg(not(feature = "strict-fips"))]
     pub(crate) fn chacha20(key_bytes: &[u8], tag_len: usize) -> Result<Self, Unspecified> {
         if chacha::KEY_LEN != key_bytes.len() {
             return Err(Unspecified);
@@ -266,8 +272,9 @@ impl AsRef<LcPtr<EVP_AEAD_CTX>> for AeadCtx {
             | AeadCtx::AES_128_GCM_TLS12(ctx)
             | AeadCtx::AES_256_GCM_TLS12(ctx)
             | AeadCtx::AES_128_GCM_TLS13(ctx)
-            | AeadCtx::AES_256_GCM_TLS13(ctx)
-            | AeadCtx::CHACHA20_POLY1305(ctx) => ctx,
+            | AeadCtx::AES_256_GCM_TLS13(ctx) => ctx,
+            #[cfg(not(feature = "strict-fips"))]
+            AeadCtx::CHACHA20_POLY1305(ctx) => ctx,
         }
     }
 }
@@ -9,6 +9,7 @@
 
 use crate::cipher::aes::encrypt_block_aes;
 use crate::cipher::block;
+#[cfg(not(feature = "strict-fips"))]
 use crate::cipher::chacha::encrypt_block_chacha20;
 use crate::cipher::key::SymmetricCipherKey;
 use crate::hkdf::KeyType;
@@ -107,6 +108,7 @@ derive_debug_via_id!(Algorithm);
 enum AlgorithmID {
     AES_128,
     AES_256,
+    #[cfg(not(feature = "strict-fips"))]
     CHACHA20,
 }
 
@@ -134,6 +136,7 @@ pub static AES_256: Algorithm = Algorithm {
 };
 
 /// `ChaCha20`.
+#[cfg(not(feature = "strict-fips"))]
 pub static CHACHA20: Algorithm = Algorithm {
     key_len: 32,
     init: SymmetricCipherKey::chacha20,
@@ -151,6 +154,7 @@ fn cipher_new_mask(
         SymmetricCipherKey::Aes128 { enc_key, .. } | SymmetricCipherKey::Aes256 { enc_key, .. } => {
             encrypt_block_aes(enc_key, block)
         }
+        #[cfg(not(feature = "strict-fips"))]
         SymmetricCipherKey::ChaCha20 { raw_key } => {
             let plaintext = block.as_ref();
             let counter_bytes: &[u8; 4] = plaintext[0..=3]
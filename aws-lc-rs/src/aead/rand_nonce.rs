@@ -35,9 +35,9 @@ impl RandomizedNonceKey {
                 algorithm.tag_len(),
                 algorithm.nonce_len(),
             ),
-            AlgorithmID::AES_128_GCM_SIV
-            | AlgorithmID::AES_256_GCM_SIV
-            | AlgorithmID::CHACHA20_POLY1305 => return Err(Unspecified),
+            AlgorithmID::AES_128_GCM_SIV | AlgorithmID::AES_256_GCM_SIV => return Err(Unspecified),
+            #[cfg(not(feature = "strict-fips"))]
+            AlgorithmID::CHACHA20_POLY1305 => return Err(Unspecified),
         }?;
         Ok(Self {
             key: UnboundKey::from(ctx),
@@ -147,8 +147,10 @@ impl Debug for RandomizedNonceKey {
 #[cfg(test)]
 mod tests {
     use super::{Aad, RandomizedNonceKey};
+    #[cfg(not(feature = "strict-fips"))]
+    use crate::aead::CHACHA20_POLY1305;
     use crate::{
-        aead::{AES_128_GCM, AES_256_GCM, CHACHA20_POLY1305},
+        aead::{AES_128_GCM, AES_256_GCM},
         test::from_hex,
     };
     use paste::paste;
@@ -221,5 +223,6 @@ mod tests {
 
     test_randnonce!(aes_128_gcm, &AES_128_GCM, TEST_128_BIT_KEY, &16, &12);
     test_randnonce!(aes_256_gcm, &AES_256_GCM, TEST_256_BIT_KEY, &16, &12);
+    #[cfg(not(feature = "strict-fips"))]
     test_randnonce!(chacha20_poly1305, &CHACHA20_POLY1305, TEST_256_BIT_KEY);
 }
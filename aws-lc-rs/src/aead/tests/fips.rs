@@ -3,6 +3,7 @@
 
 #![cfg(debug_assertions)]
 
+#[cfg(not(feature = "strict-fips"))]
 mod chacha20_poly1305_openssh;
 mod quic;
 
@@ -10,11 +11,14 @@ use crate::{
     aead::{
         nonce_sequence::Counter64Builder, Aad, BoundKey, Nonce, OpeningKey, RandomizedNonceKey,
         SealingKey, TlsProtocolId, TlsRecordOpeningKey, TlsRecordSealingKey, UnboundKey,
-        AES_128_GCM, AES_256_GCM, CHACHA20_POLY1305,
+        AES_128_GCM, AES_256_GCM,
     },
     fips::{assert_fips_status_indicator, FipsServiceStatus},
 };
 
+#[cfg(not(feature = "strict-fips"))]
+use crate::aead::CHACHA20_POLY1305;
+
 const TEST_KEY_128_BIT: [u8; 16] = [
     0x9f, 0xd9, 0x41, 0xc3, 0xa6, 0xfe, 0xb9, 0x26, 0x2a, 0x35, 0xa7, 0x44, 0xbb, 0xc0, 0x3a, 0x6a,
 ];
@@ -109,6 +113,7 @@ nonce_sequence_api!(
     FipsServiceStatus::NonApproved,
     FipsServiceStatus::Approved
 );
+#[cfg(not(feature = "strict-fips"))]
 nonce_sequence_api!(
     chacha20_poly1305_nonce_sequence_api,
     &CHACHA20_POLY1305,
@@ -180,6 +185,7 @@ randnonce_api!(
     &AES_256_GCM,
     &TEST_KEY_256_BIT[..]
 );
+#[cfg(not(feature = "strict-fips"))]
 randnonce_api!(
     chacha20_poly1305_randnonce_api,
     &CHACHA20_POLY1305,
@@ -250,6 +256,7 @@ tls_nonce_api!(
     TlsProtocolId::TLS13,
     &TEST_KEY_256_BIT
 );
+#[cfg(not(feature = "strict-fips"))]
 tls_nonce_api!(
     chaca20_poly1305_tls12_nonce_api,
     &CHACHA20_POLY1305,
@@ -257,6 +264,7 @@ tls_nonce_api!(
     &TEST_KEY_256_BIT,
     false
 );
+#[cfg(not(feature = "strict-fips"))]
 tls_nonce_api!(
     chaca20_poly1305_tls13_nonce_api,
     &CHACHA20_POLY1305,
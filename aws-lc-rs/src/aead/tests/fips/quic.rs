@@ -2,10 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
 use crate::{
-    aead::quic::{HeaderProtectionKey, AES_128, AES_256, CHACHA20},
+    aead::quic::{HeaderProtectionKey, AES_128, AES_256},
     fips::{assert_fips_status_indicator, FipsServiceStatus},
 };
 
+#[cfg(not(feature = "strict-fips"))]
+use crate::aead::quic::CHACHA20;
+
 use super::{TEST_KEY_128_BIT, TEST_KEY_256_BIT};
 
 macro_rules! quic_api {
@@ -31,6 +34,7 @@ quic_api!(
     &TEST_KEY_256_BIT,
     FipsServiceStatus::Approved
 );
+#[cfg(not(feature = "strict-fips"))]
 quic_api!(
     chacha20,
     &CHACHA20,
@@ -73,12 +73,9 @@ impl TlsRecordSealingKey {
                 algorithm.tag_len(),
                 aead_ctx::AeadDirection::Seal,
             ),
-            (
-                AlgorithmID::AES_128_GCM_SIV
-                | AlgorithmID::AES_256_GCM_SIV
-                | AlgorithmID::CHACHA20_POLY1305,
-                _,
-            ) => Err(Unspecified),
+            (AlgorithmID::AES_128_GCM_SIV | AlgorithmID::AES_256_GCM_SIV, _) => Err(Unspecified),
+            #[cfg(not(feature = "strict-fips"))]
+            (AlgorithmID::CHACHA20_POLY1305, _) => Err(Unspecified),
         }?;
         Ok(Self {
             key: UnboundKey::from(ctx),
@@ -218,12 +215,9 @@ impl TlsRecordOpeningKey {
                 algorithm.tag_len(),
                 aead_ctx::AeadDirection::Open,
             ),
-            (
-                AlgorithmID::AES_128_GCM_SIV
-                | AlgorithmID::AES_256_GCM_SIV
-                | AlgorithmID::CHACHA20_POLY1305,
-                _,
-            ) => Err(Unspecified),
+            (AlgorithmID::AES_128_GCM_SIV | AlgorithmID::AES_256_GCM_SIV, _) => Err(Unspecified),
+            #[cfg(not(feature = "strict-fips"))]
+            (AlgorithmID::CHACHA20_POLY1305, _) => Err(Unspecified),
         }?;
         Ok(Self {
             key: UnboundKey::from(ctx),
@@ -296,9 +290,11 @@ impl Debug for TlsRecordOpeningKey {
 #[cfg(test)]
 mod tests {
     use super::{TlsProtocolId, TlsRecordOpeningKey, TlsRecordSealingKey};
+    #[cfg(not(feature = "strict-fips"))]
+    use crate::aead::CHACHA20_POLY1305;
     use crate::{
         aead::Aad,
-        aead::{Nonce, AES_128_GCM, AES_256_GCM, CHACHA20_POLY1305},
+        aead::{Nonce, AES_128_GCM, AES_256_GCM},
         test::from_hex,
     };
     use paste::paste;
@@ -442,12 +438,14 @@ mod tests {
         &16,
         &12
     );
+    #[cfg(not(feature = "strict-fips"))]
     test_tls_aead!(
         chacha20_poly1305_tls12,
         &CHACHA20_POLY1305,
         TlsProtocolId::TLS12,
         TEST_256_BIT_KEY
     );
+    #[cfg(not(feature = "strict-fips"))]
     test_tls_aead!(
         chacha20_poly1305_tls13,
         &CHACHA20_POLY1305,
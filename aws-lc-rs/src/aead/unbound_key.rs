@@ -1,8 +1,10 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
+#[cfg(not(feature = "strict-fips"))]
+use super::CHACHA20_POLY1305;
 use super::{aead_ctx::AeadCtx, Algorithm, Nonce, MAX_KEY_LEN, MAX_TAG_LEN, NONCE_LEN};
-use super::{Tag, AES_128_GCM, AES_128_GCM_SIV, AES_256_GCM, AES_256_GCM_SIV, CHACHA20_POLY1305};
+use super::{Tag, AES_128_GCM, AES_128_GCM_SIV, AES_256_GCM, AES_256_GCM_SIV};
 use crate::iv::FixedLength;
 use crate::{error::Unspecified, fips::indicator_check, hkdf};
 use aws_lc::{
@@ -485,6 +487,7 @@ impl From<AeadCtx> for UnboundKey {
             | AeadCtx::AES_256_GCM_TLS12(_)
             | AeadCtx::AES_256_GCM_TLS13(_) => &AES_256_GCM,
             AeadCtx::AES_256_GCM_SIV(_) => &AES_256_GCM_SIV,
+            #[cfg(not(feature = "strict-fips"))]
             AeadCtx::CHACHA20_POLY1305(_) => &CHACHA20_POLY1305,
         };
         Self {
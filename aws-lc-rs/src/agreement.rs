@@ -49,31 +49,75 @@
 //!
 //! # Ok::<(), aws_lc_rs::error::Unspecified>(())
 //! ```
+//!
+//! Alternatively, `EphemeralPrivateKey::agree` and `PrivateKey::agree` return the key material
+//! as an opaque `SharedSecret`, which can only be used through HKDF:
+//!
+//! ```
+//! use aws_lc_rs::{agreement, hkdf, rand};
+//!
+//! let rng = rand::SystemRandom::new();
+//! let my_private_key = agreement::EphemeralPrivateKey::generate(&agreement::X25519, &rng)?;
+//! let peer_public_key = agreement::EphemeralPrivateKey::generate(&agreement::X25519, &rng)?
+//!     .compute_public_key()?;
+//!
+//! let shared_secret = my_private_key.agree(&agreement::UnparsedPublicKey::new(
+//!     &agreement::X25519,
+//!     peer_public_key,
+//! ))?;
+//!
+//! let mut session_key = [0u8; 32];
+//! shared_secret.derive(
+//!     hkdf::HKDF_SHA256,
+//!     b"salt",
+//!     &[b"session key"],
+//!     &mut session_key,
+//! )?;
+//!
+//! # Ok::<(), aws_lc_rs::error::Unspecified>(())
+//! ```
 mod ephemeral;
 
 pub use ephemeral::{agree_ephemeral, EphemeralPrivateKey};
 
+use crate::cbb::LcCBB;
 use crate::ec::{
     ec_group_from_nid, ec_point_from_bytes, evp_key_generate, evp_pkey_from_public_point,
 };
 use crate::error::{KeyRejected, Unspecified};
 use crate::fips::indicator_check;
 use crate::ptr::{ConstPointer, LcPtr, Pointer};
-use crate::{ec, hex};
+use crate::{cbs, ec, hex, hkdf};
+#[cfg(not(feature = "fips"))]
+use aws_lc::{
+    BN_bn2bin_padded, DH_compute_key_padded, DH_generate_key, DH_get0_pub_key, DH_new_by_nid,
+    NID_ffdhe2048, NID_ffdhe3072, NID_ffdhe4096, DH,
+};
+use aws_lc::{
+    EVP_PKEY_CTX_new, EVP_PKEY_derive, EVP_PKEY_derive_init, EVP_PKEY_derive_set_peer,
+    EVP_PKEY_get0_EC_KEY, EVP_marshal_public_key, EVP_parse_public_key, NID_X9_62_prime256v1,
+    NID_secp384r1, NID_secp521r1, BIGNUM, EVP_PKEY, EVP_PKEY_EC,
+};
+#[cfg(not(feature = "strict-fips"))]
 use aws_lc::{
-    EVP_PKEY_CTX_new, EVP_PKEY_CTX_new_id, EVP_PKEY_derive, EVP_PKEY_derive_init,
-    EVP_PKEY_derive_set_peer, EVP_PKEY_get0_EC_KEY, EVP_PKEY_get_raw_private_key,
-    EVP_PKEY_get_raw_public_key, EVP_PKEY_keygen, EVP_PKEY_keygen_init,
-    EVP_PKEY_new_raw_private_key, EVP_PKEY_new_raw_public_key, NID_X9_62_prime256v1, NID_secp384r1,
-    NID_secp521r1, BIGNUM, EVP_PKEY, EVP_PKEY_X25519, NID_X25519,
+    EVP_PKEY_CTX_new_id, EVP_PKEY_get_raw_private_key, EVP_PKEY_get_raw_public_key,
+    EVP_PKEY_keygen, EVP_PKEY_keygen_init, EVP_PKEY_new_raw_private_key,
+    EVP_PKEY_new_raw_public_key, EVP_PKEY_X25519, NID_X25519,
 };
+#[cfg(not(feature = "fips"))]
+use mirai_annotations::verify_unreachable;
 
+#[cfg(not(feature = "strict-fips"))]
+use crate::encoding::Curve25519SeedBin;
 use crate::encoding::{
-    AsBigEndian, AsDer, Curve25519SeedBin, EcPrivateKeyBin, EcPrivateKeyRfc5915Der,
+    AsBigEndian, AsDer, EcPrivateKeyBin, EcPrivateKeyRfc5915Der, EcPublicKeyCompressedBin,
+    PublicKeyX509Der,
 };
+use crate::pkcs8::{Document, Version};
 use core::fmt;
 use core::fmt::{Debug, Formatter};
 use core::ptr::null_mut;
+use zeroize::Zeroize;
 
 #[allow(non_camel_case_types)]
 #[derive(PartialEq, Eq)]
@@ -81,7 +125,14 @@ enum AlgorithmID {
     ECDH_P256,
     ECDH_P384,
     ECDH_P521,
+    #[cfg(not(feature = "strict-fips"))]
     X25519,
+    #[cfg(not(feature = "fips"))]
+    FFDHE2048,
+    #[cfg(not(feature = "fips"))]
+    FFDHE3072,
+    #[cfg(not(feature = "fips"))]
+    FFDHE4096,
 }
 
 impl AlgorithmID {
@@ -91,7 +142,14 @@ impl AlgorithmID {
             AlgorithmID::ECDH_P256 => NID_X9_62_prime256v1,
             AlgorithmID::ECDH_P384 => NID_secp384r1,
             AlgorithmID::ECDH_P521 => NID_secp521r1,
+            #[cfg(not(feature = "strict-fips"))]
             AlgorithmID::X25519 => NID_X25519,
+            #[cfg(not(feature = "fips"))]
+            AlgorithmID::FFDHE2048 => NID_ffdhe2048,
+            #[cfg(not(feature = "fips"))]
+            AlgorithmID::FFDHE3072 => NID_ffdhe3072,
+            #[cfg(not(feature = "fips"))]
+            AlgorithmID::FFDHE4096 => NID_ffdhe4096,
         }
     }
 
@@ -101,16 +159,55 @@ impl AlgorithmID {
             AlgorithmID::ECDH_P256 => 65,
             AlgorithmID::ECDH_P384 => 97,
             AlgorithmID::ECDH_P521 => 133,
+            #[cfg(not(feature = "strict-fips"))]
             AlgorithmID::X25519 => 32,
+            #[cfg(not(feature = "fips"))]
+            AlgorithmID::FFDHE2048 => 256,
+            #[cfg(not(feature = "fips"))]
+            AlgorithmID::FFDHE3072 => 384,
+            #[cfg(not(feature = "fips"))]
+            AlgorithmID::FFDHE4096 => 512,
         }
     }
 
     #[inline]
     const fn private_key_len(&self) -> usize {
         match self {
-            AlgorithmID::ECDH_P256 | AlgorithmID::X25519 => 32,
+            AlgorithmID::ECDH_P256 => 32,
+            #[cfg(not(feature = "strict-fips"))]
+            AlgorithmID::X25519 => 32,
             AlgorithmID::ECDH_P384 => 48,
             AlgorithmID::ECDH_P521 => 66,
+            #[cfg(not(feature = "fips"))]
+            AlgorithmID::FFDHE2048 | AlgorithmID::FFDHE3072 | AlgorithmID::FFDHE4096 => {
+                self.pub_key_len()
+            }
+        }
+    }
+
+    // The length of a SEC1 compressed point, for the algorithms that have one.
+    #[inline]
+    #[cfg_attr(feature = "strict-fips", allow(clippy::unnecessary_wraps))]
+    const fn compressed_pub_key_len(&self) -> Option<usize> {
+        match self {
+            AlgorithmID::ECDH_P256 | AlgorithmID::ECDH_P384 | AlgorithmID::ECDH_P521 => {
+                Some(1 + self.private_key_len())
+            }
+            #[cfg(not(feature = "strict-fips"))]
+            AlgorithmID::X25519 => None,
+            #[cfg(not(feature = "fips"))]
+            AlgorithmID::FFDHE2048 | AlgorithmID::FFDHE3072 | AlgorithmID::FFDHE4096 => None,
+        }
+    }
+
+    #[inline]
+    const fn is_ffdhe(&self) -> bool {
+        match self {
+            AlgorithmID::ECDH_P256 | AlgorithmID::ECDH_P384 | AlgorithmID::ECDH_P521 => false,
+            #[cfg(not(feature = "strict-fips"))]
+            AlgorithmID::X25519 => false,
+            #[cfg(not(feature = "fips"))]
+            AlgorithmID::FFDHE2048 | AlgorithmID::FFDHE3072 | AlgorithmID::FFDHE4096 => true,
         }
     }
 }
@@ -121,7 +218,14 @@ impl Debug for AlgorithmID {
             AlgorithmID::ECDH_P256 => "curve: P256",
             AlgorithmID::ECDH_P384 => "curve: P384",
             AlgorithmID::ECDH_P521 => "curve: P521",
+            #[cfg(not(feature = "strict-fips"))]
             AlgorithmID::X25519 => "curve: Curve25519",
+            #[cfg(not(feature = "fips"))]
+            AlgorithmID::FFDHE2048 => "group: ffdhe2048",
+            #[cfg(not(feature = "fips"))]
+            AlgorithmID::FFDHE3072 => "group: ffdhe3072",
+            #[cfg(not(feature = "fips"))]
+            AlgorithmID::FFDHE4096 => "group: ffdhe4096",
         };
         f.write_str(output)
     }
@@ -140,16 +244,30 @@ impl Debug for Algorithm {
 }
 
 /// ECDH using the NSA Suite B P-256 (secp256r1) curve.
+///
+/// Public keys are encoded using the Octet-String-to-Elliptic-Curve-Point algorithm in
+/// [SEC 1: Elliptic Curve Cryptography, Version 2.0]. `compute_public_key` produces the
+/// uncompressed form; the compressed form is available through `AsBigEndian`. `agree` accepts
+/// peer public keys in either form and always fully validates them: other encodings (including
+/// the point at infinity), coordinates that are not less than the field prime, and points that
+/// are not on the curve are rejected. The NIST curves have a cofactor of one, so no subgroup
+/// check is needed.
+///
+/// [SEC 1: Elliptic Curve Cryptography, Version 2.0]: http://www.secg.org/sec1-v2.pdf
 pub const ECDH_P256: Algorithm = Algorithm {
     id: AlgorithmID::ECDH_P256,
 };
 
 /// ECDH using the NSA Suite B P-384 (secp384r1) curve.
+///
+/// See [`ECDH_P256`] for the encoding and validation of public keys.
 pub const ECDH_P384: Algorithm = Algorithm {
     id: AlgorithmID::ECDH_P384,
 };
 
 /// ECDH using the NSA Suite B P-521 (secp521r1) curve.
+///
+/// See [`ECDH_P256`] for the encoding and validation of public keys.
 pub const ECDH_P521: Algorithm = Algorithm {
     id: AlgorithmID::ECDH_P521,
 };
@@ -158,20 +276,68 @@ pub const ECDH_P521: Algorithm = Algorithm {
 ///
 /// Everything is as described in RFC 7748. Key agreement will fail if the
 /// result of the X25519 operation is zero; see the notes on the
-/// "all-zero value" in [RFC 7748 section 6.1].
+/// "all-zero value" in [RFC 7748 section 6.1]. This is what rejects peer public keys of low
+/// order; as RFC 7748 specifies, peer public keys are otherwise not validated.
 ///
 /// [RFC 7748]: https://tools.ietf.org/html/rfc7748
 /// [RFC 7748 section 6.1]: https://tools.ietf.org/html/rfc7748#section-6.1
+//
+// # FIPS
+// Not available with the `strict-fips` feature, as X25519 is not approved for use in FIPS mode.
+#[cfg(not(feature = "strict-fips"))]
 pub const X25519: Algorithm = Algorithm {
     id: AlgorithmID::X25519,
 };
 
+/// Finite-field Diffie-Hellman over the 2048-bit `ffdhe2048` group from [RFC 7919].
+///
+/// Public keys are encoded as big-endian integers left-padded with zeros to the length of the
+/// group's prime, as in TLS 1.3 ([RFC 8446 section 4.2.8.1]). Peer public keys outside the range
+/// `(1, p - 1)` are rejected. The shared secret is encoded the same way.
+///
+/// These groups are only suitable for interoperability with peers that do not support ECDH. They
+/// are not available when the `fips` feature is enabled.
+///
+/// [RFC 7919]: https://tools.ietf.org/html/rfc7919
+/// [RFC 8446 section 4.2.8.1]: https://tools.ietf.org/html/rfc8446#section-4.2.8.1
+#[cfg(not(feature = "fips"))]
+pub const FFDHE2048: Algorithm = Algorithm {
+    id: AlgorithmID::FFDHE2048,
+};
+
+/// Finite-field Diffie-Hellman over the 3072-bit `ffdhe3072` group from [RFC 7919].
+///
+/// See [`FFDHE2048`] for the encoding of keys and shared secrets.
+///
+/// [RFC 7919]: https://tools.ietf.org/html/rfc7919
+#[cfg(not(feature = "fips"))]
+pub const FFDHE3072: Algorithm = Algorithm {
+    id: AlgorithmID::FFDHE3072,
+};
+
+/// Finite-field Diffie-Hellman over the 4096-bit `ffdhe4096` group from [RFC 7919].
+///
+/// See [`FFDHE2048`] for the encoding of keys and shared secrets.
+///
+/// [RFC 7919]: https://tools.ietf.org/html/rfc7919
+#[cfg(not(feature = "fips"))]
+pub const FFDHE4096: Algorithm = Algorithm {
+    id: AlgorithmID::FFDHE4096,
+};
+
 #[allow(non_camel_case_types)]
 enum KeyInner {
     ECDH_P256(LcPtr<EVP_PKEY>),
     ECDH_P384(LcPtr<EVP_PKEY>),
     ECDH_P521(LcPtr<EVP_PKEY>),
+    #[cfg(not(feature = "strict-fips"))]
     X25519(LcPtr<EVP_PKEY>),
+    #[cfg(not(feature = "fips"))]
+    FFDHE2048(LcPtr<DH>),
+    #[cfg(not(feature = "fips"))]
+    FFDHE3072(LcPtr<DH>),
+    #[cfg(not(feature = "fips"))]
+    FFDHE4096(LcPtr<DH>),
 }
 
 /// A private key for use (only) with `agree`. The
@@ -188,16 +354,29 @@ impl KeyInner {
             KeyInner::ECDH_P256(..) => &ECDH_P256,
             KeyInner::ECDH_P384(..) => &ECDH_P384,
             KeyInner::ECDH_P521(..) => &ECDH_P521,
+            #[cfg(not(feature = "strict-fips"))]
             KeyInner::X25519(..) => &X25519,
+            #[cfg(not(feature = "fips"))]
+            KeyInner::FFDHE2048(..) => &FFDHE2048,
+            #[cfg(not(feature = "fips"))]
+            KeyInner::FFDHE3072(..) => &FFDHE3072,
+            #[cfg(not(feature = "fips"))]
+            KeyInner::FFDHE4096(..) => &FFDHE4096,
         }
     }
 
-    fn get_evp_pkey(&self) -> &LcPtr<EVP_PKEY> {
+    #[cfg_attr(feature = "fips", allow(clippy::unnecessary_wraps))]
+    fn get_evp_pkey(&self) -> Result<&LcPtr<EVP_PKEY>, Unspecified> {
         match self {
             KeyInner::ECDH_P256(evp_pkey)
             | KeyInner::ECDH_P384(evp_pkey)
-            | KeyInner::ECDH_P521(evp_pkey)
-            | KeyInner::X25519(evp_pkey) => evp_pkey,
+            | KeyInner::ECDH_P521(evp_pkey) => Ok(evp_pkey),
+            #[cfg(not(feature = "strict-fips"))]
+            KeyInner::X25519(evp_pkey) => Ok(evp_pkey),
+            #[cfg(not(feature = "fips"))]
+            KeyInner::FFDHE2048(..) | KeyInner::FFDHE3072(..) | KeyInner::FFDHE4096(..) => {
+                Err(Unspecified)
+            }
         }
     }
 }
@@ -224,6 +403,7 @@ impl Debug for PrivateKey {
 impl PrivateKey {
     fn new(alg: &'static Algorithm, evp_pkey: LcPtr<EVP_PKEY>) -> Self {
         match alg.id {
+            #[cfg(not(feature = "strict-fips"))]
             AlgorithmID::X25519 => Self {
                 inner_key: KeyInner::X25519(evp_pkey),
             },
@@ -236,9 +416,28 @@ impl PrivateKey {
             AlgorithmID::ECDH_P521 => Self {
                 inner_key: KeyInner::ECDH_P521(evp_pkey),
             },
+            #[cfg(not(feature = "fips"))]
+            AlgorithmID::FFDHE2048 | AlgorithmID::FFDHE3072 | AlgorithmID::FFDHE4096 => {
+                verify_unreachable!()
+            }
         }
     }
 
+    #[cfg(not(feature = "fips"))]
+    fn new_ffdhe(alg: &'static Algorithm, dh: LcPtr<DH>) -> Self {
+        let inner_key = match alg.id {
+            AlgorithmID::FFDHE2048 => KeyInner::FFDHE2048(dh),
+            AlgorithmID::FFDHE3072 => KeyInner::FFDHE3072(dh),
+            AlgorithmID::FFDHE4096 => KeyInner::FFDHE4096(dh),
+            AlgorithmID::ECDH_P256 | AlgorithmID::ECDH_P384 | AlgorithmID::ECDH_P521 => {
+                verify_unreachable!()
+            }
+            #[cfg(not(feature = "strict-fips"))]
+            AlgorithmID::X25519 => verify_unreachable!(),
+        };
+        Self { inner_key }
+    }
+
     #[inline]
     /// Generate a new private key for the given algorithm.
     ///
@@ -252,8 +451,15 @@ impl PrivateKey {
     /// `error::Unspecified` when operation fails due to internal error.
     pub fn generate(alg: &'static Algorithm) -> Result<Self, Unspecified> {
         let evp_pkey = match alg.id {
+            #[cfg(not(feature = "strict-fips"))]
             AlgorithmID::X25519 => generate_x25519()?,
-            _ => evp_key_generate(alg.id.nid())?,
+            AlgorithmID::ECDH_P256 | AlgorithmID::ECDH_P384 | AlgorithmID::ECDH_P521 => {
+                evp_key_generate(alg.id.nid())?
+            }
+            #[cfg(not(feature = "fips"))]
+            AlgorithmID::FFDHE2048 | AlgorithmID::FFDHE3072 | AlgorithmID::FFDHE4096 => {
+                return Ok(Self::new_ffdhe(alg, generate_ffdhe(alg.id.nid())?));
+            }
         };
         Ok(Self::new(alg, evp_pkey))
     }
@@ -264,7 +470,8 @@ impl PrivateKey {
     /// attempt to automatically detect other key formats. This function supports unencrypted
     /// PKCS#8 `PrivateKeyInfo` structures as well as key type specific formats.
     ///
-    /// X25519 keys are not supported. See `PrivateKey::as_der`.
+    /// X25519 keys are only supported as PKCS#8 `PrivateKeyInfo` structures. FFDHE keys are not
+    /// supported. See `PrivateKey::as_der`.
     ///
     /// # Errors
     /// `error::KeyRejected` if parsing failed or key otherwise unacceptable.
@@ -274,9 +481,17 @@ impl PrivateKey {
         alg: &'static Algorithm,
         key_bytes: &[u8],
     ) -> Result<Self, KeyRejected> {
-        if AlgorithmID::X25519 == alg.id {
+        if alg.id.is_ffdhe() {
             return Err(KeyRejected::invalid_encoding());
         }
+        #[cfg(not(feature = "strict-fips"))]
+        if AlgorithmID::X25519 == alg.id {
+            let evp_pkey = LcPtr::<EVP_PKEY>::try_from(key_bytes)?;
+            if EVP_PKEY_X25519 != evp_pkey.id() {
+                return Err(KeyRejected::wrong_algorithm());
+            }
+            return Ok(Self::new(alg, evp_pkey));
+        }
         let evp_pkey = unsafe { ec::unmarshal_der_to_private_key(key_bytes, alg.id.nid())? };
         Ok(Self::new(alg, evp_pkey))
     }
@@ -287,31 +502,36 @@ impl PrivateKey {
     /// example, a P-256 private key must be 32 bytes prefixed with leading
     /// zeros as needed.
     ///
+    /// FFDHE keys are not supported.
+    ///
     /// # Errors
     /// `error::KeyRejected` if parsing failed or key otherwise unacceptable.
     pub fn from_private_key(
         alg: &'static Algorithm,
         key_bytes: &[u8],
     ) -> Result<Self, KeyRejected> {
+        if alg.id.is_ffdhe() {
+            return Err(KeyRejected::invalid_encoding());
+        }
         if key_bytes.len() != alg.id.private_key_len() {
             return Err(KeyRejected::wrong_algorithm());
         }
-        let evp_pkey = if AlgorithmID::X25519 == alg.id {
-            LcPtr::new(unsafe {
+        #[cfg(not(feature = "strict-fips"))]
+        if AlgorithmID::X25519 == alg.id {
+            let evp_pkey = LcPtr::new(unsafe {
                 EVP_PKEY_new_raw_private_key(
                     EVP_PKEY_X25519,
                     null_mut(),
                     key_bytes.as_ptr(),
                     AlgorithmID::X25519.private_key_len(),
                 )
-            })?
-        } else {
-            let ec_group = ec_group_from_nid(alg.id.nid())?;
-            let private_bn = LcPtr::<BIGNUM>::try_from(key_bytes)?;
-
-            ec::evp_pkey_from_private(&ec_group.as_const(), &private_bn.as_const())
-                .map_err(|_| KeyRejected::invalid_encoding())?
-        };
+            })?;
+            return Ok(Self::new(alg, evp_pkey));
+        }
+        let ec_group = ec_group_from_nid(alg.id.nid())?;
+        let private_bn = LcPtr::<BIGNUM>::try_from(key_bytes)?;
+        let evp_pkey = ec::evp_pkey_from_private(&ec_group.as_const(), &private_bn.as_const())
+            .map_err(|_| KeyRejected::invalid_encoding())?;
         Ok(Self::new(alg, evp_pkey))
     }
 
@@ -322,6 +542,7 @@ impl PrivateKey {
         rng: &dyn crate::rand::SecureRandom,
     ) -> Result<Self, Unspecified> {
         match alg.id {
+            #[cfg(not(feature = "strict-fips"))]
             AlgorithmID::X25519 => {
                 let mut priv_key = [0u8; AlgorithmID::X25519.private_key_len()];
                 rng.fill(&mut priv_key)?;
@@ -342,10 +563,14 @@ impl PrivateKey {
                 rng.fill(&mut priv_key)?;
                 Self::from_p521_private_key(&priv_key)
             }
+            #[cfg(not(feature = "fips"))]
+            AlgorithmID::FFDHE2048 | AlgorithmID::FFDHE3072 | AlgorithmID::FFDHE4096 => {
+                Self::generate(alg)
+            }
         }
     }
 
-    #[cfg(test)]
+    #[cfg(all(test, not(feature = "strict-fips")))]
     fn from_x25519_private_key(
         priv_key: &[u8; AlgorithmID::X25519.private_key_len()],
     ) -> Result<Self, Unspecified> {
@@ -387,6 +612,28 @@ impl PrivateKey {
         })
     }
 
+    /// Performs a key agreement with the given peer public key, returning the key material as
+    /// a `SharedSecret`.
+    ///
+    /// See `agree` for the validation of `peer_public_key`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the peer public key is not valid for this key's algorithm, or if
+    /// the key agreement fails.
+    pub fn agree<B: AsRef<[u8]>>(
+        &self,
+        peer_public_key: &UnparsedPublicKey<B>,
+    ) -> Result<SharedSecret, Unspecified> {
+        let mut secret = SharedSecret {
+            bytes: [0u8; MAX_AGREEMENT_SECRET_LEN],
+            len: 0,
+        };
+        secret.len = agree_into(self, peer_public_key, &mut secret.bytes)
+            .map_err(|()| Unspecified)?
+            .len();
+        Ok(secret)
+    }
+
     /// Computes the public key from the private key.
     ///
     /// # Errors
@@ -396,17 +643,30 @@ impl PrivateKey {
             KeyInner::ECDH_P256(evp_pkey)
             | KeyInner::ECDH_P384(evp_pkey)
             | KeyInner::ECDH_P521(evp_pkey) => {
+                let mut ec_buffer = [0u8; ec::PUBLIC_KEY_MAX_LEN];
+                let key_len = unsafe {
+                    ec::marshal_public_key_to_buffer(&mut ec_buffer, &evp_pkey.as_const())?
+                };
                 let mut buffer = [0u8; MAX_PUBLIC_KEY_LEN];
-                unsafe {
-                    let key_len =
-                        ec::marshal_public_key_to_buffer(&mut buffer, &evp_pkey.as_const())?;
-                    Ok(PublicKey {
-                        alg: self.algorithm(),
-                        public_key: buffer,
-                        len: key_len,
-                    })
+                buffer[..key_len].copy_from_slice(&ec_buffer[..key_len]);
+                Ok(PublicKey {
+                    alg: self.algorithm(),
+                    public_key: PublicKeyBytes::Fixed(buffer, key_len),
+                })
+            }
+            #[cfg(not(feature = "fips"))]
+            KeyInner::FFDHE2048(dh) | KeyInner::FFDHE3072(dh) | KeyInner::FFDHE4096(dh) => {
+                let mut buffer = vec![0u8; self.algorithm().id.pub_key_len()];
+                let pub_key = ConstPointer::new(unsafe { DH_get0_pub_key(**dh) })?;
+                if 1 != unsafe { BN_bn2bin_padded(buffer.as_mut_ptr(), buffer.len(), *pub_key) } {
+                    return Err(Unspecified);
                 }
+                Ok(PublicKey {
+                    alg: self.algorithm(),
+                    public_key: PublicKeyBytes::Ffdhe(buffer.into_boxed_slice()),
+                })
             }
+            #[cfg(not(feature = "strict-fips"))]
             KeyInner::X25519(priv_key) => {
                 let mut buffer = [0u8; MAX_PUBLIC_KEY_LEN];
                 let mut out_len = buffer.len();
@@ -419,8 +679,7 @@ impl PrivateKey {
 
                 Ok(PublicKey {
                     alg: self.algorithm(),
-                    public_key: buffer,
-                    len: out_len,
+                    public_key: PublicKeyBytes::Fixed(buffer, out_len),
                 })
             }
         }
@@ -437,11 +696,12 @@ impl PrivateKey {
 impl AsDer<EcPrivateKeyRfc5915Der<'static>> for PrivateKey {
     /// Serializes the key as a DER-encoded `ECPrivateKey` (RFC 5915) structure.
     ///
-    /// X25519 is not supported.
+    /// X25519 and FFDHE are not supported.
     ///
     /// # Errors
     /// `error::Unspecified`  if serialization failed.
     fn as_der(&self) -> Result<EcPrivateKeyRfc5915Der<'static>, Unspecified> {
+        #[cfg(not(feature = "strict-fips"))]
         if AlgorithmID::X25519 == self.inner_key.algorithm().id {
             return Err(Unspecified);
         }
@@ -449,7 +709,7 @@ impl AsDer<EcPrivateKeyRfc5915Der<'static>> for PrivateKey {
         let mut outp = null_mut::<u8>();
         let ec_key = {
             ConstPointer::new(unsafe {
-                EVP_PKEY_get0_EC_KEY(self.inner_key.get_evp_pkey().as_const_ptr())
+                EVP_PKEY_get0_EC_KEY(self.inner_key.get_evp_pkey()?.as_const_ptr())
             })?
         };
         let length = usize::try_from(unsafe { aws_lc::i2d_ECPrivateKey(*ec_key, &mut outp) })
@@ -461,27 +721,43 @@ impl AsDer<EcPrivateKeyRfc5915Der<'static>> for PrivateKey {
     }
 }
 
+impl PrivateKey {
+    /// Serializes the key as a PKCS#8 v1 `PrivateKeyInfo` structure.
+    ///
+    /// FFDHE is not supported.
+    ///
+    /// # Errors
+    /// `error::Unspecified`  if serialization failed.
+    pub fn to_pkcs8v1(&self) -> Result<Document, Unspecified> {
+        self.inner_key
+            .get_evp_pkey()?
+            .marshall_private_key(Version::V1)
+    }
+}
+
 impl AsBigEndian<EcPrivateKeyBin<'static>> for PrivateKey {
     /// Exposes the private key encoded as a big-endian fixed-length integer.
     ///
-    /// X25519 is not supported.
+    /// X25519 and FFDHE are not supported.
     ///
     /// # Errors
     /// `error::Unspecified` if serialization failed.
     fn as_be_bytes(&self) -> Result<EcPrivateKeyBin<'static>, Unspecified> {
+        #[cfg(not(feature = "strict-fips"))]
         if AlgorithmID::X25519 == self.inner_key.algorithm().id {
             return Err(Unspecified);
         }
         let buffer = unsafe {
             ec::marshal_private_key_to_buffer(
                 self.inner_key.algorithm().id.private_key_len(),
-                &self.inner_key.get_evp_pkey().as_const(),
+                &self.inner_key.get_evp_pkey()?.as_const(),
             )?
         };
         Ok(EcPrivateKeyBin::new(buffer))
     }
 }
 
+#[cfg(not(feature = "strict-fips"))]
 impl AsBigEndian<Curve25519SeedBin<'static>> for PrivateKey {
     /// Exposes the seed encoded as a big-endian fixed-length integer.
     ///
@@ -493,7 +769,7 @@ impl AsBigEndian<Curve25519SeedBin<'static>> for PrivateKey {
         if AlgorithmID::X25519 != self.inner_key.algorithm().id {
             return Err(Unspecified);
         }
-        let evp_pkey = self.inner_key.get_evp_pkey().as_const();
+        let evp_pkey = self.inner_key.get_evp_pkey()?.as_const();
         let mut buffer = [0u8; AlgorithmID::X25519.private_key_len()];
         let mut out_len = AlgorithmID::X25519.private_key_len();
         if 1 != unsafe {
@@ -516,6 +792,7 @@ fn from_ec_private_key(priv_key: &[u8], nid: i32) -> Result<LcPtr<EVP_PKEY>, Uns
     Ok(pkey)
 }
 
+#[cfg(not(feature = "strict-fips"))]
 pub(crate) fn generate_x25519() -> Result<LcPtr<EVP_PKEY>, Unspecified> {
     let pkey_ctx = LcPtr::new(unsafe { EVP_PKEY_CTX_new_id(EVP_PKEY_X25519, null_mut()) })?;
 
@@ -534,13 +811,46 @@ pub(crate) fn generate_x25519() -> Result<LcPtr<EVP_PKEY>, Unspecified> {
     Ok(pkey)
 }
 
+#[cfg(not(feature = "fips"))]
+fn generate_ffdhe(nid: i32) -> Result<LcPtr<DH>, Unspecified> {
+    let dh = LcPtr::new(unsafe { DH_new_by_nid(nid) })?;
+
+    if 1 != indicator_check!(unsafe { DH_generate_key(*dh) }) {
+        return Err(Unspecified);
+    }
+
+    Ok(dh)
+}
+
+// Current max public key length is P-521's. FFDHE public keys are stored on the heap.
 const MAX_PUBLIC_KEY_LEN: usize = ec::PUBLIC_KEY_MAX_LEN;
 
+/// The maximum length of a DER-encoded `SubjectPublicKeyInfo` for an agreement public key.
+///
+/// `25` is the length of the P-521 template.
+const PUBLIC_KEY_X509_DER_MAX_LEN: usize = 25 + MAX_PUBLIC_KEY_LEN;
+
+#[derive(Clone)]
+enum PublicKeyBytes {
+    Fixed([u8; MAX_PUBLIC_KEY_LEN], usize),
+    #[cfg(not(feature = "fips"))]
+    Ffdhe(Box<[u8]>),
+}
+
+impl AsRef<[u8]> for PublicKeyBytes {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            PublicKeyBytes::Fixed(bytes, len) => &bytes[..*len],
+            #[cfg(not(feature = "fips"))]
+            PublicKeyBytes::Ffdhe(bytes) => bytes,
+        }
+    }
+}
+
 /// A public key for key agreement.
 pub struct PublicKey {
     alg: &'static Algorithm,
-    public_key: [u8; MAX_PUBLIC_KEY_LEN],
-    len: usize,
+    public_key: PublicKeyBytes,
 }
 
 impl PublicKey {
@@ -549,6 +859,110 @@ impl PublicKey {
     pub fn algorithm(&self) -> &'static Algorithm {
         self.alg
     }
+
+    /// Parses a DER-encoded (X.509) `SubjectPublicKeyInfo` structure containing a public key
+    /// for `alg`.
+    ///
+    /// FFDHE is not supported.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if parsing failed or the key is not for `alg`.
+    pub fn from_der(alg: &'static Algorithm, der: &[u8]) -> Result<Self, KeyRejected> {
+        if alg.id.is_ffdhe() {
+            return Err(KeyRejected::wrong_algorithm());
+        }
+        let mut cbs = unsafe { cbs::build_CBS(der) };
+        let evp_pkey = LcPtr::new(unsafe { EVP_parse_public_key(&mut cbs) })
+            .map_err(|()| KeyRejected::invalid_encoding())?;
+
+        let mut public_key = [0u8; MAX_PUBLIC_KEY_LEN];
+        #[cfg(not(feature = "strict-fips"))]
+        if AlgorithmID::X25519 == alg.id {
+            if EVP_PKEY_X25519 != evp_pkey.id() {
+                return Err(KeyRejected::wrong_algorithm());
+            }
+            let mut out_len = AlgorithmID::X25519.pub_key_len();
+            if 1 != unsafe {
+                EVP_PKEY_get_raw_public_key(*evp_pkey, public_key.as_mut_ptr(), &mut out_len)
+            } {
+                return Err(KeyRejected::unexpected_error());
+            }
+            return Ok(PublicKey {
+                alg,
+                public_key: PublicKeyBytes::Fixed(public_key, out_len),
+            });
+        }
+        if EVP_PKEY_EC != evp_pkey.id() {
+            return Err(KeyRejected::wrong_algorithm());
+        }
+        ec::verify_evp_key_nid(&evp_pkey.as_const(), alg.id.nid())?;
+        let mut ec_buffer = [0u8; ec::PUBLIC_KEY_MAX_LEN];
+        let len =
+            unsafe { ec::marshal_public_key_to_buffer(&mut ec_buffer, &evp_pkey.as_const())? };
+        public_key[..len].copy_from_slice(&ec_buffer[..len]);
+
+        Ok(PublicKey {
+            alg,
+            public_key: PublicKeyBytes::Fixed(public_key, len),
+        })
+    }
+}
+
+impl AsBigEndian<EcPublicKeyCompressedBin<'static>> for PublicKey {
+    /// Provides the public key in the compressed form (X9.62), i.e. the x-coordinate prefixed by
+    /// a byte indicating the parity of the y-coordinate.
+    ///
+    /// Only the NIST curves are supported.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the algorithm has no compressed form.
+    fn as_be_bytes(&self) -> Result<EcPublicKeyCompressedBin<'static>, Unspecified> {
+        let compressed_len = self.alg.id.compressed_pub_key_len().ok_or(Unspecified)?;
+        // The key is the uncompressed encoding: 0x04 || x || y.
+        let mut compressed = Vec::with_capacity(compressed_len);
+        let public_key = self.as_ref();
+        compressed.push(0x02 | (public_key[public_key.len() - 1] & 1));
+        compressed.extend_from_slice(&public_key[1..compressed_len]);
+        Ok(EcPublicKeyCompressedBin::new(compressed))
+    }
+}
+
+impl AsDer<PublicKeyX509Der<'static>> for PublicKey {
+    /// Provides the public key as a DER-encoded (X.509) `SubjectPublicKeyInfo` structure.
+    ///
+    /// FFDHE is not supported.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if serialization failed.
+    fn as_der(&self) -> Result<PublicKeyX509Der<'static>, Unspecified> {
+        let evp_pkey = match self.alg.id {
+            #[cfg(not(feature = "strict-fips"))]
+            AlgorithmID::X25519 => LcPtr::new(unsafe {
+                EVP_PKEY_new_raw_public_key(
+                    EVP_PKEY_X25519,
+                    null_mut(),
+                    self.as_ref().as_ptr(),
+                    self.as_ref().len(),
+                )
+            })?,
+            AlgorithmID::ECDH_P256 | AlgorithmID::ECDH_P384 | AlgorithmID::ECDH_P521 => {
+                let ec_group = ec_group_from_nid(self.alg.id.nid())?;
+                let ec_point = ec_point_from_bytes(&ec_group, self.as_ref())?;
+                evp_pkey_from_public_point(&ec_group, &ec_point)?
+            }
+            #[cfg(not(feature = "fips"))]
+            AlgorithmID::FFDHE2048 | AlgorithmID::FFDHE3072 | AlgorithmID::FFDHE4096 => {
+                return Err(Unspecified);
+            }
+        };
+
+        let mut der = LcCBB::new(PUBLIC_KEY_X509_DER_MAX_LEN);
+        if 1 != unsafe { EVP_marshal_public_key(der.as_mut_ptr(), *evp_pkey) } {
+            return Err(Unspecified);
+        }
+
+        Ok(PublicKeyX509Der::from(der.into_buffer()?))
+    }
 }
 
 impl Debug for PublicKey {
@@ -556,14 +970,14 @@ impl Debug for PublicKey {
         f.write_str(&format!(
             "PublicKey {{ algorithm: {:?}, bytes: \"{}\" }}",
             self.alg,
-            hex::encode(&self.public_key[0..self.len])
+            hex::encode(self.as_ref())
         ))
     }
 }
 
 impl AsRef<[u8]> for PublicKey {
     fn as_ref(&self) -> &[u8] {
-        &self.public_key[0..self.len]
+        self.public_key.as_ref()
     }
 }
 
@@ -571,8 +985,7 @@ impl Clone for PublicKey {
     fn clone(&self) -> Self {
         PublicKey {
             alg: self.alg,
-            public_key: self.public_key,
-            len: self.len,
+            public_key: self.public_key.clone(),
         }
     }
 }
@@ -655,34 +1068,101 @@ pub fn agree<B: AsRef<[u8]>, F, R, E>(
 where
     F: FnOnce(&[u8]) -> Result<R, E>,
 {
+    let mut buffer = [0u8; MAX_AGREEMENT_SECRET_LEN];
+    let secret = agree_into(my_private_key, peer_public_key, &mut buffer).or(Err(error_value))?;
+    kdf(secret)
+}
+
+fn agree_into<'a, B: AsRef<[u8]>>(
+    my_private_key: &PrivateKey,
+    peer_public_key: &UnparsedPublicKey<B>,
+    buffer: &'a mut [u8; MAX_AGREEMENT_SECRET_LEN],
+) -> Result<&'a [u8], ()> {
     let expected_alg = my_private_key.algorithm();
     let expected_pub_key_len = expected_alg.id.pub_key_len();
     let expected_nid = expected_alg.id.nid();
 
     if peer_public_key.alg != expected_alg {
-        return Err(error_value);
+        return Err(());
     }
     let peer_pub_bytes = peer_public_key.bytes.as_ref();
-    if peer_pub_bytes.len() != expected_pub_key_len {
-        return Err(error_value);
+    if peer_pub_bytes.len() != expected_pub_key_len
+        && Some(peer_pub_bytes.len()) != expected_alg.id.compressed_pub_key_len()
+    {
+        return Err(());
     }
 
-    let mut buffer = [0u8; MAX_AGREEMENT_SECRET_LEN];
-
-    let secret: &[u8] = match &my_private_key.inner_key {
-        KeyInner::X25519(priv_key) => {
-            x25519_diffie_hellman(&mut buffer, priv_key, peer_pub_bytes).or(Err(error_value))?
-        }
+    match &my_private_key.inner_key {
+        #[cfg(not(feature = "strict-fips"))]
+        KeyInner::X25519(priv_key) => x25519_diffie_hellman(buffer, priv_key, peer_pub_bytes),
         KeyInner::ECDH_P256(priv_key)
         | KeyInner::ECDH_P384(priv_key)
         | KeyInner::ECDH_P521(priv_key) => {
-            ec_key_ecdh(&mut buffer, priv_key, peer_pub_bytes, expected_nid).or(Err(error_value))?
+            ec_key_ecdh(buffer, priv_key, peer_pub_bytes, expected_nid)
         }
-    };
-    kdf(secret)
+        #[cfg(not(feature = "fips"))]
+        KeyInner::FFDHE2048(dh) | KeyInner::FFDHE3072(dh) | KeyInner::FFDHE4096(dh) => {
+            ffdhe_compute_key(buffer, dh, peer_pub_bytes)
+        }
+    }
 }
 
+/// The key material produced by a key agreement.
+///
+/// The raw output of a Diffie-Hellman operation is not uniformly random and must not be used
+/// directly as a key. A `SharedSecret` can only be used as the input to HKDF, and is zeroized
+/// when dropped.
+pub struct SharedSecret {
+    bytes: [u8; MAX_AGREEMENT_SECRET_LEN],
+    len: usize,
+}
+
+impl SharedSecret {
+    /// Derives `out.len()` bytes of key material from the shared secret using HKDF
+    /// ([RFC 5869]) with the given `salt` and `info`.
+    ///
+    /// [RFC 5869]: https://tools.ietf.org/html/rfc5869
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `out` is longer than 255 times the digest length of `algorithm`,
+    /// or if the HKDF operation fails.
+    pub fn derive(
+        &self,
+        algorithm: hkdf::Algorithm,
+        salt: &[u8],
+        info: &[&[u8]],
+        out: &mut [u8],
+    ) -> Result<(), Unspecified> {
+        hkdf::Salt::new(algorithm, salt)
+            .extract(&self.bytes[..self.len])
+            .expand_multi(&mut [(info, out)])
+    }
+
+    /// Extracts a pseudorandom key from the shared secret using HKDF-Extract with the given
+    /// `salt`, for protocols that expand several keys from it.
+    #[must_use]
+    pub fn extract(&self, algorithm: hkdf::Algorithm, salt: &[u8]) -> hkdf::Prk {
+        hkdf::Salt::new(algorithm, salt).extract(&self.bytes[..self.len])
+    }
+}
+
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+impl Debug for SharedSecret {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("SharedSecret").finish_non_exhaustive()
+    }
+}
+
+// Current max secret length is ffdhe4096's.
+#[cfg(not(feature = "fips"))]
+const MAX_AGREEMENT_SECRET_LEN: usize = AlgorithmID::FFDHE4096.pub_key_len();
 // Current max secret length is P-521's.
+#[cfg(feature = "fips")]
 const MAX_AGREEMENT_SECRET_LEN: usize = AlgorithmID::ECDH_P521.private_key_len();
 
 #[inline]
@@ -722,6 +1202,28 @@ fn ec_key_ecdh<'a>(
     Ok(&buffer[0..out_key_len])
 }
 
+#[cfg(not(feature = "fips"))]
+#[inline]
+fn ffdhe_compute_key<'a>(
+    buffer: &'a mut [u8; MAX_AGREEMENT_SECRET_LEN],
+    dh: &LcPtr<DH>,
+    peer_pub_key: &[u8],
+) -> Result<&'a [u8], ()> {
+    let peer_pub_key = LcPtr::<BIGNUM>::try_from(peer_pub_key)?;
+
+    // `DH_compute_key_padded` rejects peer public keys outside of (1, p - 1).
+    let out_key_len = indicator_check!(unsafe {
+        DH_compute_key_padded(buffer.as_mut_ptr(), *peer_pub_key, **dh)
+    });
+    let out_key_len = usize::try_from(out_key_len).map_err(|_| ())?;
+    if 0 == out_key_len {
+        return Err(());
+    }
+
+    Ok(&buffer[0..out_key_len])
+}
+
+#[cfg(not(feature = "strict-fips"))]
 #[inline]
 fn x25519_diffie_hellman<'a>(
     buffer: &'a mut [u8; MAX_AGREEMENT_SECRET_LEN],
@@ -762,15 +1264,22 @@ fn x25519_diffie_hellman<'a>(
 
 #[cfg(test)]
 mod tests {
+    #[cfg(not(feature = "strict-fips"))]
+    use crate::agreement::X25519;
     use crate::agreement::{
-        agree, Algorithm, PrivateKey, PublicKey, UnparsedPublicKey, ECDH_P256, ECDH_P384,
-        ECDH_P521, X25519,
+        agree, Algorithm, PrivateKey, PublicKey, UnparsedPublicKey, ECDH_P256, ECDH_P384, ECDH_P521,
     };
+    #[cfg(not(feature = "fips"))]
+    use crate::agreement::{FFDHE2048, FFDHE3072, FFDHE4096};
+    #[cfg(not(feature = "strict-fips"))]
+    use crate::encoding::Curve25519SeedBin;
     use crate::encoding::{
-        AsBigEndian, AsDer, Curve25519SeedBin, EcPrivateKeyBin, EcPrivateKeyRfc5915Der,
+        AsBigEndian, AsDer, EcPrivateKeyBin, EcPrivateKeyRfc5915Der, EcPublicKeyCompressedBin,
+        PublicKeyX509Der,
     };
-    use crate::{rand, test};
+    use crate::{hkdf, rand, test};
 
+    #[cfg(not(feature = "strict-fips"))]
     #[test]
     fn test_agreement_x25519() {
         let alg = &X25519;
@@ -844,9 +1353,15 @@ mod tests {
             .is_err());
         }
 
-        let alg_variants: [&'static Algorithm; 4] = [&X25519, &ECDH_P256, &ECDH_P384, &ECDH_P521];
+        let alg_variants: &[&'static Algorithm] = &[
+            #[cfg(not(feature = "strict-fips"))]
+            &X25519,
+            &ECDH_P256,
+            &ECDH_P384,
+            &ECDH_P521,
+        ];
 
-        for alg in alg_variants {
+        for &alg in alg_variants {
             let my_private_key = PrivateKey::generate(alg).unwrap();
 
             let empty_key = [];
@@ -860,6 +1375,303 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_agreement_peer_public_key_validation() {
+        fn assert_rejected(my_private: &PrivateKey, peer_public_key: &[u8]) {
+            let alg = my_private.algorithm();
+            assert!(agree(
+                my_private,
+                &UnparsedPublicKey::new(alg, peer_public_key),
+                (),
+                |_| Ok(())
+            )
+            .is_err());
+        }
+
+        for alg in [&ECDH_P256, &ECDH_P384, &ECDH_P521] {
+            let my_private = PrivateKey::generate(alg).unwrap();
+            let peer_public = PrivateKey::generate(alg)
+                .unwrap()
+                .compute_public_key()
+                .unwrap();
+            let peer_public = peer_public.as_ref();
+            let coordinate_len = (peer_public.len() - 1) / 2;
+
+            // The point at infinity.
+            assert_rejected(&my_private, &[0]);
+
+            // A compressed point whose x coordinate is not less than the field prime.
+            let mut compressed = vec![2; 1 + coordinate_len];
+            compressed[1..].fill(0xff);
+            assert_rejected(&my_private, &compressed);
+
+            // An unknown point encoding.
+            let mut bad_form = peer_public.to_vec();
+            bad_form[0] = 5;
+            assert_rejected(&my_private, &bad_form);
+
+            // A point that is not on the curve.
+            let mut off_curve = peer_public.to_vec();
+            off_curve[peer_public.len() - 1] ^= 1;
+            assert_rejected(&my_private, &off_curve);
+
+            // An x coordinate that is not less than the field prime.
+            let mut too_large = peer_public.to_vec();
+            too_large[1..=coordinate_len].fill(0xff);
+            assert_rejected(&my_private, &too_large);
+        }
+
+        #[cfg(not(feature = "strict-fips"))]
+        {
+            let my_private = PrivateKey::generate(&X25519).unwrap();
+            let mut one = [0u8; 32];
+            one[0] = 1;
+            for low_order in [[0u8; 32], one] {
+                assert_rejected(&my_private, &low_order);
+            }
+        }
+    }
+
+    #[test]
+    fn test_agreement_public_key_der_round_trip() {
+        for alg in [
+            #[cfg(not(feature = "strict-fips"))]
+            &X25519,
+            &ECDH_P256,
+            &ECDH_P384,
+            &ECDH_P521,
+        ] {
+            let public_key = PrivateKey::generate(alg)
+                .unwrap()
+                .compute_public_key()
+                .unwrap();
+            let der: PublicKeyX509Der = public_key.as_der().unwrap();
+            assert!(der.as_ref().len() <= super::PUBLIC_KEY_X509_DER_MAX_LEN);
+            if alg == &ECDH_P521 {
+                assert_eq!(der.as_ref().len(), super::PUBLIC_KEY_X509_DER_MAX_LEN);
+            }
+            let parsed = PublicKey::from_der(alg, der.as_ref()).unwrap();
+            assert_eq!(parsed.algorithm(), alg);
+            assert_eq!(parsed.as_ref(), public_key.as_ref());
+
+            let other_alg = if alg == &ECDH_P256 {
+                &ECDH_P384
+            } else {
+                &ECDH_P256
+            };
+            assert!(PublicKey::from_der(other_alg, der.as_ref()).is_err());
+        }
+
+        #[cfg(not(feature = "strict-fips"))]
+        {
+            let x25519_public = PublicKey::from_der(
+                &X25519,
+                &test::from_dirty_hex(
+                    "302a300506032b656e032100de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f",
+                ),
+            )
+            .unwrap();
+            assert_eq!(
+                x25519_public.as_ref(),
+                test::from_dirty_hex(
+                    "de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f"
+                )
+            );
+
+            assert!(PublicKey::from_der(&X25519, &[]).is_err());
+        }
+        #[cfg(not(feature = "fips"))]
+        {
+            let ffdhe_public = PrivateKey::generate(&FFDHE2048)
+                .unwrap()
+                .compute_public_key()
+                .unwrap();
+            assert!(AsDer::<PublicKeyX509Der>::as_der(&ffdhe_public).is_err());
+        }
+    }
+
+    #[test]
+    fn test_agreement_shared_secret() {
+        for alg in [
+            #[cfg(not(feature = "strict-fips"))]
+            &X25519,
+            &ECDH_P256,
+            &ECDH_P384,
+            &ECDH_P521,
+            #[cfg(not(feature = "fips"))]
+            &FFDHE2048,
+        ] {
+            let my_private = PrivateKey::generate(alg).unwrap();
+            let peer_private = PrivateKey::generate(alg).unwrap();
+            let peer_public = peer_private.compute_public_key().unwrap();
+            let peer_public = UnparsedPublicKey::new(alg, peer_public.as_ref());
+
+            let expected = agree(&my_private, &peer_public, (), |secret| {
+                let mut out = [0u8; 42];
+                hkdf::Salt::new(hkdf::HKDF_SHA256, b"salt")
+                    .extract(secret)
+                    .expand(&[b"info"], hkdf::HKDF_SHA256)
+                    .unwrap()
+                    .fill(&mut out[..32])
+                    .unwrap();
+                Ok(out)
+            })
+            .unwrap();
+
+            let shared_secret = my_private.agree(&peer_public).unwrap();
+            let mut out = [0u8; 42];
+            shared_secret
+                .derive(hkdf::HKDF_SHA256, b"salt", &[b"info"], &mut out[..32])
+                .unwrap();
+            assert_eq!(out, expected);
+            assert_eq!(format!("{shared_secret:?}"), "SharedSecret { .. }");
+
+            let wrong_alg = if alg == &ECDH_P256 {
+                &ECDH_P384
+            } else {
+                &ECDH_P256
+            };
+            let wrong_public = PrivateKey::generate(wrong_alg)
+                .unwrap()
+                .compute_public_key()
+                .unwrap();
+            assert!(my_private
+                .agree(&UnparsedPublicKey::new(wrong_alg, wrong_public.as_ref()))
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_agreement_compressed_public_key() {
+        for alg in [&ECDH_P256, &ECDH_P384, &ECDH_P521] {
+            let my_private = PrivateKey::generate(alg).unwrap();
+            let peer_private = PrivateKey::generate(alg).unwrap();
+            let peer_public = peer_private.compute_public_key().unwrap();
+            let compressed: EcPublicKeyCompressedBin = peer_public.as_be_bytes().unwrap();
+            assert_eq!(compressed.as_ref().len(), 1 + alg.id.private_key_len());
+            assert_eq!(
+                &compressed.as_ref()[1..],
+                &peer_public.as_ref()[1..compressed.as_ref().len()]
+            );
+
+            let expected = agree(
+                &my_private,
+                &UnparsedPublicKey::new(alg, peer_public.as_ref()),
+                (),
+                |secret| Ok(secret.to_vec()),
+            )
+            .unwrap();
+            let actual = agree(
+                &my_private,
+                &UnparsedPublicKey::new(alg, compressed.as_ref()),
+                (),
+                |secret| Ok(secret.to_vec()),
+            )
+            .unwrap();
+            assert_eq!(expected, actual);
+        }
+
+        #[cfg(not(feature = "strict-fips"))]
+        {
+            let x25519_public = PrivateKey::generate(&X25519)
+                .unwrap()
+                .compute_public_key()
+                .unwrap();
+            assert!(AsBigEndian::<EcPublicKeyCompressedBin>::as_be_bytes(&x25519_public).is_err());
+        }
+    }
+
+    #[test]
+    fn test_agreement_pkcs8_round_trip() {
+        for alg in [
+            #[cfg(not(feature = "strict-fips"))]
+            &X25519,
+            &ECDH_P256,
+            &ECDH_P384,
+            &ECDH_P521,
+        ] {
+            let my_private = PrivateKey::generate(alg).unwrap();
+            let pkcs8 = my_private.to_pkcs8v1().unwrap();
+            let imported = PrivateKey::from_private_key_der(alg, pkcs8.as_ref()).unwrap();
+            assert_eq!(
+                my_private.compute_public_key().unwrap().as_ref(),
+                imported.compute_public_key().unwrap().as_ref()
+            );
+
+            let other_alg = if alg == &ECDH_P256 {
+                &ECDH_P384
+            } else {
+                &ECDH_P256
+            };
+            assert!(PrivateKey::from_private_key_der(other_alg, pkcs8.as_ref()).is_err());
+        }
+
+        #[cfg(not(feature = "fips"))]
+        {
+            let ffdhe_private = PrivateKey::generate(&FFDHE2048).unwrap();
+            assert!(ffdhe_private.to_pkcs8v1().is_err());
+        }
+    }
+
+    #[cfg(not(feature = "fips"))]
+    #[test]
+    fn test_agreement_ffdhe() {
+        for alg in [&FFDHE2048, &FFDHE3072, &FFDHE4096] {
+            let my_private = PrivateKey::generate(alg).unwrap();
+            let my_public = my_private.compute_public_key().unwrap();
+            let peer_private = PrivateKey::generate(alg).unwrap();
+            let peer_public = peer_private.compute_public_key().unwrap();
+            assert_eq!(my_public.as_ref().len(), alg.id.pub_key_len());
+            assert_eq!(my_public.algorithm(), alg);
+
+            let my_secret = agree(
+                &my_private,
+                &UnparsedPublicKey::new(alg, peer_public.as_ref()),
+                (),
+                |secret| Ok(secret.to_vec()),
+            )
+            .unwrap();
+            let peer_secret = agree(
+                &peer_private,
+                &UnparsedPublicKey::new(alg, my_public.as_ref()),
+                (),
+                |secret| Ok(secret.to_vec()),
+            )
+            .unwrap();
+            assert_eq!(my_secret.len(), alg.id.pub_key_len());
+            assert_eq!(my_secret, peer_secret);
+
+            // Peer public keys outside of (1, p - 1) are rejected.
+            let len = alg.id.pub_key_len();
+            let mut one = vec![0u8; len];
+            one[len - 1] = 1;
+            for bad_key in [vec![0u8; len], one, vec![0xffu8; len]] {
+                assert!(agree(
+                    &my_private,
+                    &UnparsedPublicKey::new(alg, &bad_key),
+                    (),
+                    |_| Ok(())
+                )
+                .is_err());
+            }
+
+            // Keys with the wrong length are rejected.
+            assert!(agree(
+                &my_private,
+                &UnparsedPublicKey::new(alg, &peer_public.as_ref()[1..]),
+                (),
+                |_| Ok(())
+            )
+            .is_err());
+
+            // FFDHE private keys cannot be imported or exported.
+            assert!(PrivateKey::from_private_key(alg, &vec![1u8; len]).is_err());
+            assert!(AsDer::<EcPrivateKeyRfc5915Der>::as_der(&my_private).is_err());
+            assert!(AsBigEndian::<EcPrivateKeyBin>::as_be_bytes(&my_private).is_err());
+        }
+        assert_eq!(format!("{FFDHE3072:?}"), "Algorithm { group: ffdhe3072 }");
+    }
+
     #[test]
     fn test_agreement_ecdh_p256() {
         let alg = &ECDH_P256;
@@ -1130,27 +1942,27 @@ mod tests {
 
         let bytes = [0x01, 0x02, 0x03];
 
-        let unparsed_public_key = UnparsedPublicKey::new(&X25519, &bytes);
+        let unparsed_public_key = UnparsedPublicKey::new(&ECDH_P256, &bytes);
         let unparsed_pubkey_clone = unparsed_public_key;
         assert_eq!(
             format!("{unparsed_public_key:?}"),
-            r#"UnparsedPublicKey { algorithm: Algorithm { curve: Curve25519 }, bytes: "010203" }"#
+            r#"UnparsedPublicKey { algorithm: Algorithm { curve: P256 }, bytes: "010203" }"#
         );
         assert_eq!(
             format!("{unparsed_pubkey_clone:?}"),
-            r#"UnparsedPublicKey { algorithm: Algorithm { curve: Curve25519 }, bytes: "010203" }"#
+            r#"UnparsedPublicKey { algorithm: Algorithm { curve: P256 }, bytes: "010203" }"#
         );
 
-        let unparsed_public_key = UnparsedPublicKey::new(&X25519, Vec::from(bytes));
+        let unparsed_public_key = UnparsedPublicKey::new(&ECDH_P256, Vec::from(bytes));
         #[allow(clippy::redundant_clone)]
         let unparsed_pubkey_clone = unparsed_public_key.clone();
         assert_eq!(
             format!("{unparsed_public_key:?}"),
-            r#"UnparsedPublicKey { algorithm: Algorithm { curve: Curve25519 }, bytes: "010203" }"#
+            r#"UnparsedPublicKey { algorithm: Algorithm { curve: P256 }, bytes: "010203" }"#
         );
         assert_eq!(
             format!("{unparsed_pubkey_clone:?}"),
-            r#"UnparsedPublicKey { algorithm: Algorithm { curve: Curve25519 }, bytes: "010203" }"#
+            r#"UnparsedPublicKey { algorithm: Algorithm { curve: P256 }, bytes: "010203" }"#
         );
     }
 }
@@ -1,8 +1,10 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
-use crate::agreement::{agree, Algorithm, PrivateKey, PublicKey, UnparsedPublicKey};
+use crate::agreement::{agree, Algorithm, PrivateKey, PublicKey, SharedSecret, UnparsedPublicKey};
 use crate::error::Unspecified;
+#[cfg(feature = "ephemeral-key-export")]
+use crate::pkcs8::Document;
 use crate::rand::SecureRandom;
 use core::fmt;
 use core::fmt::{Debug, Formatter};
@@ -64,6 +66,35 @@ impl EphemeralPrivateKey {
     pub fn algorithm(&self) -> &'static Algorithm {
         self.0.algorithm()
     }
+
+    /// Performs a key agreement with the given peer public key, returning the key material as
+    /// a `SharedSecret`. Like `agree_ephemeral`, this consumes the private key.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the peer public key is not valid for this key's algorithm, or if
+    /// the key agreement fails.
+    pub fn agree<B: AsRef<[u8]>>(
+        self,
+        peer_public_key: &UnparsedPublicKey<B>,
+    ) -> Result<SharedSecret, Unspecified> {
+        self.0.agree(peer_public_key)
+    }
+}
+
+#[cfg(feature = "ephemeral-key-export")]
+impl EphemeralPrivateKey {
+    /// Serializes the key as a PKCS#8 v1 `PrivateKeyInfo` structure.
+    ///
+    /// Exporting an ephemeral key defeats the guarantee that it is only used once; this is
+    /// only available with the `ephemeral-key-export` feature.
+    ///
+    /// FFDHE is not supported.
+    ///
+    /// # Errors
+    /// `error::Unspecified`  if serialization failed.
+    pub fn to_pkcs8v1(&self) -> Result<Document, Unspecified> {
+        self.0.to_pkcs8v1()
+    }
 }
 
 /// Performs a key agreement with an ephemeral private key and the given public
@@ -114,9 +145,29 @@ where
 
 #[cfg(test)]
 mod tests {
+    #[cfg(not(feature = "strict-fips"))]
     use crate::error::Unspecified;
-    use crate::{agreement, rand, test, test_file};
+    #[cfg(not(feature = "strict-fips"))]
+    use crate::test_file;
+    use crate::{agreement, rand, test};
+
+    #[cfg(feature = "ephemeral-key-export")]
+    #[test]
+    fn test_ephemeral_pkcs8_export() {
+        let rng = rand::SystemRandom::new();
+        let private_key =
+            agreement::EphemeralPrivateKey::generate(&agreement::ECDH_P256, &rng).unwrap();
+        let pkcs8 = private_key.to_pkcs8v1().unwrap();
+        let imported =
+            agreement::PrivateKey::from_private_key_der(&agreement::ECDH_P256, pkcs8.as_ref())
+                .unwrap();
+        assert_eq!(
+            private_key.compute_public_key().unwrap().as_ref(),
+            imported.compute_public_key().unwrap().as_ref()
+        );
+    }
 
+    #[cfg(not(feature = "strict-fips"))]
     #[test]
     fn test_agreement_ecdh_x25519_rfc_iterated() {
         fn expect_iterated_x25519(
@@ -168,6 +219,7 @@ mod tests {
         */
     }
 
+    #[cfg(not(feature = "strict-fips"))]
     #[test]
     fn test_agreement_x25519() {
         let alg = &agreement::X25519;
@@ -348,6 +400,8 @@ mod tests {
         );
     }
 
+    // The test vectors include X25519.
+    #[cfg(not(feature = "strict-fips"))]
     #[test]
     fn agreement_agree_ephemeral() {
         let rng = rand::SystemRandom::new();
@@ -422,6 +476,7 @@ mod tests {
         );
     }
 
+    #[cfg(not(feature = "strict-fips"))]
     fn from_hex(s: &str) -> Vec<u8> {
         match test::from_hex(s) {
             Ok(v) => v,
@@ -431,6 +486,7 @@ mod tests {
         }
     }
 
+    #[cfg(not(feature = "strict-fips"))]
     fn alg_from_curve_name(curve_name: &str) -> &'static agreement::Algorithm {
         if curve_name == "P-256" {
             &agreement::ECDH_P256
@@ -445,10 +501,12 @@ mod tests {
         }
     }
 
+    #[cfg(not(feature = "strict-fips"))]
     fn x25519(private_key: &[u8], public_key: &[u8]) -> Vec<u8> {
         try_x25519(private_key, public_key).unwrap()
     }
 
+    #[cfg(not(feature = "strict-fips"))]
     fn try_x25519(private_key: &[u8], public_key: &[u8]) -> Result<Vec<u8>, Unspecified> {
         let rng = test::rand::FixedSliceRandom { bytes: private_key };
         let private_key =
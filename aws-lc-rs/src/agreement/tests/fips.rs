@@ -6,7 +6,6 @@
 use crate::{
     agreement::{
         agree_ephemeral, EphemeralPrivateKey, UnparsedPublicKey, ECDH_P256, ECDH_P384, ECDH_P521,
-        X25519,
     },
     error::Unspecified,
     fips::{assert_fips_status_indicator, FipsServiceStatus},
@@ -55,4 +54,9 @@ macro_rules! agree_ephemeral_api {
 agree_ephemeral_api!(ecdh_p256, &ECDH_P256, FipsServiceStatus::Approved);
 agree_ephemeral_api!(ecdh_p384, &ECDH_P384, FipsServiceStatus::Approved);
 agree_ephemeral_api!(ecdh_p521, &ECDH_P521, FipsServiceStatus::Approved);
-agree_ephemeral_api!(x25519, &X25519, FipsServiceStatus::NonApproved);
+#[cfg(not(feature = "strict-fips"))]
+agree_ephemeral_api!(
+    x25519,
+    &crate::agreement::X25519,
+    FipsServiceStatus::NonApproved
+);
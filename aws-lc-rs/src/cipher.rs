@@ -191,6 +191,7 @@
 
 pub(crate) mod aes;
 pub(crate) mod block;
+#[cfg(not(feature = "strict-fips"))]
 pub(crate) mod chacha;
 pub(crate) mod key;
 mod padded;
@@ -659,6 +660,7 @@ fn encrypt_aes_ctr_mode(
         SymmetricCipherKey::Aes128 { enc_key, .. } | SymmetricCipherKey::Aes256 { enc_key, .. } => {
             enc_key
         }
+        #[cfg(not(feature = "strict-fips"))]
         _ => return Err(Unspecified),
     };
 
@@ -695,6 +697,7 @@ fn encrypt_aes_cbc_mode(
         SymmetricCipherKey::Aes128 { enc_key, .. } | SymmetricCipherKey::Aes256 { enc_key, .. } => {
             enc_key
         }
+        #[cfg(not(feature = "strict-fips"))]
         _ => return Err(Unspecified),
     };
 
@@ -721,6 +724,7 @@ fn decrypt_aes_cbc_mode<'in_out>(
         SymmetricCipherKey::Aes128 { dec_key, .. } | SymmetricCipherKey::Aes256 { dec_key, .. } => {
             dec_key
         }
+        #[cfg(not(feature = "strict-fips"))]
         _ => return Err(Unspecified),
     };
 
@@ -17,6 +17,7 @@ pub(crate) struct Block {
 pub(crate) const BLOCK_LEN: usize = 16;
 
 impl Block {
+    #[cfg(not(feature = "strict-fips"))]
     #[inline]
     pub(crate) fn zero() -> Self {
         Self { subblocks: [0, 0] }
@@ -3,6 +3,7 @@
 
 use crate::cipher::aes::encrypt_block_aes;
 use crate::cipher::block::Block;
+#[cfg(not(feature = "strict-fips"))]
 use crate::cipher::chacha::ChaCha20Key;
 use crate::cipher::{AES_128_KEY_LEN, AES_256_KEY_LEN};
 use crate::error::Unspecified;
@@ -15,9 +16,18 @@ use std::os::raw::c_uint;
 use zeroize::Zeroize;
 
 pub(crate) enum SymmetricCipherKey {
-    Aes128 { enc_key: AES_KEY, dec_key: AES_KEY },
-    Aes256 { enc_key: AES_KEY, dec_key: AES_KEY },
-    ChaCha20 { raw_key: ChaCha20Key },
+    Aes128 {
+        enc_key: AES_KEY,
+        dec_key: AES_KEY,
+    },
+    Aes256 {
+        enc_key: AES_KEY,
+        dec_key: AES_KEY,
+    },
+    #[cfg(not(feature = "strict-fips"))]
+    ChaCha20 {
+        raw_key: ChaCha20Key,
+    },
 }
 
 unsafe impl Send for SymmetricCipherKey {}
@@ -42,6 +52,7 @@ impl Drop for SymmetricCipherKey {
                     .unwrap();
                 dec_bytes.zeroize();
             },
+            #[cfg(not(feature = "strict-fips"))]
             SymmetricCipherKey::ChaCha20 { .. } => {}
         }
     }
@@ -115,6 +126,7 @@ impl SymmetricCipherKey {
         }
     }
 
+    #[cfg(not(feature = "strict-fips"))]
     pub(crate) fn chacha20(key_bytes: &[u8]) -> Result<Self, Unspecified> {
         if key_bytes.len() != 32 {
             return Err(Unspecified);
@@ -134,6 +146,7 @@ impl SymmetricCipherKey {
         match self {
             SymmetricCipherKey::Aes128 { enc_key, .. }
             | SymmetricCipherKey::Aes256 { enc_key, .. } => encrypt_block_aes(enc_key, block),
+            #[cfg(not(feature = "strict-fips"))]
             SymmetricCipherKey::ChaCha20 { .. } => panic!("Unsupported algorithm!"),
         }
     }
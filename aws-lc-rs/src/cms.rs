@@ -0,0 +1,609 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Cryptographic Message Syntax (CMS) signatures, as described in [RFC 5652].
+//!
+//! A [`SignedData`] is the `SignedData` content type of CMS, also known as PKCS#7, as used by
+//! S/MIME (`.p7s` files), code signing and package signing. Its content is either attached,
+//! and verified with [`SignedData::verify`], or detached, and verified with
+//! [`SignedData::verify_detached`]. Both verify every signer's signature and build a chain
+//! from each signer's certificate to an `x509::Verifier`'s trust anchors, using the
+//! certificates included in the `SignedData` as intermediates.
+//!
+//! Signatures are supported with RSA PKCS#1 v1.5, ECDSA on P-256, P-384 and P-521, and
+//! Ed25519 keys (RFC 8419), using SHA-256, SHA-384 or SHA-512. Only DER-encoded inputs are
+//! supported; BER-encoded ones, such as those streamed with indefinite lengths, are rejected.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::cms::SignedData;
+//! use aws_lc_rs::x509::{Certificate, VerificationOptions, Verifier};
+//!
+//! let root = Certificate::from_der(&std::fs::read("ca.der")?)?;
+//! let verifier = Verifier::new(&[root])?;
+//!
+//! let content = std::fs::read("message.txt")?;
+//! let signed_data = SignedData::from_der(&std::fs::read("message.txt.p7s")?)?;
+//! let signers = signed_data.verify_detached(&content, &verifier, &VerificationOptions::new())?;
+//! for signer in signers {
+//!     println!("signed by {}", signer.subject());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [RFC 5652]: https://tools.ietf.org/html/rfc5652
+
+use crate::der::{Reader, Tag};
+use crate::digest;
+use crate::error::{KeyRejected, Unspecified};
+use crate::policy;
+use crate::signature::{self, PublicKeyType, VerificationAlgorithm};
+use crate::x509::{Certificate, VerificationOptions, Verifier};
+use core::fmt::{self, Debug, Formatter};
+
+// The DER-encoded contents of the object identifiers used by CMS signatures.
+const OID_SIGNED_DATA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x07, 0x02];
+const OID_CONTENT_TYPE: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x03];
+const OID_MESSAGE_DIGEST: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x04];
+const OID_SHA256: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01];
+const OID_SHA384: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02];
+const OID_SHA512: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03];
+const OID_RSA_ENCRYPTION: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+const OID_SHA256_WITH_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b];
+const OID_SHA384_WITH_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0c];
+const OID_SHA512_WITH_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0d];
+const OID_ECDSA_WITH_SHA256: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02];
+const OID_ECDSA_WITH_SHA384: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x03];
+const OID_ECDSA_WITH_SHA512: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x04];
+#[cfg(not(feature = "strict-fips"))]
+const OID_ED25519: &[u8] = &[0x2b, 0x65, 0x70];
+
+/// A parsed CMS `SignedData`, whose signatures have not been verified.
+pub struct SignedData {
+    der: Box<[u8]>,
+    content_type: String,
+    content: Option<Box<[u8]>>,
+    certificates: Vec<Certificate>,
+    signer_infos: Vec<SignerInfo>,
+}
+
+// A `SignerInfo` of a `SignedData`.
+struct SignerInfo {
+    signer_id: SignerId,
+    digest_algorithm: Box<[u8]>,
+    // The DER encoding of the `[0] IMPLICIT` signed attributes, if any.
+    signed_attributes: Option<Box<[u8]>>,
+    signature_algorithm: Box<[u8]>,
+    signature: Box<[u8]>,
+}
+
+// How a `SignerInfo` identifies the signer's certificate.
+enum SignerId {
+    IssuerAndSerialNumber {
+        issuer: Box<[u8]>,
+        serial_number: Box<[u8]>,
+    },
+    SubjectKeyIdentifier(Box<[u8]>),
+}
+
+impl SignedData {
+    /// Parses a DER-encoded CMS `ContentInfo` whose content is a `SignedData`.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `der` is not a well-formed `SignedData`, including when one of
+    /// its certificates is malformed.
+    pub fn from_der(der: &[u8]) -> Result<Self, KeyRejected> {
+        Self::parse(der).map_err(|_| KeyRejected::invalid_encoding())
+    }
+
+    fn parse(der: &[u8]) -> Result<Self, Unspecified> {
+        let mut input = Reader::new(der);
+        let mut content_info = input.read_sequence()?;
+        input.finish()?;
+        if content_info.read(Tag::OBJECT_IDENTIFIER)?.remaining() != OID_SIGNED_DATA {
+            return Err(Unspecified);
+        }
+        let mut explicit = content_info.read(Tag::context_specific(0).constructed())?;
+        content_info.finish()?;
+        let mut signed_data = explicit.read_sequence()?;
+        explicit.finish()?;
+
+        signed_data.read_u64()?;
+        signed_data.read(Tag::SET)?;
+        let mut encapsulated_content_info = signed_data.read_sequence()?;
+        let content_type = encapsulated_content_info.read_oid()?;
+        let content = match encapsulated_content_info
+            .read_optional(Tag::context_specific(0).constructed())?
+        {
+            Some(mut explicit) => {
+                let content = explicit.read_octet_string()?;
+                explicit.finish()?;
+                Some(content.into())
+            }
+            None => None,
+        };
+        encapsulated_content_info.finish()?;
+
+        let mut certificates = Vec::new();
+        if let Some(mut certs) =
+            signed_data.read_optional(Tag::context_specific(0).constructed())?
+        {
+            while !certs.is_empty() {
+                // Other certificate formats, such as attribute certificates, are skipped.
+                let tag = certs.peek_tag();
+                let certificate = certs.read_any_element()?;
+                if tag == Some(Tag::SEQUENCE) {
+                    certificates.push(Certificate::from_der(certificate)?);
+                }
+            }
+        }
+        signed_data.read_optional(Tag::context_specific(1).constructed())?;
+
+        let mut signer_infos = Vec::new();
+        let mut signer_info_set = signed_data.read(Tag::SET)?;
+        signed_data.finish()?;
+        while !signer_info_set.is_empty() {
+            signer_infos.push(SignerInfo::parse(signer_info_set.read_sequence()?)?);
+        }
+        if signer_infos.is_empty() {
+            return Err(Unspecified);
+        }
+
+        Ok(Self {
+            der: der.into(),
+            content_type,
+            content,
+            certificates,
+            signer_infos,
+        })
+    }
+
+    /// The type of the content, as a dotted object identifier, e.g. `1.2.840.113549.1.7.1`
+    /// for data.
+    #[must_use]
+    pub fn content_type(&self) -> &str {
+        &self.content_type
+    }
+
+    /// The attached content, or `None` if the content is detached.
+    #[must_use]
+    pub fn content(&self) -> Option<&[u8]> {
+        self.content.as_deref()
+    }
+
+    /// The certificates included by the signers, usually their certificates and the
+    /// intermediates needed to verify them.
+    #[must_use]
+    pub fn certificates(&self) -> &[Certificate] {
+        &self.certificates
+    }
+
+    /// Verifies the signatures over the attached content, and the certificate of each signer
+    /// with `verifier` and `options`.
+    ///
+    /// Returns the signers' certificates, in the order of their `SignerInfo`s.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the content is detached, a signer's certificate is not included
+    /// or cannot be verified, or a signature is not valid or uses an unsupported algorithm.
+    pub fn verify(
+        &self,
+        verifier: &Verifier,
+        options: &VerificationOptions,
+    ) -> Result<Vec<Certificate>, Unspecified> {
+        let content = self.content.as_deref().ok_or(Unspecified)?;
+        self.verify_content(content, verifier, options)
+    }
+
+    /// Verifies the signatures over `content`, which is detached from the `SignedData`, and
+    /// the certificate of each signer with `verifier` and `options`.
+    ///
+    /// Returns the signers' certificates, in the order of their `SignerInfo`s.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the content is attached, a signer's certificate is not included
+    /// or cannot be verified, or a signature is not valid or uses an unsupported algorithm.
+    pub fn verify_detached(
+        &self,
+        content: &[u8],
+        verifier: &Verifier,
+        options: &VerificationOptions,
+    ) -> Result<Vec<Certificate>, Unspecified> {
+        if self.content.is_some() {
+            return Err(Unspecified);
+        }
+        self.verify_content(content, verifier, options)
+    }
+
+    fn verify_content(
+        &self,
+        content: &[u8],
+        verifier: &Verifier,
+        options: &VerificationOptions,
+    ) -> Result<Vec<Certificate>, Unspecified> {
+        let mut signers = Vec::with_capacity(self.signer_infos.len());
+        for signer_info in &self.signer_infos {
+            let signer = self
+                .certificates
+                .iter()
+                .find(|certificate| signer_info.signer_id.matches(certificate))
+                .ok_or(Unspecified)?;
+            signer_info.verify(signer, &self.content_type, content)?;
+            // The chain starts with the signer's certificate.
+            let chain = verifier.verify(signer, &self.certificates, options)?;
+            signers.extend(chain.into_iter().next());
+        }
+        Ok(signers)
+    }
+}
+
+impl AsRef<[u8]> for SignedData {
+    /// The DER encoding of the `ContentInfo`.
+    fn as_ref(&self) -> &[u8] {
+        &self.der
+    }
+}
+
+impl Debug for SignedData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignedData")
+            .field("content_type", &self.content_type)
+            .field("detached", &self.content.is_none())
+            .field("certificates", &self.certificates)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SignerInfo {
+    fn parse(mut signer_info: Reader<'_>) -> Result<Self, Unspecified> {
+        // Version 1 identifies the signer by issuer and serial number, and version 3 by
+        // subject key identifier.
+        let version = signer_info.read_u64()?;
+        let signer_id = if version == 1 {
+            let mut issuer_and_serial_number = signer_info.read_sequence()?;
+            let issuer = issuer_and_serial_number.read_element(Tag::SEQUENCE)?;
+            let serial_number = issuer_and_serial_number.read_unsigned_integer()?;
+            issuer_and_serial_number.finish()?;
+            SignerId::IssuerAndSerialNumber {
+                issuer: issuer.into(),
+                serial_number: serial_number.into(),
+            }
+        } else if version == 3 {
+            let key_id = signer_info.read(Tag::context_specific(0))?.remaining();
+            SignerId::SubjectKeyIdentifier(key_id.into())
+        } else {
+            return Err(Unspecified);
+        };
+        let digest_algorithm = read_algorithm(&mut signer_info)?;
+        let signed_attributes_tag = Tag::context_specific(0).constructed();
+        let signed_attributes = if signer_info.peek_tag() == Some(signed_attributes_tag) {
+            Some(signer_info.read_element(signed_attributes_tag)?.into())
+        } else {
+            None
+        };
+        let signature_algorithm = read_algorithm(&mut signer_info)?;
+        let signature = signer_info.read_octet_string()?;
+        signer_info.read_optional(Tag::context_specific(1).constructed())?;
+        signer_info.finish()?;
+        Ok(Self {
+            signer_id,
+            digest_algorithm: digest_algorithm.into(),
+            signed_attributes,
+            signature_algorithm: signature_algorithm.into(),
+            signature: signature.into(),
+        })
+    }
+
+    fn verify(
+        &self,
+        signer: &Certificate,
+        content_type: &str,
+        content: &[u8],
+    ) -> Result<(), Unspecified> {
+        let digest_algorithm = digest_algorithm(&self.digest_algorithm)?;
+        policy::enforce_digest(digest_algorithm)?;
+        let public_key = signer.public_key()?;
+        let algorithm = verification_algorithm(
+            &self.signature_algorithm,
+            &self.digest_algorithm,
+            public_key.key_type(),
+        )?;
+        match &self.signed_attributes {
+            Some(signed_attributes) => {
+                let digest = digest::digest(digest_algorithm, content);
+                check_signed_attributes(signed_attributes, content_type, digest.as_ref())?;
+                // The signature is over the attributes' DER encoding as a `SET OF`, rather
+                // than with the implicit tag.
+                let mut message = signed_attributes.to_vec();
+                message[0] = 0x31;
+                public_key.verify(algorithm, &message, &self.signature)
+            }
+            None => public_key.verify(algorithm, content, &self.signature),
+        }
+    }
+}
+
+impl SignerId {
+    fn matches(&self, certificate: &Certificate) -> bool {
+        match self {
+            SignerId::IssuerAndSerialNumber {
+                issuer,
+                serial_number,
+            } => {
+                **issuer == *certificate.issuer().as_ref()
+                    && **serial_number == *certificate.serial_number()
+            }
+            SignerId::SubjectKeyIdentifier(key_id) => {
+                certificate.subject_key_identifier() == Some(&**key_id)
+            }
+        }
+    }
+}
+
+// Reads an `AlgorithmIdentifier`, and returns the DER-encoded contents of its object
+// identifier. The parameters, if any, are ignored.
+fn read_algorithm<'a>(reader: &mut Reader<'a>) -> Result<&'a [u8], Unspecified> {
+    let mut algorithm = reader.read_sequence()?;
+    Ok(algorithm.read(Tag::OBJECT_IDENTIFIER)?.remaining())
+}
+
+fn digest_algorithm(oid: &[u8]) -> Result<&'static digest::Algorithm, Unspecified> {
+    [
+        (OID_SHA256, &digest::SHA256),
+        (OID_SHA384, &digest::SHA384),
+        (OID_SHA512, &digest::SHA512),
+    ]
+    .into_iter()
+    .find_map(|(id, algorithm)| (id == oid).then_some(algorithm))
+    .ok_or(Unspecified)
+}
+
+// Checks that the signed attributes include the content type and the digest of the content,
+// as required by RFC 5652 Section 5.3.
+fn check_signed_attributes(
+    signed_attributes: &[u8],
+    content_type: &str,
+    digest: &[u8],
+) -> Result<(), Unspecified> {
+    let mut attributes =
+        Reader::new(signed_attributes).read(Tag::context_specific(0).constructed())?;
+    let mut has_content_type = false;
+    let mut has_message_digest = false;
+    while !attributes.is_empty() {
+        let mut attribute = attributes.read_sequence()?;
+        let attribute_type = attribute.read(Tag::OBJECT_IDENTIFIER)?.remaining();
+        let mut values = attribute.read(Tag::SET)?;
+        attribute.finish()?;
+        if attribute_type == OID_CONTENT_TYPE {
+            if has_content_type || values.read_oid()? != content_type {
+                return Err(Unspecified);
+            }
+            has_content_type = true;
+        } else if attribute_type == OID_MESSAGE_DIGEST {
+            if has_message_digest || values.read_octet_string()? != digest {
+                return Err(Unspecified);
+            }
+            has_message_digest = true;
+        } else {
+            continue;
+        }
+        // Both attributes have a single value.
+        values.finish()?;
+    }
+    if !has_content_type || !has_message_digest {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
+fn verification_algorithm(
+    signature_algorithm: &[u8],
+    digest_algorithm: &[u8],
+    key_type: PublicKeyType,
+) -> Result<&'static dyn VerificationAlgorithm, Unspecified> {
+    // A signature algorithm, digest algorithm and key type, and their verification algorithm.
+    type Entry = (
+        &'static [u8],
+        &'static [u8],
+        PublicKeyType,
+        &'static dyn VerificationAlgorithm,
+    );
+    // Signers may identify RSA PKCS#1 v1.5 signatures by the key's algorithm alone.
+    let algorithms: &[Entry] = &[
+        (
+            OID_RSA_ENCRYPTION,
+            OID_SHA256,
+            PublicKeyType::Rsa,
+            &signature::RSA_PKCS1_2048_8192_SHA256,
+        ),
+        (
+            OID_RSA_ENCRYPTION,
+            OID_SHA384,
+            PublicKeyType::Rsa,
+            &signature::RSA_PKCS1_2048_8192_SHA384,
+        ),
+        (
+            OID_RSA_ENCRYPTION,
+            OID_SHA512,
+            PublicKeyType::Rsa,
+            &signature::RSA_PKCS1_2048_8192_SHA512,
+        ),
+        (
+            OID_SHA256_WITH_RSA,
+            OID_SHA256,
+            PublicKeyType::Rsa,
+            &signature::RSA_PKCS1_2048_8192_SHA256,
+        ),
+        (
+            OID_SHA384_WITH_RSA,
+            OID_SHA384,
+            PublicKeyType::Rsa,
+            &signature::RSA_PKCS1_2048_8192_SHA384,
+        ),
+        (
+            OID_SHA512_WITH_RSA,
+            OID_SHA512,
+            PublicKeyType::Rsa,
+            &signature::RSA_PKCS1_2048_8192_SHA512,
+        ),
+        (
+            OID_ECDSA_WITH_SHA256,
+            OID_SHA256,
+            PublicKeyType::EcdsaP256,
+            &signature::ECDSA_P256_SHA256_ASN1,
+        ),
+        (
+            OID_ECDSA_WITH_SHA384,
+            OID_SHA384,
+            PublicKeyType::EcdsaP256,
+            &signature::ECDSA_P256_SHA384_ASN1,
+        ),
+        (
+            OID_ECDSA_WITH_SHA256,
+            OID_SHA256,
+            PublicKeyType::EcdsaP384,
+            &signature::ECDSA_P384_SHA256_ASN1,
+        ),
+        (
+            OID_ECDSA_WITH_SHA384,
+            OID_SHA384,
+            PublicKeyType::EcdsaP384,
+            &signature::ECDSA_P384_SHA384_ASN1,
+        ),
+        (
+            OID_ECDSA_WITH_SHA512,
+            OID_SHA512,
+            PublicKeyType::EcdsaP521,
+            &signature::ECDSA_P521_SHA512_ASN1,
+        ),
+        // RFC 8419 requires SHA-512 for the signed attributes' message digest.
+        #[cfg(not(feature = "strict-fips"))]
+        (
+            OID_ED25519,
+            OID_SHA512,
+            PublicKeyType::Ed25519,
+            &signature::ED25519,
+        ),
+    ];
+    algorithms
+        .iter()
+        .copied()
+        .find_map(|(id, digest_id, algorithm_key_type, algorithm)| {
+            (id == signature_algorithm
+                && digest_id == digest_algorithm
+                && algorithm_key_type == key_type)
+                .then_some(algorithm)
+        })
+        .ok_or(Unspecified)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SignedData;
+    use crate::x509::{Certificate, KeyPurpose, VerificationOptions, Verifier};
+
+    const CA: &[u8] = include_bytes!("../tests/data/x509_test_ca.der");
+    const LEAF: &[u8] = include_bytes!("../tests/data/x509_test_leaf.der");
+    const CONTENT: &[u8] = include_bytes!("../tests/data/cms_test_content.txt");
+    const ATTACHED: &[u8] = include_bytes!("../tests/data/cms_test_attached.der");
+    const DETACHED: &[u8] = include_bytes!("../tests/data/cms_test_detached.der");
+    const RSA: &[u8] = include_bytes!("../tests/data/cms_test_rsa.der");
+
+    fn verifier() -> Verifier {
+        Verifier::new(&[Certificate::from_der(CA).unwrap()]).unwrap()
+    }
+
+    #[test]
+    fn test_attached() {
+        let signed_data = SignedData::from_der(ATTACHED).unwrap();
+        assert_eq!(ATTACHED, signed_data.as_ref());
+        assert_eq!("1.2.840.113549.1.7.1", signed_data.content_type());
+        assert_eq!(Some(CONTENT), signed_data.content());
+        assert_eq!(1, signed_data.certificates().len());
+        let signers = signed_data
+            .verify(&verifier(), &VerificationOptions::new())
+            .unwrap();
+        assert_eq!(LEAF, signers[0].as_ref());
+        assert!(signed_data
+            .verify_detached(CONTENT, &verifier(), &VerificationOptions::new())
+            .is_err());
+    }
+
+    // Signed with Ed25519.
+    #[cfg(not(feature = "strict-fips"))]
+    #[test]
+    fn test_detached() {
+        let signed_data = SignedData::from_der(DETACHED).unwrap();
+        assert_eq!(None, signed_data.content());
+        // The signer's certificate and its intermediate.
+        assert_eq!(2, signed_data.certificates().len());
+        let signers = signed_data
+            .verify_detached(CONTENT, &verifier(), &VerificationOptions::new())
+            .unwrap();
+        assert_eq!(
+            "CN=client.example.com,O=Test",
+            signers[0].subject().to_string()
+        );
+        assert!(signed_data
+            .verify(&verifier(), &VerificationOptions::new())
+            .is_err());
+        assert!(signed_data
+            .verify_detached(b"hello, world", &verifier(), &VerificationOptions::new())
+            .is_err());
+    }
+
+    #[test]
+    fn test_without_signed_attributes() {
+        // Signed with RSA, without signed attributes, by a signer identified by its subject
+        // key identifier.
+        let signed_data = SignedData::from_der(RSA).unwrap();
+        let options = VerificationOptions::new().purpose(KeyPurpose::EmailProtection);
+        let signers = signed_data.verify(&verifier(), &options).unwrap();
+        assert_eq!(
+            "CN=signer@example.com,O=Test",
+            signers[0].subject().to_string()
+        );
+    }
+
+    #[test]
+    fn test_verify_errors() {
+        // The signer's certificate is not trusted.
+        let leaf = Verifier::new(&[Certificate::from_der(LEAF).unwrap()]).unwrap();
+        let signed_data = SignedData::from_der(DETACHED).unwrap();
+        assert!(signed_data
+            .verify_detached(CONTENT, &leaf, &VerificationOptions::new())
+            .is_err());
+        // The signer's certificate is not for email protection.
+        let signed_data = SignedData::from_der(ATTACHED).unwrap();
+        let options = VerificationOptions::new().purpose(KeyPurpose::EmailProtection);
+        assert!(signed_data.verify(&verifier(), &options).is_err());
+
+        // Tampered content, with and without signed attributes.
+        for der in [ATTACHED, RSA] {
+            let mut tampered = der.to_vec();
+            let offset = tampered
+                .windows(CONTENT.len())
+                .position(|window| window == CONTENT)
+                .unwrap();
+            tampered[offset] ^= 1;
+            let signed_data = SignedData::from_der(&tampered).unwrap();
+            assert!(signed_data
+                .verify(&verifier(), &VerificationOptions::new())
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_from_der_errors() {
+        assert!(SignedData::from_der(&[]).is_err());
+        assert!(SignedData::from_der(&ATTACHED[..ATTACHED.len() - 1]).is_err());
+        let mut trailing = ATTACHED.to_vec();
+        trailing.push(0);
+        assert!(SignedData::from_der(&trailing).is_err());
+        assert!(SignedData::from_der(LEAF).is_err());
+    }
+}
@@ -0,0 +1,564 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Certificate Transparency signed certificate timestamps (SCTs), as described in [RFC 6962].
+//!
+//! An SCT is a log's promise to publish a certificate. A certificate's SCTs are delivered in
+//! one of three ways, each with its own constructor:
+//! * embedded in the certificate by its issuer: [`SignedCertificateTimestamp::from_certificate`];
+//! * in the `signed_certificate_timestamp` TLS extension:
+//!   [`SignedCertificateTimestamp::from_tls_extension`];
+//! * in a stapled OCSP response, with the `ocsp` feature:
+//!   `SignedCertificateTimestamp::from_ocsp_response`.
+//!
+//! [`SignedCertificateTimestamp::verify`] checks an SCT's signature against a set of trusted
+//! [`Log`]s, and returns the log that issued it. Policies, such as requiring SCTs from a number
+//! of distinct logs, are built on top of it.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::ct::{Log, SignedCertificateTimestamp};
+//! use aws_lc_rs::x509::Certificate;
+//! use std::time::SystemTime;
+//!
+//! let logs = [Log::from_der(&std::fs::read("log.der")?)?];
+//! let issuer = Certificate::from_der(&std::fs::read("ca.der")?)?;
+//! let certificate = Certificate::from_der(&std::fs::read("server.der")?)?;
+//!
+//! let scts = SignedCertificateTimestamp::from_certificate(&certificate)?;
+//! let log = scts[0].verify(&logs, &certificate, &issuer, SystemTime::now())?;
+//! assert_eq!(logs[0].id(), log.id());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [RFC 6962]: https://tools.ietf.org/html/rfc6962
+
+use crate::der::{self, Reader, Tag};
+use crate::digest;
+use crate::error::{KeyRejected, Unspecified};
+use crate::signature::{self, PublicKey, PublicKeyType, VerificationAlgorithm};
+use crate::x509::Certificate;
+use core::fmt::{self, Debug, Formatter};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// The extension holding embedded SCTs in certificates, and its counterpart in OCSP responses.
+const OID_CT_PRECERT_SCTS: &str = "1.3.6.1.4.1.11129.2.4.2";
+const OID_CT_PRECERT_SCTS_DER: &[u8] =
+    &[0x2b, 0x06, 0x01, 0x04, 0x01, 0xd6, 0x79, 0x02, 0x04, 0x02];
+#[cfg(feature = "ocsp")]
+const OID_CT_OCSP_SCTS_DER: &[u8] = &[0x2b, 0x06, 0x01, 0x04, 0x01, 0xd6, 0x79, 0x02, 0x04, 0x05];
+
+const LOG_ID_LEN: usize = 32;
+
+// RFC 6962 Section 3.2: the only version is v1, whose signatures cover a
+// `certificate_timestamp`, and certificates are logged as `x509_entry` or `precert_entry`.
+const VERSION_V1: u8 = 0;
+const SIGNATURE_TYPE_CERTIFICATE_TIMESTAMP: u8 = 0;
+const ENTRY_TYPE_X509: [u8; 2] = [0, 0];
+const ENTRY_TYPE_PRECERT: [u8; 2] = [0, 1];
+
+// RFC 5246 Section 7.4.1.4.1 `HashAlgorithm` and `SignatureAlgorithm` values.
+const HASH_SHA256: u8 = 4;
+const SIGNATURE_RSA: u8 = 1;
+const SIGNATURE_ECDSA: u8 = 3;
+
+/// A Certificate Transparency log, identified by its public key.
+#[derive(Clone)]
+pub struct Log {
+    id: [u8; LOG_ID_LEN],
+    public_key: PublicKey,
+}
+
+impl Log {
+    /// Creates a log from its DER-encoded (X.509) `SubjectPublicKeyInfo`, as published in log
+    /// lists.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `spki` is not an ECDSA P-256 or RSA public key.
+    pub fn from_der(spki: &[u8]) -> Result<Self, KeyRejected> {
+        let public_key = PublicKey::from_der(spki)?;
+        match public_key.key_type() {
+            PublicKeyType::EcdsaP256 | PublicKeyType::Rsa => {}
+            _ => return Err(KeyRejected::wrong_algorithm()),
+        }
+        let mut id = [0u8; LOG_ID_LEN];
+        id.copy_from_slice(digest::digest_unchecked(&digest::SHA256, spki).as_ref());
+        Ok(Self { id, public_key })
+    }
+
+    /// The log's ID: the SHA-256 hash of its `SubjectPublicKeyInfo`.
+    #[must_use]
+    pub fn id(&self) -> &[u8; LOG_ID_LEN] {
+        &self.id
+    }
+}
+
+impl Debug for Log {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Log")
+            .field("id", &crate::hex::encode(self.id))
+            .finish_non_exhaustive()
+    }
+}
+
+/// How a signed certificate timestamp was delivered.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Source {
+    /// Embedded in the certificate; the log signed the precertificate.
+    Embedded,
+    /// In the `signed_certificate_timestamp` TLS extension.
+    TlsExtension,
+    /// In an OCSP response for the certificate.
+    OcspResponse,
+}
+
+/// A version 1 signed certificate timestamp.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SignedCertificateTimestamp {
+    source: Source,
+    log_id: [u8; LOG_ID_LEN],
+    timestamp: u64,
+    extensions: Box<[u8]>,
+    hash_algorithm: u8,
+    signature_algorithm: u8,
+    signature: Box<[u8]>,
+}
+
+impl SignedCertificateTimestamp {
+    /// Parses the SCTs embedded in `certificate` by its issuer. A certificate without the
+    /// embedded SCT extension has none.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the extension is malformed.
+    pub fn from_certificate(certificate: &Certificate) -> Result<Vec<Self>, Unspecified> {
+        match certificate
+            .extensions()?
+            .into_iter()
+            .find(|extension| extension.oid() == OID_CT_PRECERT_SCTS)
+        {
+            Some(extension) => parse_extension_value(extension.value(), Source::Embedded),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Parses the contents of the `signed_certificate_timestamp` TLS extension (RFC 6962
+    /// Section 3.3), a `SignedCertificateTimestampList`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `list` is malformed.
+    pub fn from_tls_extension(list: &[u8]) -> Result<Vec<Self>, Unspecified> {
+        parse_list(list, Source::TlsExtension)
+    }
+
+    /// Verifies `response` as `ocsp::OcspResponse::verify` does, and parses the SCTs in its
+    /// response for `certificate`. A response without the SCT extension has none.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the response is not valid for `certificate` at `time`, or its
+    /// SCT extension is malformed.
+    #[cfg(feature = "ocsp")]
+    pub fn from_ocsp_response(
+        response: &[u8],
+        certificate: &Certificate,
+        issuer: &Certificate,
+        time: SystemTime,
+    ) -> Result<Vec<Self>, Unspecified> {
+        let (_, extensions) =
+            crate::ocsp::verify_with_single_extensions(response, certificate, issuer, time)?;
+        let mut extensions = match extensions {
+            Some(mut extensions) => extensions.read_sequence()?,
+            None => return Ok(Vec::new()),
+        };
+        while !extensions.is_empty() {
+            let mut extension = extensions.read_sequence()?;
+            if extension.read(Tag::OBJECT_IDENTIFIER)?.remaining() != OID_CT_OCSP_SCTS_DER {
+                continue;
+            }
+            extension.read_optional(Tag::BOOLEAN)?;
+            let value = extension.read_octet_string()?;
+            extension.finish()?;
+            return parse_extension_value(value, Source::OcspResponse);
+        }
+        Ok(Vec::new())
+    }
+
+    /// How the SCT was delivered.
+    #[must_use]
+    pub fn source(&self) -> Source {
+        self.source
+    }
+
+    /// The ID of the log that issued the SCT, i.e. `Log::id`.
+    #[must_use]
+    pub fn log_id(&self) -> &[u8; LOG_ID_LEN] {
+        &self.log_id
+    }
+
+    /// The time at which the log issued the SCT.
+    #[must_use]
+    pub fn timestamp(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.timestamp)
+    }
+
+    /// The SCT's extensions, which are not defined for version 1.
+    #[must_use]
+    pub fn extensions(&self) -> &[u8] {
+        &self.extensions
+    }
+
+    /// Verifies that the SCT was issued for `certificate` by one of `logs`, no later than
+    /// `time`, and returns that log.
+    ///
+    /// `issuer` is the certificate's issuer. Only embedded SCTs use it, since the log signed
+    /// the precertificate, which identifies the issuer by the hash of its public key.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the SCT was not issued by one of `logs`, its signature is not
+    /// valid, or its timestamp is after `time`.
+    pub fn verify<'l>(
+        &self,
+        logs: &'l [Log],
+        certificate: &Certificate,
+        issuer: &Certificate,
+        time: SystemTime,
+    ) -> Result<&'l Log, Unspecified> {
+        let log = logs
+            .iter()
+            .find(|log| log.id == self.log_id)
+            .ok_or(Unspecified)?;
+        if self.timestamp() > time {
+            return Err(Unspecified);
+        }
+
+        let mut signed_data = Vec::new();
+        signed_data.push(VERSION_V1);
+        signed_data.push(SIGNATURE_TYPE_CERTIFICATE_TIMESTAMP);
+        signed_data.extend_from_slice(&self.timestamp.to_be_bytes());
+        if self.source == Source::Embedded {
+            signed_data.extend_from_slice(&ENTRY_TYPE_PRECERT);
+            signed_data.extend_from_slice(
+                digest::digest_unchecked(&digest::SHA256, issuer.subject_public_key_info())
+                    .as_ref(),
+            );
+            write_u24_prefixed(&mut signed_data, &precertificate_tbs(certificate)?)?;
+        } else {
+            signed_data.extend_from_slice(&ENTRY_TYPE_X509);
+            write_u24_prefixed(&mut signed_data, certificate.as_ref())?;
+        }
+        signed_data.extend_from_slice(&u16::try_from(self.extensions.len())?.to_be_bytes());
+        signed_data.extend_from_slice(&self.extensions);
+
+        let algorithm = self.verification_algorithm(log.public_key.key_type())?;
+        log.public_key
+            .verify(algorithm, &signed_data, &self.signature)?;
+        Ok(log)
+    }
+
+    // Logs sign with ECDSA P-256 or RSA, both with SHA-256 (RFC 6962 Section 2.1.4).
+    fn verification_algorithm(
+        &self,
+        key_type: PublicKeyType,
+    ) -> Result<&'static dyn VerificationAlgorithm, Unspecified> {
+        match (self.hash_algorithm, self.signature_algorithm, key_type) {
+            (HASH_SHA256, SIGNATURE_ECDSA, PublicKeyType::EcdsaP256) => {
+                Ok(&signature::ECDSA_P256_SHA256_ASN1)
+            }
+            (HASH_SHA256, SIGNATURE_RSA, PublicKeyType::Rsa) => {
+                Ok(&signature::RSA_PKCS1_2048_8192_SHA256)
+            }
+            _ => Err(Unspecified),
+        }
+    }
+
+    // Parses a `SerializedSCT`, or returns `None` if its version is not supported.
+    fn parse(mut input: &[u8], source: Source) -> Result<Option<Self>, Unspecified> {
+        if read_bytes(&mut input, 1)? != [VERSION_V1] {
+            // RFC 6962 Section 3.3: clients should ignore SCTs of unknown versions.
+            return Ok(None);
+        }
+        let mut log_id = [0u8; LOG_ID_LEN];
+        log_id.copy_from_slice(read_bytes(&mut input, LOG_ID_LEN)?);
+        let mut timestamp = [0u8; 8];
+        timestamp.copy_from_slice(read_bytes(&mut input, 8)?);
+        let extensions = read_u16_prefixed(&mut input)?;
+        let algorithms = read_bytes(&mut input, 2)?;
+        let signature = read_u16_prefixed(&mut input)?;
+        if !input.is_empty() {
+            return Err(Unspecified);
+        }
+        Ok(Some(Self {
+            source,
+            log_id,
+            timestamp: u64::from_be_bytes(timestamp),
+            extensions: extensions.into(),
+            hash_algorithm: algorithms[0],
+            signature_algorithm: algorithms[1],
+            signature: signature.into(),
+        }))
+    }
+}
+
+impl Debug for SignedCertificateTimestamp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignedCertificateTimestamp")
+            .field("source", &self.source)
+            .field("log_id", &crate::hex::encode(self.log_id))
+            .field("timestamp", &self.timestamp())
+            .finish_non_exhaustive()
+    }
+}
+
+// Parses the value of an SCT extension: an OCTET STRING containing the TLS-encoded list.
+fn parse_extension_value(
+    value: &[u8],
+    source: Source,
+) -> Result<Vec<SignedCertificateTimestamp>, Unspecified> {
+    let mut value = Reader::new(value);
+    let list = value.read_octet_string()?;
+    value.finish()?;
+    parse_list(list, source)
+}
+
+// Parses a `SignedCertificateTimestampList` (RFC 6962 Section 3.3).
+fn parse_list(
+    mut input: &[u8],
+    source: Source,
+) -> Result<Vec<SignedCertificateTimestamp>, Unspecified> {
+    let mut list = read_u16_prefixed(&mut input)?;
+    if !input.is_empty() || list.is_empty() {
+        return Err(Unspecified);
+    }
+    let mut scts = Vec::new();
+    while !list.is_empty() {
+        let sct = read_u16_prefixed(&mut list)?;
+        if let Some(sct) = SignedCertificateTimestamp::parse(sct, source)? {
+            scts.push(sct);
+        }
+    }
+    Ok(scts)
+}
+
+// Reconstructs the `TBSCertificate` of the precertificate that the log signed: the
+// certificate's, without the embedded SCT extension (RFC 6962 Section 3.2).
+fn precertificate_tbs(certificate: &Certificate) -> Result<Vec<u8>, Unspecified> {
+    let mut input = Reader::new(certificate.as_ref());
+    let mut certificate = input.read_sequence()?;
+    let mut tbs = certificate.read_sequence()?;
+
+    let mut fields = Vec::new();
+    let mut extensions = Vec::new();
+    while !tbs.is_empty() {
+        if tbs.peek_tag() == Some(Tag::context_specific(3).constructed()) {
+            let mut explicit = tbs.read(Tag::context_specific(3).constructed())?;
+            let mut sequence = explicit.read_sequence()?;
+            explicit.finish()?;
+            while !sequence.is_empty() {
+                let extension = sequence.read_element(Tag::SEQUENCE)?;
+                let mut contents = Reader::new(extension).read_sequence()?;
+                if contents.read(Tag::OBJECT_IDENTIFIER)?.remaining() != OID_CT_PRECERT_SCTS_DER {
+                    extensions.push(extension);
+                }
+            }
+        } else {
+            fields.push(tbs.read_any_element()?);
+        }
+    }
+
+    der::encode(|writer| {
+        writer.write_sequence(|tbs| {
+            for field in &fields {
+                tbs.write_element(field)?;
+            }
+            if extensions.is_empty() {
+                return Ok(());
+            }
+            tbs.write(Tag::context_specific(3).constructed(), |explicit| {
+                explicit.write_sequence(|sequence| {
+                    for extension in &extensions {
+                        sequence.write_element(extension)?;
+                    }
+                    Ok(())
+                })
+            })
+        })
+    })
+}
+
+fn read_bytes<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], Unspecified> {
+    if input.len() < len {
+        return Err(Unspecified);
+    }
+    let (bytes, rest) = input.split_at(len);
+    *input = rest;
+    Ok(bytes)
+}
+
+fn read_u16_prefixed<'a>(input: &mut &'a [u8]) -> Result<&'a [u8], Unspecified> {
+    let len = read_bytes(input, 2)?;
+    read_bytes(input, usize::from(u16::from_be_bytes([len[0], len[1]])))
+}
+
+fn write_u24_prefixed(output: &mut Vec<u8>, bytes: &[u8]) -> Result<(), Unspecified> {
+    let len = u32::try_from(bytes.len())?;
+    if len >= 1 << 24 {
+        return Err(Unspecified);
+    }
+    output.extend_from_slice(&len.to_be_bytes()[1..]);
+    output.extend_from_slice(bytes);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Log, SignedCertificateTimestamp, Source};
+    use crate::x509::Certificate;
+    #[cfg(not(feature = "strict-fips"))]
+    use crate::{
+        encoding::{AsDer, PublicKeyX509Der},
+        signature::{Ed25519KeyPair, KeyPair},
+    };
+    use std::time::{Duration, SystemTime};
+
+    fn logs() -> Vec<Log> {
+        vec![Log::from_der(include_bytes!("../tests/data/ct_test_log.der")).unwrap()]
+    }
+
+    fn issuer() -> Certificate {
+        Certificate::from_der(include_bytes!("../tests/data/x509_test_ca.der")).unwrap()
+    }
+
+    // A time at which the test SCTs and OCSP response are current.
+    fn now(issuer: &Certificate) -> SystemTime {
+        issuer.not_before() + Duration::from_secs(86400)
+    }
+
+    #[test]
+    fn test_embedded() {
+        let logs = logs();
+        let issuer = issuer();
+        let certificate =
+            Certificate::from_der(include_bytes!("../tests/data/ct_test_embedded.der")).unwrap();
+        let scts = SignedCertificateTimestamp::from_certificate(&certificate).unwrap();
+        assert_eq!(1, scts.len());
+        let sct = &scts[0];
+        assert_eq!(Source::Embedded, sct.source());
+        assert_eq!(logs[0].id(), sct.log_id());
+        assert_eq!(
+            certificate.not_before() + Duration::from_secs(1),
+            sct.timestamp()
+        );
+        assert!(sct.extensions().is_empty());
+        sct.verify(&logs, &certificate, &issuer, now(&issuer))
+            .unwrap();
+
+        // The SCT is not valid before it was issued.
+        assert!(sct
+            .verify(&logs, &certificate, &issuer, certificate.not_before())
+            .is_err());
+        // The precertificate identifies its issuer.
+        assert!(sct
+            .verify(&logs, &certificate, &certificate, now(&issuer))
+            .is_err());
+        // The SCT is for another certificate.
+        let leaf =
+            Certificate::from_der(include_bytes!("../tests/data/x509_test_leaf.der")).unwrap();
+        assert!(sct.verify(&logs, &leaf, &issuer, now(&issuer)).is_err());
+
+        assert!(SignedCertificateTimestamp::from_certificate(&leaf)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_tls_extension() {
+        let logs = logs();
+        let issuer = issuer();
+        let certificate =
+            Certificate::from_der(include_bytes!("../tests/data/x509_test_leaf.der")).unwrap();
+        let list = include_bytes!("../tests/data/ct_test_sct_list.bin");
+        let scts = SignedCertificateTimestamp::from_tls_extension(list).unwrap();
+        assert_eq!(2, scts.len());
+        assert!(scts.iter().all(|sct| sct.source() == Source::TlsExtension));
+
+        // The first SCT is from a log that is not trusted.
+        assert!(scts[0]
+            .verify(&logs, &certificate, &issuer, now(&issuer))
+            .is_err());
+        let log = scts[1]
+            .verify(&logs, &certificate, &issuer, now(&issuer))
+            .unwrap();
+        assert_eq!(logs[0].id(), log.id());
+
+        let mut tampered = list.to_vec();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        let scts = SignedCertificateTimestamp::from_tls_extension(&tampered).unwrap();
+        assert!(scts[1]
+            .verify(&logs, &certificate, &issuer, now(&issuer))
+            .is_err());
+
+        assert!(SignedCertificateTimestamp::from_tls_extension(&list[..list.len() - 1]).is_err());
+        assert!(SignedCertificateTimestamp::from_tls_extension(&[0, 0]).is_err());
+        // Unknown versions are ignored.
+        let unknown_version = [0, 3, 0, 1, 1];
+        assert!(
+            SignedCertificateTimestamp::from_tls_extension(&unknown_version)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[cfg(feature = "ocsp")]
+    #[test]
+    fn test_ocsp_response() {
+        let logs = logs();
+        let issuer = issuer();
+        let certificate =
+            Certificate::from_der(include_bytes!("../tests/data/x509_test_leaf.der")).unwrap();
+        let response = include_bytes!("../tests/data/ct_test_ocsp.der");
+        let scts = SignedCertificateTimestamp::from_ocsp_response(
+            response,
+            &certificate,
+            &issuer,
+            now(&issuer),
+        )
+        .unwrap();
+        assert_eq!(1, scts.len());
+        assert_eq!(Source::OcspResponse, scts[0].source());
+        scts[0]
+            .verify(&logs, &certificate, &issuer, now(&issuer))
+            .unwrap();
+
+        assert!(SignedCertificateTimestamp::from_ocsp_response(
+            include_bytes!("../tests/data/ocsp_test_good.der"),
+            &certificate,
+            &issuer,
+            now(&issuer),
+        )
+        .unwrap()
+        .is_empty());
+    }
+
+    #[test]
+    fn test_log() {
+        #[cfg(not(feature = "strict-fips"))]
+        {
+            let key_pair = Ed25519KeyPair::from_seed_unchecked(&[7; 32]).unwrap();
+            let spki = AsDer::<PublicKeyX509Der>::as_der(key_pair.public_key()).unwrap();
+            assert!(Log::from_der(spki.as_ref()).is_err());
+        }
+        let log = Log::from_der(include_bytes!(
+            "../tests/data/rsa_test_public_key_2048.x509"
+        ))
+        .unwrap();
+        assert_eq!(
+            format!("Log {{ id: \"{}\", .. }}", crate::hex::encode(log.id())),
+            format!("{log:?}")
+        );
+    }
+}
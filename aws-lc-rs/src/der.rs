@@ -0,0 +1,720 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! DER encoding and decoding of ASN.1 values.
+//!
+//! A [`Reader`] parses DER-encoded input one element at a time, and [`encode`] builds a DER
+//! encoding with a [`Writer`]. They cover what is needed to handle custom certificate
+//! extensions and similar structures: tagged values, booleans, integers, object identifiers,
+//! strings and sequences. As elsewhere in this crate, malformed input is reported as
+//! [`Unspecified`].
+//!
+//! Unlike `io::der`, which mirrors *ring*'s API, these do not depend on
+//! `untrusted` and also support writing.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::der::{self, Reader, Tag};
+//!
+//! // VendorInfo ::= SEQUENCE { id OBJECT IDENTIFIER, version INTEGER, data [0] IMPLICIT OCTET STRING OPTIONAL }
+//! let encoded = der::encode(|writer| {
+//!     writer.write_sequence(|vendor_info| {
+//!         vendor_info.write_oid("1.3.6.1.4.1.99999.1")?;
+//!         vendor_info.write_u64(2)
+//!     })
+//! })?;
+//!
+//! let mut reader = Reader::new(&encoded);
+//! let mut vendor_info = reader.read_sequence()?;
+//! reader.finish()?;
+//! assert_eq!("1.3.6.1.4.1.99999.1", vendor_info.read_oid()?);
+//! assert_eq!(2, vendor_info.read_u64()?);
+//! assert!(vendor_info.read_optional(Tag::context_specific(0))?.is_none());
+//! vendor_info.finish()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::cbb::LcCBB;
+use crate::cbs;
+use crate::error::Unspecified;
+use crate::ptr::LcPtr;
+use aws_lc::{
+    CBB_add_asn1, CBB_add_asn1_bool, CBB_add_asn1_int64, CBB_add_asn1_oid_from_text,
+    CBB_add_asn1_uint64, CBB_add_bytes, CBB_flush, CBS_asn1_oid_to_text, CBS_data,
+    CBS_get_any_asn1, CBS_get_any_asn1_element, CBS_get_asn1, CBS_get_asn1_bool,
+    CBS_get_asn1_element, CBS_get_asn1_int64, CBS_get_asn1_uint64, CBS_get_optional_asn1,
+    CBS_is_unsigned_asn1_integer, CBS_len, CBB, CBS, CBS_ASN1_TAG,
+};
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use std::ffi::CStr;
+
+const CONSTRUCTED: CBS_ASN1_TAG = 0x20 << 24;
+const CONTEXT_SPECIFIC: CBS_ASN1_TAG = 0x80 << 24;
+const CLASS_MASK: CBS_ASN1_TAG = 0xc0 << 24;
+const NUMBER_MASK: CBS_ASN1_TAG = (1 << 29) - 1;
+
+/// The tag of an ASN.1 element: its class, whether it is constructed, and its number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Tag(CBS_ASN1_TAG);
+
+impl Tag {
+    /// `BOOLEAN`
+    pub const BOOLEAN: Self = Self(0x01);
+    /// `INTEGER`
+    pub const INTEGER: Self = Self(0x02);
+    /// `BIT STRING`
+    pub const BIT_STRING: Self = Self(0x03);
+    /// `OCTET STRING`
+    pub const OCTET_STRING: Self = Self(0x04);
+    /// `NULL`
+    pub const NULL: Self = Self(0x05);
+    /// `OBJECT IDENTIFIER`
+    pub const OBJECT_IDENTIFIER: Self = Self(0x06);
+    /// `ENUMERATED`
+    pub const ENUMERATED: Self = Self(0x0a);
+    /// `UTF8String`
+    pub const UTF8_STRING: Self = Self(0x0c);
+    /// `SEQUENCE` and `SEQUENCE OF`
+    pub const SEQUENCE: Self = Self(0x10 | CONSTRUCTED);
+    /// `SET` and `SET OF`
+    pub const SET: Self = Self(0x11 | CONSTRUCTED);
+    /// `PrintableString`
+    pub const PRINTABLE_STRING: Self = Self(0x13);
+    /// `IA5String`
+    pub const IA5_STRING: Self = Self(0x16);
+    /// `UTCTime`
+    pub const UTC_TIME: Self = Self(0x17);
+    /// `GeneralizedTime`
+    pub const GENERALIZED_TIME: Self = Self(0x18);
+
+    /// The primitive, context-specific tag `[number]`, as used by `IMPLICIT` tagging of
+    /// primitive types. Use `Tag::constructed` for `EXPLICIT` tagging, or `IMPLICIT` tagging
+    /// of constructed types.
+    ///
+    /// # Panics
+    /// If `number` is 2^29 or more.
+    #[must_use]
+    pub const fn context_specific(number: u32) -> Self {
+        assert!(number <= NUMBER_MASK);
+        Self(CONTEXT_SPECIFIC | number)
+    }
+
+    /// The constructed form of this tag.
+    #[must_use]
+    pub const fn constructed(self) -> Self {
+        Self(self.0 | CONSTRUCTED)
+    }
+
+    /// Whether the tag is constructed, i.e. its contents are a series of elements.
+    #[must_use]
+    pub const fn is_constructed(self) -> bool {
+        self.0 & CONSTRUCTED != 0
+    }
+
+    /// Whether the tag is context-specific.
+    #[must_use]
+    pub const fn is_context_specific(self) -> bool {
+        self.0 & CLASS_MASK == CONTEXT_SPECIFIC
+    }
+
+    /// The tag's number within its class.
+    #[must_use]
+    pub const fn number(self) -> u32 {
+        self.0 & NUMBER_MASK
+    }
+}
+
+/// Parses DER-encoded elements from the start of its input.
+///
+/// A read that fails leaves the reader unchanged, so alternatives can be tried in turn.
+#[derive(Clone, Copy, Debug)]
+pub struct Reader<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Creates a reader of the elements encoded in `input`.
+    #[must_use]
+    pub fn new(input: &'a [u8]) -> Self {
+        Self { input }
+    }
+
+    /// Whether all input has been read.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.input.is_empty()
+    }
+
+    /// The input that has not been read.
+    #[must_use]
+    pub fn remaining(&self) -> &'a [u8] {
+        self.input
+    }
+
+    /// Checks that all input has been read.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if input remains.
+    pub fn finish(self) -> Result<(), Unspecified> {
+        if !self.is_empty() {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+
+    /// The tag of the next element, if there is a well-formed one.
+    #[must_use]
+    pub fn peek_tag(&self) -> Option<Tag> {
+        let mut reader = *self;
+        reader.read_any().ok().map(|(tag, _)| tag)
+    }
+
+    /// Reads the next element, which must have the given `tag`, and returns a reader of its
+    /// contents.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the next element is malformed or has another tag.
+    pub fn read(&mut self, tag: Tag) -> Result<Reader<'a>, Unspecified> {
+        self.advance(|input| {
+            let mut contents = empty_cbs();
+            if 1 != unsafe { CBS_get_asn1(input, &mut contents, tag.0) } {
+                return Err(Unspecified);
+            }
+            Ok(Reader::new(unsafe { cbs_bytes(&contents) }))
+        })
+    }
+
+    /// Reads the next element if it has the given `tag`, and returns a reader of its
+    /// contents.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the next element is malformed.
+    pub fn read_optional(&mut self, tag: Tag) -> Result<Option<Reader<'a>>, Unspecified> {
+        self.advance(|input| {
+            let mut contents = empty_cbs();
+            let mut present = 0;
+            if 1 != unsafe { CBS_get_optional_asn1(input, &mut contents, &mut present, tag.0) } {
+                return Err(Unspecified);
+            }
+            Ok((present != 0).then_some(Reader::new(unsafe { cbs_bytes(&contents) })))
+        })
+    }
+
+    /// Reads the next element, whatever its tag, and returns its tag and a reader of its
+    /// contents.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the input is empty or the next element is malformed.
+    pub fn read_any(&mut self) -> Result<(Tag, Reader<'a>), Unspecified> {
+        self.advance(|input| {
+            let mut contents = empty_cbs();
+            let mut tag = 0;
+            if 1 != unsafe { CBS_get_any_asn1(input, &mut contents, &mut tag) } {
+                return Err(Unspecified);
+            }
+            Ok((Tag(tag), Reader::new(unsafe { cbs_bytes(&contents) })))
+        })
+    }
+
+    /// Reads the next element, which must have the given `tag`, and returns its complete
+    /// encoding, including the tag and length.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the next element is malformed or has another tag.
+    pub fn read_element(&mut self, tag: Tag) -> Result<&'a [u8], Unspecified> {
+        self.advance(|input| {
+            let mut element = empty_cbs();
+            if 1 != unsafe { CBS_get_asn1_element(input, &mut element, tag.0) } {
+                return Err(Unspecified);
+            }
+            Ok(unsafe { cbs_bytes(&element) })
+        })
+    }
+
+    /// Reads the next element, whatever its tag, and returns its complete encoding.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the input is empty or the next element is malformed.
+    pub fn read_any_element(&mut self) -> Result<&'a [u8], Unspecified> {
+        self.advance(|input| {
+            let mut element = empty_cbs();
+            let mut tag = 0;
+            let mut header_len = 0;
+            if 1 != unsafe {
+                CBS_get_any_asn1_element(input, &mut element, &mut tag, &mut header_len)
+            } {
+                return Err(Unspecified);
+            }
+            Ok(unsafe { cbs_bytes(&element) })
+        })
+    }
+
+    /// Reads a `SEQUENCE`, and returns a reader of its contents.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the next element is not a well-formed `SEQUENCE`.
+    pub fn read_sequence(&mut self) -> Result<Reader<'a>, Unspecified> {
+        self.read(Tag::SEQUENCE)
+    }
+
+    /// Reads a `BOOLEAN`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the next element is not a well-formed `BOOLEAN`.
+    pub fn read_bool(&mut self) -> Result<bool, Unspecified> {
+        self.advance(|input| {
+            let mut value = 0;
+            if 1 != unsafe { CBS_get_asn1_bool(input, &mut value) } {
+                return Err(Unspecified);
+            }
+            Ok(value != 0)
+        })
+    }
+
+    /// Reads a `NULL`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the next element is not a well-formed `NULL`.
+    pub fn read_null(&mut self) -> Result<(), Unspecified> {
+        let mut reader = *self;
+        reader.read(Tag::NULL)?.finish()?;
+        *self = reader;
+        Ok(())
+    }
+
+    /// Reads an `INTEGER` that fits in a `u64`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the next element is not a well-formed `INTEGER`, or its value
+    /// is negative or too large.
+    pub fn read_u64(&mut self) -> Result<u64, Unspecified> {
+        self.advance(|input| {
+            let mut value = 0;
+            if 1 != unsafe { CBS_get_asn1_uint64(input, &mut value) } {
+                return Err(Unspecified);
+            }
+            Ok(value)
+        })
+    }
+
+    /// Reads an `INTEGER` that fits in an `i64`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the next element is not a well-formed `INTEGER`, or its value
+    /// is out of range.
+    pub fn read_i64(&mut self) -> Result<i64, Unspecified> {
+        self.advance(|input| {
+            let mut value = 0;
+            if 1 != unsafe { CBS_get_asn1_int64(input, &mut value) } {
+                return Err(Unspecified);
+            }
+            Ok(value)
+        })
+    }
+
+    /// Reads a non-negative `INTEGER` of any size, such as a certificate serial number, and
+    /// returns the big-endian bytes of its value without the leading zero byte DER adds when
+    /// the top bit is set.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the next element is not a well-formed `INTEGER`, or its value
+    /// is negative.
+    pub fn read_unsigned_integer(&mut self) -> Result<&'a [u8], Unspecified> {
+        let mut reader = *self;
+        let contents = reader.read(Tag::INTEGER)?.input;
+        if 1 != unsafe { CBS_is_unsigned_asn1_integer(&cbs::build_CBS(contents)) } {
+            return Err(Unspecified);
+        }
+        *self = reader;
+        Ok(match contents {
+            [0, rest @ ..] if !rest.is_empty() => rest,
+            contents => contents,
+        })
+    }
+
+    /// Reads an `OBJECT IDENTIFIER`, and returns its dotted-decimal form, e.g.
+    /// "2.5.29.19".
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the next element is not a well-formed `OBJECT IDENTIFIER`.
+    pub fn read_oid(&mut self) -> Result<String, Unspecified> {
+        let mut reader = *self;
+        let contents = reader.read(Tag::OBJECT_IDENTIFIER)?.input;
+        let text =
+            LcPtr::new(unsafe { CBS_asn1_oid_to_text(&cbs::build_CBS(contents)) }.cast::<u8>())?;
+        let text = unsafe { CStr::from_ptr((*text).cast()) };
+        let text = text.to_str().map_err(|_| Unspecified)?.to_owned();
+        *self = reader;
+        Ok(text)
+    }
+
+    /// Reads an `OCTET STRING`, and returns its contents.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the next element is not a well-formed `OCTET STRING`.
+    pub fn read_octet_string(&mut self) -> Result<&'a [u8], Unspecified> {
+        Ok(self.read(Tag::OCTET_STRING)?.input)
+    }
+
+    /// Reads a `BIT STRING` whose length is a whole number of bytes, such as a signature or
+    /// a public key, and returns those bytes.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the next element is not a well-formed `BIT STRING`, or has
+    /// unused bits.
+    pub fn read_bit_string(&mut self) -> Result<&'a [u8], Unspecified> {
+        let mut reader = *self;
+        match reader.read(Tag::BIT_STRING)?.input {
+            [0, bytes @ ..] => {
+                *self = reader;
+                Ok(bytes)
+            }
+            _ => Err(Unspecified),
+        }
+    }
+
+    // Runs `read` on the input, and consumes what it read if it succeeds.
+    fn advance<T, F>(&mut self, read: F) -> Result<T, Unspecified>
+    where
+        F: FnOnce(&mut CBS) -> Result<T, Unspecified>,
+    {
+        let mut input = unsafe { cbs::build_CBS(self.input) };
+        let value = read(&mut input)?;
+        self.input = &self.input[self.input.len() - unsafe { CBS_len(&input) }..];
+        Ok(value)
+    }
+}
+
+/// Builds a DER encoding with `contents`, which writes the top-level elements.
+///
+/// # Errors
+/// `error::Unspecified` if `contents` fails.
+pub fn encode<F>(contents: F) -> Result<Vec<u8>, Unspecified>
+where
+    F: FnOnce(&mut Writer<'_>) -> Result<(), Unspecified>,
+{
+    let mut cbb = LcCBB::new(64);
+    contents(&mut Writer::new(cbb.as_mut_ptr()))?;
+    Ok(cbb.into_buffer::<()>()?.as_ref().to_vec())
+}
+
+/// Writes DER-encoded elements; see [`encode`].
+pub struct Writer<'a> {
+    cbb: *mut CBB,
+    _cbb: PhantomData<&'a mut CBB>,
+}
+
+impl Writer<'_> {
+    fn new(cbb: *mut CBB) -> Self {
+        Self {
+            cbb,
+            _cbb: PhantomData,
+        }
+    }
+
+    /// Writes an element with the given `tag`, whose contents are written by `contents`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `contents` fails.
+    pub fn write<F>(&mut self, tag: Tag, contents: F) -> Result<(), Unspecified>
+    where
+        F: FnOnce(&mut Writer<'_>) -> Result<(), Unspecified>,
+    {
+        let mut child = MaybeUninit::<CBB>::uninit();
+        if 1 != unsafe { CBB_add_asn1(self.cbb, child.as_mut_ptr(), tag.0) } {
+            return Err(Unspecified);
+        }
+        let result = contents(&mut Writer::new(child.as_mut_ptr()));
+        // `self.cbb` refers to `child` until it is flushed, even if `contents` failed.
+        if 1 != unsafe { CBB_flush(self.cbb) } {
+            return Err(Unspecified);
+        }
+        result
+    }
+
+    /// Writes an element with the given `tag` and `contents`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the element cannot be written.
+    pub fn write_value(&mut self, tag: Tag, contents: &[u8]) -> Result<(), Unspecified> {
+        self.write(tag, |writer| writer.write_bytes(contents))
+    }
+
+    /// Writes `element`, a complete DER-encoded element.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `element` is not a single well-formed element.
+    pub fn write_element(&mut self, element: &[u8]) -> Result<(), Unspecified> {
+        let mut reader = Reader::new(element);
+        reader.read_any_element()?;
+        reader.finish()?;
+        self.write_bytes(element)
+    }
+
+    /// Writes a `SEQUENCE`, whose contents are written by `contents`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `contents` fails.
+    pub fn write_sequence<F>(&mut self, contents: F) -> Result<(), Unspecified>
+    where
+        F: FnOnce(&mut Writer<'_>) -> Result<(), Unspecified>,
+    {
+        self.write(Tag::SEQUENCE, contents)
+    }
+
+    /// Writes a `BOOLEAN`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the element cannot be written.
+    pub fn write_bool(&mut self, value: bool) -> Result<(), Unspecified> {
+        if 1 != unsafe { CBB_add_asn1_bool(self.cbb, value.into()) } {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+
+    /// Writes a `NULL`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the element cannot be written.
+    pub fn write_null(&mut self) -> Result<(), Unspecified> {
+        self.write_value(Tag::NULL, &[])
+    }
+
+    /// Writes an `INTEGER`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the element cannot be written.
+    pub fn write_u64(&mut self, value: u64) -> Result<(), Unspecified> {
+        if 1 != unsafe { CBB_add_asn1_uint64(self.cbb, value) } {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+
+    /// Writes an `INTEGER`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the element cannot be written.
+    pub fn write_i64(&mut self, value: i64) -> Result<(), Unspecified> {
+        if 1 != unsafe { CBB_add_asn1_int64(self.cbb, value) } {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+
+    /// Writes a non-negative `INTEGER` of any size, given the big-endian bytes of its value.
+    /// Leading zero bytes of `value` are ignored.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the element cannot be written.
+    pub fn write_unsigned_integer(&mut self, value: &[u8]) -> Result<(), Unspecified> {
+        let start = value.iter().position(|b| *b != 0).unwrap_or(value.len());
+        let value = &value[start..];
+        self.write(Tag::INTEGER, |integer| {
+            // Zero, and values whose top bit is set, need a leading zero byte.
+            if value.first().map_or(true, |b| b & 0x80 != 0) {
+                integer.write_bytes(&[0])?;
+            }
+            integer.write_bytes(value)
+        })
+    }
+
+    /// Writes an `OBJECT IDENTIFIER`, given its dotted-decimal form, e.g. "2.5.29.19".
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `oid` is not a valid object identifier.
+    pub fn write_oid(&mut self, oid: &str) -> Result<(), Unspecified> {
+        self.write(Tag::OBJECT_IDENTIFIER, |contents| {
+            if 1 != unsafe {
+                CBB_add_asn1_oid_from_text(contents.cbb, oid.as_ptr().cast(), oid.len())
+            } {
+                return Err(Unspecified);
+            }
+            Ok(())
+        })
+    }
+
+    /// Writes an `OCTET STRING`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the element cannot be written.
+    pub fn write_octet_string(&mut self, value: &[u8]) -> Result<(), Unspecified> {
+        self.write_value(Tag::OCTET_STRING, value)
+    }
+
+    /// Writes a `BIT STRING` whose length is a whole number of bytes.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the element cannot be written.
+    pub fn write_bit_string(&mut self, value: &[u8]) -> Result<(), Unspecified> {
+        self.write(Tag::BIT_STRING, |contents| {
+            contents.write_bytes(&[0])?;
+            contents.write_bytes(value)
+        })
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Unspecified> {
+        if 1 != unsafe { CBB_add_bytes(self.cbb, bytes.as_ptr(), bytes.len()) } {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+}
+
+// The bytes remaining in `cbs`, which borrows from an input that outlives `'a`.
+unsafe fn cbs_bytes<'a>(cbs: &CBS) -> &'a [u8] {
+    if CBS_len(cbs) == 0 {
+        return &[];
+    }
+    core::slice::from_raw_parts(CBS_data(cbs), CBS_len(cbs))
+}
+
+fn empty_cbs() -> CBS {
+    unsafe { cbs::build_CBS(&[]) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode, Reader, Tag};
+
+    #[test]
+    fn test_round_trip() {
+        let der = encode(|writer| {
+            writer.write_sequence(|sequence| {
+                sequence.write_bool(true)?;
+                sequence.write_null()?;
+                sequence.write_u64(0x80)?;
+                sequence.write_i64(-129)?;
+                sequence.write_unsigned_integer(&[0, 0, 0xff, 0x01])?;
+                sequence.write_oid("1.2.840.113549.1.1.11")?;
+                sequence.write_octet_string(b"octets")?;
+                sequence.write_bit_string(&[0xa5])?;
+                sequence.write(Tag::context_specific(0).constructed(), |explicit| {
+                    explicit.write_value(Tag::UTF8_STRING, "ünïcödé".as_bytes())
+                })?;
+                sequence.write_value(Tag::context_specific(31), &[1, 2])?;
+                sequence.write_element(&[0x05, 0x00])
+            })
+        })
+        .unwrap();
+        assert_eq!(
+            &[
+                0x30, 0x3f, 0x01, 0x01, 0xff, 0x05, 0x00, 0x02, 0x02, 0x00, 0x80, 0x02, 0x02, 0xff,
+                0x7f, 0x02, 0x03, 0x00, 0xff, 0x01, 0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d,
+                0x01, 0x01, 0x0b, 0x04, 0x06, 0x6f, 0x63, 0x74, 0x65, 0x74, 0x73, 0x03, 0x02, 0x00,
+                0xa5, 0xa0, 0x0d, 0x0c, 0x0b, 0xc3, 0xbc, 0x6e, 0xc3, 0xaf, 0x63, 0xc3, 0xb6, 0x64,
+                0xc3, 0xa9, 0x9f, 0x1f, 0x02, 0x01, 0x02, 0x05, 0x00
+            ][..],
+            der
+        );
+
+        let mut reader = Reader::new(&der);
+        let mut sequence = reader.read_sequence().unwrap();
+        reader.finish().unwrap();
+        assert!(sequence.read_bool().unwrap());
+        sequence.read_null().unwrap();
+        assert_eq!(0x80, sequence.read_u64().unwrap());
+        assert_eq!(-129, sequence.read_i64().unwrap());
+        assert_eq!(&[0xff, 0x01], sequence.read_unsigned_integer().unwrap());
+        assert_eq!("1.2.840.113549.1.1.11", sequence.read_oid().unwrap());
+        assert_eq!(b"octets", sequence.read_octet_string().unwrap());
+        assert_eq!(&[0xa5], sequence.read_bit_string().unwrap());
+        let (tag, mut explicit) = sequence.read_any().unwrap();
+        assert!(tag.is_constructed() && tag.is_context_specific());
+        assert_eq!(0, tag.number());
+        assert_eq!(
+            "ünïcödé".as_bytes(),
+            explicit.read(Tag::UTF8_STRING).unwrap().remaining()
+        );
+        explicit.finish().unwrap();
+        assert!(sequence
+            .read_optional(Tag::context_specific(1))
+            .unwrap()
+            .is_none());
+        assert_eq!(Some(Tag::context_specific(31)), sequence.peek_tag());
+        let mut tagged = sequence
+            .read_optional(Tag::context_specific(31))
+            .unwrap()
+            .unwrap();
+        assert_eq!(&[1, 2], tagged.remaining());
+        assert_eq!(&[0x05, 0x00], sequence.read_any_element().unwrap());
+        sequence.finish().unwrap();
+        assert!(tagged.read_any().is_err());
+    }
+
+    #[test]
+    fn test_integers() {
+        for (value, der, decoded) in [
+            (&[][..], &[0x02, 0x01, 0x00][..], &[0][..]),
+            (&[0, 0], &[0x02, 0x01, 0x00], &[0]),
+            (&[0, 0x7f], &[0x02, 0x01, 0x7f], &[0x7f]),
+            (&[0x80], &[0x02, 0x02, 0x00, 0x80], &[0x80]),
+        ] {
+            assert_eq!(
+                der,
+                encode(|writer| writer.write_unsigned_integer(value)).unwrap()
+            );
+            assert_eq!(decoded, Reader::new(der).read_unsigned_integer().unwrap());
+        }
+        // Negative and non-minimal integers.
+        for der in [
+            &[0x02, 0x01, 0x80][..],
+            &[0x02, 0x02, 0x00, 0x01],
+            &[0x02, 0x00],
+        ] {
+            assert!(Reader::new(der).read_unsigned_integer().is_err());
+            assert!(Reader::new(der).read_u64().is_err());
+        }
+        assert_eq!(-128, Reader::new(&[0x02, 0x01, 0x80]).read_i64().unwrap());
+        assert!(Reader::new(&[0x02, 0x02, 0xff, 0x80]).read_i64().is_err());
+        assert_eq!(
+            u64::MAX,
+            Reader::new(&[0x02, 0x09, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff])
+                .read_u64()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let der = [0x30, 0x03, 0x02, 0x01, 0x01];
+        let mut reader = Reader::new(&der);
+        // Failed reads consume nothing.
+        assert!(reader.read(Tag::SET).is_err());
+        assert!(reader.read_oid().is_err());
+        assert!(reader.read_null().is_err());
+        assert!(reader.read_bit_string().is_err());
+        assert!(reader.finish().is_err());
+        assert_eq!(&der, reader.remaining());
+        assert_eq!(&der, reader.read_element(Tag::SEQUENCE).unwrap());
+        assert!(reader.is_empty());
+        assert!(reader.read_any().is_err());
+        assert_eq!(None, reader.peek_tag());
+
+        // Truncated, indefinite-length and non-minimal encodings are rejected.
+        for der in [
+            &[0x30, 0x03, 0x02, 0x01][..],
+            &[0x30, 0x80, 0x00, 0x00],
+            &[0x04, 0x81, 0x01, 0x00],
+            &[0x01, 0x01, 0x01],
+        ] {
+            let mut reader = Reader::new(der);
+            assert!(reader.read_any_element().is_err() || reader.read_bool().is_err());
+        }
+        // A BIT STRING with unused bits.
+        assert!(Reader::new(&[0x03, 0x02, 0x01, 0x80])
+            .read_bit_string()
+            .is_err());
+
+        assert!(encode(|writer| writer.write_oid("1")).is_err());
+        assert!(encode(|writer| writer.write_oid("1.2.x")).is_err());
+        assert!(encode(|writer| writer.write_element(&[0x05, 0x00, 0x05, 0x00])).is_err());
+        assert!(encode(|writer| writer.write_element(&[0x05])).is_err());
+    }
+}
@@ -3,7 +3,10 @@
 // Modifications copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
-//! SHA-2 and the legacy SHA-1 digest algorithm.
+//! SHA-2, SHA-3 and the legacy SHA-1 digest algorithms.
+//!
+//! The legacy RIPEMD-160 digest algorithm is also available when the
+//! `legacy-digests` feature is enabled.
 //!
 //! If all the data is available in a single contiguous slice then the `digest`
 //! function should be used. Otherwise, the digest can be calculated in
@@ -29,9 +32,11 @@
 
 #![allow(non_snake_case)]
 use crate::fips::indicator_check;
-use crate::{debug, derive_debug_via_id};
+use crate::{debug, derive_debug_via_id, policy};
 
 pub(crate) mod digest_ctx;
+#[cfg(feature = "legacy-digests")]
+mod ripemd;
 mod sha;
 use crate::error::Unspecified;
 use crate::ptr::ConstPointer;
@@ -41,6 +46,8 @@ use aws_lc::{
 };
 use core::mem::MaybeUninit;
 use digest_ctx::DigestContext;
+#[cfg(feature = "legacy-digests")]
+pub use ripemd::{RIPEMD160_FOR_LEGACY_USE_ONLY, RIPEMD160_OUTPUT_LEN};
 pub use sha::{
     SHA1_FOR_LEGACY_USE_ONLY, SHA1_OUTPUT_LEN, SHA224, SHA224_OUTPUT_LEN, SHA256,
     SHA256_OUTPUT_LEN, SHA384, SHA384_OUTPUT_LEN, SHA3_256, SHA3_384, SHA3_512, SHA512, SHA512_256,
@@ -49,6 +56,9 @@ pub use sha::{
 // TODO: Uncomment when MSRV >= 1.64
 //use core::ffi::c_uint;
 use std::os::raw::c_uint;
+use zeroize::Zeroize;
+
+const DENIED_BY_POLICY: &str = "digest algorithm denied by the installed policy";
 
 /// A context for multi-step (Init-Update-Finish) digest calculations.
 //
@@ -81,9 +91,22 @@ impl Context {
     /// # Panics
     ///
     /// `new` panics if it fails to initialize an aws-lc digest context for the given
-    /// algorithm.
+    /// algorithm, or if the installed [`policy`](crate::policy) denies `algorithm`.
     #[must_use]
     pub fn new(algorithm: &'static Algorithm) -> Self {
+        policy::enforce_digest(algorithm).expect(DENIED_BY_POLICY);
+        Self::new_unchecked(algorithm)
+    }
+
+    // Constructs a context, failing instead of panicking if the installed policy denies
+    // `algorithm`, for operations of the crate that can fail.
+    pub(crate) fn try_new(algorithm: &'static Algorithm) -> Result<Self, Unspecified> {
+        policy::enforce_digest(algorithm)?;
+        Ok(Self::new_unchecked(algorithm))
+    }
+
+    // Constructs a context without consulting the installed policy.
+    fn new_unchecked(algorithm: &'static Algorithm) -> Self {
         Self {
             algorithm,
             digest_ctx: DigestContext::new(algorithm).unwrap(),
@@ -170,6 +193,9 @@ impl Context {
 
 /// Returns the digest of `data` using the given digest algorithm.
 ///
+/// # Panics
+/// Panics if the installed [`policy`](crate::policy) denies `algorithm`.
+///
 // # FIPS
 // This function must only be used with one of the following algorithms:
 // * `SHA1_FOR_LEGACY_USE_ONLY`
@@ -194,6 +220,23 @@ impl Context {
 #[inline]
 #[must_use]
 pub fn digest(algorithm: &'static Algorithm, data: &[u8]) -> Digest {
+    policy::enforce_digest(algorithm).expect(DENIED_BY_POLICY);
+    digest_unchecked(algorithm, data)
+}
+
+// Computes the digest, failing instead of panicking if the installed policy denies `algorithm`,
+// for operations of the crate that can fail.
+pub(crate) fn try_digest(
+    algorithm: &'static Algorithm,
+    data: &[u8],
+) -> Result<Digest, Unspecified> {
+    policy::enforce_digest(algorithm)?;
+    Ok(digest_unchecked(algorithm, data))
+}
+
+// Computes the digest without consulting the installed policy, for hashes that the crate uses
+// to identify keys and certificates, e.g. the SHA-1 `CertID` hashes that OCSP requires.
+pub(crate) fn digest_unchecked(algorithm: &'static Algorithm, data: &[u8]) -> Digest {
     let mut output = [0u8; MAX_OUTPUT_LEN];
     (algorithm.one_shot_hash)(data, &mut output);
 
@@ -204,6 +247,71 @@ pub fn digest(algorithm: &'static Algorithm, data: &[u8]) -> Digest {
     }
 }
 
+/// The size of the buffer used by [`hash_reader`] for each read.
+const HASH_READER_BUFFER_LEN: usize = 64 * 1024;
+
+/// Returns the digest of all the data read from `reader` until end-of-file,
+/// using the given digest algorithm.
+///
+/// Data is read in large chunks to minimize the number of reads issued to the
+/// underlying reader, so there is no need to wrap `reader` in a
+/// `std::io::BufReader`. Reads that fail with `std::io::ErrorKind::Interrupted`
+/// are retried.
+///
+// # FIPS
+// This function must only be used with one of the following algorithms:
+// * `SHA1_FOR_LEGACY_USE_ONLY`
+// * `SHA224`
+// * `SHA256`
+// * `SHA384`
+// * `SHA512`
+// * `SHA512_256`
+//
+/// # Examples:
+///
+/// ```
+/// use aws_lc_rs::digest;
+///
+/// let file_contents: &[u8] = b"hello, world";
+/// let actual = digest::hash_reader(&digest::SHA256, file_contents)?;
+///
+/// assert_eq!(
+///     digest::digest(&digest::SHA256, b"hello, world").as_ref(),
+///     actual.as_ref()
+/// );
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// # Errors
+/// Any error returned by `reader`, other than `std::io::ErrorKind::Interrupted`,
+/// an error of kind `std::io::ErrorKind::InvalidData` if the input exceeds
+/// the maximum input length of the algorithm, or an error of kind
+/// `std::io::ErrorKind::PermissionDenied`, wrapping a `policy::Denied`, if the
+/// installed policy denies `algorithm`.
+pub fn hash_reader<R: std::io::Read>(
+    algorithm: &'static Algorithm,
+    mut reader: R,
+) -> std::io::Result<Digest> {
+    policy::enforce_digest(algorithm)
+        .map_err(|denied| std::io::Error::new(std::io::ErrorKind::PermissionDenied, denied))?;
+    let mut ctx = Context::new_unchecked(algorithm);
+    let mut buffer = vec![0u8; HASH_READER_BUFFER_LEN];
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(len) => ctx.try_update(&buffer[..len]).map_err(|Unspecified| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "input exceeds maximum length for digest algorithm",
+                )
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(ctx.finish())
+}
+
 /// A calculated digest value.
 ///
 /// Use [`Self::as_ref`] to get the value as a `&[u8]`.
@@ -224,6 +332,11 @@ impl Digest {
     pub fn algorithm(&self) -> &'static Algorithm {
         self.algorithm
     }
+
+    // Overwrites the digest value, for digests of secrets such as keys.
+    pub(crate) fn zeroize(&mut self) {
+        self.digest_msg.zeroize();
+    }
 }
 
 impl AsRef<[u8]> for Digest {
@@ -315,6 +428,8 @@ pub(crate) enum AlgorithmID {
     SHA3_256,
     SHA3_384,
     SHA3_512,
+    #[cfg(feature = "legacy-digests")]
+    RIPEMD160,
 }
 
 impl PartialEq for Algorithm {
@@ -352,6 +467,8 @@ pub(crate) fn match_digest_type(algorithm_id: &AlgorithmID) -> ConstPointer<EVP_
             AlgorithmID::SHA3_256 => EVP_sha3_256(),
             AlgorithmID::SHA3_384 => EVP_sha3_384(),
             AlgorithmID::SHA3_512 => EVP_sha3_512(),
+            #[cfg(feature = "legacy-digests")]
+            AlgorithmID::RIPEMD160 => aws_lc::EVP_ripemd160(),
         })
         .unwrap_or_else(|()| panic!("Digest algorithm not found: {algorithm_id:?}"))
     }
@@ -435,6 +552,8 @@ mod tests {
         max_input_tests!(SHA512);
         max_input_tests!(SHA3_384);
         max_input_tests!(SHA3_512);
+        #[cfg(feature = "legacy-digests")]
+        max_input_tests!(RIPEMD160_FOR_LEGACY_USE_ONLY);
     }
 
     #[test]
@@ -449,6 +568,8 @@ mod tests {
             &digest::SHA512,
             &digest::SHA3_384,
             &digest::SHA3_512,
+            #[cfg(feature = "legacy-digests")]
+            &digest::RIPEMD160_FOR_LEGACY_USE_ONLY,
         ] {
             // Clone after updating context with message, then check if the final Digest is the same.
             let mut ctx = digest::Context::new(alg);
@@ -0,0 +1,36 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use crate::digest::sha::DIGEST_MAX_INPUT_LEN;
+use crate::digest::{Algorithm, AlgorithmID};
+
+/// The length of a block for RIPEMD-160, in bytes.
+const RIPEMD160_BLOCK_LEN: usize = 512 / 8;
+
+/// The length of the output of RIPEMD-160, in bytes.
+pub const RIPEMD160_OUTPUT_LEN: usize = 160 / 8;
+
+/// RIPEMD-160 as specified in [ISO/IEC 10118-3]. Legacy.
+///
+/// RIPEMD-160 is only provided for interoperability with existing systems,
+/// such as Bitcoin address derivation. It is not approved for use in FIPS mode
+/// and should not be used in new protocols.
+///
+/// [ISO/IEC 10118-3]: https://www.iso.org/standard/67116.html
+#[allow(deprecated)]
+pub static RIPEMD160_FOR_LEGACY_USE_ONLY: Algorithm = Algorithm {
+    output_len: RIPEMD160_OUTPUT_LEN,
+    chaining_len: RIPEMD160_OUTPUT_LEN,
+    block_len: RIPEMD160_BLOCK_LEN,
+    max_input_len: DIGEST_MAX_INPUT_LEN,
+
+    one_shot_hash: ripemd160_digest,
+
+    id: AlgorithmID::RIPEMD160,
+};
+
+fn ripemd160_digest(msg: &[u8], output: &mut [u8]) {
+    unsafe {
+        aws_lc::RIPEMD160(msg.as_ptr(), msg.len(), output.as_mut_ptr());
+    }
+}
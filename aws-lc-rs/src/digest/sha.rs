@@ -54,7 +54,7 @@ pub const SHA3_512_OUTPUT_LEN: usize = 512 / 8;
 /// SHA-384, SHA-512, and SHA-512/256 are limited to an input size of 2^128-1 bits according to the spec.
 /// u64 is more than sufficient enough for practical usecases, so we limit the input length to 2^64-1 bits.
 #[allow(clippy::cast_possible_truncation)]
-const DIGEST_MAX_INPUT_LEN: u64 = u64::MAX;
+pub(super) const DIGEST_MAX_INPUT_LEN: u64 = u64::MAX;
 
 /// SHA-1 as specified in [FIPS 180-4]. Deprecated.
 ///
@@ -234,21 +234,21 @@ fn sha512_256_digest(msg: &[u8], output: &mut [u8]) {
 }
 
 fn sha3_256_digest(msg: &[u8], output: &mut [u8]) {
-    let mut ctx = Context::new(&SHA3_256);
+    let mut ctx = Context::new_unchecked(&SHA3_256);
     ctx.update(msg);
     let digest = ctx.finish();
     output[0..SHA3_256_OUTPUT_LEN].copy_from_slice(digest.as_ref());
 }
 
 fn sha3_384_digest(msg: &[u8], output: &mut [u8]) {
-    let mut ctx = Context::new(&SHA3_384);
+    let mut ctx = Context::new_unchecked(&SHA3_384);
     ctx.update(msg);
     let digest = ctx.finish();
     output[0..SHA3_384_OUTPUT_LEN].copy_from_slice(digest.as_ref());
 }
 
 fn sha3_512_digest(msg: &[u8], output: &mut [u8]) {
-    let mut ctx = Context::new(&SHA3_512);
+    let mut ctx = Context::new_unchecked(&SHA3_512);
     ctx.update(msg);
     let digest = ctx.finish();
     output[0..SHA3_512_OUTPUT_LEN].copy_from_slice(digest.as_ref());
@@ -0,0 +1,108 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use core::fmt::{self, Debug, Formatter};
+
+#[cfg(feature = "ring-sig-verify")]
+use untrusted::Input;
+
+use aws_lc::{DSA_bits, DSA_verify, EVP_PKEY_get0_DSA, EVP_parse_public_key};
+
+use crate::error::Unspecified;
+use crate::ptr::{ConstPointer, LcPtr};
+use crate::signature::VerificationAlgorithm;
+use crate::{cbs, digest, sealed};
+
+/// A DSA signature verification algorithm.
+///
+/// Public keys are DER-encoded X.509 `SubjectPublicKeyInfo` structures, since a DSA public key
+/// is not usable without its domain parameters. Signatures are DER-encoded `Dss-Sig-Value`
+/// structures ([RFC 3279 Section 2.2.2]). Only keys with a 1024 to 3072-bit modulus are
+/// accepted.
+///
+/// [RFC 3279 Section 2.2.2]: https://tools.ietf.org/html/rfc3279#section-2.2.2
+pub struct DsaVerificationAlgorithm {
+    id: DsaVerificationAlgorithmId,
+    digest: &'static digest::Algorithm,
+}
+
+#[derive(Debug)]
+#[allow(non_camel_case_types)]
+enum DsaVerificationAlgorithmId {
+    DSA_SHA1_FOR_LEGACY_USE_ONLY,
+    DSA_SHA224_FOR_LEGACY_USE_ONLY,
+    DSA_SHA256_FOR_LEGACY_USE_ONLY,
+}
+
+/// Verification of DSA signatures using SHA-1.
+pub static DSA_SHA1_FOR_LEGACY_USE_ONLY: DsaVerificationAlgorithm = DsaVerificationAlgorithm {
+    id: DsaVerificationAlgorithmId::DSA_SHA1_FOR_LEGACY_USE_ONLY,
+    digest: &digest::SHA1_FOR_LEGACY_USE_ONLY,
+};
+
+/// Verification of DSA signatures using SHA-224.
+pub static DSA_SHA224_FOR_LEGACY_USE_ONLY: DsaVerificationAlgorithm = DsaVerificationAlgorithm {
+    id: DsaVerificationAlgorithmId::DSA_SHA224_FOR_LEGACY_USE_ONLY,
+    digest: &digest::SHA224,
+};
+
+/// Verification of DSA signatures using SHA-256.
+pub static DSA_SHA256_FOR_LEGACY_USE_ONLY: DsaVerificationAlgorithm = DsaVerificationAlgorithm {
+    id: DsaVerificationAlgorithmId::DSA_SHA256_FOR_LEGACY_USE_ONLY,
+    digest: &digest::SHA256,
+};
+
+impl Debug for DsaVerificationAlgorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&format!("{{ {:?} }}", self.id))
+    }
+}
+
+impl sealed::Sealed for DsaVerificationAlgorithm {}
+
+impl VerificationAlgorithm for DsaVerificationAlgorithm {
+    #[cfg(feature = "ring-sig-verify")]
+    fn verify(
+        &self,
+        public_key: Input<'_>,
+        msg: Input<'_>,
+        signature: Input<'_>,
+    ) -> Result<(), Unspecified> {
+        self.verify_sig(
+            public_key.as_slice_less_safe(),
+            msg.as_slice_less_safe(),
+            signature.as_slice_less_safe(),
+        )
+    }
+
+    fn verify_sig(
+        &self,
+        public_key: &[u8],
+        msg: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Unspecified> {
+        let mut der = unsafe { cbs::build_CBS(public_key) };
+        let evp_pkey = LcPtr::new(unsafe { EVP_parse_public_key(&mut der) })?;
+        let dsa = ConstPointer::new(unsafe { EVP_PKEY_get0_DSA(*evp_pkey) })?;
+        if !(1024..=3072).contains(&unsafe { DSA_bits(*dsa) }) {
+            return Err(Unspecified);
+        }
+
+        let digest = digest::try_digest(self.digest, msg)?;
+        let digest = digest.as_ref();
+        if 1 != unsafe {
+            DSA_verify(
+                0,
+                digest.as_ptr(),
+                digest.len(),
+                signature.as_ptr(),
+                signature.len(),
+                *dsa,
+            )
+        } {
+            return Err(Unspecified);
+        }
+
+        Ok(())
+    }
+}
@@ -8,7 +8,11 @@ use core::fmt::{Debug, Formatter};
 use core::mem::MaybeUninit;
 use core::ptr::{null, null_mut};
 
-use aws_lc::{EVP_DigestSign, EVP_DigestSignInit, EVP_PKEY_get0_EC_KEY, EVP_PKEY};
+use aws_lc::{
+    EC_KEY_check_fips, EVP_DigestSign, EVP_DigestSignFinal, EVP_DigestSignInit,
+    EVP_DigestSignUpdate, EVP_PKEY_CTX_new, EVP_PKEY_get0_EC_KEY, EVP_PKEY_sign,
+    EVP_PKEY_sign_init, EVP_PKEY,
+};
 
 use crate::digest::digest_ctx::DigestContext;
 #[cfg(feature = "fips")]
@@ -20,11 +24,17 @@ use crate::ec::{evp_key_generate, EcdsaSignatureFormat, EcdsaSigningAlgorithm, P
 use crate::encoding::{AsBigEndian, AsDer, EcPrivateKeyBin, EcPrivateKeyRfc5915Der};
 use crate::error::{KeyRejected, Unspecified};
 use crate::fips::indicator_check;
+#[cfg(feature = "jwk")]
+use crate::jwk::Jwk;
 use crate::pkcs8::{Document, Version};
 use crate::ptr::{ConstPointer, DetachableLcPtr, LcPtr};
-use crate::rand::SecureRandom;
-use crate::signature::{KeyPair, Signature};
-use crate::{digest, ec};
+use crate::rand::{SecureRandom, SystemRandom};
+use crate::signature::{DigestSigner, KeyPair, Signature, Signer};
+#[cfg(feature = "ssh")]
+use crate::ssh;
+use crate::{digest, ec, policy};
+#[cfg(any(feature = "jwk", feature = "ssh"))]
+use zeroize::Zeroize;
 
 /// An ECDSA key pair, used for signing.
 #[allow(clippy::module_name_repetitions)]
@@ -54,6 +64,30 @@ impl KeyPair for EcdsaKeyPair {
     }
 }
 
+impl DigestSigner for EcdsaKeyPair {
+    #[inline]
+    fn sign_digest(&self, digest: &[u8]) -> Result<Vec<u8>, Unspecified> {
+        Ok(EcdsaKeyPair::sign_digest(self, digest)?.as_ref().to_vec())
+    }
+
+    #[inline]
+    fn public_key_bytes(&self) -> &[u8] {
+        self.pubkey.as_ref()
+    }
+}
+
+impl Signer for EcdsaKeyPair {
+    #[inline]
+    fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>, Unspecified> {
+        Ok(self.sign(&SystemRandom::new(), message)?.as_ref().to_vec())
+    }
+
+    #[inline]
+    fn public_key_bytes(&self) -> &[u8] {
+        self.pubkey.as_ref()
+    }
+}
+
 impl EcdsaKeyPair {
     #[allow(clippy::needless_pass_by_value)]
     fn new(
@@ -69,6 +103,16 @@ impl EcdsaKeyPair {
         })
     }
 
+    #[cfg(feature = "x509")]
+    pub(crate) fn evp_pkey(&self) -> &LcPtr<EVP_PKEY> {
+        &self.evp_pkey
+    }
+
+    #[cfg(feature = "x509")]
+    pub(crate) fn digest_algorithm(&self) -> &'static digest::Algorithm {
+        self.algorithm.digest
+    }
+
     /// Generates a new key pair.
     ///
     /// # Errors
@@ -129,6 +173,24 @@ impl EcdsaKeyPair {
         self.evp_pkey.marshall_private_key(Version::V1)
     }
 
+    /// Runs a signing pairwise consistency test (PCT) on the key pair: signs a test digest with
+    /// the private key and verifies the signature with the public key, after checking that the
+    /// public key matches the private key.
+    ///
+    /// FIPS key generation already runs this test, and does not return a key pair that fails
+    /// it. Calling it again gives high-assurance deployments evidence that the check ran, e.g.
+    /// to log it or to refuse provisioning a key.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the key pair fails the test.
+    pub fn pairwise_consistency_test(&self) -> Result<(), Unspecified> {
+        let ec_key = ConstPointer::new(unsafe { EVP_PKEY_get0_EC_KEY(*self.evp_pkey) })?;
+        if 1 != unsafe { EC_KEY_check_fips(*ec_key) } {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+
     /// Constructs an ECDSA key pair from the private key and public key bytes
     ///
     /// The private key must encoded as a big-endian fixed-length integer. For
@@ -187,6 +249,96 @@ impl EcdsaKeyPair {
         Ok(Self::new(alg, evp_pkey)?)
     }
 
+    /// Constructs an ECDSA key pair from an `EC` JSON Web Key (RFC 7518, section 6.2) with the
+    /// private key member `d`.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the key's `kty` or `crv` does not match `alg`, a member is
+    /// missing or malformed, or the key is otherwise unacceptable.
+    #[cfg(feature = "jwk")]
+    pub fn from_jwk(alg: &'static EcdsaSigningAlgorithm, jwk: &Jwk) -> Result<Self, KeyRejected> {
+        jwk.expect("EC", Some(alg.id.jwk_crv()))?;
+        let field_len = alg.id.private_key_size();
+        let x = jwk.bytes("x")?;
+        let y = jwk.bytes("y")?;
+        let mut d = jwk.bytes("d")?;
+        if x.len() != field_len || y.len() != field_len || d.len() != field_len {
+            d.zeroize();
+            return Err(KeyRejected::invalid_encoding());
+        }
+        let mut public_key = Vec::with_capacity(1 + 2 * field_len);
+        public_key.push(0x04);
+        public_key.extend_from_slice(&x);
+        public_key.extend_from_slice(&y);
+
+        let key_pair = Self::from_private_key_and_public_key(alg, &d, &public_key);
+        d.zeroize();
+        key_pair
+    }
+
+    /// Serializes this `EcdsaKeyPair` as an `EC` JSON Web Key (RFC 7518, section 6.2),
+    /// including the private key member `d`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    #[cfg(feature = "jwk")]
+    pub fn to_jwk(&self) -> Result<Jwk, Unspecified> {
+        let mut jwk = self.pubkey.to_jwk();
+        let d = self.private_key().as_be_bytes()?;
+        jwk.set_bytes("d", d.as_ref());
+        Ok(jwk)
+    }
+
+    /// Constructs an ECDSA key pair from an `ecdsa-sha2-*` OpenSSH private key (RFC 5656).
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the key's curve does not match `alg`, or the key is otherwise
+    /// unacceptable.
+    #[cfg(feature = "ssh")]
+    pub fn from_openssh(
+        alg: &'static EcdsaSigningAlgorithm,
+        key: &ssh::PrivateKey,
+    ) -> Result<Self, KeyRejected> {
+        let (curve, public_key, private_key) = match key.fields()? {
+            (
+                ssh::PublicKeyFields::Ecdsa { curve, public_key },
+                ssh::PrivateKeyFields::Ecdsa { private_key },
+            ) => (curve, public_key, private_key),
+            _ => return Err(KeyRejected::wrong_algorithm()),
+        };
+        if Some(curve) != alg.id.ssh_curve() {
+            return Err(KeyRejected::wrong_algorithm());
+        }
+        // The `mpint` encoding omits leading zeros.
+        let mut d = vec![0u8; alg.id.private_key_size()];
+        let offset = d.len() - private_key.len();
+        d[offset..].copy_from_slice(private_key);
+
+        let key_pair = Self::from_private_key_and_public_key(alg, &d, public_key);
+        d.zeroize();
+        key_pair
+    }
+
+    /// Serializes this `EcdsaKeyPair` as an `ecdsa-sha2-*` OpenSSH private key (RFC 5656),
+    /// with an empty comment.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the curve has no SSH key type, i.e. for secp256k1.
+    #[cfg(feature = "ssh")]
+    pub fn to_openssh(&self) -> Result<ssh::PrivateKey, Unspecified> {
+        let curve = self.algorithm.id.ssh_curve().ok_or(Unspecified)?;
+        let d = self.private_key().as_be_bytes()?;
+        ssh::PrivateKey::new(
+            &ssh::PublicKeyFields::Ecdsa {
+                curve,
+                public_key: self.pubkey.as_ref(),
+            },
+            &ssh::PrivateKeyFields::Ecdsa {
+                private_key: d.as_ref(),
+            },
+        )
+    }
+
     /// Access functions related to the private key.
     #[must_use]
     pub fn private_key(&self) -> PrivateKey<'_> {
@@ -207,6 +359,7 @@ impl EcdsaKeyPair {
     // * Digest Algorithms: SHA256, SHA384, SHA512
     #[inline]
     pub fn sign(&self, _rng: &dyn SecureRandom, message: &[u8]) -> Result<Signature, Unspecified> {
+        policy::enforce_digest(self.algorithm.digest)?;
         let mut md_ctx = DigestContext::new_uninit();
 
         let digest = digest::match_digest_type(&self.algorithm.digest.id);
@@ -227,16 +380,174 @@ impl EcdsaKeyPair {
 
         let out_sig = compute_ecdsa_signature(&mut md_ctx, message, out_sig.as_mut_slice())?;
 
+        self.encode_signature(out_sig)
+    }
+
+    // # FIPS
+    // Signing a precomputed digest is not a FIPS-approved service.
+    /// Returns the signature of a precomputed message `digest`, for callers that have already
+    /// hashed the message with this key pair's digest algorithm.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `digest` has the wrong length for the digest algorithm, or on
+    /// internal error.
+    pub fn sign_digest(&self, digest: &[u8]) -> Result<Signature, Unspecified> {
+        if digest.len() != self.algorithm.digest.output_len() {
+            return Err(Unspecified);
+        }
+        policy::enforce_digest(self.algorithm.digest)?;
+
+        let pctx = LcPtr::new(unsafe { EVP_PKEY_CTX_new(*self.evp_pkey, null_mut()) })?;
+        if 1 != unsafe { EVP_PKEY_sign_init(*pctx) } {
+            return Err(Unspecified);
+        }
+
+        let mut out_sig_len = 0usize;
+        if 1 != unsafe {
+            EVP_PKEY_sign(
+                *pctx,
+                null_mut(),
+                &mut out_sig_len,
+                digest.as_ptr(),
+                digest.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+
+        let mut out_sig = vec![0u8; out_sig_len];
+        if 1 != indicator_check!(unsafe {
+            EVP_PKEY_sign(
+                *pctx,
+                out_sig.as_mut_ptr(),
+                &mut out_sig_len,
+                digest.as_ptr(),
+                digest.len(),
+            )
+        }) {
+            return Err(Unspecified);
+        }
+
+        self.encode_signature(&out_sig[..out_sig_len])
+    }
+
+    fn encode_signature(&self, asn1_sig: &[u8]) -> Result<Signature, Unspecified> {
         Ok(match self.algorithm.sig_format {
             EcdsaSignatureFormat::ASN1 => Signature::new(|slice| {
-                slice[..out_sig.len()].copy_from_slice(out_sig);
-                out_sig.len()
+                slice[..asn1_sig.len()].copy_from_slice(asn1_sig);
+                asn1_sig.len()
             }),
-            EcdsaSignatureFormat::Fixed => ec::ecdsa_asn1_to_fixed(self.algorithm.id, out_sig)?,
+            EcdsaSignatureFormat::Fixed => ec::ecdsa_asn1_to_fixed(self.algorithm.id, asn1_sig)?,
+        })
+    }
+
+    /// Returns the signature of the message in the canonical "low-S" form, i.e. with an `s`
+    /// component of at most half the curve order.
+    ///
+    /// See [`crate::signature::EcdsaVerificationAlgorithm::normalize_low_s`].
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    pub fn sign_low_s(
+        &self,
+        rng: &dyn SecureRandom,
+        message: &[u8],
+    ) -> Result<Signature, Unspecified> {
+        let signature = self.sign(rng, message)?;
+        self.algorithm.normalize_low_s(signature.as_ref())
+    }
+
+    /// Begins a multi-part signing operation, allowing the message to be provided incrementally
+    /// via [`EcdsaSigningContext::update`] rather than buffered in memory.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    pub fn sign_context(&self) -> Result<EcdsaSigningContext<'_>, Unspecified> {
+        policy::enforce_digest(self.algorithm.digest)?;
+        let mut md_ctx = DigestContext::new_uninit();
+
+        let digest = digest::match_digest_type(&self.algorithm.digest.id);
+
+        if 1 != unsafe {
+            EVP_DigestSignInit(
+                md_ctx.as_mut_ptr(),
+                null_mut(),
+                *digest,
+                null_mut(),
+                *self.evp_pkey,
+            )
+        } {
+            return Err(Unspecified);
+        }
+
+        Ok(EcdsaSigningContext {
+            key_pair: self,
+            md_ctx,
         })
     }
 }
 
+/// A multi-part ECDSA signing operation, created by [`EcdsaKeyPair::sign_context`].
+#[allow(clippy::module_name_repetitions)]
+pub struct EcdsaSigningContext<'a> {
+    key_pair: &'a EcdsaKeyPair,
+    md_ctx: DigestContext,
+}
+
+impl Debug for EcdsaSigningContext<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("EcdsaSigningContext")
+            .field("key_pair", self.key_pair)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EcdsaSigningContext<'_> {
+    /// Updates the signing operation with the next part of the message.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    pub fn update(&mut self, data: &[u8]) -> Result<(), Unspecified> {
+        if 1 != unsafe {
+            EVP_DigestSignUpdate(self.md_ctx.as_mut_ptr(), data.as_ptr().cast(), data.len())
+        } {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+
+    // # FIPS
+    // The following conditions must be met:
+    // * NIST Elliptic Curves: P256, P384, P521
+    // * Digest Algorithms: SHA256, SHA384, SHA512
+    /// Completes the signing operation, returning the signature of all data provided via
+    /// [`Self::update`].
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    pub fn finish(mut self) -> Result<Signature, Unspecified> {
+        let mut out_sig_len = 0usize;
+        if 1 != unsafe {
+            EVP_DigestSignFinal(self.md_ctx.as_mut_ptr(), null_mut(), &mut out_sig_len)
+        } {
+            return Err(Unspecified);
+        }
+
+        let mut out_sig = vec![0u8; out_sig_len];
+        if 1 != indicator_check!(unsafe {
+            EVP_DigestSignFinal(
+                self.md_ctx.as_mut_ptr(),
+                out_sig.as_mut_ptr(),
+                &mut out_sig_len,
+            )
+        }) {
+            return Err(Unspecified);
+        }
+
+        self.key_pair.encode_signature(&out_sig[..out_sig_len])
+    }
+}
+
 #[inline]
 fn get_signature_length(ctx: &mut DigestContext) -> Result<usize, Unspecified> {
     let mut out_sig_len = MaybeUninit::<usize>::uninit();
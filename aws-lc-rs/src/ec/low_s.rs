@@ -0,0 +1,189 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+#[cfg(feature = "ring-sig-verify")]
+use untrusted::Input;
+
+use aws_lc::{BN_cmp, BN_new, BN_rshift1, BN_sub, EC_GROUP_get0_order, BIGNUM};
+
+use crate::ec::{ec_group_from_nid, AlgorithmID, EcdsaVerificationAlgorithm};
+use crate::error::Unspecified;
+use crate::ptr::{ConstPointer, LcPtr};
+use crate::sealed;
+use crate::signature::{Signature, VerificationAlgorithm};
+
+impl EcdsaVerificationAlgorithm {
+    /// Returns whether `signature`, encoded in this algorithm's signature format, is in the
+    /// canonical "low-S" form, i.e. its `s` component is at most half the curve order.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `signature` is not a well-formed signature for this algorithm's
+    /// curve.
+    pub fn is_low_s(&self, signature: &[u8]) -> Result<bool, Unspecified> {
+        let (_, s) = self.signature_components(signature)?;
+        Ok(negate_high_s(self.id, &s)?.is_none())
+    }
+
+    /// Converts `signature`, encoded in this algorithm's signature format, to the canonical
+    /// "low-S" form by replacing `s` with `n - s` if `s` is greater than half the curve order
+    /// `n`. Both forms are valid signatures of the same message under the same key.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `signature` is not a well-formed signature for this algorithm's
+    /// curve.
+    pub fn normalize_low_s(&self, signature: &[u8]) -> Result<Signature, Unspecified> {
+        let (r, s) = self.signature_components(signature)?;
+        match negate_high_s(self.id, &s)? {
+            Some(low_s) => self.signature_from_components(&r, &low_s),
+            None => self.signature_from_components(&r, &s),
+        }
+    }
+}
+
+/// An ECDSA verification algorithm that only accepts signatures in the canonical "low-S" form,
+/// as required by Bitcoin-derived systems and other protocols sensitive to signature
+/// malleability.
+///
+/// ```
+/// # #[cfg(not(feature = "strict-fips"))]
+/// use aws_lc_rs::signature::{EcdsaLowSVerificationAlgorithm, ECDSA_P256K1_SHA256_ASN1};
+///
+/// # #[cfg(not(feature = "strict-fips"))]
+/// static ECDSA_P256K1_SHA256_ASN1_LOW_S: EcdsaLowSVerificationAlgorithm =
+///     EcdsaLowSVerificationAlgorithm::new(&ECDSA_P256K1_SHA256_ASN1);
+/// ```
+#[derive(Debug)]
+pub struct EcdsaLowSVerificationAlgorithm(&'static EcdsaVerificationAlgorithm);
+
+impl EcdsaLowSVerificationAlgorithm {
+    /// Wraps `algorithm` to additionally reject signatures that are not in "low-S" form.
+    #[must_use]
+    pub const fn new(algorithm: &'static EcdsaVerificationAlgorithm) -> Self {
+        Self(algorithm)
+    }
+}
+
+impl sealed::Sealed for EcdsaLowSVerificationAlgorithm {}
+
+impl VerificationAlgorithm for EcdsaLowSVerificationAlgorithm {
+    #[inline]
+    #[cfg(feature = "ring-sig-verify")]
+    fn verify(
+        &self,
+        public_key: Input<'_>,
+        msg: Input<'_>,
+        signature: Input<'_>,
+    ) -> Result<(), Unspecified> {
+        self.verify_sig(
+            public_key.as_slice_less_safe(),
+            msg.as_slice_less_safe(),
+            signature.as_slice_less_safe(),
+        )
+    }
+
+    fn verify_sig(
+        &self,
+        public_key: &[u8],
+        msg: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Unspecified> {
+        if !self.0.is_low_s(signature)? {
+            return Err(Unspecified);
+        }
+        self.0.verify_sig(public_key, msg, signature)
+    }
+}
+
+// Returns `n - s` if `s` is greater than half the order `n`, otherwise `None`.
+fn negate_high_s(alg_id: &'static AlgorithmID, s: &[u8]) -> Result<Option<Vec<u8>>, Unspecified> {
+    let ec_group = ec_group_from_nid(alg_id.nid())?;
+    let order = ConstPointer::new(unsafe { EC_GROUP_get0_order(*ec_group) })?;
+    let s = LcPtr::<BIGNUM>::try_from(s)?;
+
+    let half_order = LcPtr::new(unsafe { BN_new() })?;
+    if 1 != unsafe { BN_rshift1(*half_order, *order) } {
+        return Err(Unspecified);
+    }
+    if unsafe { BN_cmp(*s, *half_order) } <= 0 {
+        return Ok(None);
+    }
+
+    if 1 != unsafe { BN_sub(*s, *order, *s) } {
+        return Err(Unspecified);
+    }
+    Ok(Some(s.as_const().to_be_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::signature;
+    #[cfg(not(feature = "strict-fips"))]
+    use crate::{
+        rand::SystemRandom,
+        signature::{EcdsaKeyPair, EcdsaLowSVerificationAlgorithm, KeyPair, UnparsedPublicKey},
+    };
+
+    #[cfg(not(feature = "strict-fips"))]
+    static ECDSA_P256K1_SHA256_FIXED_LOW_S: EcdsaLowSVerificationAlgorithm =
+        EcdsaLowSVerificationAlgorithm::new(&signature::ECDSA_P256K1_SHA256_FIXED);
+
+    #[cfg(not(feature = "strict-fips"))]
+    #[test]
+    fn low_s() {
+        let rng = SystemRandom::new();
+        let alg = &signature::ECDSA_P256K1_SHA256_FIXED_SIGNING;
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(alg, &rng).unwrap();
+        let key_pair = EcdsaKeyPair::from_pkcs8(alg, pkcs8.as_ref()).unwrap();
+        let public_key = key_pair.public_key().as_ref();
+        let msg = b"low-s";
+
+        let mut seen_high_s = false;
+        for _ in 0..32 {
+            let sig = key_pair.sign(&rng, msg).unwrap();
+            let low = alg.normalize_low_s(sig.as_ref()).unwrap();
+            assert!(alg.is_low_s(low.as_ref()).unwrap());
+            UnparsedPublicKey::new(&ECDSA_P256K1_SHA256_FIXED_LOW_S, public_key)
+                .verify(msg, low.as_ref())
+                .unwrap();
+
+            if alg.is_low_s(sig.as_ref()).unwrap() {
+                assert_eq!(sig.as_ref(), low.as_ref());
+            } else {
+                seen_high_s = true;
+                assert_ne!(sig.as_ref(), low.as_ref());
+                UnparsedPublicKey::new(&signature::ECDSA_P256K1_SHA256_FIXED, public_key)
+                    .verify(msg, sig.as_ref())
+                    .unwrap();
+                assert!(
+                    UnparsedPublicKey::new(&ECDSA_P256K1_SHA256_FIXED_LOW_S, public_key)
+                        .verify(msg, sig.as_ref())
+                        .is_err()
+                );
+            }
+
+            let sig = key_pair.sign_low_s(&rng, msg).unwrap();
+            assert!(alg.is_low_s(sig.as_ref()).unwrap());
+        }
+        // The chance of 32 consecutive low-S signatures is 2^-32.
+        assert!(seen_high_s);
+    }
+
+    #[test]
+    fn low_s_boundary() {
+        let alg = &signature::ECDSA_P256_SHA256_FIXED;
+        // n = ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551
+        let half_n = crate::test::from_hex(
+            "7fffffff800000007fffffffffffffffde737d56d38bcf4279dce5617e3192a8",
+        )
+        .unwrap();
+        let mut sig = vec![0u8; 64];
+        sig[31] = 1;
+        sig[32..].copy_from_slice(&half_n);
+        assert!(alg.is_low_s(&sig).unwrap());
+
+        sig[63] += 1;
+        assert!(!alg.is_low_s(&sig).unwrap());
+        let low = alg.normalize_low_s(&sig).unwrap();
+        assert_eq!(&low.as_ref()[32..], half_n.as_slice());
+    }
+}
@@ -0,0 +1,203 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use core::ptr::null_mut;
+
+// TODO: Uncomment when MSRV >= 1.64
+// use core::ffi::c_int;
+use std::os::raw::c_int;
+
+use aws_lc::{
+    BN_CTX_new, BN_add, BN_cmp, BN_is_zero, BN_mod_inverse, BN_mod_mul, BN_mod_sub, BN_new,
+    BN_nnmod, EC_GROUP_get0_order, EC_GROUP_get_curve_GFp, EC_POINT_is_at_infinity, EC_POINT_mul,
+    EC_POINT_new, EC_POINT_set_compressed_coordinates_GFp, BIGNUM,
+};
+
+use crate::digest;
+use crate::ec::{
+    ec_group_from_nid, ec_point_to_bytes, AlgorithmID, EcdsaVerificationAlgorithm,
+    PUBLIC_KEY_MAX_LEN,
+};
+use crate::error::Unspecified;
+use crate::ptr::{ConstPointer, LcPtr};
+use crate::signature::VerificationAlgorithm;
+
+impl EcdsaVerificationAlgorithm {
+    /// Recovers the public key under which `signature`, encoded in this algorithm's signature
+    /// format, is a valid signature of `msg`.
+    ///
+    /// `recovery_id` selects among the (up to four) candidate public keys: bit 0 is the parity of
+    /// the `y` coordinate of the signature's ephemeral point *R*, and bit 1 is set if the `x`
+    /// coordinate of *R* is `r + n` rather than `r`. Callers without a recovery id can try each
+    /// value in `0..4`; every candidate returned is a key under which `signature` verifies.
+    ///
+    /// The public key is returned in uncompressed form (X9.62), as accepted by
+    /// [`crate::signature::UnparsedPublicKey`].
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `recovery_id` is greater than 3, if `signature` is not a
+    /// well-formed signature for this algorithm's curve, or if no public key corresponds to
+    /// `recovery_id`.
+    pub fn recover_public_key(
+        &self,
+        msg: &[u8],
+        signature: &[u8],
+        recovery_id: u8,
+    ) -> Result<Vec<u8>, Unspecified> {
+        if recovery_id > 3 {
+            return Err(Unspecified);
+        }
+        let (r, s) = self.signature_components(signature)?;
+        let digest = digest::try_digest(self.digest, msg)?;
+        let public_key = recover_public_key(self.id, digest.as_ref(), &r, &s, recovery_id)?;
+        self.verify_sig(&public_key, msg, signature)?;
+        Ok(public_key)
+    }
+}
+
+// Computes Q = r⁻¹(sR - eG) (SEC 1 Section 4.1.6). All inputs are public, so variable-time
+// arithmetic is acceptable here.
+#[allow(clippy::many_single_char_names)]
+fn recover_public_key(
+    alg_id: &'static AlgorithmID,
+    digest: &[u8],
+    r: &[u8],
+    s: &[u8],
+    recovery_id: u8,
+) -> Result<Vec<u8>, Unspecified> {
+    let ec_group = ec_group_from_nid(alg_id.nid())?;
+    let bn_ctx = LcPtr::new(unsafe { BN_CTX_new() })?;
+    let order = ConstPointer::new(unsafe { EC_GROUP_get0_order(*ec_group) })?;
+
+    let r = LcPtr::<BIGNUM>::try_from(r)?;
+    let s = LcPtr::<BIGNUM>::try_from(s)?;
+    for component in [&r, &s] {
+        if 1 == unsafe { BN_is_zero(**component) } || 0 <= unsafe { BN_cmp(**component, *order) } {
+            return Err(Unspecified);
+        }
+    }
+
+    // The x-coordinate of R is either r or r + n, and must be a field element.
+    let x = new_bn()?;
+    if 1 != unsafe { BN_add(*x, *r, *order) } {
+        return Err(Unspecified);
+    }
+    let x = if recovery_id & 2 == 0 { &r } else { &x };
+    let p = new_bn()?;
+    if 1 != unsafe { EC_GROUP_get_curve_GFp(*ec_group, *p, null_mut(), null_mut(), *bn_ctx) } {
+        return Err(Unspecified);
+    }
+    if 0 <= unsafe { BN_cmp(**x, *p) } {
+        return Err(Unspecified);
+    }
+
+    let r_point = LcPtr::new(unsafe { EC_POINT_new(*ec_group) })?;
+    if 1 != unsafe {
+        EC_POINT_set_compressed_coordinates_GFp(
+            *ec_group,
+            *r_point,
+            **x,
+            c_int::from(recovery_id & 1),
+            *bn_ctx,
+        )
+    } {
+        return Err(Unspecified);
+    }
+
+    // The digest is truncated to the bit length of the order. The orders of P-256, P-384 and
+    // secp256k1 are a whole number of bytes, and no supported digest is longer than P-521's
+    // order, so a byte-wise truncation suffices.
+    let e = LcPtr::<BIGNUM>::try_from(&digest[..digest.len().min(alg_id.private_key_size())])?;
+    if 1 != unsafe { BN_nnmod(*e, *e, *order, *bn_ctx) } {
+        return Err(Unspecified);
+    }
+
+    let r_inv = new_bn()?;
+    if unsafe { BN_mod_inverse(*r_inv, *r, *order, *bn_ctx) }.is_null() {
+        return Err(Unspecified);
+    }
+    // u1 = -e * r⁻¹ mod n
+    let u1 = new_bn()?;
+    if 1 != unsafe { BN_mod_mul(*u1, *e, *r_inv, *order, *bn_ctx) }
+        || 1 != unsafe { BN_mod_sub(*u1, *order, *u1, *order, *bn_ctx) }
+    {
+        return Err(Unspecified);
+    }
+    // u2 = s * r⁻¹ mod n
+    let u2 = new_bn()?;
+    if 1 != unsafe { BN_mod_mul(*u2, *s, *r_inv, *order, *bn_ctx) } {
+        return Err(Unspecified);
+    }
+
+    let q_point = LcPtr::new(unsafe { EC_POINT_new(*ec_group) })?;
+    if 1 != unsafe { EC_POINT_mul(*ec_group, *q_point, *u1, *r_point, *u2, *bn_ctx) }
+        || 1 == unsafe { EC_POINT_is_at_infinity(*ec_group, *q_point) }
+    {
+        return Err(Unspecified);
+    }
+
+    let mut buffer = [0u8; PUBLIC_KEY_MAX_LEN];
+    let len = unsafe { ec_point_to_bytes(&ec_group.as_const(), &q_point.as_const(), &mut buffer)? };
+    Ok(buffer[..len].to_vec())
+}
+
+#[inline]
+fn new_bn() -> Result<LcPtr<BIGNUM>, Unspecified> {
+    Ok(LcPtr::new(unsafe { BN_new() })?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rand::SystemRandom;
+    use crate::signature::{self, EcdsaKeyPair, EcdsaSigningAlgorithm, KeyPair};
+
+    #[test]
+    fn recover_public_key() {
+        let rng = SystemRandom::new();
+        let msg = b"recoverable";
+        for alg in [
+            &signature::ECDSA_P256_SHA256_FIXED_SIGNING,
+            &signature::ECDSA_P256_SHA256_ASN1_SIGNING,
+            &signature::ECDSA_P384_SHA384_ASN1_SIGNING,
+            &signature::ECDSA_P521_SHA512_FIXED_SIGNING,
+            #[cfg(not(feature = "strict-fips"))]
+            &signature::ECDSA_P256K1_SHA256_FIXED_SIGNING,
+            #[cfg(not(feature = "strict-fips"))]
+            &signature::ECDSA_P256K1_SHA256_ASN1_SIGNING,
+        ] {
+            let alg: &'static EcdsaSigningAlgorithm = alg;
+            let pkcs8 = EcdsaKeyPair::generate_pkcs8(alg, &rng).unwrap();
+            let key_pair = EcdsaKeyPair::from_pkcs8(alg, pkcs8.as_ref()).unwrap();
+            let sig = key_pair.sign(&rng, msg).unwrap();
+
+            let candidates: Vec<Vec<u8>> = (0..4)
+                .filter_map(|id| alg.recover_public_key(msg, sig.as_ref(), id).ok())
+                .collect();
+            assert!(!candidates.is_empty());
+            assert!(candidates
+                .iter()
+                .any(|key| key.as_slice() == key_pair.public_key().as_ref()));
+
+            assert!(alg.recover_public_key(msg, sig.as_ref(), 4).is_err());
+            assert!(alg
+                .recover_public_key(b"other message", sig.as_ref(), 0)
+                .map_or(true, |key| key.as_slice() != key_pair.public_key().as_ref()));
+        }
+    }
+
+    #[test]
+    fn recover_public_key_rejects_invalid_components() {
+        let alg = &signature::ECDSA_P256_SHA256_FIXED;
+        // s = 0
+        let mut sig = [0u8; 64];
+        sig[31] = 1;
+        assert!(alg.recover_public_key(b"", &sig, 0).is_err());
+        // r = n
+        let sig = crate::test::from_hex(
+            "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551\
+             0000000000000000000000000000000000000000000000000000000000000001",
+        )
+        .unwrap();
+        assert!(alg.recover_public_key(b"", &sig, 0).is_err());
+    }
+}
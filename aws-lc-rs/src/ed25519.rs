@@ -15,16 +15,22 @@ use zeroize::Zeroize;
 use aws_lc::{
     ED25519_keypair_from_seed, ED25519_sign, ED25519_verify, EVP_PKEY_CTX_new_id,
     EVP_PKEY_get_raw_private_key, EVP_PKEY_get_raw_public_key, EVP_PKEY_keygen,
-    EVP_PKEY_keygen_init, EVP_PKEY_new_raw_private_key, EVP_PKEY, EVP_PKEY_ED25519,
+    EVP_PKEY_keygen_init, EVP_PKEY_new_raw_private_key, EVP_PKEY_new_raw_public_key,
+    EVP_marshal_public_key, EVP_PKEY, EVP_PKEY_ED25519,
 };
 
-use crate::encoding::{AsBigEndian, Curve25519SeedBin};
+use crate::cbb::LcCBB;
+use crate::encoding::{AsBigEndian, AsDer, Curve25519SeedBin, PublicKeyX509Der};
 use crate::error::{KeyRejected, Unspecified};
 use crate::fips::indicator_check;
+#[cfg(feature = "jwk")]
+use crate::jwk::Jwk;
 use crate::pkcs8::{Document, Version};
 use crate::ptr::LcPtr;
 use crate::rand::SecureRandom;
-use crate::signature::{KeyPair, Signature, VerificationAlgorithm};
+use crate::signature::{KeyPair, Signature, Signer, VerificationAlgorithm};
+#[cfg(feature = "ssh")]
+use crate::ssh;
 use crate::{constant_time, hex, sealed};
 
 /// The length of an Ed25519 public key.
@@ -63,19 +69,143 @@ impl VerificationAlgorithm for EdDSAParameters {
         msg: &[u8],
         signature: &[u8],
     ) -> Result<(), Unspecified> {
-        if 1 != unsafe {
-            ED25519_verify(
-                msg.as_ptr(),
-                msg.len(),
-                signature.as_ptr(),
-                public_key.as_ptr(),
-            )
-        } {
+        verify_ed25519(public_key, msg, signature)
+    }
+}
+
+/// The acceptance criteria for Ed25519 signature verification.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ed25519VerificationPolicy {
+    /// The RFC 8032 verification performed by [`crate::signature::ED25519`]. The `S` component of
+    /// the signature must be less than the group order, but public keys and `R` components of
+    /// small order are accepted. Whether non-canonically encoded public keys are accepted may
+    /// differ between the platform-specific implementations in *AWS-LC*.
+    Permissive,
+
+    /// In addition to the checks of [`Self::Permissive`], rejects public keys and `R` components
+    /// that are not canonically encoded or that are points of small order. The result does not
+    /// depend on the platform.
+    Strict,
+}
+
+/// Ed25519 signature verification with an explicit [`Ed25519VerificationPolicy`].
+#[derive(Debug)]
+pub struct Ed25519VerificationAlgorithm {
+    policy: Ed25519VerificationPolicy,
+}
+
+impl Ed25519VerificationAlgorithm {
+    /// Constructs an Ed25519 verification algorithm that applies `policy`.
+    #[must_use]
+    pub const fn with_policy(policy: Ed25519VerificationPolicy) -> Self {
+        Self { policy }
+    }
+
+    /// The policy applied by this algorithm.
+    #[must_use]
+    pub fn policy(&self) -> Ed25519VerificationPolicy {
+        self.policy
+    }
+}
+
+impl sealed::Sealed for Ed25519VerificationAlgorithm {}
+
+impl VerificationAlgorithm for Ed25519VerificationAlgorithm {
+    #[inline]
+    #[cfg(feature = "ring-sig-verify")]
+    fn verify(
+        &self,
+        public_key: Input<'_>,
+        msg: Input<'_>,
+        signature: Input<'_>,
+    ) -> Result<(), Unspecified> {
+        self.verify_sig(
+            public_key.as_slice_less_safe(),
+            msg.as_slice_less_safe(),
+            signature.as_slice_less_safe(),
+        )
+    }
+
+    fn verify_sig(
+        &self,
+        public_key: &[u8],
+        msg: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Unspecified> {
+        if public_key.len() != ED25519_PUBLIC_KEY_LEN || signature.len() != ED25519_SIGNATURE_LEN {
             return Err(Unspecified);
         }
-        crate::fips::set_fips_service_status_unapproved();
-        Ok(())
+        if let Ed25519VerificationPolicy::Strict = self.policy {
+            for point in [public_key, &signature[..ED25519_PUBLIC_KEY_LEN]] {
+                if !is_canonical_point_encoding(point) || is_small_order_point_encoding(point) {
+                    return Err(Unspecified);
+                }
+            }
+        }
+        verify_ed25519(public_key, msg, signature)
+    }
+}
+
+fn verify_ed25519(public_key: &[u8], msg: &[u8], signature: &[u8]) -> Result<(), Unspecified> {
+    if public_key.len() != ED25519_PUBLIC_KEY_LEN || signature.len() != ED25519_SIGNATURE_LEN {
+        return Err(Unspecified);
+    }
+    if 1 != unsafe {
+        ED25519_verify(
+            msg.as_ptr(),
+            msg.len(),
+            signature.as_ptr(),
+            public_key.as_ptr(),
+        )
+    } {
+        return Err(Unspecified);
     }
+    crate::fips::set_fips_service_status_unapproved();
+    Ok(())
+}
+
+// The y-coordinates, little-endian, of the points of small order. Each is shared by a point and
+// its negation, which differ only in the sign bit.
+const SMALL_ORDER_Y: [[u8; 32]; 5] = [
+    // 0 (order 4)
+    [0; 32],
+    // 1 (order 1)
+    [
+        0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00,
+    ],
+    // p - 1 (order 2)
+    [
+        0xec, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0x7f,
+    ],
+    // order 8
+    [
+        0x26, 0xe8, 0x95, 0x8f, 0xc2, 0xb2, 0x27, 0xb0, 0x45, 0xc3, 0xf4, 0x89, 0xf2, 0xef, 0x98,
+        0xf0, 0xd5, 0xdf, 0xac, 0x05, 0xd3, 0xc6, 0x33, 0x39, 0xb1, 0x38, 0x02, 0x88, 0x6d, 0x53,
+        0xfc, 0x05,
+    ],
+    // order 8
+    [
+        0xc7, 0x17, 0x6a, 0x70, 0x3d, 0x4d, 0xd8, 0x4f, 0xba, 0x3c, 0x0b, 0x76, 0x0d, 0x10, 0x67,
+        0x0f, 0x2a, 0x20, 0x53, 0xfa, 0x2c, 0x39, 0xcc, 0xc6, 0x4e, 0xc7, 0xfd, 0x77, 0x92, 0xac,
+        0x03, 0x7a,
+    ],
+];
+
+// Whether the y-coordinate of the encoded point, ignoring the sign bit, is less than
+// p = 2^255 - 19.
+fn is_canonical_point_encoding(encoded: &[u8]) -> bool {
+    encoded[31] & 0x7f != 0x7f || encoded[1..31].iter().any(|b| *b != 0xff) || encoded[0] < 0xed
+}
+
+fn is_small_order_point_encoding(encoded: &[u8]) -> bool {
+    SMALL_ORDER_Y
+        .iter()
+        .any(|y| encoded[..31] == y[..31] && encoded[31] & 0x7f == y[31])
 }
 
 /// An Ed25519 key pair, for signing.
@@ -135,6 +265,52 @@ impl AsRef<[u8]> for PublicKey {
     }
 }
 
+impl PublicKey {
+    /// The raw 32-byte public key (RFC 8032), as used by the `x` member of an `OKP` JSON Web Key
+    /// (RFC 8037) or COSE_Key (RFC 9053).
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8; ED25519_PUBLIC_KEY_LEN] {
+        &self.0
+    }
+
+    /// Provides the public key as an `OKP` JSON Web Key (RFC 8037).
+    #[cfg(feature = "jwk")]
+    #[must_use]
+    pub fn to_jwk(&self) -> Jwk {
+        let mut jwk = Jwk::new("OKP");
+        jwk.set("crv", "Ed25519");
+        jwk.set_bytes("x", &self.0);
+        jwk
+    }
+
+    /// Provides the public key as an `ssh-ed25519` OpenSSH public key, with an empty comment.
+    #[cfg(feature = "ssh")]
+    #[must_use]
+    pub fn to_openssh(&self) -> ssh::PublicKey {
+        ssh::PublicKey::new(&ssh::PublicKeyFields::Ed25519 {
+            public_key: &self.0,
+        })
+    }
+}
+
+impl AsDer<PublicKeyX509Der<'static>> for PublicKey {
+    /// Provides the public key as a DER-encoded (X.509) `SubjectPublicKeyInfo` structure.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if serialization failed.
+    fn as_der(&self) -> Result<PublicKeyX509Der<'static>, Unspecified> {
+        let evp_pkey = LcPtr::new(unsafe {
+            EVP_PKEY_new_raw_public_key(EVP_PKEY_ED25519, null_mut(), self.0.as_ptr(), self.0.len())
+        })?;
+        // An Ed25519 `SubjectPublicKeyInfo` is 44 bytes.
+        let mut der = LcCBB::new(44);
+        if 1 != unsafe { EVP_marshal_public_key(der.as_mut_ptr(), *evp_pkey) } {
+            return Err(Unspecified);
+        }
+        Ok(PublicKeyX509Der::from(der.into_buffer()?))
+    }
+}
+
 impl Debug for PublicKey {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.write_str(&format!("PublicKey(\"{}\")", hex::encode(self.0)))
@@ -201,16 +377,7 @@ impl Ed25519KeyPair {
     /// `error::Unspecified` on internal error.
     ///
     pub fn to_pkcs8(&self) -> Result<Document, Unspecified> {
-        unsafe {
-            let evp_pkey: LcPtr<EVP_PKEY> = LcPtr::new(EVP_PKEY_new_raw_private_key(
-                EVP_PKEY_ED25519,
-                null_mut(),
-                self.private_key.as_ref().as_ptr(),
-                ED25519_PRIVATE_KEY_SEED_LEN,
-            ))?;
-
-            evp_pkey.marshall_private_key(Version::V2)
-        }
+        self.evp_pkey()?.marshall_private_key(Version::V2)
     }
 
     /// Generates a `Ed25519KeyPair` using the `rng` provided, then serializes that key as a
@@ -238,16 +405,18 @@ impl Ed25519KeyPair {
     /// `error::Unspecified` on internal error.
     ///
     pub fn to_pkcs8v1(&self) -> Result<Document, Unspecified> {
-        let evp_pkey: LcPtr<EVP_PKEY> = LcPtr::new(unsafe {
+        self.evp_pkey()?.marshall_private_key(Version::V1)
+    }
+
+    pub(crate) fn evp_pkey(&self) -> Result<LcPtr<EVP_PKEY>, Unspecified> {
+        Ok(LcPtr::new(unsafe {
             EVP_PKEY_new_raw_private_key(
                 EVP_PKEY_ED25519,
                 null_mut(),
                 self.private_key.as_ref().as_ptr(),
                 ED25519_PRIVATE_KEY_SEED_LEN,
             )
-        })?;
-
-        evp_pkey.marshall_private_key(Version::V1)
+        })?)
     }
 
     /// Constructs an Ed25519 key pair from the private key seed `seed` and its
@@ -268,6 +437,32 @@ impl Ed25519KeyPair {
             return Err(KeyRejected::inconsistent_components());
         }
 
+        let key_pair = Self::from_seed(&seed[..ED25519_SEED_LEN]);
+
+        constant_time::verify_slices_are_equal(public_key, key_pair.public_key.as_ref())
+            .map_err(|_| KeyRejected::inconsistent_components())?;
+
+        Ok(key_pair)
+    }
+
+    /// Constructs an Ed25519 key pair from the 32-byte private key seed `seed`, as exchanged
+    /// by formats such as libsodium, OpenSSH and age.
+    ///
+    /// The public key is derived from the seed. Prefer
+    /// `Ed25519KeyPair::from_seed_and_public_key()` when the public key is also available, so that
+    /// the two can be checked for consistency.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `seed` is not exactly 32 bytes.
+    pub fn from_seed_unchecked(seed: &[u8]) -> Result<Self, KeyRejected> {
+        if seed.len() != ED25519_SEED_LEN {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        Ok(Self::from_seed(seed))
+    }
+
+    fn from_seed(seed: &[u8]) -> Self {
+        debug_assert_eq!(seed.len(), ED25519_SEED_LEN);
         let mut derived_public_key = MaybeUninit::<[u8; ED25519_PUBLIC_KEY_LEN]>::uninit();
         let mut private_key = MaybeUninit::<[u8; ED25519_PRIVATE_KEY_LEN]>::uninit();
         unsafe {
@@ -280,15 +475,12 @@ impl Ed25519KeyPair {
         let derived_public_key = unsafe { derived_public_key.assume_init() };
         let mut private_key = unsafe { private_key.assume_init() };
 
-        constant_time::verify_slices_are_equal(public_key, &derived_public_key)
-            .map_err(|_| KeyRejected::inconsistent_components())?;
-
         let key_pair = Self {
             private_key: Box::new(private_key),
             public_key: PublicKey(derived_public_key),
         };
         private_key.zeroize();
-        Ok(key_pair)
+        key_pair
     }
 
     /// Constructs an Ed25519 key pair by parsing an unencrypted PKCS#8 v1 or v2
@@ -406,6 +598,85 @@ impl Ed25519KeyPair {
     pub fn seed(&self) -> Result<Seed, Unspecified> {
         Ok(Seed(self))
     }
+
+    /// Constructs an Ed25519 key pair from an `OKP` JSON Web Key (RFC 8037) with the private key
+    /// member `d`. The public key member `x` is checked for consistency with `d`.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the key's `kty` or `crv` is not for Ed25519, a member is missing
+    /// or malformed, or the key is otherwise unacceptable.
+    #[cfg(feature = "jwk")]
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, KeyRejected> {
+        jwk.expect("OKP", Some("Ed25519"))?;
+        let x = jwk.bytes("x")?;
+        let mut d = jwk.bytes("d")?;
+        let key_pair = if d.len() == ED25519_SEED_LEN {
+            Self::from_seed_and_public_key(&d, &x)
+        } else {
+            Err(KeyRejected::invalid_encoding())
+        };
+        d.zeroize();
+        key_pair
+    }
+
+    /// Serializes this key pair as an `OKP` JSON Web Key (RFC 8037), including the private key
+    /// member `d`.
+    ///
+    /// # Errors
+    /// Currently the function cannot fail, but it might in future implementations.
+    #[cfg(feature = "jwk")]
+    pub fn to_jwk(&self) -> Result<Jwk, Unspecified> {
+        let mut jwk = self.public_key.to_jwk();
+        let seed = self.seed()?.as_be_bytes()?;
+        jwk.set_bytes("d", seed.as_ref());
+        Ok(jwk)
+    }
+
+    /// Constructs an Ed25519 key pair from an `ssh-ed25519` OpenSSH private key. The public key
+    /// is checked for consistency with the seed.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the key is not an `ssh-ed25519` key, or is otherwise
+    /// unacceptable.
+    #[cfg(feature = "ssh")]
+    pub fn from_openssh(key: &ssh::PrivateKey) -> Result<Self, KeyRejected> {
+        match key.fields()? {
+            (
+                ssh::PublicKeyFields::Ed25519 { public_key },
+                ssh::PrivateKeyFields::Ed25519 { seed },
+            ) => Self::from_seed_and_public_key(seed, public_key),
+            _ => Err(KeyRejected::wrong_algorithm()),
+        }
+    }
+
+    /// Serializes this key pair as an `ssh-ed25519` OpenSSH private key, with an empty comment.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    #[cfg(feature = "ssh")]
+    pub fn to_openssh(&self) -> Result<ssh::PrivateKey, Unspecified> {
+        let seed = self.seed()?.as_be_bytes()?;
+        ssh::PrivateKey::new(
+            &ssh::PublicKeyFields::Ed25519 {
+                public_key: &self.public_key.0,
+            },
+            &ssh::PrivateKeyFields::Ed25519 {
+                seed: seed.as_ref(),
+            },
+        )
+    }
+}
+
+impl Signer for Ed25519KeyPair {
+    #[inline]
+    fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>, Unspecified> {
+        Ok(self.sign(message).as_ref().to_vec())
+    }
+
+    #[inline]
+    fn public_key_bytes(&self) -> &[u8] {
+        self.public_key.as_ref()
+    }
 }
 
 #[cfg(test)]
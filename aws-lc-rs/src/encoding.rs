@@ -4,8 +4,11 @@
 //! Serialization formats
 
 use crate::buffer::Buffer;
+use crate::error::{KeyRejected, Unspecified};
 use paste::paste;
 
+pub(crate) mod pem;
+
 macro_rules! generated_encodings {
     ($($name:ident),*) => { paste! {
         use core::fmt::{Debug, Error, Formatter};
@@ -58,9 +61,91 @@ pub(crate) use generated_encodings;
 generated_encodings!(
     EcPrivateKeyBin,
     EcPrivateKeyRfc5915Der,
+    EcPublicKeyCompressedBin,
     PublicKeyX509Der,
     Curve25519SeedBin,
-    Pkcs8V1Der
+    Pkcs8V1Der,
+    EncryptedPkcs8Der,
+    RsaPrivateKeyRfc8017Der,
+    RsaPublicKeyRfc8017Der,
+    CertificateDer,
+    CertificateSigningRequestDer
+);
+
+macro_rules! pem_encodings {
+    ($($name:ident => $label:literal),*) => {
+        $(
+            impl $name<'_> {
+                #[doc = concat!("Encodes as PEM ([RFC 7468]) with the `", $label, "` label.")]
+                ///
+                /// [RFC 7468]: https://tools.ietf.org/html/rfc7468
+                ///
+                /// # Errors
+                /// `error::Unspecified` if the document is too large to encode.
+                pub fn to_pem(&self) -> Result<String, Unspecified> {
+                    pem::encode($label, self.as_ref())
+                }
+            }
+
+            impl $name<'static> {
+                #[doc = concat!("Decodes a PEM ([RFC 7468]) document with the `", $label, "` label.")]
+                ///
+                /// Text before the `BEGIN` line is ignored.
+                ///
+                /// [RFC 7468]: https://tools.ietf.org/html/rfc7468
+                ///
+                /// # Errors
+                /// `error::KeyRejected` if no PEM document with the expected label is found, or
+                /// its contents are not valid base64.
+                pub fn from_pem(pem: &str) -> Result<Self, KeyRejected> {
+                    Ok(Self::new(pem::decode($label, pem)?))
+                }
+            }
+        )*
+    };
+}
+pem_encodings!(
+    EcPrivateKeyRfc5915Der => "EC PRIVATE KEY",
+    PublicKeyX509Der => "PUBLIC KEY",
+    Pkcs8V1Der => "PRIVATE KEY",
+    EncryptedPkcs8Der => "ENCRYPTED PRIVATE KEY",
+    RsaPrivateKeyRfc8017Der => "RSA PRIVATE KEY",
+    RsaPublicKeyRfc8017Der => "RSA PUBLIC KEY",
+    CertificateDer => "CERTIFICATE",
+    CertificateSigningRequestDer => "CERTIFICATE REQUEST"
+);
+
+#[cfg(feature = "legacy-pem")]
+macro_rules! encrypted_pem_encodings {
+    ($($name:ident => $label:literal),*) => {
+        $(
+            impl $name<'static> {
+                #[doc = concat!("Decrypts and decodes a PEM document with the `", $label, "` label, encrypted")]
+                /// with `password` in the OpenSSL "traditional" format, i.e. with
+                /// `Proc-Type: 4,ENCRYPTED` and `DEK-Info` headers ([RFC 1421]), as produced by
+                /// `openssl genrsa -aes256` or `openssl ec -aes256` in OpenSSL 1.x.
+                ///
+                /// The AES-128-CBC, AES-192-CBC, AES-256-CBC and DES-EDE3-CBC ciphers are
+                /// supported. The key is derived from `password` by a single iteration of MD5,
+                /// which offers little protection against guessing; keys should be migrated to
+                /// `pkcs8::encrypt`. Unencrypted documents are rejected; use `from_pem` for those.
+                ///
+                /// [RFC 1421]: https://tools.ietf.org/html/rfc1421
+                ///
+                /// # Errors
+                /// `error::KeyRejected` if no encrypted PEM document with the expected label is
+                /// found, its cipher is not supported, or the password is incorrect.
+                pub fn from_encrypted_pem(pem: &str, password: &[u8]) -> Result<Self, KeyRejected> {
+                    Ok(Self::new(pem::decode_encrypted($label, pem, password)?))
+                }
+            }
+        )*
+    };
+}
+#[cfg(feature = "legacy-pem")]
+encrypted_pem_encodings!(
+    EcPrivateKeyRfc5915Der => "EC PRIVATE KEY",
+    RsaPrivateKeyRfc8017Der => "RSA PRIVATE KEY"
 );
 
 /// Trait for types that can be serialized into a DER format.
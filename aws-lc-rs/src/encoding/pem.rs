@@ -0,0 +1,351 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Textual encoding of DER structures as described in [RFC 7468].
+//!
+//! [RFC 7468]: https://tools.ietf.org/html/rfc7468
+
+use crate::error::{KeyRejected, Unspecified};
+#[cfg(feature = "legacy-pem")]
+use crate::ptr::{LcPtr, Pointer};
+#[cfg(feature = "legacy-pem")]
+use aws_lc::{
+    EVP_BytesToKey, EVP_CIPHER_CTX_new, EVP_CIPHER_iv_length, EVP_DecryptFinal_ex,
+    EVP_DecryptInit_ex, EVP_DecryptUpdate, EVP_aes_128_cbc, EVP_aes_192_cbc, EVP_aes_256_cbc,
+    EVP_des_ede3_cbc, EVP_md5, EVP_CIPHER, EVP_MAX_KEY_LENGTH,
+};
+use aws_lc::{EVP_DecodeBase64, EVP_DecodedLength, EVP_EncodeBlock, EVP_EncodedLength};
+#[cfg(feature = "legacy-pem")]
+use core::ptr::null_mut;
+use zeroize::Zeroize;
+
+// RFC 7468 section 2: generators must wrap base64-encoded lines at 64 characters.
+const LINE_LEN: usize = 64;
+
+pub(crate) fn encode(label: &str, der: &[u8]) -> Result<String, Unspecified> {
+    encode_with_line_len(label, der, LINE_LEN)
+}
+
+// Formats that predate RFC 7468, such as OpenSSH's, may wrap lines at another length.
+pub(crate) fn encode_with_line_len(
+    label: &str,
+    der: &[u8],
+    line_len: usize,
+) -> Result<String, Unspecified> {
+    let mut base64 = base64_encode(der)?;
+
+    let mut pem =
+        String::with_capacity(2 * label.len() + base64.len() + base64.len() / line_len + 40);
+    pem.push_str("-----BEGIN ");
+    pem.push_str(label);
+    pem.push_str("-----\n");
+    for line in base64.as_bytes().chunks(line_len) {
+        // The base64 alphabet is ASCII.
+        pem.extend(line.iter().map(|&b| char::from(b)));
+        pem.push('\n');
+    }
+    pem.push_str("-----END ");
+    pem.push_str(label);
+    pem.push_str("-----\n");
+
+    base64.zeroize();
+    Ok(pem)
+}
+
+pub(crate) fn decode(label: &str, pem: &str) -> Result<Vec<u8>, KeyRejected> {
+    let (headers, mut der) = decode_with_headers(label, pem)?;
+    if !headers.is_empty() {
+        // RFC 1421 headers, such as those of an encrypted document, are not permitted here.
+        der.zeroize();
+        return Err(KeyRejected::invalid_encoding());
+    }
+    Ok(der)
+}
+
+/// Decodes a document with the OpenSSL "traditional" encryption: RFC 1421 `Proc-Type` and
+/// `DEK-Info` headers, and a key derived from `password` with `EVP_BytesToKey` and MD5.
+#[cfg(feature = "legacy-pem")]
+pub(crate) fn decode_encrypted(
+    label: &str,
+    pem: &str,
+    password: &[u8],
+) -> Result<Vec<u8>, KeyRejected> {
+    let (headers, mut ciphertext) = decode_with_headers(label, pem)?;
+    let der = match headers.as_slice() {
+        [("Proc-Type", "4,ENCRYPTED"), ("DEK-Info", dek_info)] => {
+            decrypt(dek_info, password, &ciphertext)
+        }
+        _ => Err(KeyRejected::invalid_encoding()),
+    };
+    ciphertext.zeroize();
+    der
+}
+
+#[cfg(feature = "legacy-pem")]
+fn decrypt(dek_info: &str, password: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, KeyRejected> {
+    const CIPHERS: [(&str, unsafe extern "C" fn() -> *const EVP_CIPHER); 4] = [
+        ("AES-128-CBC", EVP_aes_128_cbc),
+        ("AES-192-CBC", EVP_aes_192_cbc),
+        ("AES-256-CBC", EVP_aes_256_cbc),
+        ("DES-EDE3-CBC", EVP_des_ede3_cbc),
+    ];
+
+    let (cipher_name, iv) = dek_info
+        .split_once(',')
+        .ok_or_else(KeyRejected::invalid_encoding)?;
+    let cipher = CIPHERS
+        .iter()
+        .find(|(name, _)| *name == cipher_name)
+        .map(|(_, cipher)| unsafe { cipher() })
+        .ok_or_else(KeyRejected::invalid_encoding)?;
+    let iv = crate::hex::decode(iv).map_err(|_| KeyRejected::invalid_encoding())?;
+    // The first 8 bytes of the IV are the salt of the key derivation.
+    if iv.len() != usize::try_from(unsafe { EVP_CIPHER_iv_length(cipher) })? || iv.len() < 8 {
+        return Err(KeyRejected::invalid_encoding());
+    }
+
+    let mut key = [0u8; EVP_MAX_KEY_LENGTH as usize];
+    let key_len = unsafe {
+        EVP_BytesToKey(
+            cipher,
+            EVP_md5(),
+            iv.as_ptr(),
+            password.as_ptr(),
+            password.len(),
+            1,
+            key.as_mut_ptr(),
+            null_mut(),
+        )
+    };
+
+    let mut der = vec![0u8; ciphertext.len() + 16];
+    let der_len = if key_len > 0 {
+        cbc_decrypt(cipher, &key, &iv, ciphertext, &mut der)
+    } else {
+        Err(Unspecified)
+    };
+    key.zeroize();
+    match der_len {
+        Ok(der_len) => {
+            der.truncate(der_len);
+            Ok(der)
+        }
+        Err(Unspecified) => {
+            // A bad padding is how an incorrect password is usually detected.
+            der.zeroize();
+            Err(KeyRejected::invalid_encoding())
+        }
+    }
+}
+
+#[cfg(feature = "legacy-pem")]
+fn cbc_decrypt(
+    cipher: *const EVP_CIPHER,
+    key: &[u8],
+    iv: &[u8],
+    ciphertext: &[u8],
+    output: &mut [u8],
+) -> Result<usize, Unspecified> {
+    let mut ctx = LcPtr::new(unsafe { EVP_CIPHER_CTX_new() })?;
+    if 1 != unsafe {
+        EVP_DecryptInit_ex(
+            ctx.as_mut_ptr(),
+            cipher,
+            null_mut(),
+            key.as_ptr(),
+            iv.as_ptr(),
+        )
+    } {
+        return Err(Unspecified);
+    }
+    let mut update_len = 0;
+    if 1 != unsafe {
+        EVP_DecryptUpdate(
+            ctx.as_mut_ptr(),
+            output.as_mut_ptr(),
+            &mut update_len,
+            ciphertext.as_ptr(),
+            ciphertext.len().try_into()?,
+        )
+    } {
+        return Err(Unspecified);
+    }
+    let update_len = usize::try_from(update_len)?;
+    let mut final_len = 0;
+    if 1 != unsafe {
+        EVP_DecryptFinal_ex(
+            ctx.as_mut_ptr(),
+            output[update_len..].as_mut_ptr(),
+            &mut final_len,
+        )
+    } {
+        return Err(Unspecified);
+    }
+    Ok(update_len + usize::try_from(final_len)?)
+}
+
+// An RFC 1421 header's name and value.
+type Header<'a> = (&'a str, &'a str);
+
+// Decodes a document, and returns its RFC 1421 headers, which precede the base64 text and are
+// separated from it by an empty line.
+fn decode_with_headers<'a>(
+    label: &str,
+    pem: &'a str,
+) -> Result<(Vec<Header<'a>>, Vec<u8>), KeyRejected> {
+    let begin = format!("-----BEGIN {label}-----");
+    let end = format!("-----END {label}-----");
+
+    let mut lines = pem.lines().map(str::trim);
+    // Text before the encapsulation boundary is permitted (RFC 7468 section 5.2).
+    if !lines.by_ref().any(|line| line == begin) {
+        return Err(KeyRejected::invalid_encoding());
+    }
+
+    let mut headers = Vec::new();
+    let mut base64 = Vec::with_capacity(pem.len());
+    let mut found_end = false;
+    let mut in_headers = true;
+    for line in lines {
+        if line == end {
+            found_end = true;
+            break;
+        }
+        if in_headers {
+            if let Some((name, value)) = line.split_once(':') {
+                headers.push((name.trim(), value.trim()));
+                continue;
+            }
+            in_headers = false;
+            if line.is_empty() && !headers.is_empty() {
+                continue;
+            }
+        }
+        if line.starts_with("-----") || line.contains(':') {
+            // Another boundary, or a header after the base64 text.
+            base64.zeroize();
+            return Err(KeyRejected::invalid_encoding());
+        }
+        base64.extend(line.bytes().filter(|b| !b.is_ascii_whitespace()));
+    }
+    if !found_end || (!headers.is_empty() && in_headers) {
+        base64.zeroize();
+        return Err(KeyRejected::invalid_encoding());
+    }
+
+    let der = base64_decode(&base64);
+    base64.zeroize();
+    match der {
+        Ok(der) if !der.is_empty() => Ok((headers, der)),
+        _ => Err(KeyRejected::invalid_encoding()),
+    }
+}
+
+/// Encodes `bytes` using standard base64 with padding, without line breaks.
+pub(crate) fn base64_encode(bytes: &[u8]) -> Result<String, Unspecified> {
+    let mut base64_len = 0;
+    // `EVP_EncodedLength` includes the trailing NUL written by `EVP_EncodeBlock`.
+    if 1 != unsafe { EVP_EncodedLength(&mut base64_len, bytes.len()) } {
+        return Err(Unspecified);
+    }
+    let mut base64 = vec![0u8; base64_len];
+    let written = unsafe { EVP_EncodeBlock(base64.as_mut_ptr(), bytes.as_ptr(), bytes.len()) };
+    base64.truncate(written);
+    String::from_utf8(base64).map_err(|_| Unspecified)
+}
+
+/// Decodes standard base64 with padding and without whitespace.
+pub(crate) fn base64_decode(base64: &[u8]) -> Result<Vec<u8>, KeyRejected> {
+    let mut len = 0;
+    if 1 != unsafe { EVP_DecodedLength(&mut len, base64.len()) } {
+        return Err(KeyRejected::invalid_encoding());
+    }
+    let mut bytes = vec![0u8; len];
+    if 1 != unsafe {
+        EVP_DecodeBase64(
+            bytes.as_mut_ptr(),
+            &mut len,
+            bytes.len(),
+            base64.as_ptr(),
+            base64.len(),
+        )
+    } {
+        bytes.zeroize();
+        return Err(KeyRejected::invalid_encoding());
+    }
+    bytes.truncate(len);
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, encode};
+
+    #[test]
+    fn test_round_trip() {
+        for len in [1u8, 2, 3, 47, 48, 49, 200] {
+            let der: Vec<u8> = (0..len).collect();
+            let pem = encode("TEST", &der).unwrap();
+            assert!(pem.starts_with("-----BEGIN TEST-----\n"));
+            assert!(pem.ends_with("\n-----END TEST-----\n"));
+            assert!(pem.lines().all(|line| line.len() <= 64));
+            assert_eq!(der, decode("TEST", &pem).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_decode() {
+        let pem =
+            "Explanatory text\n-----BEGIN TEST-----\r\nAAEC\r\nAw==\r\n-----END TEST-----\r\n";
+        assert_eq!(vec![0u8, 1, 2, 3], decode("TEST", pem).unwrap());
+
+        // Wrong label.
+        assert!(decode("OTHER", pem).is_err());
+        // Missing end boundary.
+        assert!(decode("TEST", "-----BEGIN TEST-----\nAAEC\n").is_err());
+        // Headers.
+        assert!(decode(
+            "TEST",
+            "-----BEGIN TEST-----\nProc-Type: 4,ENCRYPTED\nAAEC\n-----END TEST-----\n"
+        )
+        .is_err());
+        // Invalid base64.
+        assert!(decode("TEST", "-----BEGIN TEST-----\nA*EC\n-----END TEST-----\n").is_err());
+        // Empty.
+        assert!(decode("TEST", "-----BEGIN TEST-----\n-----END TEST-----\n").is_err());
+    }
+
+    #[cfg(feature = "legacy-pem")]
+    #[test]
+    fn test_decode_encrypted() {
+        use crate::encoding::{EcPrivateKeyRfc5915Der, RsaPrivateKeyRfc8017Der};
+        use crate::signature::{EcdsaKeyPair, RsaKeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+
+        let ec_der = include_bytes!("../../tests/data/legacy_pem_test_ec.der");
+        for pem in [
+            include_str!("../../tests/data/legacy_pem_test_ec_aes128.pem"),
+            include_str!("../../tests/data/legacy_pem_test_ec_des3.pem"),
+        ] {
+            let der = EcPrivateKeyRfc5915Der::from_encrypted_pem(pem, b"secret").unwrap();
+            assert_eq!(&ec_der[..], der.as_ref());
+            EcdsaKeyPair::from_private_key_der(&ECDSA_P256_SHA256_ASN1_SIGNING, der.as_ref())
+                .unwrap();
+
+            assert!(EcPrivateKeyRfc5915Der::from_encrypted_pem(pem, b"wrong").is_err());
+            assert!(RsaPrivateKeyRfc8017Der::from_encrypted_pem(pem, b"secret").is_err());
+            assert!(EcPrivateKeyRfc5915Der::from_pem(pem).is_err());
+        }
+
+        let pem = include_str!("../../tests/data/legacy_pem_test_rsa_aes256.pem");
+        let der = RsaPrivateKeyRfc8017Der::from_encrypted_pem(pem, b"secret").unwrap();
+        RsaKeyPair::from_der(der.as_ref()).unwrap();
+
+        // Unencrypted documents.
+        let pem = encode("EC PRIVATE KEY", ec_der).unwrap();
+        assert!(EcPrivateKeyRfc5915Der::from_encrypted_pem(&pem, b"secret").is_err());
+        // Unsupported cipher.
+        let pem = include_str!("../../tests/data/legacy_pem_test_ec_aes128.pem")
+            .replace("AES-128-CBC", "AES-128-CFB");
+        assert!(EcPrivateKeyRfc5915Der::from_encrypted_pem(&pem, b"secret").is_err());
+    }
+}
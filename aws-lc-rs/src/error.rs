@@ -128,6 +128,10 @@ impl KeyRejected {
         KeyRejected("InconsistentComponents")
     }
 
+    pub(crate) fn invalid_component() -> Self {
+        KeyRejected("InvalidComponent")
+    }
+
     #[inline]
     pub(crate) fn invalid_encoding() -> Self {
         KeyRejected("InvalidEncoding")
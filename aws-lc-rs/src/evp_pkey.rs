@@ -1,6 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
+use crate::buffer::Buffer;
 use crate::cbb::LcCBB;
 use crate::cbs;
 use crate::ec::PKCS8_DOCUMENT_MAX_LEN;
@@ -8,12 +9,12 @@ use crate::error::{KeyRejected, Unspecified};
 use crate::pkcs8::{Document, Version};
 use crate::ptr::LcPtr;
 use aws_lc::{
-    EVP_PKEY_bits, EVP_PKEY_get1_EC_KEY, EVP_PKEY_get1_RSA, EVP_PKEY_id, EVP_PKEY_up_ref,
-    EVP_marshal_private_key, EVP_marshal_private_key_v2, EVP_parse_private_key, EC_KEY, EVP_PKEY,
-    RSA,
+    EVP_PKEY_get1_EC_KEY, EVP_PKEY_get1_RSA, EVP_PKEY_id, EVP_PKEY_up_ref, EVP_marshal_private_key,
+    EVP_marshal_private_key_v2, EVP_parse_private_key, EC_KEY, EVP_PKEY, RSA,
 };
 // TODO: Uncomment when MSRV >= 1.64
 // use core::ffi::c_int;
+#[cfg(not(feature = "strict-fips"))]
 use std::os::raw::c_int;
 
 impl TryFrom<&[u8]> for LcPtr<EVP_PKEY> {
@@ -30,6 +31,7 @@ impl TryFrom<&[u8]> for LcPtr<EVP_PKEY> {
 }
 
 impl LcPtr<EVP_PKEY> {
+    #[cfg(not(feature = "strict-fips"))]
     pub(crate) fn validate_as_ed25519(&self) -> Result<(), KeyRejected> {
         const ED25519_KEY_TYPE: c_int = aws_lc::EVP_PKEY_ED25519;
         const ED25519_MIN_BITS: c_int = 253;
@@ -68,8 +70,9 @@ impl LcPtr<EVP_PKEY> {
         unsafe { EVP_PKEY_id(**self) }
     }
 
+    #[cfg(not(feature = "strict-fips"))]
     pub(crate) fn bits(&self) -> i32 {
-        unsafe { EVP_PKEY_bits(**self) }
+        unsafe { aws_lc::EVP_PKEY_bits(**self) }
     }
 
     #[allow(dead_code)]
@@ -86,31 +89,24 @@ impl LcPtr<EVP_PKEY> {
     }
 
     pub(crate) fn marshall_private_key(&self, version: Version) -> Result<Document, Unspecified> {
-        let mut buffer = vec![0u8; PKCS8_DOCUMENT_MAX_LEN];
+        // The capacity suffices for EC and Ed25519 keys; the buffer grows for RSA keys.
+        let mut cbb = LcCBB::new(PKCS8_DOCUMENT_MAX_LEN);
 
-        let out_len = {
-            let mut cbb = LcCBB::new_fixed(<&mut [u8; PKCS8_DOCUMENT_MAX_LEN]>::try_from(
-                buffer.as_mut_slice(),
-            )?);
-
-            match version {
-                Version::V1 => {
-                    if 1 != unsafe { EVP_marshal_private_key(cbb.as_mut_ptr(), **self) } {
-                        return Err(Unspecified);
-                    }
+        match version {
+            Version::V1 => {
+                if 1 != unsafe { EVP_marshal_private_key(cbb.as_mut_ptr(), **self) } {
+                    return Err(Unspecified);
                 }
-                Version::V2 => {
-                    if 1 != unsafe { EVP_marshal_private_key_v2(cbb.as_mut_ptr(), **self) } {
-                        return Err(Unspecified);
-                    }
+            }
+            Version::V2 => {
+                if 1 != unsafe { EVP_marshal_private_key_v2(cbb.as_mut_ptr(), **self) } {
+                    return Err(Unspecified);
                 }
             }
-            cbb.finish()?
-        };
-
-        buffer.truncate(out_len);
+        }
+        let buffer: Buffer<'static, ()> = cbb.into_buffer()?;
 
-        Ok(Document::new(buffer.into_boxed_slice()))
+        Ok(Document::new(buffer.as_ref().into()))
     }
 }
 
@@ -1,9 +1,183 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
+//! FIPS module status and service indicator.
+//!
+//! [`is_enabled`], [`self_test`] and, with the `fips` feature, `integrity_test` report the
+//! health of the cryptographic module, e.g. for a service to check at startup or to expose
+//! through a health endpoint. [`module_info`] identifies the module and
+//! [`CONFIGURED_ENTROPY_SOURCE`] records where the build seeds its random number generators
+//! from.
+//!
+//! With the `fips` feature, every cryptographic operation records whether it was performed by
+//! an approved service of the FIPS module. `service_status` and `assert_approved` let
+//! callers check this for individual operations, e.g. to demonstrate to an auditor that only
+//! approved services are used.
+//!
+//! ```
+//! # #[cfg(feature = "fips")]
+//! # {
+//! use aws_lc_rs::{fips, hmac};
+//!
+//! let key = hmac::Key::new(hmac::HMAC_SHA256, &[0u8; 32]);
+//! let tag = fips::assert_approved(|| hmac::sign(&key, b"hello, world"));
+//! # }
+//! ```
+//!
+//! # Failures
+//!
+//! The FIPS module aborts the process when a test that it runs on its own fails: the
+//! integrity test when it is loaded, and the self tests for an algorithm before that algorithm
+//! is first used. FIPS 140-3 does not allow the module to continue, and it offers no hook to
+//! run code first; it prints the name of the failed test to standard error before aborting.
+//! An application that needs to report a failed self test and enter a degraded mode instead
+//! can call [`self_test`] at startup, before using any algorithm: a failure there is returned
+//! as an error, and the application can then avoid the algorithms whose tests would abort it.
+
+use crate::error::Unspecified;
+use aws_lc::{BORINGSSL_self_test, FIPS_mode, OpenSSL_version, OPENSSL_VERSION};
+use core::fmt;
+use std::ffi::CStr;
+
+/// Returns `true` if the cryptographic module is the FIPS module and operates in FIPS mode.
+#[must_use]
+pub fn is_enabled() -> bool {
+    crate::init();
+    1 == unsafe { FIPS_mode() }
+}
+
+/// Runs the module's known-answer self tests for all of its approved algorithms.
+///
+/// The module already runs its self tests when it is loaded, or before an algorithm is first
+/// used; this function lets the caller run them again on demand. Unlike those, a failure is
+/// returned rather than aborting the process, see [Failures](self#failures).
+///
+/// # Errors
+/// `error::Unspecified` if a self test failed.
+pub fn self_test() -> Result<(), Unspecified> {
+    crate::init();
+    if 1 != unsafe { BORINGSSL_self_test() } {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
+/// Runs the FIPS module's integrity test, which compares a hash of the module's code and data
+/// with the hash recorded when it was built.
+///
+/// # Errors
+/// `error::Unspecified` if the module does not match the recorded hash.
+#[cfg(all(feature = "fips", not(feature = "asan")))]
+pub fn integrity_test() -> Result<(), Unspecified> {
+    crate::init();
+    if 1 != unsafe { aws_lc::BORINGSSL_integrity_test() } {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
+/// Identifies the cryptographic module, as reported by the module itself, e.g. for compliance
+/// reports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ModuleInfo {
+    name: &'static str,
+    version: &'static str,
+}
+
+impl ModuleInfo {
+    /// The name of the module, e.g. `AWS-LC FIPS` for the FIPS module.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The version of the module, e.g. `2.0.13`.
+    #[must_use]
+    pub fn version(&self) -> &'static str {
+        self.version
+    }
+}
+
+impl fmt::Display for ModuleInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.name, self.version)
+    }
+}
+
+/// Returns the name and version of the cryptographic module aws-lc-rs was built with.
+///
+/// The module does not record its validation certificate; it is listed in the vendor security
+/// policy of the module version returned here.
+#[must_use]
+pub fn module_info() -> ModuleInfo {
+    // The version string is a static string of the form "<name> <version>".
+    let version_string = unsafe { CStr::from_ptr(OpenSSL_version(OPENSSL_VERSION)) }
+        .to_str()
+        .unwrap_or_default();
+    let (name, version) = version_string
+        .rsplit_once(' ')
+        .unwrap_or((version_string, ""));
+    ModuleInfo { name, version }
+}
+
+/// The source of the entropy that seeds the module's random number generators.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntropySource {
+    /// The operating system's random number generator, e.g. `getrandom` on Linux.
+    OperatingSystem,
+
+    /// The CPU Jitter entropy source built into the FIPS module, which derives entropy from
+    /// variations in the execution time of the CPU and does not depend on the host.
+    CpuJitter,
+}
+
+/// The entropy source the crate configures the module with when building it.
+///
+/// This is [`EntropySource::CpuJitter`] when the `fips-cpu-jitter-entropy` feature is enabled,
+/// and [`EntropySource::OperatingSystem`] otherwise. It is fixed at compile time from the
+/// crate's features; the module offers no way to query its entropy source at run time.
+pub const CONFIGURED_ENTROPY_SOURCE: EntropySource = if cfg!(feature = "fips-cpu-jitter-entropy") {
+    EntropySource::CpuJitter
+} else {
+    EntropySource::OperatingSystem
+};
+
+/// Runs `f` and returns its result along with the service indicator status of the last
+/// cryptographic operation `f` performed on the current thread.
+///
+/// The status only reflects the last operation, so each operation whose status matters should
+/// be checked separately.
+#[cfg(feature = "fips")]
+pub fn service_status<R, F>(f: F) -> FipsServiceStatus<R>
+where
+    F: FnOnce() -> R,
+{
+    clear_fips_service_status();
+    let result = f();
+    get_fips_service_status().map(|()| result)
+}
+
+/// Runs `f` and returns its result, checking that the last cryptographic operation `f`
+/// performed on the current thread was an approved service of the FIPS module.
+///
+/// # Panics
+/// Panics if the last operation was not an approved service, or if `f` performed no
+/// operation that sets the service indicator.
+#[cfg(feature = "fips")]
+pub fn assert_approved<R, F>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    match service_status(f) {
+        FipsServiceStatus::Approved(result) => result,
+        FipsServiceStatus::NonApproved(_) => panic!("a non-approved FIPS service was used"),
+        FipsServiceStatus::Unset(_) => panic!("the FIPS service indicator was not set"),
+    }
+}
+
 /// Retrieve the FIPS module service status.
-#[allow(dead_code)] // appease clippy
-#[cfg(all(feature = "fips", debug_assertions))]
+#[cfg(feature = "fips")]
 pub(crate) fn get_fips_service_status() -> FipsServiceStatus<()> {
     if let Some(status) = indicator::get_status() {
         if status {
@@ -16,20 +190,20 @@ pub(crate) fn get_fips_service_status() -> FipsServiceStatus<()> {
     }
 }
 
+#[cfg(not(feature = "strict-fips"))]
 #[inline]
 pub(crate) fn set_fips_service_status_unapproved() {
-    #[cfg(all(feature = "fips", debug_assertions))]
+    #[cfg(feature = "fips")]
     indicator::set_unapproved();
 }
 
-#[allow(dead_code)]
-#[cfg(all(feature = "fips", debug_assertions))]
+#[cfg(feature = "fips")]
 #[inline]
 pub(crate) fn clear_fips_service_status() {
     indicator::clear();
 }
 
-#[cfg(all(feature = "fips", debug_assertions))]
+#[cfg(feature = "fips")]
 pub(crate) mod indicator {
     use core::cell::Cell;
 
@@ -60,41 +234,37 @@ pub(crate) mod indicator {
     }
 }
 
-#[cfg(all(feature = "fips", debug_assertions))]
+#[cfg(feature = "fips")]
 #[inline]
 pub(crate) fn service_indicator_before_call() -> u64 {
     unsafe { aws_lc::FIPS_service_indicator_before_call() }
 }
 
-#[cfg(all(feature = "fips", debug_assertions))]
+#[cfg(feature = "fips")]
 #[inline]
 pub(crate) fn service_indicator_after_call() -> u64 {
     unsafe { aws_lc::FIPS_service_indicator_after_call() }
 }
 
-/// The FIPS Module Service Status
-#[allow(dead_code)] // appease clippy
-#[cfg(all(feature = "fips", debug_assertions))]
-#[allow(clippy::module_name_repetitions)]
+/// The FIPS module service indicator status of an operation, returned by [`service_status`].
+#[cfg(feature = "fips")]
+#[allow(clippy::module_name_repetitions, clippy::exhaustive_enums)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub(crate) enum FipsServiceStatus<R> {
-    /// Indicates that the current thread is using approved FIPS cryptographic services.
+pub enum FipsServiceStatus<R> {
+    /// The operation was performed by an approved service.
     Approved(R),
 
-    /// Indicates that the current thread has used non-approved FIPS cryptographic services.
-    /// The service indicator status can be reset using `reset_fips_service_status`.
-    /// `reset_fips_service_status` will return `NonApprovedMode` if the service used a non-approved
-    /// service, and automatically resets the service status for you.
+    /// The operation was performed by a non-approved service.
     NonApproved(R),
 
-    /// Indicates that the service indicator is not set
+    /// No operation that sets the service indicator was performed.
     Unset(R),
 }
 
-#[cfg(all(feature = "fips", debug_assertions))]
+#[cfg(feature = "fips")]
 impl<R> FipsServiceStatus<R> {
-    /// Maps a `ServiceStatus<R>` to a `ServiceStatus<S>` by applying a function to a contained value.
-    #[allow(dead_code)]
+    /// Maps a `FipsServiceStatus<R>` to a `FipsServiceStatus<S>` by applying a function to a
+    /// contained value.
     pub fn map<S, F>(self, op: F) -> FipsServiceStatus<S>
     where
         F: FnOnce(R) -> S,
@@ -109,7 +279,7 @@ impl<R> FipsServiceStatus<R> {
 
 macro_rules! indicator_check {
     ($function:expr) => {{
-        #[cfg(all(feature = "fips", debug_assertions))]
+        #[cfg(feature = "fips")]
         {
             use crate::fips::{service_indicator_after_call, service_indicator_before_call};
             let before = service_indicator_before_call();
@@ -123,7 +293,7 @@ macro_rules! indicator_check {
                 result
             }
         }
-        #[cfg(any(not(feature = "fips"), not(debug_assertions)))]
+        #[cfg(not(feature = "fips"))]
         {
             $function
         }
@@ -133,7 +303,7 @@ macro_rules! indicator_check {
 pub(crate) use indicator_check;
 
 #[allow(unused_macros)]
-#[cfg(all(feature = "fips", debug_assertions))]
+#[cfg(feature = "fips")]
 macro_rules! check_fips_service_status {
     ($function:expr) => {{
         // Clear the current indicator status first by retrieving it
@@ -147,11 +317,11 @@ macro_rules! check_fips_service_status {
 }
 
 #[allow(unused_imports)]
-#[cfg(all(feature = "fips", debug_assertions))]
+#[cfg(feature = "fips")]
 pub(crate) use check_fips_service_status;
 
 #[allow(unused_macros)]
-#[cfg(all(feature = "fips", debug_assertions))]
+#[cfg(feature = "fips")]
 macro_rules! assert_fips_status_indicator {
     ($function:expr, $expect:path) => {
         assert_fips_status_indicator!($function, $expect, "unexpected service indicator")
@@ -165,13 +335,50 @@ macro_rules! assert_fips_status_indicator {
 }
 
 #[allow(unused_imports)]
-#[cfg(all(feature = "fips", debug_assertions))]
+#[cfg(feature = "fips")]
 pub(crate) use assert_fips_status_indicator;
 
 #[cfg(test)]
 mod tests {
+    #[test]
+    fn test_module_status() {
+        assert_eq!(cfg!(feature = "fips"), crate::fips::is_enabled());
+        assert_eq!(crate::try_fips_mode().is_ok(), crate::fips::is_enabled());
+        crate::fips::self_test().unwrap();
+        #[cfg(all(feature = "fips", not(feature = "asan")))]
+        crate::fips::integrity_test().unwrap();
+    }
+
+    #[test]
+    fn test_module_info() {
+        let info = crate::fips::module_info();
+        if cfg!(feature = "fips") {
+            assert_eq!("AWS-LC FIPS", info.name());
+        } else {
+            assert_eq!("AWS-LC", info.name());
+        }
+        let version: Vec<&str> = info.version().split('.').collect();
+        assert_eq!(3, version.len());
+        assert!(version.iter().all(|n| n.parse::<u32>().is_ok()));
+        assert_eq!(
+            format!("{} {}", info.name(), info.version()),
+            info.to_string()
+        );
+    }
+
+    #[test]
+    fn test_configured_entropy_source() {
+        use crate::fips::{EntropySource, CONFIGURED_ENTROPY_SOURCE};
 
-    #[cfg(all(feature = "fips", debug_assertions))]
+        let expected = if cfg!(feature = "fips-cpu-jitter-entropy") {
+            EntropySource::CpuJitter
+        } else {
+            EntropySource::OperatingSystem
+        };
+        assert_eq!(expected, CONFIGURED_ENTROPY_SOURCE);
+    }
+
+    #[cfg(feature = "fips")]
     #[test]
     fn test_service_status() {
         use crate::fips::FipsServiceStatus;
@@ -189,4 +396,68 @@ mod tests {
             FipsServiceStatus::Unset(()).map(|()| true)
         );
     }
+
+    #[cfg(feature = "fips")]
+    #[test]
+    fn test_public_service_status() {
+        use crate::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_128_GCM};
+        use crate::fips::{assert_approved, service_status, FipsServiceStatus};
+        use crate::{digest, hmac};
+
+        let key = hmac::Key::new(hmac::HMAC_SHA256, &[0u8; 32]);
+        let tag = assert_approved(|| hmac::sign(&key, b"hello, world"));
+        assert_eq!(digest::SHA256_OUTPUT_LEN, tag.as_ref().len());
+
+        assert_eq!(
+            FipsServiceStatus::Unset(()),
+            service_status(|| {
+                let _ = digest::Context::new(&digest::SHA256);
+            })
+        );
+
+        let key = LessSafeKey::new(UnboundKey::new(&AES_128_GCM, &[0u8; 16]).unwrap());
+        let mut in_out = vec![0u8; 16];
+        assert_approved(|| {
+            key.seal_in_place_append_tag(
+                Nonce::assume_unique_for_key([0u8; 12]),
+                Aad::empty(),
+                &mut in_out,
+            )
+        })
+        .unwrap();
+
+        #[cfg(not(feature = "strict-fips"))]
+        {
+            let key = LessSafeKey::new(
+                UnboundKey::new(&crate::aead::CHACHA20_POLY1305, &[0u8; 32]).unwrap(),
+            );
+            let mut in_out = vec![0u8; 16];
+            assert!(matches!(
+                service_status(|| key.seal_in_place_append_tag(
+                    Nonce::assume_unique_for_key([0u8; 12]),
+                    Aad::empty(),
+                    &mut in_out,
+                )),
+                FipsServiceStatus::NonApproved(Ok(()))
+            ));
+        }
+    }
+
+    #[cfg(all(feature = "fips", not(feature = "strict-fips")))]
+    #[test]
+    #[should_panic(expected = "a non-approved FIPS service was used")]
+    fn test_assert_approved_non_approved() {
+        use crate::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305};
+        use crate::fips::assert_approved;
+
+        let key = LessSafeKey::new(UnboundKey::new(&CHACHA20_POLY1305, &[0u8; 32]).unwrap());
+        let mut in_out = vec![0u8; 16];
+        let _ = assert_approved(|| {
+            key.seal_in_place_append_tag(
+                Nonce::assume_unique_for_key([0u8; 12]),
+                Aad::empty(),
+                &mut in_out,
+            )
+        });
+    }
 }
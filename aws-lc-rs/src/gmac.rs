@@ -0,0 +1,279 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! GMAC is specified in [NIST SP 800-38D].
+//!
+//! GMAC is the authentication-only mode of AES-GCM: the message is processed
+//! entirely as additional authenticated data and no plaintext is encrypted.
+//! It is appropriate for authenticating data that does not need to be kept
+//! confidential.
+//!
+//! Unlike HMAC, GMAC requires a nonce for every tag that is computed. The
+//! user must ensure, for a particular key, that each nonce is unique;
+//! reusing a nonce with the same key allows an attacker to forge tags.
+//!
+//! # Example
+//!
+//! ```
+//! use aws_lc_rs::aead::Nonce;
+//! use aws_lc_rs::{gmac, rand};
+//!
+//! let rng = rand::SystemRandom::new();
+//! let key = gmac::Key::generate(&gmac::GMAC_AES_256, &rng)?;
+//!
+//! let msg = "hello, world";
+//!
+//! let tag = gmac::sign(&key, Nonce::assume_unique_for_key([0u8; 12]), msg.as_bytes())?;
+//!
+//! // [We give access to the message and tag to an untrusted party, and they
+//! // give it back to us. We need to verify they didn't tamper with it.]
+//!
+//! gmac::verify(
+//!     &key,
+//!     Nonce::assume_unique_for_key([0u8; 12]),
+//!     msg.as_bytes(),
+//!     tag.as_ref(),
+//! )?;
+//!
+//! # Ok::<(), aws_lc_rs::error::Unspecified>(())
+//! ```
+//!
+//! [NIST SP 800-38D]: https://nvlpubs.nist.gov/nistpubs/Legacy/SP/nistspecialpublication800-38d.pdf
+
+use crate::aead::{self, Nonce, UnboundKey};
+use crate::cipher::aes::{AES_128_KEY_LEN, AES_256_KEY_LEN};
+use crate::error::Unspecified;
+use crate::{derive_debug_via_id, rand};
+use core::fmt::Debug;
+
+/// The length of a GMAC tag, in bytes.
+pub const TAG_LEN: usize = 16;
+
+/// The maximum length of a key for the algorithms in this module.
+const MAX_KEY_LEN: usize = AES_256_KEY_LEN;
+
+/// A GMAC algorithm.
+pub struct Algorithm {
+    aead: &'static aead::Algorithm,
+    key_len: usize,
+    id: AlgorithmID,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AlgorithmID {
+    GMAC_AES_128,
+    GMAC_AES_256,
+}
+
+impl PartialEq for Algorithm {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Algorithm {}
+
+derive_debug_via_id!(Algorithm);
+
+impl Algorithm {
+    /// The length of the key for this algorithm, in bytes.
+    #[inline]
+    #[must_use]
+    pub fn key_len(&self) -> usize {
+        self.key_len
+    }
+}
+
+/// GMAC using AES-128.
+pub static GMAC_AES_128: Algorithm = Algorithm {
+    aead: &aead::AES_128_GCM,
+    key_len: AES_128_KEY_LEN,
+    id: AlgorithmID::GMAC_AES_128,
+};
+
+/// GMAC using AES-256.
+pub static GMAC_AES_256: Algorithm = Algorithm {
+    aead: &aead::AES_256_GCM,
+    key_len: AES_256_KEY_LEN,
+    id: AlgorithmID::GMAC_AES_256,
+};
+
+/// A GMAC tag.
+///
+/// For a given tag `t`, use `t.as_ref()` to get the tag value as a byte slice.
+#[derive(Clone, Copy, Debug)]
+pub struct Tag([u8; TAG_LEN]);
+
+impl AsRef<[u8]> for Tag {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// A key to use for GMAC signing and verification.
+//
+// # FIPS
+// Use this type with one of the following algorithms:
+// * `GMAC_AES_128`
+// * `GMAC_AES_256`
+pub struct Key {
+    algorithm: &'static Algorithm,
+    key: UnboundKey,
+}
+
+#[allow(clippy::missing_fields_in_debug)]
+impl Debug for Key {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        f.debug_struct("Key")
+            .field("algorithm", &self.algorithm)
+            .finish()
+    }
+}
+
+impl Key {
+    /// Constructs a GMAC key using the given algorithm and key value.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `key_value.len() != algorithm.key_len()`.
+    pub fn new(algorithm: &'static Algorithm, key_value: &[u8]) -> Result<Self, Unspecified> {
+        if key_value.len() != algorithm.key_len {
+            return Err(Unspecified);
+        }
+        Ok(Self {
+            algorithm,
+            key: UnboundKey::new(algorithm.aead, key_value)?,
+        })
+    }
+
+    /// Generate a GMAC key using the given algorithm with a random value
+    /// generated from `rng`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the `rng` fails.
+    pub fn generate(
+        algorithm: &'static Algorithm,
+        rng: &dyn rand::SecureRandom,
+    ) -> Result<Self, Unspecified> {
+        let mut key_bytes = [0u8; MAX_KEY_LEN];
+        let key_bytes = &mut key_bytes[..algorithm.key_len];
+        rng.fill(key_bytes)?;
+        Self::new(algorithm, key_bytes)
+    }
+
+    /// The algorithm for the key.
+    #[inline]
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        self.algorithm
+    }
+}
+
+/// Calculates the GMAC of `data` using the key `key` and the given `nonce`.
+///
+/// It is generally not safe to implement GMAC verification by comparing the
+/// return value of `sign` to a tag. Use `verify` for verification instead.
+///
+/// # Errors
+/// `error::Unspecified` if `data` is too long.
+//
+// # FIPS
+// Use this function with one of the following algorithms:
+// * `GMAC_AES_128`
+// * `GMAC_AES_256`
+#[inline]
+pub fn sign(key: &Key, nonce: Nonce, data: &[u8]) -> Result<Tag, Unspecified> {
+    let (_, aead_tag) = key
+        .key
+        .seal_in_place_separate_tag(Some(nonce), data, &mut [])?;
+    let mut tag = [0u8; TAG_LEN];
+    tag.copy_from_slice(aead_tag.as_ref());
+    Ok(Tag(tag))
+}
+
+/// Calculates the GMAC of `data` using the key `key` and the given `nonce`,
+/// and verifies whether the resultant value equals `tag`, in one step.
+///
+/// The verification will be done in constant time to prevent timing attacks.
+///
+/// # Errors
+/// `error::Unspecified` if the inputs are not verified.
+//
+// # FIPS
+// Use this function with one of the following algorithms:
+// * `GMAC_AES_128`
+// * `GMAC_AES_256`
+#[inline]
+#[allow(clippy::needless_pass_by_value)]
+pub fn verify(key: &Key, nonce: Nonce, data: &[u8], tag: &[u8]) -> Result<(), Unspecified> {
+    if tag.len() != TAG_LEN {
+        return Err(Unspecified);
+    }
+    key.key
+        .open_separate_gather(&nonce, data, &[], tag, &mut [])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aead::Nonce;
+    use crate::test::from_hex;
+    use crate::{gmac, rand};
+
+    #[test]
+    fn gmac_known_answer() {
+        // NIST CAVP gcmEncryptExtIV128.rsp, [PTlen = 0], [AADlen = 128], Count = 0
+        let key = from_hex("77be63708971c4e240d1cb79e8d77feb").unwrap();
+        let nonce = from_hex("e0e00f19fed7ba0136a797f3").unwrap();
+        let aad = from_hex("7a43ec1d9c0a5a78a0b16533a6213cab").unwrap();
+        let expected = from_hex("209fcc8d3675ed938e9c7166709dd946").unwrap();
+
+        let key = gmac::Key::new(&gmac::GMAC_AES_128, &key).unwrap();
+        let tag = gmac::sign(
+            &key,
+            Nonce::try_assume_unique_for_key(&nonce).unwrap(),
+            &aad,
+        )
+        .unwrap();
+        assert_eq!(expected.as_slice(), tag.as_ref());
+
+        let nonce = Nonce::try_assume_unique_for_key(&nonce).unwrap();
+        assert!(gmac::verify(&key, nonce, &aad, &expected).is_ok());
+    }
+
+    #[test]
+    fn gmac_sign_verify() {
+        const MSG: &[u8] = b"hello, world";
+        let rng = rand::SystemRandom::new();
+
+        for alg in [&gmac::GMAC_AES_128, &gmac::GMAC_AES_256] {
+            let key = gmac::Key::generate(alg, &rng).unwrap();
+            assert_eq!(alg, key.algorithm());
+            let tag = gmac::sign(&key, Nonce::assume_unique_for_key([1u8; 12]), MSG).unwrap();
+
+            let nonce = Nonce::assume_unique_for_key([1u8; 12]);
+            assert!(gmac::verify(&key, nonce, MSG, tag.as_ref()).is_ok());
+
+            // Wrong nonce
+            let nonce = Nonce::assume_unique_for_key([2u8; 12]);
+            assert!(gmac::verify(&key, nonce, MSG, tag.as_ref()).is_err());
+
+            // Wrong message
+            let nonce = Nonce::assume_unique_for_key([1u8; 12]);
+            assert!(gmac::verify(&key, nonce, b"hello, worle", tag.as_ref()).is_err());
+
+            // Truncated tag
+            let nonce = Nonce::assume_unique_for_key([1u8; 12]);
+            assert!(gmac::verify(&key, nonce, MSG, &tag.as_ref()[..15]).is_err());
+        }
+
+        assert!(gmac::Key::new(&gmac::GMAC_AES_128, &[0u8; 32]).is_err());
+        assert!(gmac::Key::new(&gmac::GMAC_AES_256, &[0u8; 16]).is_err());
+    }
+
+    #[test]
+    fn gmac_debug() {
+        let key = gmac::Key::new(&gmac::GMAC_AES_128, &[0u8; 16]).unwrap();
+        assert_eq!("Key { algorithm: GMAC_AES_128 }", format!("{key:?}"));
+    }
+}
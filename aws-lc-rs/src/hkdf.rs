@@ -41,9 +41,9 @@ use crate::error::Unspecified;
 use crate::fips::indicator_check;
 use crate::{digest, hmac};
 use alloc::sync::Arc;
-use aws_lc::{HKDF_expand, HKDF};
+use aws_lc::{HKDF_expand, HKDF_extract, HKDF};
 use core::fmt;
-use zeroize::Zeroize;
+use zeroize::{Zeroize, Zeroizing};
 
 /// An HKDF algorithm.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -214,23 +214,47 @@ enum PrkMode {
 }
 
 impl PrkMode {
+    /// Writes the PRK bytes to `out`, which must be at least `MAX_HKDF_PRK_LEN` long, and returns
+    /// the length of the PRK.
+    fn extract(&self, algorithm: Algorithm, out: &mut [u8]) -> Result<usize, Unspecified> {
+        debug_assert!(out.len() >= MAX_HKDF_PRK_LEN);
+        match &self {
+            PrkMode::Expand { key_bytes, key_len } => {
+                out[..*key_len].copy_from_slice(&key_bytes[..*key_len]);
+                Ok(*key_len)
+            }
+            PrkMode::ExtractExpand {
+                secret,
+                salt,
+                salt_len,
+            } => {
+                let digest = *digest::match_digest_type(&algorithm.0.digest_algorithm().id);
+                let mut out_len = 0usize;
+                if 1 != indicator_check!(unsafe {
+                    HKDF_extract(
+                        out.as_mut_ptr(),
+                        &mut out_len,
+                        digest,
+                        secret.as_ptr(),
+                        secret.len(),
+                        salt.as_ptr(),
+                        *salt_len,
+                    )
+                }) {
+                    return Err(Unspecified);
+                }
+                Ok(out_len)
+            }
+        }
+    }
+
     fn fill(&self, algorithm: Algorithm, out: &mut [u8], info: &[u8]) -> Result<(), Unspecified> {
         let digest = *digest::match_digest_type(&algorithm.0.digest_algorithm().id);
 
         match &self {
-            PrkMode::Expand { key_bytes, key_len } => unsafe {
-                if 1 != indicator_check!(HKDF_expand(
-                    out.as_mut_ptr(),
-                    out.len(),
-                    digest,
-                    key_bytes.as_ptr(),
-                    *key_len,
-                    info.as_ptr(),
-                    info.len(),
-                )) {
-                    return Err(Unspecified);
-                }
-            },
+            PrkMode::Expand { key_bytes, key_len } => {
+                expand(algorithm, &key_bytes[..*key_len], out, info)?;
+            }
             PrkMode::ExtractExpand {
                 secret,
                 salt,
@@ -258,6 +282,29 @@ impl PrkMode {
     }
 }
 
+fn expand(
+    algorithm: Algorithm,
+    prk: &[u8],
+    out: &mut [u8],
+    info: &[u8],
+) -> Result<(), Unspecified> {
+    let digest = *digest::match_digest_type(&algorithm.0.digest_algorithm().id);
+    if 1 != indicator_check!(unsafe {
+        HKDF_expand(
+            out.as_mut_ptr(),
+            out.len(),
+            digest,
+            prk.as_ptr(),
+            prk.len(),
+            info.as_ptr(),
+            info.len(),
+        )
+    }) {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
 impl fmt::Debug for PrkMode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -338,6 +385,84 @@ impl Prk {
         })
     }
 
+    /// Writes the bytes of this PRK to `out`, and returns the number of bytes written.
+    ///
+    /// Usually one can avoid using this. It is useful for protocols that need to
+    /// transmit, store, or compare the PRK itself. The returned bytes are secret
+    /// and the caller is responsible for zeroizing `out` when no longer needed.
+    ///
+    // # FIPS
+    // This function must not be used.
+    //
+    /// # Errors
+    /// `error::Unspecified` if `out` is too small to hold the PRK, or if the
+    /// HKDF-Extract operation fails.
+    pub fn export_less_safe(&self, out: &mut [u8]) -> Result<usize, Unspecified> {
+        let mut key_bytes = [0u8; MAX_HKDF_PRK_LEN];
+        let result = self
+            .mode
+            .extract(self.algorithm, &mut key_bytes)
+            .and_then(|key_len| {
+                let out = out.get_mut(..key_len).ok_or(Unspecified)?;
+                out.copy_from_slice(&key_bytes[..key_len]);
+                Ok(key_len)
+            });
+        key_bytes.zeroize();
+        result
+    }
+
+    /// Performs the [HKDF-Expand] operation for several outputs at once.
+    ///
+    /// Each element of `outputs` is a pair of `info` components, which are
+    /// concatenated as with [`Prk::expand`], and the buffer to fill with the
+    /// output keying material. The result for each pair is identical to
+    /// calling `prk.expand(info, len)?.fill(out)` with `len` equal to
+    /// `out.len()`, but the extract step is only performed once.
+    ///
+    /// [HKDF-Expand]: https://tools.ietf.org/html/rfc5869#section-2.3
+    ///
+    /// # Errors
+    /// Returns `error::Unspecified` if:
+    ///   * Any of the output buffers is longer than 255 times the digest
+    ///     algorithm's output length.
+    ///   * The HKDF operation fails.
+    ///
+    /// On error, all output buffers are zeroized.
+    // # FIPS
+    // The following conditions must be met:
+    // * `Prk` must be constructed using `Salt::extract` prior to calling
+    // this method.
+    // * After concatination of the `info` slices the resulting `[u8].len() > 0` is true.
+    pub fn expand_multi(&self, outputs: &mut [(&[&[u8]], &mut [u8])]) -> Result<(), Unspecified> {
+        let result = self.try_expand_multi(outputs);
+        if result.is_err() {
+            for (_, out) in outputs.iter_mut() {
+                out.zeroize();
+            }
+        }
+        result
+    }
+
+    fn try_expand_multi(&self, outputs: &mut [(&[&[u8]], &mut [u8])]) -> Result<(), Unspecified> {
+        let max_len = 255 * self.algorithm.0.digest_algorithm().output_len;
+        if outputs.iter().any(|(_, out)| out.len() > max_len) {
+            return Err(Unspecified);
+        }
+
+        let mut key_bytes = Zeroizing::new([0u8; MAX_HKDF_PRK_LEN]);
+        let key_len = self.mode.extract(self.algorithm, key_bytes.as_mut())?;
+        let mut info_bytes: Zeroizing<Vec<u8>> =
+            Zeroizing::new(Vec::with_capacity(HKDF_INFO_DEFAULT_CAPACITY_LEN));
+        for (info, out) in outputs.iter_mut() {
+            info_bytes.clear();
+            for &byte_ary in *info {
+                info_bytes.extend_from_slice(byte_ary);
+            }
+            expand(self.algorithm, &key_bytes[..key_len], out, &info_bytes)?;
+        }
+        Ok(())
+    }
+
     /// The [HKDF-Expand] operation.
     ///
     /// [HKDF-Expand]: https://tools.ietf.org/html/rfc5869#section-2.3
@@ -453,7 +578,8 @@ impl<L: KeyType> Okm<'_, L> {
 
 #[cfg(test)]
 mod tests {
-    use crate::hkdf::{Salt, HKDF_SHA256, HKDF_SHA384};
+    use crate::hkdf::{Prk, Salt, HKDF_SHA256, HKDF_SHA384};
+    use crate::test::from_hex;
 
     #[cfg(feature = "fips")]
     mod fips;
@@ -504,4 +630,79 @@ mod tests {
             format!("{okm:?}")
         );
     }
+
+    #[test]
+    fn prk_export_less_safe() {
+        // RFC 5869 Appendix A.1
+        let ikm = from_hex("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b").unwrap();
+        let salt = from_hex("000102030405060708090a0b0c").unwrap();
+        let expected_prk =
+            from_hex("077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5").unwrap();
+
+        let prk = Salt::new(HKDF_SHA256, &salt).extract(&ikm);
+        let mut out = [0u8; 64];
+        let len = prk.export_less_safe(&mut out).unwrap();
+        assert_eq!(expected_prk.as_slice(), &out[..len]);
+
+        let mut too_small = [0u8; 31];
+        assert!(prk.export_less_safe(&mut too_small).is_err());
+
+        let prk = Prk::new_less_safe(HKDF_SHA256, &expected_prk);
+        let len = prk.export_less_safe(&mut out).unwrap();
+        assert_eq!(expected_prk.as_slice(), &out[..len]);
+    }
+
+    #[test]
+    fn prk_expand_multi() {
+        // RFC 5869 Appendix A.1
+        let ikm = from_hex("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b").unwrap();
+        let salt = from_hex("000102030405060708090a0b0c").unwrap();
+        let info = from_hex("f0f1f2f3f4f5f6f7f8f9").unwrap();
+        let expected_okm = from_hex(
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865",
+        )
+        .unwrap();
+
+        let prk = Salt::new(HKDF_SHA256, &salt).extract(&ikm);
+
+        let mut okm1 = [0u8; 42];
+        let mut okm2 = [0u8; 16];
+        let mut okm3 = [0u8; 64];
+        prk.expand_multi(&mut [
+            (&[&info[..5], &info[5..]], &mut okm1),
+            (&[b"second"], &mut okm2),
+            (&[], &mut okm3),
+        ])
+        .unwrap();
+        assert_eq!(expected_okm.as_slice(), &okm1);
+
+        let mut expected = [0u8; 16];
+        prk.expand(&[b"second"], Len(16))
+            .unwrap()
+            .fill(&mut expected)
+            .unwrap();
+        assert_eq!(expected, okm2);
+
+        let mut expected = [0u8; 64];
+        prk.expand(&[], Len(64))
+            .unwrap()
+            .fill(&mut expected)
+            .unwrap();
+        assert_eq!(expected, okm3);
+
+        let mut too_long = vec![0u8; 255 * 32 + 1];
+        let mut okm = [1u8; 16];
+        assert!(prk
+            .expand_multi(&mut [(&[b"a"], &mut okm), (&[b"b"], &mut too_long)])
+            .is_err());
+        assert_eq!([0u8; 16], okm);
+    }
+
+    struct Len(usize);
+
+    impl crate::hkdf::KeyType for Len {
+        fn len(&self) -> usize {
+            self.0
+        }
+    }
 }
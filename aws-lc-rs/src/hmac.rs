@@ -95,9 +95,13 @@
 //! ```
 //! [RFC 2104]: https://tools.ietf.org/html/rfc2104
 
+#[cfg(feature = "jwk")]
+use crate::error::KeyRejected;
 use crate::error::Unspecified;
 use crate::fips::indicator_check;
-use crate::{constant_time, digest, hkdf};
+#[cfg(feature = "jwk")]
+use crate::jwk::Jwk;
+use crate::{constant_time, digest, hkdf, policy};
 use aws_lc::{
     HMAC_CTX_cleanup, HMAC_CTX_copy_ex, HMAC_CTX_init, HMAC_Final, HMAC_Init_ex, HMAC_Update,
     HMAC_CTX,
@@ -107,6 +111,7 @@ use core::ptr::null_mut;
 // TODO: Uncomment when MSRV >= 1.64
 // use core::ffi::c_uint;
 use std::os::raw::c_uint;
+use zeroize::Zeroize;
 
 /// A deprecated alias for `Tag`.
 #[deprecated]
@@ -165,6 +170,13 @@ impl AsRef<[u8]> for Tag {
     }
 }
 
+impl Tag {
+    // Overwrites the tag value, for tags that are used as keys.
+    pub(crate) fn zeroize(&mut self) {
+        self.msg.zeroize();
+    }
+}
+
 struct LcHmacCtx(HMAC_CTX);
 
 impl LcHmacCtx {
@@ -248,7 +260,8 @@ impl Key {
     // * `HMAC_SHA512`
     //
     /// # Errors
-    /// `error::Unspecified` is the `rng` fails.
+    /// `error::Unspecified` is the `rng` fails, or if the installed
+    /// [`policy`](crate::policy) denies the algorithm's digest algorithm.
     pub fn generate(
         algorithm: Algorithm,
         rng: &dyn crate::rand::SecureRandom,
@@ -263,7 +276,7 @@ impl Key {
         let mut key_bytes = [0; digest::MAX_OUTPUT_LEN];
         let key_bytes = &mut key_bytes[..algorithm.0.output_len];
         fill(key_bytes)?;
-        Ok(Self::new(algorithm, key_bytes))
+        Self::try_new(algorithm, key_bytes)
     }
 
     /// Construct an HMAC signing key using the given digest algorithm and key
@@ -286,14 +299,33 @@ impl Key {
     /// `digest_alg.output_len * 8` bits.
     ///
     /// # Panics
-    /// Panics if the HMAC context cannot be constructed
+    /// Panics if the HMAC context cannot be constructed, or if the installed
+    /// [`policy`](crate::policy) denies the algorithm's digest algorithm.
     #[inline]
     #[must_use]
     pub fn new(algorithm: Algorithm, key_value: &[u8]) -> Self {
         Key::try_new(algorithm, key_value).expect("Unable to create HmacContext")
     }
 
-    fn try_new(algorithm: Algorithm, key_value: &[u8]) -> Result<Self, Unspecified> {
+    /// Construct an HMAC signing key from the `k` member of an `oct` JSON Web Key
+    /// (RFC 7518, section 6.4).
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the key's `kty` is not `oct`, if `k` is missing or malformed, or
+    /// if the installed [`policy`](crate::policy) denies the algorithm's digest algorithm.
+    #[cfg(feature = "jwk")]
+    pub fn from_jwk(algorithm: Algorithm, jwk: &Jwk) -> Result<Self, KeyRejected> {
+        jwk.expect("oct", None)?;
+        let mut key_value = jwk.bytes("k")?;
+        let key = Key::try_new(algorithm, &key_value);
+        key_value.zeroize();
+        Ok(key?)
+    }
+
+    // Constructs a key, failing instead of panicking if the installed policy denies the
+    // algorithm's digest algorithm, for operations of the crate that can fail.
+    pub(crate) fn try_new(algorithm: Algorithm, key_value: &[u8]) -> Result<Self, Unspecified> {
+        policy::enforce_digest(algorithm.digest_algorithm())?;
         unsafe {
             let mut ctx = MaybeUninit::<HMAC_CTX>::uninit();
             HMAC_CTX_init(ctx.as_mut_ptr());
@@ -484,6 +516,55 @@ pub fn verify(key: &Key, data: &[u8], tag: &[u8]) -> Result<(), Unspecified> {
     constant_time::verify_slices_are_equal(sign(key, data).as_ref(), tag)
 }
 
+/// Calculates the HMAC of `data` using the key `key`, and verifies whether
+/// the leftmost `len` bytes of the resultant value equal `tag`, in one step.
+///
+/// This is intended for protocols that transmit a truncated HMAC value, e.g.
+/// HMAC-SHA-256-128 as used in IPsec. `len` is the truncated length mandated
+/// by the protocol, and `tag` must be exactly that long, so that a peer
+/// cannot weaken verification by sending a shorter tag. Following the
+/// recommendation in [RFC 2104 Section 5], `len` must be at least half of the
+/// digest algorithm's output length and no shorter than 80 bits.
+///
+/// The verification will be done in constant time to prevent timing attacks.
+///
+/// [RFC 2104 Section 5]: https://tools.ietf.org/html/rfc2104#section-5
+///
+/// # Errors
+/// `error::Unspecified` if `len` is too short or longer than the digest
+/// algorithm's output length, if `tag.len()` is not `len`, or if the inputs
+/// are not verified.
+//
+// # FIPS
+// Use this function with one of the following algorithms:
+// * `HMAC_SHA1_FOR_LEGACY_USE_ONLY`
+// * `HMAC_SHA224`
+// * `HMAC_SHA256`
+// * `HMAC_SHA384`
+// * `HMAC_SHA512`
+#[inline]
+pub fn verify_truncated(key: &Key, data: &[u8], tag: &[u8], len: usize) -> Result<(), Unspecified> {
+    let output_len = key.algorithm.digest_algorithm().output_len;
+    if len < min_truncated_tag_len(output_len) || len > output_len || tag.len() != len {
+        return Err(Unspecified);
+    }
+    constant_time::verify_slices_are_equal(&sign(key, data).as_ref()[..len], tag)
+}
+
+/// The minimum truncated tag length permitted by [RFC 2104 Section 5]: the
+/// larger of half the digest output length and 80 bits.
+///
+/// [RFC 2104 Section 5]: https://tools.ietf.org/html/rfc2104#section-5
+const fn min_truncated_tag_len(output_len: usize) -> usize {
+    const MIN_TRUNCATED_TAG_LEN: usize = 80 / 8;
+    let half = (output_len + 1) / 2;
+    if half > MIN_TRUNCATED_TAG_LEN {
+        half
+    } else {
+        MIN_TRUNCATED_TAG_LEN
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{hmac, rand};
@@ -515,6 +596,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn hmac_verify_truncated() {
+        const MSG: &[u8] = b"hello, world";
+
+        for &alg in &[
+            hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY,
+            hmac::HMAC_SHA224,
+            hmac::HMAC_SHA256,
+            hmac::HMAC_SHA384,
+            hmac::HMAC_SHA512,
+        ] {
+            let key = hmac::Key::new(alg, &[0; 32]);
+            let tag = hmac::sign(&key, MSG);
+            let output_len = tag.as_ref().len();
+            let min_len = core::cmp::max((output_len + 1) / 2, 10);
+
+            for len in min_len..=output_len {
+                let truncated = &tag.as_ref()[..len];
+                assert!(hmac::verify_truncated(&key, MSG, truncated, len).is_ok());
+                assert!(hmac::verify_truncated(&key, b"hello, worle", truncated, len).is_err());
+
+                let mut bad_tag = truncated.to_vec();
+                bad_tag[len - 1] ^= 1;
+                assert!(hmac::verify_truncated(&key, MSG, &bad_tag, len).is_err());
+            }
+
+            // Tag length differs from the expected truncation length
+            let truncated = &tag.as_ref()[..min_len];
+            assert!(hmac::verify_truncated(&key, MSG, truncated, min_len + 1).is_err());
+            assert!(hmac::verify_truncated(&key, MSG, tag.as_ref(), min_len).is_err());
+
+            // Too short
+            let truncated = &tag.as_ref()[..min_len - 1];
+            assert!(hmac::verify_truncated(&key, MSG, truncated, min_len - 1).is_err());
+            assert!(hmac::verify_truncated(&key, MSG, &[], 0).is_err());
+
+            // Too long
+            let mut long_tag = tag.as_ref().to_vec();
+            long_tag.push(0);
+            assert!(hmac::verify_truncated(&key, MSG, &long_tag, output_len + 1).is_err());
+        }
+    }
+
     #[test]
     fn hmac_coverage() {
         // Something would have gone horribly wrong for this to not pass, but we test this so our
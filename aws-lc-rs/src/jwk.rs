@@ -0,0 +1,844 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! JSON Web Key (JWK) import and export, as described in [RFC 7517].
+//!
+//! A [`Jwk`] is parsed from, or serialized to, a single JWK JSON object. Conversions to and from
+//! *aws-lc-rs* key types are provided by the key types themselves:
+//!
+//! | `kty` | `crv`                                  | Conversions                                                                                      |
+//! |-------|----------------------------------------|--------------------------------------------------------------------------------------------------|
+//! | `EC`  | `P-256`, `P-384`, `P-521`, `secp256k1` | `EcdsaKeyPair::{from_jwk, to_jwk}`, `EcdsaPublicKey::to_jwk`                                     |
+//! | `OKP` | `Ed25519`                              | `Ed25519KeyPair::{from_jwk, to_jwk}`, the key pair's `public_key().to_jwk()`                     |
+//! | `RSA` |                                        | `RsaKeyPair::{from_jwk, to_jwk}`, `RsaSubjectPublicKey::to_jwk`, `RsaPublicKeyComponents::from_jwk` |
+//! | `oct` |                                        | `hmac::Key::from_jwk`, [`Jwk::from_symmetric_key`]                                               |
+//!
+//! Public keys of any of these types can be used for verification with
+//! `signature::UnparsedPublicKey::from_jwk`.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::jwk::Jwk;
+//! use aws_lc_rs::signature::{self, KeyPair};
+//!
+//! let key_pair = signature::EcdsaKeyPair::generate(&signature::ECDSA_P256_SHA256_FIXED_SIGNING)?;
+//! let mut jwk = key_pair.public_key().to_jwk();
+//! jwk.set("kid", "my-key");
+//! let jwks = format!(r#"{{"keys":[{}]}}"#, jwk.to_json());
+//!
+//! let jwk = Jwk::parse_set(&jwks)?
+//!     .into_iter()
+//!     .find(|jwk| jwk.get("kid") == Some("my-key"))
+//!     .unwrap();
+//! let public_key =
+//!     signature::UnparsedPublicKey::from_jwk(&signature::ECDSA_P256_SHA256_FIXED, &jwk)?;
+//!
+//! let message = b"hello, world";
+//! let signature = key_pair.sign(&aws_lc_rs::rand::SystemRandom::new(), message)?;
+//! public_key.verify(message, signature.as_ref())?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [RFC 7517]: https://tools.ietf.org/html/rfc7517
+
+use crate::error::KeyRejected;
+use core::fmt::{self, Debug, Formatter, Write};
+use zeroize::Zeroize;
+
+// Nesting limit for the (ignored) non-string members of a JWK.
+const MAX_DEPTH: usize = 16;
+
+/// A JSON Web Key.
+///
+/// The string-valued members, which include all of the key material defined by [RFC 7518], are
+/// accessible with [`Jwk::get`]. Other members, such as `key_ops`, are preserved verbatim when the
+/// key is serialized again.
+///
+/// The key material is zeroized when the `Jwk` is dropped.
+///
+/// [RFC 7518]: https://tools.ietf.org/html/rfc7518
+#[derive(Clone)]
+pub struct Jwk {
+    members: Vec<(String, Member)>,
+}
+
+#[derive(Clone)]
+enum Member {
+    String(String),
+    // Any other JSON value, as it appeared in the input.
+    Raw(String),
+}
+
+impl Drop for Jwk {
+    fn drop(&mut self) {
+        for (_, member) in &mut self.members {
+            match member {
+                Member::String(value) | Member::Raw(value) => value.zeroize(),
+            }
+        }
+    }
+}
+
+impl Debug for Jwk {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("Jwk");
+        debug.field("kty", &self.kty());
+        for name in ["crv", "kid", "alg", "use"] {
+            if let Some(value) = self.get(name) {
+                debug.field(name, &value);
+            }
+        }
+        debug.finish_non_exhaustive()
+    }
+}
+
+impl Jwk {
+    /// Parses a single JWK JSON object.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `json` is not a JSON object, contains duplicate member names, or
+    /// does not have a string-valued `kty` member.
+    pub fn parse(json: &str) -> Result<Self, KeyRejected> {
+        let mut parser = Parser::new(json);
+        parser.skip_ws();
+        let jwk = parser.parse_jwk()?;
+        parser.skip_ws();
+        if !parser.is_empty() {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        Ok(jwk)
+    }
+
+    /// Parses a JWK Set (RFC 7517, section 5), i.e. a JSON object whose `keys` member is an array
+    /// of JWKs, such as the document published at an OpenID Connect provider's `jwks_uri`.
+    ///
+    /// As recommended by RFC 7517, keys without a `kty` member are ignored; the caller is
+    /// expected to similarly ignore keys it cannot convert.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `json` is not a JSON object with a `keys` array of JSON objects.
+    pub fn parse_set(json: &str) -> Result<Vec<Self>, KeyRejected> {
+        let mut parser = Parser::new(json);
+        let mut keys = None;
+        parser.skip_ws();
+        parser
+            .parse_object(|parser, name| {
+                if name == "keys" {
+                    if keys.is_some() {
+                        return Err(());
+                    }
+                    let mut set = Vec::new();
+                    parser.parse_array(|parser| {
+                        if let Some(jwk) = parser.parse_jwk_lenient()? {
+                            set.push(jwk);
+                        }
+                        Ok(())
+                    })?;
+                    keys = Some(set);
+                    Ok(())
+                } else {
+                    parser.skip_value(0)
+                }
+            })
+            .map_err(|()| KeyRejected::invalid_encoding())?;
+        parser.skip_ws();
+        if !parser.is_empty() {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        keys.ok_or_else(KeyRejected::invalid_encoding)
+    }
+
+    /// Serializes the key as a JWK JSON object.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{");
+        for (i, (name, member)) in self.members.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            write_json_string(&mut json, name);
+            json.push(':');
+            match member {
+                Member::String(value) => write_json_string(&mut json, value),
+                Member::Raw(value) => json.push_str(value),
+            }
+        }
+        json.push('}');
+        json
+    }
+
+    /// Constructs an `oct` key (RFC 7518, section 6.4) for the given symmetric key value.
+    ///
+    /// Symmetric keys such as `hmac::Key` do not expose their key value, so the value the key was
+    /// constructed from must be provided.
+    #[must_use]
+    pub fn from_symmetric_key(key_value: &[u8]) -> Self {
+        let mut jwk = Self::new("oct");
+        jwk.set_bytes("k", key_value);
+        jwk
+    }
+
+    /// The key type, i.e. the `kty` member.
+    #[must_use]
+    pub fn kty(&self) -> &str {
+        // Every `Jwk` is constructed with a `kty`.
+        self.get("kty").unwrap_or_default()
+    }
+
+    /// Returns the value of the string-valued member `name`, e.g. `kid`, `alg` or `use`.
+    ///
+    /// Binary members, such as the key material, are base64url-encoded.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.members.iter().find_map(|(n, member)| match member {
+            Member::String(value) if n == name => Some(value.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Sets the string-valued member `name`, e.g. `kid`, `alg` or `use`, replacing any existing
+    /// member with that name.
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.set_member(name, Member::String(value.to_owned()));
+    }
+
+    pub(crate) fn new(kty: &str) -> Self {
+        let mut jwk = Jwk {
+            members: Vec::new(),
+        };
+        jwk.set("kty", kty);
+        jwk
+    }
+
+    pub(crate) fn set_bytes(&mut self, name: &str, value: &[u8]) {
+        self.set_member(name, Member::String(base64url_encode(value)));
+    }
+
+    fn set_member(&mut self, name: &str, member: Member) {
+        if let Some((_, existing)) = self.members.iter_mut().find(|(n, _)| n == name) {
+            *existing = member;
+        } else {
+            self.members.push((name.to_owned(), member));
+        }
+    }
+
+    pub(crate) fn contains(&self, name: &str) -> bool {
+        self.members.iter().any(|(n, _)| n == name)
+    }
+
+    /// Decodes the required base64url-encoded member `name`.
+    pub(crate) fn bytes(&self, name: &str) -> Result<Vec<u8>, KeyRejected> {
+        let value = self.get(name).ok_or_else(KeyRejected::invalid_encoding)?;
+        base64url_decode(value).map_err(|()| KeyRejected::invalid_encoding())
+    }
+
+    /// Checks the `kty` and, if given, the `crv` of the key.
+    pub(crate) fn expect(&self, kty: &str, crv: Option<&str>) -> Result<(), KeyRejected> {
+        if self.kty() != kty {
+            return Err(KeyRejected::wrong_algorithm());
+        }
+        if let Some(crv) = crv {
+            if self.get("crv") != Some(crv) {
+                return Err(KeyRejected::wrong_algorithm());
+            }
+        }
+        Ok(())
+    }
+}
+
+const BASE64URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// RFC 7515, section 2: base64url encoding with all trailing '=' characters omitted.
+pub(crate) fn base64url_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity((bytes.len() * 4 + 2) / 3);
+    for chunk in bytes.chunks(3) {
+        let mut group = [0u8; 3];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let n = u32::from(group[0]) << 16 | u32::from(group[1]) << 8 | u32::from(group[2]);
+        for i in 0..=chunk.len() {
+            let index = (n >> (18 - 6 * i)) & 0x3f;
+            encoded.push(char::from(BASE64URL_ALPHABET[index as usize]));
+        }
+    }
+    encoded
+}
+
+pub(crate) fn base64url_decode(encoded: &str) -> Result<Vec<u8>, ()> {
+    let encoded = encoded.as_bytes();
+    if encoded.len() % 4 == 1 {
+        return Err(());
+    }
+    let mut decoded = Vec::with_capacity(encoded.len() * 3 / 4);
+    for chunk in encoded.chunks(4) {
+        let mut n = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            let value = match c {
+                b'A'..=b'Z' => c - b'A',
+                b'a'..=b'z' => c - b'a' + 26,
+                b'0'..=b'9' => c - b'0' + 52,
+                b'-' => 62,
+                b'_' => 63,
+                _ => return Err(()),
+            };
+            n |= u32::from(value) << (18 - 6 * i);
+        }
+        let len = chunk.len() - 1;
+        // The unused bits of a final partial group must be zero for the encoding to be canonical.
+        if n & (0x00ff_ffff >> (8 * len)) != 0 {
+            return Err(());
+        }
+        decoded.extend_from_slice(&n.to_be_bytes()[1..=len]);
+    }
+    Ok(decoded)
+}
+
+fn write_json_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(json, "\\u{:04x}", u32::from(c));
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+// A strict parser for the subset of RFC 8259 needed to read JWKs.
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Parser { input, pos: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos == self.input.len()
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Result<u8, ()> {
+        let byte = self.peek().ok_or(())?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), ()> {
+        if self.next()? == byte {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    fn skip_ws(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn parse_jwk(&mut self) -> Result<Jwk, KeyRejected> {
+        self.parse_jwk_lenient()
+            .ok()
+            .flatten()
+            .ok_or_else(KeyRejected::invalid_encoding)
+    }
+
+    // Returns `None` for a well-formed JSON object without a string-valued `kty`.
+    fn parse_jwk_lenient(&mut self) -> Result<Option<Jwk>, ()> {
+        let mut jwk = Jwk {
+            members: Vec::new(),
+        };
+        self.parse_object(|parser, name| {
+            if jwk.contains(&name) {
+                return Err(());
+            }
+            let member = if parser.peek() == Some(b'"') {
+                Member::String(parser.parse_string()?)
+            } else {
+                let start = parser.pos;
+                parser.skip_value(0)?;
+                Member::Raw(parser.input[start..parser.pos].to_owned())
+            };
+            jwk.members.push((name, member));
+            Ok(())
+        })?;
+        Ok(if jwk.get("kty").is_some() {
+            Some(jwk)
+        } else {
+            None
+        })
+    }
+
+    // Calls `member` with the parser positioned at the start of each member's value.
+    fn parse_object<F>(&mut self, mut member: F) -> Result<(), ()>
+    where
+        F: FnMut(&mut Self, String) -> Result<(), ()>,
+    {
+        self.expect(b'{')?;
+        self.skip_ws();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(());
+        }
+        loop {
+            self.skip_ws();
+            let name = self.parse_string()?;
+            self.skip_ws();
+            self.expect(b':')?;
+            self.skip_ws();
+            member(self, name)?;
+            self.skip_ws();
+            match self.next()? {
+                b',' => {}
+                b'}' => return Ok(()),
+                _ => return Err(()),
+            }
+        }
+    }
+
+    // Calls `element` with the parser positioned at the start of each element.
+    fn parse_array<F>(&mut self, mut element: F) -> Result<(), ()>
+    where
+        F: FnMut(&mut Self) -> Result<(), ()>,
+    {
+        self.expect(b'[')?;
+        self.skip_ws();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(());
+        }
+        loop {
+            self.skip_ws();
+            element(self)?;
+            self.skip_ws();
+            match self.next()? {
+                b',' => {}
+                b']' => return Ok(()),
+                _ => return Err(()),
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, ()> {
+        self.expect(b'"')?;
+        let mut value = String::new();
+        loop {
+            let rest = &self.input[self.pos..];
+            let end = rest.find(['"', '\\']).ok_or(())?;
+            if rest[..end].chars().any(|c| c < ' ') {
+                return Err(());
+            }
+            value.push_str(&rest[..end]);
+            self.pos += end;
+            if self.next()? == b'"' {
+                return Ok(value);
+            }
+            let c = match self.next()? {
+                b'"' => '"',
+                b'\\' => '\\',
+                b'/' => '/',
+                b'b' => '\u{8}',
+                b'f' => '\u{c}',
+                b'n' => '\n',
+                b'r' => '\r',
+                b't' => '\t',
+                b'u' => {
+                    let high = self.parse_hex4()?;
+                    let code_point = if (0xd800..0xdc00).contains(&high) {
+                        self.expect(b'\\')?;
+                        self.expect(b'u')?;
+                        let low = self.parse_hex4()?;
+                        if !(0xdc00..0xe000).contains(&low) {
+                            return Err(());
+                        }
+                        0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+                    } else {
+                        high
+                    };
+                    char::from_u32(code_point).ok_or(())?
+                }
+                _ => return Err(()),
+            };
+            value.push(c);
+        }
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, ()> {
+        let digits = self.input.get(self.pos..self.pos + 4).ok_or(())?;
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(());
+        }
+        self.pos += 4;
+        u32::from_str_radix(digits, 16).map_err(|_| ())
+    }
+
+    fn skip_value(&mut self, depth: usize) -> Result<(), ()> {
+        if depth > MAX_DEPTH {
+            return Err(());
+        }
+        match self.peek().ok_or(())? {
+            b'{' => self.parse_object(|parser, _| parser.skip_value(depth + 1)),
+            b'[' => self.parse_array(|parser| parser.skip_value(depth + 1)),
+            b'"' => self.parse_string().map(|_| ()),
+            b't' => self.skip_literal("true"),
+            b'f' => self.skip_literal("false"),
+            b'n' => self.skip_literal("null"),
+            _ => self.skip_number(),
+        }
+    }
+
+    fn skip_literal(&mut self, literal: &str) -> Result<(), ()> {
+        if self.input[self.pos..].starts_with(literal) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    fn skip_number(&mut self) -> Result<(), ()> {
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        match self.next()? {
+            b'0' => {}
+            b'1'..=b'9' => self.skip_digits(),
+            _ => return Err(()),
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            self.skip_digits1()?;
+        }
+        if let Some(b'e' | b'E') = self.peek() {
+            self.pos += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.pos += 1;
+            }
+            self.skip_digits1()?;
+        }
+        Ok(())
+    }
+
+    fn skip_digits(&mut self) {
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_digits1(&mut self) -> Result<(), ()> {
+        let start = self.pos;
+        self.skip_digits();
+        if self.pos == start {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{base64url_decode, base64url_encode, Jwk};
+    use crate::encoding::AsBigEndian;
+    use crate::rand::SystemRandom;
+    use crate::signature::{self, KeyPair};
+    use crate::{hmac, rsa};
+
+    #[test]
+    fn test_base64url() {
+        // RFC 4648, section 10, without padding.
+        for (bytes, encoded) in [
+            (&b""[..], ""),
+            (b"f", "Zg"),
+            (b"fo", "Zm8"),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg"),
+            (b"fooba", "Zm9vYmE"),
+            (b"foobar", "Zm9vYmFy"),
+            (&[0xfb, 0xff, 0xbf], "-_-_"),
+        ] {
+            assert_eq!(encoded, base64url_encode(bytes));
+            assert_eq!(bytes, base64url_decode(encoded).unwrap());
+        }
+
+        for encoded in ["Zg==", "Z", "Zh", "Zm9", "Zm+v", "Zm/v", "Zm9v\n"] {
+            assert!(base64url_decode(encoded).is_err(), "{encoded}");
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        // RFC 7517, appendix A.1.
+        let json = r#"
+            {"kty":"EC",
+             "crv":"P-256",
+             "x":"MKBCTNIcKUSDii11ySs3526iDZ8AiTo7Tu6KPAqv7D4",
+             "y":"4Etl6SRW2YiLUrN5vfvVHuhp7x8PxltmWWlbbM4IFyM",
+             "use":"enc",
+             "key_ops": ["deriveKey", "deriveBits"],
+             "ext": true,
+             "n": [1, -2.5e+3, {"a": null}, false],
+             "kid":"1é😀\/\"\\"}
+        "#;
+        let jwk = Jwk::parse(json).unwrap();
+        assert_eq!("EC", jwk.kty());
+        assert_eq!(Some("P-256"), jwk.get("crv"));
+        assert_eq!(Some("enc"), jwk.get("use"));
+        assert_eq!(Some("1\u{e9}\u{1f600}/\"\\"), jwk.get("kid"));
+        assert_eq!(None, jwk.get("key_ops"));
+        assert_eq!(None, jwk.get("n"));
+        assert_eq!(32, jwk.bytes("x").unwrap().len());
+        assert!(jwk.expect("EC", Some("P-256")).is_ok());
+        assert!(jwk.expect("EC", Some("P-384")).is_err());
+        assert!(jwk.expect("RSA", None).is_err());
+        assert_eq!(
+            r#"Jwk { kty: "EC", crv: "P-256", kid: "1é😀/\"\\", use: "enc", .. }"#,
+            format!("{jwk:?}")
+        );
+
+        let reparsed = Jwk::parse(&jwk.to_json()).unwrap();
+        assert_eq!(jwk.to_json(), reparsed.to_json());
+        assert!(jwk
+            .to_json()
+            .contains(r#""key_ops":["deriveKey", "deriveBits"]"#));
+
+        for json in [
+            "",
+            "[]",
+            "{}",
+            r#"{"kty":1}"#,
+            r#"{"kty":"EC","kty":"EC"}"#,
+            r#"{"kty":"EC",}"#,
+            r#"{"kty":"EC"} {}"#,
+            r#"{"kty":"EC","x":01}"#,
+            r#"{"kty":"EC","x":1.}"#,
+            r#"{"kty":"EC","x":tru}"#,
+            r#"{"kty":"EC","x":"\ud83d"}"#,
+            r#"{"kty":"EC","x":"\x"}"#,
+            "{\"kty\":\"EC\",\"x\":\"\n\"}",
+            r#"{"kty":"EC","x":[[[[[[[[[[[[[[[[[[[[]]]]]]]]]]]]]]]]]]]}"#,
+        ] {
+            assert!(Jwk::parse(json).is_err(), "{json}");
+        }
+    }
+
+    #[test]
+    fn test_parse_set() {
+        let json = r#"{"keys":[
+            {"kty":"oct","k":"AAEC","kid":"a"},
+            {"k":"AAEC"},
+            {"kty":"OKP","crv":"Ed25519","x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}
+        ], "other": {"keys": 1}}"#;
+        let keys = Jwk::parse_set(json).unwrap();
+        assert_eq!(2, keys.len());
+        assert_eq!(Some("a"), keys[0].get("kid"));
+        assert_eq!(vec![0u8, 1, 2], keys[0].bytes("k").unwrap());
+        assert_eq!("OKP", keys[1].kty());
+
+        for json in [
+            "{}",
+            r#"{"keys":{}}"#,
+            r#"{"keys":[1]}"#,
+            r#"{"keys":[],"keys":[]}"#,
+        ] {
+            assert!(Jwk::parse_set(json).is_err(), "{json}");
+        }
+    }
+
+    #[test]
+    fn test_set() {
+        let mut jwk = Jwk::from_symmetric_key(b"\x00\x01\x02");
+        jwk.set("kid", "a\n\u{1}");
+        jwk.set("kid", "b\n\u{1}");
+        assert_eq!(
+            r#"{"kty":"oct","k":"AAEC","kid":"b\n\u0001"}"#,
+            jwk.to_json()
+        );
+        assert_eq!(
+            Some("b\n\u{1}"),
+            Jwk::parse(&jwk.to_json()).unwrap().get("kid")
+        );
+    }
+
+    #[test]
+    fn test_ecdsa() {
+        // RFC 7515, appendix A.3.
+        let json = r#"{"kty":"EC","crv":"P-256",
+            "x":"f83OJ3D2xF1Bg8vub9tLe1gHMzV76e8Tus9uPHvRVEU",
+            "y":"x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0",
+            "d":"jpsQnnGQmL-YBIffH1136cspYG6-0iY7X1fCE9-E9LI"}"#;
+        let jwk = Jwk::parse(json).unwrap();
+        let key_pair =
+            signature::EcdsaKeyPair::from_jwk(&signature::ECDSA_P256_SHA256_FIXED_SIGNING, &jwk)
+                .unwrap();
+        let exported = key_pair.to_jwk().unwrap();
+        for name in ["kty", "crv", "x", "y", "d"] {
+            assert_eq!(jwk.get(name), exported.get(name));
+        }
+        assert_eq!(None, key_pair.public_key().to_jwk().get("d"));
+
+        let message = b"hello, world";
+        let signature = key_pair.sign(&SystemRandom::new(), message).unwrap();
+        let public_key =
+            signature::UnparsedPublicKey::from_jwk(&signature::ECDSA_P256_SHA256_FIXED, &jwk)
+                .unwrap();
+        assert!(public_key.verify(message, signature.as_ref()).is_ok());
+
+        assert!(signature::EcdsaKeyPair::from_jwk(
+            &signature::ECDSA_P384_SHA384_FIXED_SIGNING,
+            &jwk
+        )
+        .is_err());
+
+        let mut inconsistent = jwk.clone();
+        inconsistent.set("y", "8_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0");
+        assert!(signature::EcdsaKeyPair::from_jwk(
+            &signature::ECDSA_P256_SHA256_FIXED_SIGNING,
+            &inconsistent
+        )
+        .is_err());
+
+        for alg in [
+            &signature::ECDSA_P384_SHA384_FIXED_SIGNING,
+            &signature::ECDSA_P521_SHA512_FIXED_SIGNING,
+            #[cfg(not(feature = "strict-fips"))]
+            &signature::ECDSA_P256K1_SHA256_FIXED_SIGNING,
+        ] {
+            let key_pair = signature::EcdsaKeyPair::generate(alg).unwrap();
+            let jwk = Jwk::parse(&key_pair.to_jwk().unwrap().to_json()).unwrap();
+            let parsed = signature::EcdsaKeyPair::from_jwk(alg, &jwk).unwrap();
+            assert_eq!(
+                key_pair.private_key().as_be_bytes().unwrap().as_ref(),
+                parsed.private_key().as_be_bytes().unwrap().as_ref()
+            );
+        }
+    }
+
+    #[cfg(not(feature = "strict-fips"))]
+    #[test]
+    fn test_ed25519() {
+        // RFC 8037, appendices A.1 and A.4.
+        let json = r#"{"kty":"OKP","crv":"Ed25519",
+            "d":"nWGxne_9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A",
+            "x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}"#;
+        let jwk = Jwk::parse(json).unwrap();
+        let key_pair = signature::Ed25519KeyPair::from_jwk(&jwk).unwrap();
+        let signature = key_pair.sign(b"eyJhbGciOiJFZERTQSJ9.RXhhbXBsZSBvZiBFZDI1NTE5IHNpZ25pbmc");
+        assert_eq!(
+            "hgyY0il_MGCjP0JzlnLWG1PPOt7-09PGcvMg3AIbQR6dWbhijcNR4ki4iylGjg5BhVsPt9g7sVvpAr_MuM0KAg",
+            base64url_encode(signature.as_ref())
+        );
+
+        let exported = key_pair.to_jwk().unwrap();
+        for name in ["kty", "crv", "x", "d"] {
+            assert_eq!(jwk.get(name), exported.get(name));
+        }
+        let public_jwk = key_pair.public_key().to_jwk();
+        assert_eq!(None, public_jwk.get("d"));
+        let public_key =
+            signature::UnparsedPublicKey::from_jwk(&signature::ED25519, &public_jwk).unwrap();
+        assert!(public_key
+            .verify(
+                b"eyJhbGciOiJFZERTQSJ9.RXhhbXBsZSBvZiBFZDI1NTE5IHNpZ25pbmc",
+                signature.as_ref()
+            )
+            .is_ok());
+
+        let mut inconsistent = jwk.clone();
+        inconsistent.set("x", "12qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo");
+        assert!(signature::Ed25519KeyPair::from_jwk(&inconsistent).is_err());
+        let mut x448 = jwk.clone();
+        x448.set("crv", "X448");
+        assert!(signature::Ed25519KeyPair::from_jwk(&x448).is_err());
+        assert!(signature::UnparsedPublicKey::from_jwk(&signature::ED25519, &x448).is_err());
+    }
+
+    #[test]
+    fn test_rsa() {
+        let key_pair = signature::RsaKeyPair::generate(rsa::KeySize::Rsa2048).unwrap();
+        let jwk = Jwk::parse(&key_pair.to_jwk().unwrap().to_json()).unwrap();
+        let parsed = signature::RsaKeyPair::from_jwk(&jwk).unwrap();
+        assert_eq!(
+            key_pair.to_der().unwrap().as_ref(),
+            parsed.to_der().unwrap().as_ref()
+        );
+        assert_eq!(Some("AQAB"), jwk.get("e"));
+
+        let public_jwk = key_pair.public_key().to_jwk().unwrap();
+        assert_eq!(
+            r#"{"kty":"RSA","n":"N","e":"AQAB"}"#.replace('N', jwk.get("n").unwrap()),
+            public_jwk.to_json()
+        );
+
+        let message = b"hello, world";
+        let mut signature = vec![0u8; key_pair.public_modulus_len()];
+        key_pair
+            .sign(
+                &signature::RSA_PKCS1_SHA256,
+                &SystemRandom::new(),
+                message,
+                &mut signature,
+            )
+            .unwrap();
+        let public_key = signature::UnparsedPublicKey::from_jwk(
+            &signature::RSA_PKCS1_2048_8192_SHA256,
+            &public_jwk,
+        )
+        .unwrap();
+        assert!(public_key.verify(message, &signature).is_ok());
+        let components = signature::RsaPublicKeyComponents::from_jwk(&public_jwk).unwrap();
+        assert!(components
+            .verify(&signature::RSA_PKCS1_2048_8192_SHA256, message, &signature)
+            .is_ok());
+
+        // A public key is not a key pair.
+        assert!(signature::RsaKeyPair::from_jwk(&public_jwk).is_err());
+        let mut multi_prime = jwk.clone();
+        multi_prime.set("oth", "");
+        assert!(signature::RsaKeyPair::from_jwk(&multi_prime).is_err());
+        let mut inconsistent = jwk.clone();
+        inconsistent.set("qi", jwk.get("dp").unwrap());
+        assert!(signature::RsaKeyPair::from_jwk(&inconsistent).is_err());
+    }
+
+    #[test]
+    fn test_hmac() {
+        // RFC 7515, appendix A.1.
+        let jwk = Jwk::parse(
+            r#"{"kty":"oct",
+                "k":"AyM1SysPpbyDfgZld3umj1qzKObwVMkoqQ-EstJQLr_T-1qS0gZH75aKtMN3Yj0iPS4hcgUuTwjAzZr1Z9CAow"}"#,
+        )
+        .unwrap();
+        let key = hmac::Key::from_jwk(hmac::HMAC_SHA256, &jwk).unwrap();
+        let tag = hmac::sign(
+            &key,
+            b"eyJ0eXAiOiJKV1QiLA0KICJhbGciOiJIUzI1NiJ9.eyJpc3MiOiJqb2UiLA0KICJleHAiOjEzMDA4MTkzODAsDQogImh0dHA6Ly9leGFtcGxlLmNvbS9pc19yb290Ijp0cnVlfQ",
+        );
+        assert_eq!(
+            "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk",
+            base64url_encode(tag.as_ref())
+        );
+
+        let exported = Jwk::from_symmetric_key(&jwk.bytes("k").unwrap());
+        assert_eq!(jwk.to_json().replace(['\n', ' '], ""), exported.to_json());
+        assert!(hmac::Key::from_jwk(hmac::HMAC_SHA256, &Jwk::new("EC")).is_err());
+    }
+}
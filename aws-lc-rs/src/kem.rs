@@ -50,6 +50,7 @@
 //! ```
 use crate::{
     buffer::Buffer,
+    constant_time,
     encoding::generated_encodings,
     error::{KeyRejected, Unspecified},
     ptr::LcPtr,
@@ -241,6 +242,24 @@ where
 
         Ok(SharedSecret(shared_secret.into_boxed_slice()))
     }
+
+    /// Runs a pairwise consistency test (PCT) on the key: encapsulates a shared secret to the
+    /// corresponding encapsulation key, decapsulates it with this key, and checks that both
+    /// sides agree.
+    ///
+    /// High-assurance deployments can run it after [`DecapsulationKey::generate`] to obtain
+    /// evidence that the check ran, e.g. to log it or to refuse provisioning a key.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the key fails the test.
+    pub fn pairwise_consistency_test(&self) -> Result<(), Unspecified> {
+        let (ciphertext, encapsulated_secret) = self.encapsulation_key()?.encapsulate()?;
+        let decapsulated_secret = self.decapsulate(ciphertext)?;
+        constant_time::verify_slices_are_equal(
+            encapsulated_secret.as_ref(),
+            decapsulated_secret.as_ref(),
+        )
+    }
 }
 
 unsafe impl<Id> Send for DecapsulationKey<Id> where Id: AlgorithmIdentifier {}
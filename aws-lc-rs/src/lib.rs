@@ -43,6 +43,107 @@
 //! Enable feature to preserve compatibility with ring's `signature::VerificationAlgorithm::verify`
 //! function. This adds a requirement on `untrusted = "0.7.1"`.
 //!
+//! #### rand-core
+//!
+//! Enable this feature to access `rand::RandCoreAdapter`, which implements the `RngCore` and
+//! `CryptoRng` traits of `rand_core` 0.6 on top of `rand::SystemRandom`. This adds a requirement
+//! on `rand_core = "0.6.4"`.
+//!
+//! #### getrandom-custom
+//!
+//! Enable this feature to access `rand::getrandom_custom`, which a binary can register as the
+//! custom implementation of `getrandom` 0.2, so that on targets without an operating system
+//! random number generator other crates using `getrandom` draw from *AWS-LC*. This adds a
+//! requirement on `getrandom = "0.2.10"`.
+//!
+//! #### legacy-digests
+//!
+//! Enable this feature to access digest algorithms that are only suitable for interoperability
+//! with legacy systems, such as `digest::RIPEMD160_FOR_LEGACY_USE_ONLY`. These algorithms are not
+//! approved for use in FIPS mode.
+//!
+//! #### legacy-dsa
+//!
+//! Enable this feature to access verification-only DSA signature algorithms, such as
+//! `signature::DSA_SHA256_FOR_LEGACY_USE_ONLY`, for validating signatures from legacy systems.
+//! DSA signing is not supported.
+//!
+//! #### legacy-pem
+//!
+//! Enable this feature to decrypt private keys in the OpenSSL "traditional" encrypted PEM format
+//! (`Proc-Type: 4,ENCRYPTED`) with `encoding::RsaPrivateKeyRfc8017Der::from_encrypted_pem` and
+//! `encoding::EcPrivateKeyRfc5915Der::from_encrypted_pem`, for importing keys from legacy
+//! systems. Its key derivation is weak; keys should be re-encrypted with `pkcs8::encrypt`.
+//!
+//! #### legacy-pkcs1-encryption
+//!
+//! Enable this feature to access RSA PKCS#1 v1.5 encryption, `rsa::Pkcs1PublicEncryptingKey` and
+//! `rsa::Pkcs1PrivateDecryptingKey`, for legacy protocols such as SCEP. Decryption uses implicit
+//! rejection to resist Bleichenbacher's attack; new protocols should use RSA-OAEP.
+//!
+//! #### ephemeral-key-export
+//!
+//! Enable this feature to serialize an `agreement::EphemeralPrivateKey` as a PKCS#8 document, for
+//! example to escrow it or to move it into an HSM. Doing so gives up the guarantee that the key is
+//! used for only one key agreement.
+//!
+//! #### rsa-key-components
+//!
+//! Enable this feature to export the private components of an `rsa::KeyPair` (n, e, d, p, q,
+//! dp, dq and qinv) with `rsa::KeyPair::private_key_components`, e.g. to import the key into an
+//! HSM or to serialize it in a format this crate does not write.
+//!
+//! #### jwk
+//!
+//! Enable this feature to access the `jwk` module, which converts RSA, EC, Ed25519 and symmetric
+//! keys to and from JSON Web Keys (RFC 7517), e.g. for consuming an OpenID Connect JWK Set.
+//!
+//! #### pkcs12
+//!
+//! Enable this feature to access the `pkcs12` module, which decrypts PKCS#12 (`.p12`/`.pfx`)
+//! archives to extract their private key and certificate chain.
+//!
+//! #### x509
+//!
+//! Enable this feature to access the `x509` module, which parses X.509 certificates to inspect
+//! their names, validity period, extensions and public key, verifies certificate chains
+//! against a set of trust anchors, optionally checking revocation against CRLs, builds
+//! certificate signing requests, and creates self-signed certificates.
+//!
+//! This feature, and the `ocsp`, `cms` and `ct` features that imply it, cannot be combined with
+//! `fips`, as the FIPS module lacks some of the functions they need.
+//!
+//! #### ocsp
+//!
+//! Enable this feature, which implies `x509`, to access the `ocsp` module, which creates OCSP
+//! requests and verifies the signed responses, including their nonce and validity period, e.g.
+//! to check a response stapled to a TLS handshake.
+//!
+//! #### cms
+//!
+//! Enable this feature, which implies `x509`, to access the `cms` module, which verifies CMS
+//! (PKCS#7) `SignedData` signatures, attached or detached, such as S/MIME signatures, and the
+//! certificate chains of their signers.
+//!
+//! #### ct
+//!
+//! Enable this feature, which implies `x509`, to access the `ct` module, which parses and
+//! verifies Certificate Transparency signed certificate timestamps against a set of trusted
+//! logs, whether embedded in certificates, sent in the TLS extension, or, with the `ocsp`
+//! feature, stapled in OCSP responses.
+//!
+//! #### ssh
+//!
+//! Enable this feature to access the `ssh` module, which converts Ed25519, ECDSA and RSA keys to
+//! and from the OpenSSH public and private key formats, e.g. for building SSH agents or signing
+//! git commits with SSH keys.
+//!
+//! #### test-utils
+//!
+//! Enable this feature to access `test::rand::DeterministicRandom`, a `rand::SecureRandom` whose
+//! output is determined by a fixed seed, for writing reproducible known-answer tests. It is not
+//! secure and must never be used in production code.
+//!
 //! #### fips
 //!
 //! Enable this feature to have aws-lc-rs use the [*aws-lc-fips-sys*](https://crates.io/crates/aws-lc-fips-sys)
@@ -51,6 +152,12 @@
 //! FIPS validation testing by an accredited lab and has been submitted to NIST for certification.
 //! The static build of AWS-LC-FIPS is used.
 //!
+//! The build fails if this feature is combined with `non-fips` or with a feature that only
+//! provides algorithms that are not FIPS-approved: `legacy-digests`, `legacy-dsa`, `legacy-pem`
+//! or `legacy-pkcs1-encryption`. Algorithms that are available without such a feature but are
+//! not approved, such as ChaCha20-Poly1305, are still provided by the FIPS module as non-approved
+//! services; use `fips::assert_approved` to check that an operation used an approved service.
+//!
 //! Refer to the
 //! [NIST Cryptographic Module Validation Program's Modules In Progress List](https://csrc.nist.gov/Projects/cryptographic-module-validation-program/modules-in-process/Modules-In-Process-List)
 //! for the latest status of the static or dynamic AWS-LC Cryptographic Module. A complete list of supported operating
@@ -58,6 +165,32 @@
 //! will also update our release notes
 //! and documentation to reflect any changes in FIPS certification status.
 //!
+//! #### fips-cpu-jitter-entropy
+//!
+//! Enable this feature, which implies `fips`, to build the FIPS module with its CPU Jitter
+//! entropy source, which seeds the random number generators from CPU timing variations rather
+//! than the operating system, e.g. for virtualized or air-gapped hosts whose entropy is not
+//! trusted. `fips::CONFIGURED_ENTROPY_SOURCE` records which source the build selected; it is
+//! a compile-time constant, not a query of the running module.
+//!
+//! #### strict-fips
+//!
+//! Enable this feature, which implies `fips`, to leave algorithms that are not approved for use
+//! in FIPS mode out of the crate, so that a review of an application's build can show that it
+//! cannot reach them. The following are not compiled:
+//!
+//! * ChaCha20-Poly1305: `aead::CHACHA20_POLY1305`, `quic::CHACHA20` and the
+//!   `aead::chacha20_poly1305_openssh` module.
+//! * Ed25519: `signature::ED25519` and `signature::Ed25519KeyPair`, together with Ed25519
+//!   support in the `jwk`, `ssh`, `x509`, `ocsp` and `cms` modules.
+//! * X25519: `agreement::X25519` and the `unstable::agreement` module.
+//! * ECDSA over secp256k1: the `signature::ECDSA_P256K1_*` algorithms and
+//!   `signature::PublicKeyType::EcdsaP256K1`.
+//! * scrypt: the `scrypt` module and `password::Params::Scrypt`.
+//!
+//! The `legacy-*` features, including the legacy digest algorithms, cannot be combined with
+//! `fips` in any case.
+//!
 //! #### asan
 //!
 //! Performs an "address sanitizer" build. This can be used to help detect memory leaks. See the
@@ -103,6 +236,11 @@
 #[cfg(feature = "fips")]
 extern crate aws_lc_fips_sys as aws_lc;
 
+#[cfg(all(feature = "fips", feature = "x509"))]
+compile_error!(
+    "the `x509` feature, which `ocsp`, `cms` and `ct` imply, is not supported with `fips`"
+);
+
 extern crate alloc;
 #[cfg(not(feature = "fips"))]
 extern crate aws_lc_sys as aws_lc;
@@ -110,17 +248,32 @@ extern crate core;
 pub mod aead;
 pub mod agreement;
 pub mod constant_time;
+pub mod der;
 pub mod digest;
 pub mod error;
+pub mod gmac;
 pub mod hkdf;
 pub mod hmac;
 #[cfg(feature = "ring-io")]
 pub mod io;
+#[cfg(feature = "jwk")]
+pub mod jwk;
 pub mod key_wrap;
+#[cfg(feature = "ocsp")]
+pub mod ocsp;
+pub mod password;
 pub mod pbkdf2;
+#[cfg(feature = "pkcs12")]
+pub mod pkcs12;
 pub mod pkcs8;
+pub mod policy;
 pub mod rand;
+pub mod raw_public_key;
+#[cfg(not(feature = "strict-fips"))]
+pub mod scrypt;
 pub mod signature;
+#[cfg(feature = "ssh")]
+pub mod ssh;
 pub mod test;
 
 mod bn;
@@ -128,13 +281,20 @@ mod buffer;
 mod cbb;
 mod cbs;
 pub mod cipher;
+#[cfg(feature = "cms")]
+pub mod cms;
+#[cfg(feature = "ct")]
+pub mod ct;
 mod debug;
+#[cfg(feature = "legacy-dsa")]
+mod dsa;
 mod ec;
+#[cfg(not(feature = "strict-fips"))]
 mod ed25519;
 pub mod encoding;
 mod endian;
 mod evp_pkey;
-mod fips;
+pub mod fips;
 mod hex;
 pub mod iv;
 #[allow(clippy::module_name_repetitions)]
@@ -143,6 +303,8 @@ mod ptr;
 pub mod rsa;
 pub mod tls_prf;
 pub mod unstable;
+#[cfg(feature = "x509")]
+pub mod x509;
 
 pub(crate) use debug::derive_debug_via_id;
 // TODO: Uncomment when MSRV >= 1.64
@@ -200,6 +362,87 @@ unsafe fn dump_error() {
     eprintln!("Raw Error -- {error_msg:?}\nErr: {err}, Lib: {lib}, Reason: {reason}, Func: {func}");
 }
 
+/// With the `strict-fips` feature, none of the algorithms it leaves out can be named. Each
+/// example must fail to compile with an unresolved import.
+///
+/// ```compile_fail,E0432
+/// use aws_lc_rs::aead::CHACHA20_POLY1305;
+/// ```
+///
+/// ```compile_fail,E0432
+/// use aws_lc_rs::aead::quic::CHACHA20;
+/// ```
+///
+/// ```compile_fail,E0432
+/// use aws_lc_rs::aead::chacha20_poly1305_openssh;
+/// ```
+///
+/// ```compile_fail,E0432
+/// use aws_lc_rs::signature::ED25519;
+/// ```
+///
+/// ```compile_fail,E0432
+/// use aws_lc_rs::signature::ED25519_STRICT;
+/// ```
+///
+/// ```compile_fail,E0432
+/// use aws_lc_rs::signature::Ed25519KeyPair;
+/// ```
+///
+/// ```compile_fail,E0432
+/// use aws_lc_rs::agreement::X25519;
+/// ```
+///
+/// ```compile_fail,E0432
+/// use aws_lc_rs::unstable::agreement;
+/// ```
+///
+/// ```compile_fail,E0432
+/// use aws_lc_rs::signature::ECDSA_P256K1_SHA256_FIXED;
+/// ```
+///
+/// ```compile_fail,E0432
+/// use aws_lc_rs::signature::ECDSA_P256K1_SHA3_256_FIXED;
+/// ```
+///
+/// ```compile_fail,E0432
+/// use aws_lc_rs::signature::ECDSA_P256K1_SHA256_ASN1;
+/// ```
+///
+/// ```compile_fail,E0432
+/// use aws_lc_rs::signature::ECDSA_P256K1_SHA3_256_ASN1;
+/// ```
+///
+/// ```compile_fail,E0432
+/// use aws_lc_rs::signature::ECDSA_P256K1_SHA256_FIXED_SIGNING;
+/// ```
+///
+/// ```compile_fail,E0432
+/// use aws_lc_rs::signature::ECDSA_P256K1_SHA3_256_FIXED_SIGNING;
+/// ```
+///
+/// ```compile_fail,E0432
+/// use aws_lc_rs::signature::ECDSA_P256K1_SHA256_ASN1_SIGNING;
+/// ```
+///
+/// ```compile_fail,E0432
+/// use aws_lc_rs::signature::ECDSA_P256K1_SHA3_256_ASN1_SIGNING;
+/// ```
+///
+/// ```compile_fail,E0432
+/// use aws_lc_rs::signature::PublicKeyType::EcdsaP256K1;
+/// ```
+///
+/// ```compile_fail,E0432
+/// use aws_lc_rs::scrypt;
+/// ```
+///
+/// ```compile_fail,E0432
+/// use aws_lc_rs::password::Params::Scrypt;
+/// ```
+#[cfg(all(doctest, feature = "strict-fips"))]
+struct StrictFipsExclusions;
+
 mod sealed {
     /// Traits that are designed to only be implemented internally in *aws-lc-rs*.
     //
@@ -0,0 +1,741 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Online Certificate Status Protocol (OCSP) requests and responses, as described in
+//! [RFC 6960].
+//!
+//! An [`OcspRequest`] asks a responder for the status of a certificate, and is sent to the
+//! responder named in the certificate's authority information access extension. The response
+//! is verified with [`OcspRequest::verify_response`], which also checks that it echoes the
+//! request's nonce. Responses obtained without a request, such as those stapled to a TLS
+//! handshake, are verified with [`OcspResponse::verify`].
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::ocsp::{CertificateStatus, OcspRequest, OcspResponse};
+//! use aws_lc_rs::x509::Certificate;
+//! use std::time::SystemTime;
+//!
+//! let issuer = Certificate::from_der(&std::fs::read("ca.der")?)?;
+//! let certificate = Certificate::from_der(&std::fs::read("server.der")?)?;
+//!
+//! // Send the request to the responder as `application/ocsp-request`, and pass its response
+//! // to `request.verify_response`.
+//! let request = OcspRequest::new(&certificate, &issuer)?;
+//! assert_eq!(32, request.nonce().unwrap().len());
+//!
+//! let stapled = std::fs::read("server.ocsp")?;
+//! let response = OcspResponse::verify(&stapled, &certificate, &issuer, SystemTime::now())?;
+//! assert_eq!(CertificateStatus::Good, response.status());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [RFC 6960]: https://tools.ietf.org/html/rfc6960
+
+use crate::der::{self, Reader, Tag, Writer};
+use crate::digest;
+use crate::error::Unspecified;
+use crate::ptr::LcPtr;
+use crate::rand;
+use crate::signature::{self, PublicKey, PublicKeyType, VerificationAlgorithm};
+use crate::x509::{asn1_time_to_system_time, Certificate};
+use aws_lc::{d2i_ASN1_GENERALIZEDTIME, X509_get_extended_key_usage, XKU_OCSP_SIGN};
+use core::ptr::null_mut;
+use std::os::raw::c_long;
+use std::time::SystemTime;
+
+// RFC 8954 limits nonces to 32 bytes, and recommends using that many.
+const NONCE_LEN: usize = 32;
+
+// The DER-encoded contents of the object identifiers used by OCSP.
+const OID_SHA1: &[u8] = &[0x2b, 0x0e, 0x03, 0x02, 0x1a];
+const OID_SHA256: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01];
+const OID_SHA384: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02];
+const OID_SHA512: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03];
+const OID_SHA256_WITH_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b];
+const OID_SHA384_WITH_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0c];
+const OID_SHA512_WITH_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0d];
+const OID_ECDSA_WITH_SHA256: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02];
+const OID_ECDSA_WITH_SHA384: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x03];
+const OID_ECDSA_WITH_SHA512: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x04];
+#[cfg(not(feature = "strict-fips"))]
+const OID_ED25519: &[u8] = &[0x2b, 0x65, 0x70];
+const OID_PKIX_OCSP_BASIC: &[u8] = &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x30, 0x01, 0x01];
+const OID_PKIX_OCSP_NONCE: &[u8] = &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x30, 0x01, 0x02];
+
+/// A request for the revocation status of a certificate.
+#[derive(Clone, Debug)]
+pub struct OcspRequest {
+    der: Box<[u8]>,
+    cert_id: CertId,
+    nonce: Option<Box<[u8]>>,
+}
+
+impl OcspRequest {
+    /// Creates a request for the status of `certificate`, issued by `issuer`, with a random
+    /// nonce that the response must echo.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `issuer`'s public key cannot be parsed, or the nonce cannot be
+    /// generated.
+    pub fn new(certificate: &Certificate, issuer: &Certificate) -> Result<Self, Unspecified> {
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::fill(&mut nonce)?;
+        Self::build(certificate, issuer, Some(nonce.into()))
+    }
+
+    /// Creates a request for the status of `certificate`, issued by `issuer`, without a
+    /// nonce. Such requests can be answered with cached responses, which makes them
+    /// susceptible to replay until the response expires.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `issuer`'s public key cannot be parsed.
+    pub fn without_nonce(
+        certificate: &Certificate,
+        issuer: &Certificate,
+    ) -> Result<Self, Unspecified> {
+        Self::build(certificate, issuer, None)
+    }
+
+    fn build(
+        certificate: &Certificate,
+        issuer: &Certificate,
+        nonce: Option<Box<[u8]>>,
+    ) -> Result<Self, Unspecified> {
+        let cert_id = CertId::new(certificate.serial_number(), issuer)?;
+        let der = der::encode(|ocsp_request| {
+            ocsp_request.write_sequence(|ocsp_request| {
+                ocsp_request.write_sequence(|tbs_request| {
+                    tbs_request.write_sequence(|request_list| {
+                        request_list.write_sequence(|request| {
+                            request.write_sequence(|contents| cert_id.encode(contents))
+                        })
+                    })?;
+                    if let Some(nonce) = &nonce {
+                        tbs_request.write(Tag::context_specific(2).constructed(), |explicit| {
+                            explicit.write_sequence(|extensions| encode_nonce(extensions, nonce))
+                        })?;
+                    }
+                    Ok(())
+                })
+            })
+        })?;
+        Ok(Self {
+            der: der.into(),
+            cert_id,
+            nonce,
+        })
+    }
+
+    /// The request's nonce, if any.
+    #[must_use]
+    pub fn nonce(&self) -> Option<&[u8]> {
+        self.nonce.as_deref()
+    }
+
+    /// Verifies that `response` is a valid response to this request, signed by `issuer` or by
+    /// a responder it delegated to, and current at `time`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the response is malformed or was not successful, its signature
+    /// is not valid, it does not echo the request's nonce, it does not cover the requested
+    /// certificate, or `time` is outside its validity period.
+    pub fn verify_response(
+        &self,
+        response: &[u8],
+        issuer: &Certificate,
+        time: SystemTime,
+    ) -> Result<OcspResponse, Unspecified> {
+        if CertId::new(&self.cert_id.serial_number, issuer)? != self.cert_id {
+            return Err(Unspecified);
+        }
+        Ok(verify(response, &self.cert_id, issuer, self.nonce.as_deref(), time)?.0)
+    }
+}
+
+impl AsRef<[u8]> for OcspRequest {
+    /// The DER encoding of the request.
+    fn as_ref(&self) -> &[u8] {
+        &self.der
+    }
+}
+
+/// The revocation status of a certificate.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CertificateStatus {
+    /// The certificate is not revoked.
+    Good,
+    /// The certificate was revoked at the given time.
+    Revoked(SystemTime),
+    /// The responder does not know the certificate.
+    Unknown,
+}
+
+/// A verified response to an OCSP request.
+#[derive(Clone, Copy, Debug)]
+pub struct OcspResponse {
+    status: CertificateStatus,
+    produced_at: SystemTime,
+    this_update: SystemTime,
+    next_update: Option<SystemTime>,
+}
+
+impl OcspResponse {
+    /// Verifies that `response` is a valid response for the status of `certificate`, signed
+    /// by `issuer` or by a responder it delegated to, and current at `time`.
+    ///
+    /// Use this for responses that were not obtained with an `OcspRequest`, such as those
+    /// stapled to a TLS handshake; they cannot be checked for a nonce.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the response is malformed or was not successful, its signature
+    /// is not valid, it does not cover `certificate`, or `time` is outside its validity
+    /// period.
+    pub fn verify(
+        response: &[u8],
+        certificate: &Certificate,
+        issuer: &Certificate,
+        time: SystemTime,
+    ) -> Result<Self, Unspecified> {
+        let cert_id = CertId::new(certificate.serial_number(), issuer)?;
+        Ok(verify(response, &cert_id, issuer, None, time)?.0)
+    }
+
+    /// The status of the certificate.
+    #[must_use]
+    pub fn status(&self) -> CertificateStatus {
+        self.status
+    }
+
+    /// The time at which the response was signed.
+    #[must_use]
+    pub fn produced_at(&self) -> SystemTime {
+        self.produced_at
+    }
+
+    /// The time at which the status was known to be correct.
+    #[must_use]
+    pub fn this_update(&self) -> SystemTime {
+        self.this_update
+    }
+
+    /// The time at or before which newer information will be available, if any.
+    #[must_use]
+    pub fn next_update(&self) -> Option<SystemTime> {
+        self.next_update
+    }
+}
+
+// The identity of a certificate in requests and responses: its serial number, and the issuer
+// that assigned it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct CertId {
+    issuer_name: Box<[u8]>,
+    issuer_key: Box<[u8]>,
+    serial_number: Box<[u8]>,
+}
+
+impl CertId {
+    fn new(serial_number: &[u8], issuer: &Certificate) -> Result<Self, Unspecified> {
+        Ok(Self {
+            issuer_name: issuer.subject().as_ref().into(),
+            issuer_key: issuer.subject_public_key()?.into(),
+            serial_number: serial_number.into(),
+        })
+    }
+
+    // Encodes the contents of the `CertID`, identifying the issuer with SHA-1 hashes as required by RFC 5019.
+    fn encode(&self, cert_id: &mut Writer<'_>) -> Result<(), Unspecified> {
+        let algorithm = &digest::SHA1_FOR_LEGACY_USE_ONLY;
+        cert_id.write_sequence(|hash_algorithm| {
+            hash_algorithm.write_value(Tag::OBJECT_IDENTIFIER, OID_SHA1)?;
+            hash_algorithm.write_null()
+        })?;
+        cert_id
+            .write_octet_string(digest::digest_unchecked(algorithm, &self.issuer_name).as_ref())?;
+        cert_id
+            .write_octet_string(digest::digest_unchecked(algorithm, &self.issuer_key).as_ref())?;
+        cert_id.write_unsigned_integer(&self.serial_number)
+    }
+
+    // Whether the `CertID` with the contents `cert_id` identifies this certificate.
+    fn matches(&self, mut cert_id: Reader<'_>) -> Result<bool, Unspecified> {
+        let mut hash_algorithm = cert_id.read_sequence()?;
+        let oid = hash_algorithm.read(Tag::OBJECT_IDENTIFIER)?.remaining();
+        let algorithm = match [
+            (OID_SHA1, &digest::SHA1_FOR_LEGACY_USE_ONLY),
+            (OID_SHA256, &digest::SHA256),
+            (OID_SHA384, &digest::SHA384),
+            (OID_SHA512, &digest::SHA512),
+        ]
+        .into_iter()
+        .find_map(|(id, algorithm)| (id == oid).then_some(algorithm))
+        {
+            Some(algorithm) => algorithm,
+            None => return Ok(false),
+        };
+        let issuer_name_hash = cert_id.read_octet_string()?;
+        let issuer_key_hash = cert_id.read_octet_string()?;
+        let serial_number = cert_id.read_unsigned_integer()?;
+        Ok(
+            issuer_name_hash == digest::digest_unchecked(algorithm, &self.issuer_name).as_ref()
+                && issuer_key_hash
+                    == digest::digest_unchecked(algorithm, &self.issuer_key).as_ref()
+                && serial_number == &*self.serial_number,
+        )
+    }
+}
+
+/// Verifies `response` as `OcspResponse::verify` does, and also returns the contents of the
+/// `singleExtensions` of its response for `certificate`, if any.
+#[cfg(feature = "ct")]
+pub(crate) fn verify_with_single_extensions<'a>(
+    response: &'a [u8],
+    certificate: &Certificate,
+    issuer: &Certificate,
+    time: SystemTime,
+) -> Result<(OcspResponse, Option<Reader<'a>>), Unspecified> {
+    let cert_id = CertId::new(certificate.serial_number(), issuer)?;
+    verify(response, &cert_id, issuer, None, time)
+}
+
+// Verifies `response`, and returns the status it gives `cert_id` along with the contents of
+// that `SingleResponse`'s extensions, if any.
+fn verify<'a>(
+    response: &'a [u8],
+    cert_id: &CertId,
+    issuer: &Certificate,
+    nonce: Option<&[u8]>,
+    time: SystemTime,
+) -> Result<(OcspResponse, Option<Reader<'a>>), Unspecified> {
+    let mut input = Reader::new(response);
+    let mut response = input.read_sequence()?;
+    input.finish()?;
+
+    // Unsuccessful responses, e.g. `tryLater`, are not signed.
+    if response.read(Tag::ENUMERATED)?.remaining() != [0] {
+        return Err(Unspecified);
+    }
+    let mut response_bytes = response.read(Tag::context_specific(0).constructed())?;
+    response.finish()?;
+    let mut response = response_bytes.read_sequence()?;
+    response_bytes.finish()?;
+    if response.read(Tag::OBJECT_IDENTIFIER)?.remaining() != OID_PKIX_OCSP_BASIC {
+        return Err(Unspecified);
+    }
+    let mut basic_response = Reader::new(response.read_octet_string()?);
+    response.finish()?;
+
+    let mut response = basic_response.read_sequence()?;
+    basic_response.finish()?;
+    let tbs_response_data = response.read_element(Tag::SEQUENCE)?;
+    let signature_algorithm = response.read_sequence()?;
+    let signature = response.read_bit_string()?;
+    let certs = response.read_optional(Tag::context_specific(0).constructed())?;
+    response.finish()?;
+
+    let mut response_data = Reader::new(tbs_response_data).read_sequence()?;
+    if let Some(mut version) =
+        response_data.read_optional(Tag::context_specific(0).constructed())?
+    {
+        // Only v1 responses exist, although DER omits the default version.
+        if version.read_u64()? != 0 {
+            return Err(Unspecified);
+        }
+        version.finish()?;
+    }
+    let (responder_id_tag, responder_id) = response_data.read_any()?;
+    let responder_id = ResponderId::parse(responder_id_tag, responder_id)?;
+    let produced_at = read_time(&mut response_data)?;
+    let mut responses = response_data.read_sequence()?;
+    let extensions = response_data.read_optional(Tag::context_specific(1).constructed())?;
+    response_data.finish()?;
+
+    let signer = find_signer(&responder_id, certs, issuer, time)?;
+    let algorithm = verification_algorithm(signature_algorithm, signer.key_type())?;
+    signer.verify(algorithm, tbs_response_data, signature)?;
+
+    if let Some(nonce) = nonce {
+        let extensions = extensions.ok_or(Unspecified)?;
+        if find_nonce(extensions)?.ok_or(Unspecified)? != nonce {
+            return Err(Unspecified);
+        }
+    }
+
+    while !responses.is_empty() {
+        let mut single_response = responses.read_sequence()?;
+        if !cert_id.matches(single_response.read_sequence()?)? {
+            continue;
+        }
+        let status = parse_status(&mut single_response)?;
+        let this_update = read_time(&mut single_response)?;
+        let next_update =
+            match single_response.read_optional(Tag::context_specific(0).constructed())? {
+                Some(mut next_update) => Some(read_time(&mut next_update)?),
+                None => None,
+            };
+        if time < this_update || next_update.map_or(false, |next_update| next_update < time) {
+            return Err(Unspecified);
+        }
+        let single_extensions =
+            single_response.read_optional(Tag::context_specific(1).constructed())?;
+        single_response.finish()?;
+        return Ok((
+            OcspResponse {
+                status,
+                produced_at,
+                this_update,
+                next_update,
+            },
+            single_extensions,
+        ));
+    }
+    Err(Unspecified)
+}
+
+// The `ResponderID` of a response: the subject name or SHA-1 key hash of the signer.
+enum ResponderId<'a> {
+    Name(&'a [u8]),
+    KeyHash(&'a [u8]),
+}
+
+impl<'a> ResponderId<'a> {
+    fn parse(tag: Tag, mut contents: Reader<'a>) -> Result<Self, Unspecified> {
+        let responder_id = if tag == Tag::context_specific(1).constructed() {
+            ResponderId::Name(contents.read_element(Tag::SEQUENCE)?)
+        } else if tag == Tag::context_specific(2).constructed() {
+            ResponderId::KeyHash(contents.read_octet_string()?)
+        } else {
+            return Err(Unspecified);
+        };
+        contents.finish()?;
+        Ok(responder_id)
+    }
+
+    fn matches(&self, certificate: &Certificate) -> Result<bool, Unspecified> {
+        Ok(match self {
+            ResponderId::Name(name) => *name == certificate.subject().as_ref(),
+            ResponderId::KeyHash(hash) => {
+                let key = certificate.subject_public_key()?;
+                *hash == digest::digest_unchecked(&digest::SHA1_FOR_LEGACY_USE_ONLY, key).as_ref()
+            }
+        })
+    }
+}
+
+// The public key of the response's signer: the issuer itself, or a responder whose
+// certificate is included in the response.
+fn find_signer(
+    responder_id: &ResponderId<'_>,
+    certs: Option<Reader<'_>>,
+    issuer: &Certificate,
+    time: SystemTime,
+) -> Result<PublicKey, Unspecified> {
+    if responder_id.matches(issuer)? {
+        return Ok(issuer.public_key()?);
+    }
+    let mut certs = match certs {
+        Some(mut certs) => certs.read_sequence()?,
+        None => return Err(Unspecified),
+    };
+    while !certs.is_empty() {
+        let certificate = Certificate::from_der(certs.read_element(Tag::SEQUENCE)?)?;
+        if responder_id.matches(&certificate)? {
+            check_delegated_responder(&certificate, issuer, time)?;
+            return Ok(certificate.public_key()?);
+        }
+    }
+    Err(Unspecified)
+}
+
+// Checks that `responder` was authorized by `issuer` to sign responses, as described in
+// RFC 6960 Section 4.2.2.2.
+fn check_delegated_responder(
+    responder: &Certificate,
+    issuer: &Certificate,
+    time: SystemTime,
+) -> Result<(), Unspecified> {
+    if responder.issuer() != issuer.subject() || !responder.is_valid_at(time) {
+        return Err(Unspecified);
+    }
+    responder.verify_signature(&issuer.public_key()?)?;
+    #[allow(clippy::cast_sign_loss)]
+    if 0 == unsafe { X509_get_extended_key_usage(**responder.x509()) } & XKU_OCSP_SIGN as u32 {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
+fn verification_algorithm(
+    mut signature_algorithm: Reader<'_>,
+    key_type: PublicKeyType,
+) -> Result<&'static dyn VerificationAlgorithm, Unspecified> {
+    let oid = signature_algorithm
+        .read(Tag::OBJECT_IDENTIFIER)?
+        .remaining();
+    let algorithms: &[(&[u8], PublicKeyType, &'static dyn VerificationAlgorithm)] = &[
+        (
+            OID_SHA256_WITH_RSA,
+            PublicKeyType::Rsa,
+            &signature::RSA_PKCS1_2048_8192_SHA256,
+        ),
+        (
+            OID_SHA384_WITH_RSA,
+            PublicKeyType::Rsa,
+            &signature::RSA_PKCS1_2048_8192_SHA384,
+        ),
+        (
+            OID_SHA512_WITH_RSA,
+            PublicKeyType::Rsa,
+            &signature::RSA_PKCS1_2048_8192_SHA512,
+        ),
+        (
+            OID_ECDSA_WITH_SHA256,
+            PublicKeyType::EcdsaP256,
+            &signature::ECDSA_P256_SHA256_ASN1,
+        ),
+        (
+            OID_ECDSA_WITH_SHA384,
+            PublicKeyType::EcdsaP256,
+            &signature::ECDSA_P256_SHA384_ASN1,
+        ),
+        (
+            OID_ECDSA_WITH_SHA256,
+            PublicKeyType::EcdsaP384,
+            &signature::ECDSA_P384_SHA256_ASN1,
+        ),
+        (
+            OID_ECDSA_WITH_SHA384,
+            PublicKeyType::EcdsaP384,
+            &signature::ECDSA_P384_SHA384_ASN1,
+        ),
+        (
+            OID_ECDSA_WITH_SHA512,
+            PublicKeyType::EcdsaP521,
+            &signature::ECDSA_P521_SHA512_ASN1,
+        ),
+        #[cfg(not(feature = "strict-fips"))]
+        (OID_ED25519, PublicKeyType::Ed25519, &signature::ED25519),
+    ];
+    algorithms
+        .iter()
+        .copied()
+        .find_map(|(id, algorithm_key_type, algorithm)| {
+            (id == oid && algorithm_key_type == key_type).then_some(algorithm)
+        })
+        .ok_or(Unspecified)
+}
+
+fn parse_status(single_response: &mut Reader<'_>) -> Result<CertificateStatus, Unspecified> {
+    let (tag, mut contents) = single_response.read_any()?;
+    if tag == Tag::context_specific(0) && contents.is_empty() {
+        Ok(CertificateStatus::Good)
+    } else if tag == Tag::context_specific(1).constructed() {
+        // The revocation reason is optional, and not exposed.
+        Ok(CertificateStatus::Revoked(read_time(&mut contents)?))
+    } else if tag == Tag::context_specific(2) && contents.is_empty() {
+        Ok(CertificateStatus::Unknown)
+    } else {
+        Err(Unspecified)
+    }
+}
+
+// Encodes the nonce extension of RFC 8954, whose value is an OCTET STRING.
+fn encode_nonce(extensions: &mut Writer<'_>, nonce: &[u8]) -> Result<(), Unspecified> {
+    extensions.write_sequence(|extension| {
+        extension.write_value(Tag::OBJECT_IDENTIFIER, OID_PKIX_OCSP_NONCE)?;
+        extension.write(Tag::OCTET_STRING, |value| value.write_octet_string(nonce))
+    })
+}
+
+// The nonce in the response's extensions, if any.
+fn find_nonce<'a>(mut extensions: Reader<'a>) -> Result<Option<&'a [u8]>, Unspecified> {
+    let mut extensions = extensions.read_sequence()?;
+    while !extensions.is_empty() {
+        let mut extension = extensions.read_sequence()?;
+        if extension.read(Tag::OBJECT_IDENTIFIER)?.remaining() != OID_PKIX_OCSP_NONCE {
+            continue;
+        }
+        extension.read_optional(Tag::BOOLEAN)?;
+        let mut value = Reader::new(extension.read_octet_string()?);
+        let nonce = value.read_octet_string()?;
+        value.finish()?;
+        return Ok(Some(nonce));
+    }
+    Ok(None)
+}
+
+fn read_time(reader: &mut Reader<'_>) -> Result<SystemTime, Unspecified> {
+    let der = reader.read_element(Tag::GENERALIZED_TIME)?;
+    let mut input = der.as_ptr();
+    unsafe {
+        let time = LcPtr::new(d2i_ASN1_GENERALIZEDTIME(
+            null_mut(),
+            &mut input,
+            c_long::try_from(der.len())?,
+        ))?;
+        asn1_time_to_system_time(*time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CertificateStatus, OcspRequest, OcspResponse};
+    use crate::x509::Certificate;
+    use std::time::{Duration, SystemTime};
+
+    const OPENSSL_NONCE: &[u8] = &[
+        0xf9, 0x52, 0x11, 0x7a, 0x25, 0x2f, 0x63, 0x4b, 0x22, 0xe0, 0xbf, 0xe5, 0x55, 0xd7, 0x1a,
+        0x52,
+    ];
+
+    fn certificates() -> (Certificate, Certificate) {
+        (
+            Certificate::from_der(include_bytes!("../tests/data/x509_test_leaf.der")).unwrap(),
+            Certificate::from_der(include_bytes!("../tests/data/x509_test_ca.der")).unwrap(),
+        )
+    }
+
+    // A time at which the test responses are current.
+    fn now(issuer: &Certificate) -> SystemTime {
+        issuer.not_before() + Duration::from_secs(3600)
+    }
+
+    #[test]
+    fn test_request() {
+        let (certificate, issuer) = certificates();
+        let request =
+            OcspRequest::build(&certificate, &issuer, Some(OPENSSL_NONCE.into())).unwrap();
+        assert_eq!(
+            include_bytes!("../tests/data/ocsp_test_request.der"),
+            request.as_ref()
+        );
+        assert_eq!(Some(OPENSSL_NONCE), request.nonce());
+
+        let request = OcspRequest::new(&certificate, &issuer).unwrap();
+        assert_ne!(
+            request.nonce(),
+            OcspRequest::new(&certificate, &issuer).unwrap().nonce()
+        );
+        assert_eq!(
+            None,
+            OcspRequest::without_nonce(&certificate, &issuer)
+                .unwrap()
+                .nonce()
+        );
+    }
+
+    #[test]
+    fn test_verify_response() {
+        let (certificate, issuer) = certificates();
+        let response = include_bytes!("../tests/data/ocsp_test_good.der");
+        let request =
+            OcspRequest::build(&certificate, &issuer, Some(OPENSSL_NONCE.into())).unwrap();
+        let verified = request
+            .verify_response(response, &issuer, now(&issuer))
+            .unwrap();
+        assert_eq!(CertificateStatus::Good, verified.status());
+        assert_eq!(
+            Some(verified.this_update() + Duration::from_secs(7 * 86400)),
+            verified.next_update()
+        );
+        assert!(verified.produced_at() <= verified.this_update());
+
+        // The response must echo the request's nonce.
+        let request = OcspRequest::new(&certificate, &issuer).unwrap();
+        assert!(request
+            .verify_response(response, &issuer, now(&issuer))
+            .is_err());
+        let request = OcspRequest::without_nonce(&certificate, &issuer).unwrap();
+        request
+            .verify_response(response, &issuer, now(&issuer))
+            .unwrap();
+        // The issuer must be the one the request was created for.
+        assert!(request
+            .verify_response(
+                include_bytes!("../tests/data/ocsp_test_delegated.der"),
+                &certificate,
+                now(&issuer)
+            )
+            .is_err());
+
+        // The response must be current.
+        let request =
+            OcspRequest::build(&certificate, &issuer, Some(OPENSSL_NONCE.into())).unwrap();
+        assert!(request
+            .verify_response(
+                response,
+                &issuer,
+                verified.this_update() - Duration::from_secs(1)
+            )
+            .is_err());
+        let expired = verified.next_update().unwrap() + Duration::from_secs(1);
+        assert!(request.verify_response(response, &issuer, expired).is_err());
+
+        // The signature must be valid.
+        let mut tampered = response.to_vec();
+        let len = tampered.len();
+        tampered[len - 1] ^= 1;
+        assert!(request
+            .verify_response(&tampered, &issuer, now(&issuer))
+            .is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let (certificate, issuer) = certificates();
+        let response = OcspResponse::verify(
+            include_bytes!("../tests/data/ocsp_test_delegated.der"),
+            &certificate,
+            &issuer,
+            now(&issuer),
+        )
+        .unwrap();
+        assert_eq!(CertificateStatus::Good, response.status());
+
+        let response = OcspResponse::verify(
+            include_bytes!("../tests/data/ocsp_test_revoked.der"),
+            &certificate,
+            &issuer,
+            now(&issuer),
+        )
+        .unwrap();
+        let revocation_time = match response.status() {
+            CertificateStatus::Revoked(revocation_time) => revocation_time,
+            status => panic!("{status:?}"),
+        };
+        assert!(revocation_time < response.this_update());
+
+        // The response only covers the leaf certificate.
+        assert!(OcspResponse::verify(
+            include_bytes!("../tests/data/ocsp_test_revoked.der"),
+            &issuer,
+            &issuer,
+            now(&issuer),
+        )
+        .is_err());
+        // A delegated responder must be certified by the issuer.
+        let other_issuer =
+            Certificate::from_der(include_bytes!("../tests/data/x509_test_intermediate.der"))
+                .unwrap();
+        assert!(OcspResponse::verify(
+            include_bytes!("../tests/data/ocsp_test_delegated.der"),
+            &certificate,
+            &other_issuer,
+            now(&issuer),
+        )
+        .is_err());
+        assert!(OcspResponse::verify(
+            &[0x30, 0x03, 0x0a, 0x01, 0x03],
+            &certificate,
+            &issuer,
+            now(&issuer)
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,529 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Password hashing using the PHC string format.
+//!
+//! Use `hash` to hash a password with a freshly generated salt, producing a
+//! self-describing string suitable for storage. Use `verify` to verify a
+//! password against a previously stored string.
+//!
+//! The strings follow the [PHC string format], and are compatible with the
+//! encodings used by other implementations:
+//!
+//! | Algorithm          | Example
+//! |--------------------|------------------------------------------------
+//! | PBKDF2-HMAC-SHA256 | `$pbkdf2-sha256$i=600000,l=32$<salt>$<hash>`
+//! | PBKDF2-HMAC-SHA512 | `$pbkdf2-sha512$i=210000,l=64$<salt>$<hash>`
+//! | scrypt             | `$scrypt$ln=17,r=8,p=1$<salt>$<hash>`
+//!
+//! where `<salt>` and `<hash>` are encoded using standard Base64 without padding.
+//! scrypt is not available with the `strict-fips` feature.
+//!
+//! [PHC string format]: https://github.com/P-H-C/phc-string-format/blob/master/phc-sf-spec.md
+//!
+//! # Example
+//!
+//! ```
+//! use aws_lc_rs::password;
+//! use core::num::NonZeroU32;
+//!
+//! let params = password::Params::Pbkdf2Sha256 {
+//!     iterations: NonZeroU32::new(600_000).unwrap(),
+//! };
+//!
+//! let stored = password::hash(&params, b"@74d7]404j|W}6u")?;
+//! assert!(stored.starts_with("$pbkdf2-sha256$i=600000,l=32$"));
+//!
+//! // An attempt to log in with the wrong password fails.
+//! assert!(password::verify(&stored, b"wrong password").is_err());
+//!
+//! // An attempt to log in with the right password succeeds.
+//! assert!(password::verify(&stored, b"@74d7]404j|W}6u").is_ok());
+//!
+//! # Ok::<(), aws_lc_rs::error::Unspecified>(())
+//! ```
+
+use crate::error::Unspecified;
+#[cfg(not(feature = "strict-fips"))]
+use crate::scrypt;
+use crate::{digest, pbkdf2, rand};
+use aws_lc::{EVP_DecodeBase64, EVP_DecodedLength, EVP_EncodeBlock, EVP_EncodedLength};
+use core::fmt::Write;
+use core::num::NonZeroU32;
+use zeroize::Zeroize;
+
+/// The length of the salt generated by `hash`, in bytes.
+pub const SALT_LEN: usize = 16;
+
+/// The minimum length of a salt accepted by `verify`, in bytes.
+const MIN_SALT_LEN: usize = 8;
+
+/// The maximum length of a salt accepted by `verify`, in bytes.
+const MAX_SALT_LEN: usize = 64;
+
+/// The minimum length of a hash accepted by `verify`, in bytes.
+const MIN_HASH_LEN: usize = 10;
+
+/// The maximum length of a hash accepted by `verify`, in bytes.
+const MAX_HASH_LEN: usize = 64;
+
+/// The length of the scrypt hash produced by `hash`, in bytes.
+#[cfg(not(feature = "strict-fips"))]
+const SCRYPT_HASH_LEN: usize = 32;
+
+/// The maximum number of PBKDF2 iterations accepted by `verify`.
+const MAX_PBKDF2_ITERATIONS: u32 = 10_000_000;
+
+/// The maximum scrypt `ln` accepted by `verify`.
+#[cfg(not(feature = "strict-fips"))]
+const MAX_SCRYPT_LOG_N: u8 = 20;
+
+/// The maximum scrypt `r` accepted by `verify`.
+#[cfg(not(feature = "strict-fips"))]
+const MAX_SCRYPT_R: u32 = 32;
+
+/// The maximum scrypt `p` accepted by `verify`.
+#[cfg(not(feature = "strict-fips"))]
+const MAX_SCRYPT_P: u32 = 16;
+
+/// The maximum memory, in bytes, that the scrypt parameters accepted by `verify` may need:
+/// 1 GiB for the `2^ln` blocks, and 1 MiB for the few blocks scrypt uses besides them.
+#[cfg(not(feature = "strict-fips"))]
+const MAX_SCRYPT_MEM: usize = (1 << 30) + (1 << 20);
+
+const PBKDF2_SHA256_ID: &str = "pbkdf2-sha256";
+const PBKDF2_SHA512_ID: &str = "pbkdf2-sha512";
+#[cfg(not(feature = "strict-fips"))]
+const SCRYPT_ID: &str = "scrypt";
+
+/// The algorithm and cost parameters used to hash a password.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Params {
+    /// PBKDF2 using HMAC-SHA256, producing a 32 byte hash.
+    Pbkdf2Sha256 {
+        /// The number of iterations.
+        iterations: NonZeroU32,
+    },
+
+    /// PBKDF2 using HMAC-SHA512, producing a 64 byte hash.
+    Pbkdf2Sha512 {
+        /// The number of iterations.
+        iterations: NonZeroU32,
+    },
+
+    /// scrypt, producing a 32 byte hash.
+    // # FIPS
+    // Not available with the `strict-fips` feature, as scrypt is not approved for use in FIPS
+    // mode.
+    #[cfg(not(feature = "strict-fips"))]
+    Scrypt(scrypt::Params),
+}
+
+#[cfg_attr(
+    feature = "strict-fips",
+    allow(clippy::trivially_copy_pass_by_ref, clippy::unnecessary_wraps)
+)]
+impl Params {
+    fn id(&self) -> &'static str {
+        match self {
+            Params::Pbkdf2Sha256 { .. } => PBKDF2_SHA256_ID,
+            Params::Pbkdf2Sha512 { .. } => PBKDF2_SHA512_ID,
+            #[cfg(not(feature = "strict-fips"))]
+            Params::Scrypt(_) => SCRYPT_ID,
+        }
+    }
+
+    fn hash_len(&self) -> usize {
+        match self {
+            Params::Pbkdf2Sha256 { .. } => digest::SHA256_OUTPUT_LEN,
+            Params::Pbkdf2Sha512 { .. } => digest::SHA512_OUTPUT_LEN,
+            #[cfg(not(feature = "strict-fips"))]
+            Params::Scrypt(_) => SCRYPT_HASH_LEN,
+        }
+    }
+
+    fn derive(&self, salt: &[u8], password: &[u8], out: &mut [u8]) -> Result<(), Unspecified> {
+        match self {
+            Params::Pbkdf2Sha256 { iterations } => {
+                pbkdf2::derive(pbkdf2::PBKDF2_HMAC_SHA256, *iterations, salt, password, out);
+                Ok(())
+            }
+            Params::Pbkdf2Sha512 { iterations } => {
+                pbkdf2::derive(pbkdf2::PBKDF2_HMAC_SHA512, *iterations, salt, password, out);
+                Ok(())
+            }
+            #[cfg(not(feature = "strict-fips"))]
+            Params::Scrypt(params) => scrypt::derive(params, salt, password, out),
+        }
+    }
+
+    fn verify(&self, salt: &[u8], password: &[u8], hash: &[u8]) -> Result<(), Unspecified> {
+        match self {
+            Params::Pbkdf2Sha256 { iterations } => pbkdf2::verify(
+                pbkdf2::PBKDF2_HMAC_SHA256,
+                *iterations,
+                salt,
+                password,
+                hash,
+            ),
+            Params::Pbkdf2Sha512 { iterations } => pbkdf2::verify(
+                pbkdf2::PBKDF2_HMAC_SHA512,
+                *iterations,
+                salt,
+                password,
+                hash,
+            ),
+            #[cfg(not(feature = "strict-fips"))]
+            Params::Scrypt(params) => scrypt::verify(params, salt, password, hash),
+        }
+    }
+
+    fn write_params(&self, out: &mut String, hash_len: usize) {
+        // Writing to a `String` never fails.
+        let _ = match self {
+            Params::Pbkdf2Sha256 { iterations } | Params::Pbkdf2Sha512 { iterations } => {
+                write!(out, "i={iterations},l={hash_len}")
+            }
+            #[cfg(not(feature = "strict-fips"))]
+            Params::Scrypt(params) => write!(
+                out,
+                "ln={},r={},p={}",
+                params.log_n(),
+                params.r(),
+                params.p()
+            ),
+        };
+    }
+
+    fn parse(id: &str, params: &str) -> Result<(Self, Option<usize>), Unspecified> {
+        let mut iterations = None;
+        let mut log_n = None;
+        let mut r = None;
+        let mut p = None;
+        let mut hash_len = None;
+        for param in params.split(',') {
+            let (name, value) = param.split_once('=').ok_or(Unspecified)?;
+            let slot = match name {
+                "i" => &mut iterations,
+                "ln" => &mut log_n,
+                "r" => &mut r,
+                "p" => &mut p,
+                "l" => &mut hash_len,
+                _ => return Err(Unspecified),
+            };
+            if slot.replace(parse_decimal(value)?).is_some() {
+                return Err(Unspecified);
+            }
+        }
+        let hash_len = hash_len.map(|l| l as usize);
+
+        let params = match id {
+            PBKDF2_SHA256_ID | PBKDF2_SHA512_ID => {
+                if log_n.is_some() || r.is_some() || p.is_some() {
+                    return Err(Unspecified);
+                }
+                let iterations = iterations.ok_or(Unspecified)?;
+                if iterations > MAX_PBKDF2_ITERATIONS {
+                    return Err(Unspecified);
+                }
+                let iterations = NonZeroU32::new(iterations).ok_or(Unspecified)?;
+                if id == PBKDF2_SHA256_ID {
+                    Params::Pbkdf2Sha256 { iterations }
+                } else {
+                    Params::Pbkdf2Sha512 { iterations }
+                }
+            }
+            #[cfg(not(feature = "strict-fips"))]
+            SCRYPT_ID => {
+                if iterations.is_some() {
+                    return Err(Unspecified);
+                }
+                let log_n = u8::try_from(log_n.ok_or(Unspecified)?).map_err(|_| Unspecified)?;
+                let r = r.ok_or(Unspecified)?;
+                let p = p.ok_or(Unspecified)?;
+                if log_n > MAX_SCRYPT_LOG_N || r > MAX_SCRYPT_R || p > MAX_SCRYPT_P {
+                    return Err(Unspecified);
+                }
+                let params = scrypt::Params::new(log_n, r, p)?;
+                if params.max_mem()? > MAX_SCRYPT_MEM {
+                    return Err(Unspecified);
+                }
+                Params::Scrypt(params)
+            }
+            _ => return Err(Unspecified),
+        };
+        Ok((params, hash_len))
+    }
+}
+
+/// Hashes `password` using `params` and a randomly generated salt, and
+/// returns the result encoded as a PHC string.
+///
+/// # Errors
+/// `error::Unspecified` if salt generation or the key derivation fails.
+pub fn hash(params: &Params, password: &[u8]) -> Result<String, Unspecified> {
+    let mut salt = [0u8; SALT_LEN];
+    rand::fill(&mut salt)?;
+
+    let hash_len = params.hash_len();
+    let mut hash = [0u8; MAX_HASH_LEN];
+    let hash = &mut hash[..hash_len];
+    params.derive(&salt, password, hash)?;
+
+    let mut phc = String::new();
+    phc.push('$');
+    phc.push_str(params.id());
+    phc.push('$');
+    params.write_params(&mut phc, hash_len);
+    phc.push('$');
+    phc.push_str(&b64_encode(&salt));
+    phc.push('$');
+    phc.push_str(&b64_encode(hash));
+    hash.zeroize();
+
+    Ok(phc)
+}
+
+/// Verifies `password` against the PHC string `phc`, which was previously
+/// produced by `hash` or a compatible implementation.
+///
+/// The comparison is done in constant time to prevent timing attacks.
+///
+/// To bound the work an attacker-supplied string can cause, PBKDF2 strings
+/// with more than 10,000,000 iterations, and scrypt strings with `ln` above
+/// 20, `r` above 32, `p` above 16, or that need more than about 1 GiB of
+/// memory, are rejected. scrypt strings are always rejected with the
+/// `strict-fips` feature.
+///
+/// # Errors
+/// `error::Unspecified` if `phc` is malformed, uses an unsupported algorithm,
+/// exceeds the cost limits above, or the password was not verified.
+pub fn verify(phc: &str, password: &[u8]) -> Result<(), Unspecified> {
+    let (params, salt, hash) = parse(phc)?;
+    params.verify(&salt, password, &hash)
+}
+
+/// Parses the algorithm parameters from the PHC string `phc`.
+///
+/// This can be used to determine whether a stored hash should be upgraded to
+/// stronger parameters after a successful `verify`.
+///
+/// # Errors
+/// `error::Unspecified` if `phc` is malformed, uses an unsupported algorithm,
+/// or exceeds the cost limits described in `verify`.
+pub fn params(phc: &str) -> Result<Params, Unspecified> {
+    parse(phc).map(|(params, _, _)| params)
+}
+
+fn parse(phc: &str) -> Result<(Params, Vec<u8>, Vec<u8>), Unspecified> {
+    let fields: Vec<&str> = phc
+        .strip_prefix('$')
+        .ok_or(Unspecified)?
+        .split('$')
+        .collect();
+    let (id, params, salt, hash) = match fields[..] {
+        [id, params, salt, hash] => (id, params, salt, hash),
+        _ => return Err(Unspecified),
+    };
+
+    let (params, hash_len) = Params::parse(id, params)?;
+
+    let salt = b64_decode(salt)?;
+    if salt.len() < MIN_SALT_LEN || salt.len() > MAX_SALT_LEN {
+        return Err(Unspecified);
+    }
+    let hash = b64_decode(hash)?;
+    if hash.len() < MIN_HASH_LEN
+        || hash.len() > MAX_HASH_LEN
+        || hash_len.map_or(false, |len| len != hash.len())
+    {
+        return Err(Unspecified);
+    }
+
+    Ok((params, salt, hash))
+}
+
+fn parse_decimal(value: &str) -> Result<u32, Unspecified> {
+    // Leading zeros and signs are not permitted by the PHC string format.
+    if value.is_empty()
+        || (value.len() > 1 && value.starts_with('0'))
+        || !value.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(Unspecified);
+    }
+    value.parse().map_err(|_| Unspecified)
+}
+
+/// Encodes `bytes` using standard Base64 without padding.
+fn b64_encode(bytes: &[u8]) -> String {
+    let mut encoded_len = 0;
+    // `EVP_EncodedLength` includes the trailing NUL.
+    if 1 != unsafe { EVP_EncodedLength(&mut encoded_len, bytes.len()) } {
+        unreachable!("encoded length overflow");
+    }
+    let mut encoded = vec![0u8; encoded_len];
+    let written = unsafe { EVP_EncodeBlock(encoded.as_mut_ptr(), bytes.as_ptr(), bytes.len()) };
+    encoded.truncate(written);
+    while encoded.last() == Some(&b'=') {
+        encoded.pop();
+    }
+    // DON'T PANIC: the Base64 alphabet is ASCII.
+    String::from_utf8(encoded).unwrap()
+}
+
+/// Decodes standard Base64 without padding.
+fn b64_decode(encoded: &str) -> Result<Vec<u8>, Unspecified> {
+    if encoded.is_empty() || encoded.len() % 4 == 1 || encoded.contains('=') {
+        return Err(Unspecified);
+    }
+    let mut padded = String::from(encoded);
+    while padded.len() % 4 != 0 {
+        padded.push('=');
+    }
+
+    let mut max_len = 0;
+    if 1 != unsafe { EVP_DecodedLength(&mut max_len, padded.len()) } {
+        return Err(Unspecified);
+    }
+    let mut decoded = vec![0u8; max_len];
+    let mut decoded_len = 0;
+    if 1 != unsafe {
+        EVP_DecodeBase64(
+            decoded.as_mut_ptr(),
+            &mut decoded_len,
+            decoded.len(),
+            padded.as_ptr(),
+            padded.len(),
+        )
+    } {
+        return Err(Unspecified);
+    }
+    decoded.truncate(decoded_len);
+
+    // Reject non-canonical encodings, whose unused trailing bits are non-zero.
+    if b64_encode(&decoded) != encoded {
+        return Err(Unspecified);
+    }
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::password;
+    use crate::password::{b64_decode, b64_encode, Params};
+    #[cfg(not(feature = "strict-fips"))]
+    use crate::scrypt;
+    use core::num::NonZeroU32;
+
+    #[test]
+    fn password_hash_verify() {
+        let iterations = NonZeroU32::new(1000).unwrap();
+        for params in [
+            Params::Pbkdf2Sha256 { iterations },
+            Params::Pbkdf2Sha512 { iterations },
+            #[cfg(not(feature = "strict-fips"))]
+            Params::Scrypt(scrypt::Params::new(10, 8, 1).unwrap()),
+        ] {
+            let phc = password::hash(&params, b"password").unwrap();
+            assert!(password::verify(&phc, b"password").is_ok());
+            assert!(password::verify(&phc, b"passwore").is_err());
+            assert_eq!(Ok(params), password::params(&phc));
+
+            // A new salt is generated each time.
+            assert_ne!(phc, password::hash(&params, b"password").unwrap());
+        }
+    }
+
+    #[test]
+    fn password_known_answer() {
+        // PBKDF2-HMAC-SHA256, password "password", salt "saltsaltsaltsalt", 1000 iterations.
+        let phc = "$pbkdf2-sha256$i=1000,l=32$c2FsdHNhbHRzYWx0c2FsdA\
+                   $8nX7hwFEzIB8aPajJTYK8weHQc5Ngz0pFVAKvSu4jQA";
+        assert!(password::verify(phc, b"password").is_ok());
+        assert!(password::verify(phc, b"Password").is_err());
+
+        // scrypt, password "password", salt "saltsaltsaltsalt", ln=10, r=8, p=1.
+        let phc = "$scrypt$ln=10,r=8,p=1$c2FsdHNhbHRzYWx0c2FsdA\
+                   $BVMRKqdiVYikKAaPR1wucsKUKvw4TuPLkdEYtoSHas4";
+        #[cfg(not(feature = "strict-fips"))]
+        {
+            assert!(password::verify(phc, b"password").is_ok());
+            assert!(password::verify(phc, b"Password").is_err());
+            assert_eq!(
+                Ok(Params::Scrypt(scrypt::Params::new(10, 8, 1).unwrap())),
+                password::params(phc)
+            );
+        }
+        #[cfg(feature = "strict-fips")]
+        assert!(password::verify(phc, b"password").is_err());
+    }
+
+    #[test]
+    fn password_malformed() {
+        for phc in [
+            "",
+            "$",
+            "pbkdf2-sha256$i=1000$c2FsdHNhbHRzYWx0c2FsdA$8nX7hwFEzIB8aPajJTYK8weHQc5Ngz0pFVAKvSu4jQA",
+            "$pbkdf2-sha1$i=1000$c2FsdHNhbHRzYWx0c2FsdA$8nX7hwFEzIB8aPajJTYK8weHQc5Ngz0pFVAKvSu4jQA",
+            "$pbkdf2-sha256$i=0$c2FsdHNhbHRzYWx0c2FsdA$8nX7hwFEzIB8aPajJTYK8weHQc5Ngz0pFVAKvSu4jQA",
+            "$pbkdf2-sha256$i=01000$c2FsdHNhbHRzYWx0c2FsdA$8nX7hwFEzIB8aPajJTYK8weHQc5Ngz0pFVAKvSu4jQA",
+            "$pbkdf2-sha256$i=1000,i=1000$c2FsdHNhbHRzYWx0c2FsdA$8nX7hwFEzIB8aPajJTYK8weHQc5Ngz0pFVAKvSu4jQA",
+            "$pbkdf2-sha256$i=1000,l=31$c2FsdHNhbHRzYWx0c2FsdA$8nX7hwFEzIB8aPajJTYK8weHQc5Ngz0pFVAKvSu4jQA",
+            "$pbkdf2-sha256$i=1000,r=8$c2FsdHNhbHRzYWx0c2FsdA$8nX7hwFEzIB8aPajJTYK8weHQc5Ngz0pFVAKvSu4jQA",
+            "$pbkdf2-sha256$i=1000$c2FsdA$8nX7hwFEzIB8aPajJTYK8weHQc5Ngz0pFVAKvSu4jQA",
+            "$pbkdf2-sha256$i=1000$c2FsdHNhbHRzYWx0c2FsdA==$8nX7hwFEzIB8aPajJTYK8weHQc5Ngz0pFVAKvSu4jQA",
+            "$pbkdf2-sha256$i=1000$c2FsdHNhbHRzYWx0c2FsdA$8nX7hwFEzIB8aPajJTYK8weHQc5Ngz0pFVAKvSu4jQA$",
+            "$scrypt$ln=10,r=8$c2FsdHNhbHRzYWx0c2FsdA$BVMRKqdiVYikKAaPR1wucsKUKvw4TuPLkdEYtoSHas4",
+            "$scrypt$ln=300,r=8,p=1$c2FsdHNhbHRzYWx0c2FsdA$BVMRKqdiVYikKAaPR1wucsKUKvw4TuPLkdEYtoSHas4",
+        ] {
+            assert!(password::verify(phc, b"password").is_err(), "{phc}");
+        }
+    }
+
+    #[test]
+    fn password_cost_limits() {
+        const SALT_AND_HASH: &str =
+            "$c2FsdHNhbHRzYWx0c2FsdA$8nX7hwFEzIB8aPajJTYK8weHQc5Ngz0pFVAKvSu4jQA";
+        for (params, accepted) in [
+            ("$pbkdf2-sha256$i=10000000", true),
+            ("$pbkdf2-sha256$i=10000001", false),
+            ("$pbkdf2-sha512$i=10000000", true),
+            ("$pbkdf2-sha512$i=10000001", false),
+            #[cfg(not(feature = "strict-fips"))]
+            ("$scrypt$ln=20,r=8,p=1", true),
+            ("$scrypt$ln=21,r=8,p=1", false),
+            ("$scrypt$ln=20,r=9,p=1", false),
+            #[cfg(not(feature = "strict-fips"))]
+            ("$scrypt$ln=10,r=32,p=16", true),
+            ("$scrypt$ln=10,r=33,p=1", false),
+            ("$scrypt$ln=10,r=1,p=17", false),
+            ("$scrypt$ln=10,r=32768,p=32767", false),
+            ("$scrypt$ln=10,r=32768,p=32768", false),
+            ("$scrypt$ln=10,r=1073741824,p=1", false),
+        ] {
+            let phc = format!("{params}{SALT_AND_HASH}");
+            assert_eq!(accepted, password::params(&phc).is_ok(), "{phc}");
+        }
+    }
+
+    #[test]
+    fn b64() {
+        for (bytes, encoded) in [
+            (&b"f"[..], "Zg"),
+            (b"fo", "Zm8"),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg"),
+            (b"fooba", "Zm9vYmE"),
+            (b"foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(encoded, b64_encode(bytes));
+            assert_eq!(Ok(bytes.to_vec()), b64_decode(encoded));
+        }
+        assert!(b64_decode("").is_err());
+        assert!(b64_decode("Z").is_err());
+        assert!(b64_decode("Zh").is_err());
+        assert!(b64_decode("Zg==").is_err());
+        assert!(b64_decode("Zm9v!").is_err());
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! PKCS#12 (`.p12`/`.pfx`) archives, as described in [RFC 7292].
+//!
+//! PKCS#12 archives bundle a private key with its certificate and the certificate's chain,
+//! protected by a password. Archives produced by OpenSSL 3 (PBES2 with AES and a SHA-256 MAC),
+//! as well as those produced by older tools and Windows (`pbeWithSHAAnd3-KeyTripleDES-CBC` and
+//! `pbeWithSHAAnd40BitRC2-CBC`), are supported.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::{pkcs12, signature};
+//!
+//! let archive = std::fs::read("server.p12")?;
+//! let contents = pkcs12::parse(&archive, "test")?;
+//!
+//! let private_key = contents.private_key().ok_or("no private key")?;
+//! let key_pair = signature::EcdsaKeyPair::from_pkcs8(
+//!     &signature::ECDSA_P256_SHA256_ASN1_SIGNING,
+//!     private_key.as_ref(),
+//! )?;
+//! let certificate = contents.certificate().ok_or("no certificate")?;
+//! let chain = contents.ca_certificates();
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [RFC 7292]: https://tools.ietf.org/html/rfc7292
+
+use crate::encoding::CertificateDer;
+use crate::error::{KeyRejected, Unspecified};
+use crate::pkcs8::{Document, Version};
+use crate::ptr::LcPtr;
+use aws_lc::{d2i_PKCS12, i2d_X509, PKCS12_parse, EVP_PKEY, X509};
+#[cfg(not(feature = "fips"))]
+use aws_lc::{OPENSSL_sk_free, OPENSSL_sk_pop, OPENSSL_STACK};
+// The FIPS module predates the `OPENSSL_sk_*` names of the stack functions.
+#[cfg(feature = "fips")]
+use aws_lc::{sk_free as OPENSSL_sk_free, sk_pop as OPENSSL_sk_pop, _STACK as OPENSSL_STACK};
+use core::fmt::{self, Debug, Formatter};
+use core::ptr::null_mut;
+use std::ffi::CString;
+
+/// The contents of a PKCS#12 archive.
+pub struct Contents {
+    private_key: Option<Document>,
+    certificate: Option<CertificateDer<'static>>,
+    ca_certificates: Vec<CertificateDer<'static>>,
+}
+
+impl Contents {
+    /// The private key, as an unencrypted PKCS#8 v1 document for use with the `from_pkcs8`
+    /// function of the corresponding key pair type.
+    #[must_use]
+    pub fn private_key(&self) -> Option<&Document> {
+        self.private_key.as_ref()
+    }
+
+    /// The certificate for the private key.
+    #[must_use]
+    pub fn certificate(&self) -> Option<&CertificateDer<'static>> {
+        self.certificate.as_ref()
+    }
+
+    /// The remaining certificates, typically the chain of the certificate, in the order they
+    /// appear in the archive.
+    #[must_use]
+    pub fn ca_certificates(&self) -> &[CertificateDer<'static>] {
+        &self.ca_certificates
+    }
+}
+
+impl Debug for Contents {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Contents")
+            .field("private_key", &self.private_key.as_ref().map(|_| ".."))
+            .field("certificate", &self.certificate)
+            .field("ca_certificates", &self.ca_certificates)
+            .finish()
+    }
+}
+
+/// Decrypts and parses the DER-encoded PKCS#12 archive `der` with `password`.
+///
+/// The archive's MAC is verified with `password` before anything is decrypted.
+///
+/// # Errors
+/// `error::KeyRejected` if `der` is not a PKCS#12 archive, `password` is incorrect or contains
+/// a NUL character, or the archive uses an unsupported encryption scheme or key type.
+pub fn parse(der: &[u8], password: &str) -> Result<Contents, KeyRejected> {
+    let password = CString::new(password).map_err(|_| KeyRejected::invalid_encoding())?;
+
+    let mut input = der.as_ptr();
+    let p12 = LcPtr::new(unsafe { d2i_PKCS12(null_mut(), &mut input, der.len()) })
+        .map_err(|()| KeyRejected::invalid_encoding())?;
+    if input != der.as_ptr().wrapping_add(der.len()) {
+        return Err(KeyRejected::invalid_encoding());
+    }
+
+    let mut private_key = null_mut::<EVP_PKEY>();
+    let mut certificate = null_mut::<X509>();
+    let mut ca_certificates = null_mut();
+    if 1 != unsafe {
+        PKCS12_parse(
+            *p12,
+            password.as_ptr(),
+            &mut private_key,
+            &mut certificate,
+            &mut ca_certificates,
+        )
+    } {
+        return Err(KeyRejected::invalid_encoding());
+    }
+    // Take ownership of every output before anything can fail.
+    let private_key = LcPtr::new(private_key).ok();
+    let certificate = LcPtr::new(certificate).ok();
+    let ca_certificates = unsafe { take_certificates(ca_certificates.cast()) };
+
+    Ok(Contents {
+        private_key: private_key
+            .map(|key| key.marshall_private_key(Version::V1))
+            .transpose()?,
+        certificate: certificate
+            .map(|cert| encode_certificate(&cert))
+            .transpose()?,
+        ca_certificates: ca_certificates
+            .iter()
+            .map(encode_certificate)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+// Frees `stack`, returning the certificates it contained in order.
+unsafe fn take_certificates(stack: *mut OPENSSL_STACK) -> Vec<LcPtr<X509>> {
+    let mut certificates = Vec::new();
+    if stack.is_null() {
+        return certificates;
+    }
+    while let Ok(certificate) = LcPtr::new(OPENSSL_sk_pop(stack).cast::<X509>()) {
+        certificates.push(certificate);
+    }
+    OPENSSL_sk_free(stack);
+    certificates.reverse();
+    certificates
+}
+
+fn encode_certificate(certificate: &LcPtr<X509>) -> Result<CertificateDer<'static>, Unspecified> {
+    let mut der = null_mut::<u8>();
+    let len = unsafe { i2d_X509(**certificate, &mut der) };
+    let der = LcPtr::new(der)?;
+    let len = usize::try_from(len)?;
+    Ok(CertificateDer::new(unsafe { der.as_slice(len) }.to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use crate::signature::{self, KeyPair};
+
+    const ARCHIVE: &[u8] = include_bytes!("../tests/data/pkcs12_test.p12");
+    const LEGACY_ARCHIVE: &[u8] = include_bytes!("../tests/data/pkcs12_test_legacy.p12");
+    const CA: &[u8] = include_bytes!("../tests/data/x509_test_ca.der");
+    const LEAF: &[u8] = include_bytes!("../tests/data/x509_test_leaf.der");
+    const LEAF_KEY: &[u8] = include_bytes!("../tests/data/x509_test_leaf_key.p8");
+
+    #[test]
+    fn test_parse() {
+        let expected_key_pair = signature::EcdsaKeyPair::from_pkcs8(
+            &signature::ECDSA_P256_SHA256_ASN1_SIGNING,
+            LEAF_KEY,
+        )
+        .unwrap();
+
+        for archive in [ARCHIVE, LEGACY_ARCHIVE] {
+            let contents = parse(archive, "test").unwrap();
+            let key_pair = signature::EcdsaKeyPair::from_pkcs8(
+                &signature::ECDSA_P256_SHA256_ASN1_SIGNING,
+                contents.private_key().unwrap().as_ref(),
+            )
+            .unwrap();
+            assert_eq!(
+                expected_key_pair.public_key().as_ref(),
+                key_pair.public_key().as_ref()
+            );
+            assert_eq!(LEAF, contents.certificate().unwrap().as_ref());
+            assert_eq!(1, contents.ca_certificates().len());
+            assert_eq!(CA, contents.ca_certificates()[0].as_ref());
+            assert!(!format!("{contents:?}").contains("Document"));
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse(ARCHIVE, "wrong").is_err());
+        assert!(parse(ARCHIVE, "").is_err());
+        assert!(parse(ARCHIVE, "te\0st").is_err());
+        assert!(parse(&ARCHIVE[..ARCHIVE.len() - 1], "test").is_err());
+        let mut trailing = ARCHIVE.to_vec();
+        trailing.push(0);
+        assert!(parse(&trailing, "test").is_err());
+        assert!(parse(LEAF, "test").is_err());
+    }
+}
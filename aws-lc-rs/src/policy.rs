@@ -0,0 +1,305 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Process-wide restrictions on the algorithms *aws-lc-rs* will use.
+//!
+//! A [`CryptoPolicy`] lists algorithms and parameters an application does not allow, for
+//! organizations that enforce cryptographic standards beyond those of FIPS. Once
+//! [installed](CryptoPolicy::install), it applies to the whole process.
+//!
+//! The installed policy applies to signing and signature verification, including of X.509
+//! certificates, CRLs, CMS signed data and OCSP responses, to [`digest`] and to [`hmac`].
+//! Operations that it refuses fail with their usual error, e.g. `error::Unspecified` from
+//! signature verification, so that their signatures stay compatible with *ring*. Run them with
+//! [`checked`] to get the distinct [`Denied`] error instead. Operations that cannot fail, such
+//! as [`digest::digest`] and [`hmac::Key::new`], panic when the policy refuses them; check the
+//! policy up front with [`check_digest`] to avoid this.
+//!
+//! Curves smaller than P-256, such as P-192, are not implemented by *aws-lc-rs* and need no
+//! policy to deny.
+//!
+//! # Example
+//!
+//! ```
+//! use aws_lc_rs::rand::SystemRandom;
+//! use aws_lc_rs::rsa::KeySize;
+//! use aws_lc_rs::signature::{self, RsaKeyPair};
+//! use aws_lc_rs::{digest, policy};
+//!
+//! let policy = policy::CryptoPolicy::new()
+//!     .deny_digest(&digest::SHA1_FOR_LEGACY_USE_ONLY)
+//!     .min_rsa_modulus_bits(3072);
+//!
+//! assert!(policy.check_digest(&digest::SHA1_FOR_LEGACY_USE_ONLY).is_err());
+//! assert!(policy.check_digest(&digest::SHA256).is_ok());
+//! assert!(policy.check_rsa_modulus_bits(2048).is_err());
+//!
+//! // Applies `policy` to all later operations in this process.
+//! policy.install();
+//!
+//! let key_pair = RsaKeyPair::generate(KeySize::Rsa2048)?;
+//! let mut signature = vec![0; key_pair.public_modulus_len()];
+//! let denied = policy::checked(|| {
+//!     key_pair.sign(
+//!         &signature::RSA_PKCS1_SHA256,
+//!         &SystemRandom::new(),
+//!         b"message",
+//!         &mut signature,
+//!     )
+//! })
+//! .unwrap_err();
+//! assert_eq!("RsaModulusTooSmall", denied.to_string());
+//! # policy::uninstall();
+//! # Ok::<(), aws_lc_rs::error::Unspecified>(())
+//! ```
+//!
+//! [`hmac`]: crate::hmac
+//! [`hmac::Key::new`]: crate::hmac::Key::new
+
+use crate::digest;
+use crate::error::Unspecified;
+use core::cell::Cell;
+use core::fmt;
+use std::error::Error;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{PoisonError, RwLock};
+
+static INSTALLED: RwLock<Option<CryptoPolicy>> = RwLock::new(None);
+
+// Whether `INSTALLED` holds a policy, so that operations skip the lock when none is installed.
+static IS_INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    // The last refusal of an operation on this thread, reported by `checked`.
+    static LAST_DENIAL: Cell<Option<Denied>> = const { Cell::new(None) };
+}
+
+/// A set of algorithms and parameters that are not allowed.
+///
+/// A new policy allows everything; each builder method narrows it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CryptoPolicy {
+    denied_digests: Vec<&'static digest::Algorithm>,
+    min_rsa_modulus_bits: u32,
+}
+
+impl CryptoPolicy {
+    /// A policy that allows all algorithms.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Denies the use of `algorithm`.
+    #[must_use]
+    pub fn deny_digest(mut self, algorithm: &'static digest::Algorithm) -> Self {
+        if !self.denied_digests.contains(&algorithm) {
+            self.denied_digests.push(algorithm);
+        }
+        self
+    }
+
+    /// Denies RSA keys with a modulus shorter than `bits`.
+    #[must_use]
+    pub fn min_rsa_modulus_bits(mut self, bits: u32) -> Self {
+        self.min_rsa_modulus_bits = bits;
+        self
+    }
+
+    /// Checks whether this policy allows `algorithm`.
+    ///
+    /// # Errors
+    /// `Denied` if the policy denies `algorithm`.
+    pub fn check_digest(&self, algorithm: &'static digest::Algorithm) -> Result<(), Denied> {
+        if self.denied_digests.contains(&algorithm) {
+            return Err(Denied::digest());
+        }
+        Ok(())
+    }
+
+    /// Checks whether this policy allows RSA keys with a modulus of `bits` bits.
+    ///
+    /// # Errors
+    /// `Denied` if the modulus is shorter than the policy allows.
+    pub fn check_rsa_modulus_bits(&self, bits: u32) -> Result<(), Denied> {
+        if bits < self.min_rsa_modulus_bits {
+            return Err(Denied::rsa_modulus_too_small());
+        }
+        Ok(())
+    }
+
+    /// Installs this policy for the whole process, replacing any policy installed before.
+    pub fn install(self) {
+        let mut installed = INSTALLED.write().unwrap_or_else(PoisonError::into_inner);
+        *installed = Some(self);
+        IS_INSTALLED.store(true, Ordering::Release);
+    }
+
+    /// Returns the policy installed for the process, if any.
+    #[must_use]
+    pub fn installed() -> Option<Self> {
+        INSTALLED
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+/// Removes the installed policy, allowing all algorithms again.
+pub fn uninstall() {
+    let mut installed = INSTALLED.write().unwrap_or_else(PoisonError::into_inner);
+    *installed = None;
+    IS_INSTALLED.store(false, Ordering::Release);
+}
+
+/// Runs `f` and returns its result, or `Denied` if the installed policy refused an operation
+/// that `f` performed on the current thread.
+///
+/// Refused operations fail with their usual error type, which this tells apart from other
+/// failures.
+///
+/// # Errors
+/// `Denied` if the installed policy refused an operation performed by `f`.
+pub fn checked<T, F>(f: F) -> Result<T, Denied>
+where
+    F: FnOnce() -> T,
+{
+    let outer = LAST_DENIAL.with(Cell::take);
+    let result = f();
+    let denial = LAST_DENIAL.with(Cell::take);
+    // A refusal inside a nested call is also reported by the enclosing one.
+    LAST_DENIAL.with(|last| last.set(outer.or(denial)));
+    match denial {
+        Some(denied) => Err(denied),
+        None => Ok(result),
+    }
+}
+
+/// Checks whether the installed policy allows `algorithm`.
+///
+/// # Errors
+/// `Denied` if the installed policy denies `algorithm`.
+pub fn check_digest(algorithm: &'static digest::Algorithm) -> Result<(), Denied> {
+    with_installed(|policy| policy.check_digest(algorithm))
+}
+
+/// Checks whether the installed policy allows RSA keys with a modulus of `bits` bits.
+///
+/// # Errors
+/// `Denied` if the modulus is shorter than the installed policy allows.
+pub fn check_rsa_modulus_bits(bits: u32) -> Result<(), Denied> {
+    with_installed(|policy| policy.check_rsa_modulus_bits(bits))
+}
+
+// Checks the installed policy on behalf of an operation that uses `algorithm`, recording a
+// refusal for `checked`.
+pub(crate) fn enforce_digest(algorithm: &'static digest::Algorithm) -> Result<(), Denied> {
+    record(check_digest(algorithm))
+}
+
+// Checks the installed policy on behalf of an operation with an RSA key of `bits` bits,
+// recording a refusal for `checked`.
+pub(crate) fn enforce_rsa_modulus_bits(bits: u32) -> Result<(), Denied> {
+    record(check_rsa_modulus_bits(bits))
+}
+
+fn record(result: Result<(), Denied>) -> Result<(), Denied> {
+    if let Err(denied) = result {
+        LAST_DENIAL.with(|last| last.set(Some(denied)));
+    }
+    result
+}
+
+fn with_installed<F>(check: F) -> Result<(), Denied>
+where
+    F: FnOnce(&CryptoPolicy) -> Result<(), Denied>,
+{
+    if !IS_INSTALLED.load(Ordering::Acquire) {
+        return Ok(());
+    }
+    match INSTALLED
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()
+    {
+        Some(policy) => check(policy),
+        None => Ok(()),
+    }
+}
+
+/// An operation was refused because the installed [`CryptoPolicy`] does not allow it.
+///
+/// The `Display` implementation and `<Denied as Error>::description()` name the kind of
+/// restriction that applied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Denied(&'static str);
+
+impl Denied {
+    pub(crate) fn digest() -> Self {
+        Denied("DigestDenied")
+    }
+
+    pub(crate) fn rsa_modulus_too_small() -> Self {
+        Denied("RsaModulusTooSmall")
+    }
+
+    /// The value returned from `<Self as std::error::Error>::description()`
+    #[must_use]
+    pub fn description_(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl fmt::Display for Denied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.description_())
+    }
+}
+
+impl Error for Denied {
+    fn description(&self) -> &str {
+        self.description_()
+    }
+}
+
+impl From<Denied> for Unspecified {
+    fn from(_: Denied) -> Self {
+        Unspecified
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::digest;
+    use crate::policy::{CryptoPolicy, Denied};
+
+    #[test]
+    fn test_crypto_policy() {
+        let policy = CryptoPolicy::new();
+        assert!(policy
+            .check_digest(&digest::SHA1_FOR_LEGACY_USE_ONLY)
+            .is_ok());
+        assert!(policy.check_rsa_modulus_bits(1024).is_ok());
+
+        let policy = policy
+            .deny_digest(&digest::SHA1_FOR_LEGACY_USE_ONLY)
+            .deny_digest(&digest::SHA1_FOR_LEGACY_USE_ONLY)
+            .deny_digest(&digest::SHA224)
+            .min_rsa_modulus_bits(2048);
+        assert_eq!(
+            Err(Denied::digest()),
+            policy.check_digest(&digest::SHA1_FOR_LEGACY_USE_ONLY)
+        );
+        assert_eq!(Err(Denied::digest()), policy.check_digest(&digest::SHA224));
+        assert!(policy.check_digest(&digest::SHA256).is_ok());
+        assert_eq!(
+            Err(Denied::rsa_modulus_too_small()),
+            policy.check_rsa_modulus_bits(2047)
+        );
+        assert!(policy.check_rsa_modulus_bits(2048).is_ok());
+        assert_eq!(
+            "RsaModulusTooSmall",
+            Denied::rsa_modulus_too_small().to_string()
+        );
+    }
+}
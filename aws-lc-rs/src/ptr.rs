@@ -5,11 +5,20 @@ use core::ops::Deref;
 use std::ops::DerefMut;
 
 use aws_lc::{
-    BN_free, ECDSA_SIG_free, EC_GROUP_free, EC_KEY_free, EC_POINT_free, EVP_AEAD_CTX_free,
-    EVP_CIPHER_CTX_free, EVP_PKEY_CTX_free, EVP_PKEY_free, OPENSSL_free, RSA_free, BIGNUM,
-    ECDSA_SIG, EC_GROUP, EC_KEY, EC_POINT, EVP_AEAD_CTX, EVP_CIPHER_CTX, EVP_PKEY, EVP_PKEY_CTX,
-    RSA,
+    ASN1_OBJECT_free, ASN1_STRING_free, BASIC_CONSTRAINTS_free, BIO_free, BN_CTX_free,
+    BN_GENCB_free, BN_free, CMAC_CTX_free, DH_free, ECDSA_SIG_free, EC_GROUP_free, EC_KEY_free,
+    EC_POINT_free, EVP_AEAD_CTX_free, EVP_CIPHER_CTX_free, EVP_PKEY_CTX_free, EVP_PKEY_free,
+    GENERAL_NAMES_free, GENERAL_NAME_free, OPENSSL_free, PKCS12_free, RSA_free, X509_CRL_free,
+    X509_EXTENSION_free, X509_NAME_free, X509_REQ_free, X509_STORE_CTX_free, X509_STORE_free,
+    X509_free, ASN1_OBJECT, ASN1_STRING, BASIC_CONSTRAINTS, BIGNUM, BIO, BN_CTX, BN_GENCB,
+    CMAC_CTX, DH, ECDSA_SIG, EC_GROUP, EC_KEY, EC_POINT, EVP_AEAD_CTX, EVP_CIPHER_CTX, EVP_PKEY,
+    EVP_PKEY_CTX, GENERAL_NAME, GENERAL_NAMES, PKCS12, RSA, X509, X509_CRL, X509_EXTENSION,
+    X509_NAME, X509_REQ, X509_STORE, X509_STORE_CTX,
 };
+#[cfg(not(feature = "fips"))]
+use aws_lc::{CTR_DRBG_free, CTR_DRBG_STATE};
+#[cfg(all(any(feature = "x509", feature = "pkcs12"), not(feature = "fips")))]
+use aws_lc::{OPENSSL_sk_free, OPENSSL_STACK};
 
 use mirai_annotations::verify_unreachable;
 
@@ -212,11 +221,33 @@ create_pointer!(EC_POINT, EC_POINT_free);
 create_pointer!(EC_KEY, EC_KEY_free);
 create_pointer!(ECDSA_SIG, ECDSA_SIG_free);
 create_pointer!(BIGNUM, BN_free);
+create_pointer!(BN_CTX, BN_CTX_free);
+create_pointer!(BN_GENCB, BN_GENCB_free);
 create_pointer!(EVP_PKEY, EVP_PKEY_free);
 create_pointer!(EVP_PKEY_CTX, EVP_PKEY_CTX_free);
 create_pointer!(RSA, RSA_free);
 create_pointer!(EVP_AEAD_CTX, EVP_AEAD_CTX_free);
 create_pointer!(EVP_CIPHER_CTX, EVP_CIPHER_CTX_free);
+create_pointer!(CMAC_CTX, CMAC_CTX_free);
+#[cfg(not(feature = "fips"))]
+create_pointer!(CTR_DRBG_STATE, CTR_DRBG_free);
+create_pointer!(DH, DH_free);
+create_pointer!(X509, X509_free);
+create_pointer!(PKCS12, PKCS12_free);
+create_pointer!(GENERAL_NAMES, GENERAL_NAMES_free);
+create_pointer!(BIO, BIO_free);
+create_pointer!(X509_STORE, X509_STORE_free);
+create_pointer!(X509_STORE_CTX, X509_STORE_CTX_free);
+#[cfg(all(any(feature = "x509", feature = "pkcs12"), not(feature = "fips")))]
+create_pointer!(OPENSSL_STACK, OPENSSL_sk_free);
+create_pointer!(GENERAL_NAME, GENERAL_NAME_free);
+create_pointer!(ASN1_STRING, ASN1_STRING_free);
+create_pointer!(ASN1_OBJECT, ASN1_OBJECT_free);
+create_pointer!(X509_NAME, X509_NAME_free);
+create_pointer!(X509_EXTENSION, X509_EXTENSION_free);
+create_pointer!(X509_REQ, X509_REQ_free);
+create_pointer!(BASIC_CONSTRAINTS, BASIC_CONSTRAINTS_free);
+create_pointer!(X509_CRL, X509_CRL_free);
 
 #[cfg(test)]
 mod tests {
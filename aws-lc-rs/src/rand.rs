@@ -15,6 +15,14 @@
 //! can be replayed. Following this pattern also may help with sandboxing
 //! (seccomp filters on Linux in particular). See `SystemRandom`'s
 //! documentation for more details.
+//!
+//! # Entropy source
+//!
+//! *AWS-LC* seeds its generator from the operating system, e.g. `getrandom` on Linux or
+//! `BCryptGenRandom` on Windows, and falls back to `/dev/urandom` where needed, unless the
+//! `fips-cpu-jitter-entropy` feature selects the FIPS module's CPU Jitter entropy source. It
+//! offers no interface for registering a different entropy source at runtime, so this crate
+//! cannot provide one; targets without an operating system entropy source are not supported.
 
 //! # Example
 //! ```
@@ -31,11 +39,36 @@
 //! // Using `rand::generate`
 //! let random_array = rand::generate(&rng).unwrap();
 //! let more_rand_bytes: [u8; 64] = random_array.expose();
+//!
+//! // Using `rand::random_u64` and `rand::random_range`
+//! let random_u64 = rand::random_u64(&rng).unwrap();
+//! let die_roll = rand::random_range(&rng, 1..7).unwrap();
+//! assert!((1..7).contains(&die_roll));
 //! ```
 use crate::error::Unspecified;
 use crate::fips::indicator_check;
+#[cfg(not(feature = "fips"))]
+use crate::ptr::LcPtr;
 use aws_lc::RAND_bytes;
+#[cfg(not(feature = "fips"))]
+use aws_lc::{
+    CTR_DRBG_generate, CTR_DRBG_new, CTR_DRBG_reseed, RAND_get_system_entropy_for_custom_prng,
+    CTR_DRBG_ENTROPY_LEN, CTR_DRBG_MAX_GENERATE_LENGTH, CTR_DRBG_STATE,
+};
+#[cfg(not(feature = "fips"))]
+use core::cell::{Cell, RefCell};
 use core::fmt::Debug;
+#[cfg(not(feature = "fips"))]
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+#[cfg(not(feature = "fips"))]
+use core::ptr::null;
+#[cfg(not(feature = "fips"))]
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+#[cfg(not(feature = "fips"))]
+use std::sync::{Mutex, Once};
+#[cfg(not(feature = "fips"))]
+use zeroize::Zeroizing;
 
 /// A secure random number generator.
 pub trait SecureRandom: sealed::SecureRandom {
@@ -44,6 +77,14 @@ pub trait SecureRandom: sealed::SecureRandom {
     /// # Errors
     /// `error::Unspecified` if unable to fill `dest`.
     fn fill(&self, dest: &mut [u8]) -> Result<(), Unspecified>;
+
+    /// Fills `dest`, which need not be initialized, with random bytes, and returns it as an
+    /// initialized slice. This avoids zero-initializing buffers that will be overwritten.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if unable to fill `dest`.
+    fn fill_uninit<'a>(&self, dest: &'a mut [MaybeUninit<u8>])
+        -> Result<&'a mut [u8], Unspecified>;
 }
 
 impl<T> SecureRandom for T
@@ -54,6 +95,14 @@ where
     fn fill(&self, dest: &mut [u8]) -> Result<(), Unspecified> {
         self.fill_impl(dest)
     }
+
+    #[inline]
+    fn fill_uninit<'a>(
+        &self,
+        dest: &'a mut [MaybeUninit<u8>],
+    ) -> Result<&'a mut [u8], Unspecified> {
+        self.fill_uninit_impl(dest)
+    }
 }
 
 /// A random value constructed from a `SecureRandom` that hasn't been exposed
@@ -83,12 +132,83 @@ pub fn generate<T: RandomlyConstructable>(
     Ok(Random(r))
 }
 
+/// Generates a random `u32` using `rng`.
+///
+/// # Errors
+/// `error::Unspecified` if unable to fill buffer.
+#[inline]
+pub fn random_u32(rng: &dyn SecureRandom) -> Result<u32, Unspecified> {
+    Ok(u32::from_ne_bytes(generate(rng)?.expose()))
+}
+
+/// Generates a random `u64` using `rng`.
+///
+/// # Errors
+/// `error::Unspecified` if unable to fill buffer.
+#[inline]
+pub fn random_u64(rng: &dyn SecureRandom) -> Result<u64, Unspecified> {
+    Ok(u64::from_ne_bytes(generate(rng)?.expose()))
+}
+
+// The probability of a candidate being rejected is below one half, so exhausting this many
+// attempts indicates a broken `SecureRandom`.
+const MAX_RANGE_ATTEMPTS: usize = 128;
+
+/// Generates a random `u64` uniformly distributed in `range` using `rng`.
+///
+/// Unlike reducing a random value modulo the size of the range, which favours the smaller
+/// values, the result is unbiased: candidates are masked to the bit length of the range and
+/// those outside of it are rejected.
+///
+/// # Errors
+/// `error::Unspecified` if `range` is empty or if unable to fill buffer.
+pub fn random_range(
+    rng: &dyn SecureRandom,
+    range: core::ops::Range<u64>,
+) -> Result<u64, Unspecified> {
+    let span = range.end.checked_sub(range.start).ok_or(Unspecified)?;
+    if span == 0 {
+        return Err(Unspecified);
+    }
+    let mask = u64::MAX
+        .checked_shr((span - 1).leading_zeros())
+        .unwrap_or(0);
+    for _ in 0..MAX_RANGE_ATTEMPTS {
+        let candidate = random_u64(rng)? & mask;
+        if candidate < span {
+            return Ok(range.start + candidate);
+        }
+    }
+    Err(Unspecified)
+}
+
 pub(crate) mod sealed {
     use crate::error;
+    use core::mem::MaybeUninit;
 
     pub trait SecureRandom: core::fmt::Debug {
         /// Fills `dest` with random bytes.
         fn fill_impl(&self, dest: &mut [u8]) -> Result<(), error::Unspecified>;
+
+        /// Fills the uninitialized `dest` with random bytes. By default `dest` is zeroed and
+        /// then filled by `fill_impl`.
+        fn fill_uninit_impl<'a>(
+            &self,
+            dest: &'a mut [MaybeUninit<u8>],
+        ) -> Result<&'a mut [u8], error::Unspecified> {
+            for byte in dest.iter_mut() {
+                byte.write(0);
+            }
+            // Safety: every byte of `dest` was initialized above.
+            let dest = unsafe { assume_init_mut(dest) };
+            self.fill_impl(dest)?;
+            Ok(dest)
+        }
+    }
+
+    // Safety: every byte of `dest` must be initialized.
+    pub(super) unsafe fn assume_init_mut(dest: &mut [MaybeUninit<u8>]) -> &mut [u8] {
+        core::slice::from_raw_parts_mut(dest.as_mut_ptr().cast::<u8>(), dest.len())
     }
 
     pub trait RandomlyConstructable: Sized {
@@ -147,6 +267,14 @@ impl sealed::SecureRandom for SystemRandom {
     fn fill_impl(&self, dest: &mut [u8]) -> Result<(), Unspecified> {
         fill(dest)
     }
+
+    #[inline]
+    fn fill_uninit_impl<'a>(
+        &self,
+        dest: &'a mut [MaybeUninit<u8>],
+    ) -> Result<&'a mut [u8], Unspecified> {
+        fill_uninit(dest)
+    }
 }
 
 /// Fills `dest` with random bytes.
@@ -163,12 +291,457 @@ pub fn fill(dest: &mut [u8]) -> Result<(), Unspecified> {
     Ok(())
 }
 
+/// Fills `dest`, which need not be initialized, with random bytes, and returns it as an
+/// initialized slice.
+///
+// # FIPS
+// Use this for retrieving random bytes or [`SystemRandom`].
+//
+/// # Errors
+/// `error::Unspecified` if unable to fill `dest`.
+pub fn fill_uninit(dest: &mut [MaybeUninit<u8>]) -> Result<&mut [u8], Unspecified> {
+    // `RAND_bytes` only writes to `dest`.
+    if 1 != indicator_check!(unsafe { RAND_bytes(dest.as_mut_ptr().cast::<u8>(), dest.len()) }) {
+        return Err(Unspecified);
+    }
+    // Safety: `RAND_bytes` initialized every byte of `dest`.
+    Ok(unsafe { sealed::assume_init_mut(dest) })
+}
+
+/// A `SystemRandom` that implements the `RngCore` and `CryptoRng` traits of `rand_core` 0.6,
+/// so that randomness from *AWS-LC* can be used with the `rand` ecosystem, e.g. for shuffling
+/// or generating UUIDs.
+///
+/// ```
+/// use aws_lc_rs::rand::RandCoreAdapter;
+/// use rand_core::RngCore;
+///
+/// let mut rng = RandCoreAdapter::default();
+/// let jitter_ms = rng.next_u32() % 100;
+/// ```
+#[cfg(feature = "rand-core")]
+#[derive(Clone, Debug, Default)]
+pub struct RandCoreAdapter(SystemRandom);
+
+#[cfg(feature = "rand-core")]
+impl RandCoreAdapter {
+    /// Constructs a new `RandCoreAdapter`.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+// The `rand_core::Error` code reported when *AWS-LC* fails to generate random bytes.
+#[cfg(feature = "rand-core")]
+const RAND_CORE_ERROR_CODE: core::num::NonZeroU32 =
+    match core::num::NonZeroU32::new(rand_core::Error::CUSTOM_START) {
+        Some(code) => code,
+        None => panic!(),
+    };
+
+#[cfg(feature = "rand-core")]
+impl rand_core::RngCore for RandCoreAdapter {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+
+    /// # Panics
+    /// Panics if *AWS-LC* is unable to produce random bytes.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.try_fill_bytes(dest)
+            .expect("AWS-LC failed to generate random bytes");
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        fill(dest).map_err(|Unspecified| rand_core::Error::from(RAND_CORE_ERROR_CODE))
+    }
+}
+
+#[cfg(feature = "rand-core")]
+impl rand_core::CryptoRng for RandCoreAdapter {}
+
+/// Fills `dest` with random bytes from *AWS-LC*, for use as the custom implementation of
+/// `getrandom` 0.2.
+///
+/// On targets without an operating system random number generator, `getrandom` calls the
+/// registered custom implementation, so crates depending on `getrandom`, like `uuid` or `rand`,
+/// draw their randomness from the same generator as aws-lc-rs. On other targets `getrandom`
+/// keeps using the operating system and this function is unused.
+///
+/// Only one crate in a build may register a custom implementation, so this crate does not
+/// register it itself; the binary does, with `getrandom`'s `custom` feature enabled:
+///
+/// ```
+/// getrandom::register_custom_getrandom!(aws_lc_rs::rand::getrandom_custom);
+/// # fn main() {}
+/// ```
+///
+/// # Errors
+/// `getrandom::Error` with the code `getrandom::Error::CUSTOM_START` if unable to fill `dest`.
+#[cfg(feature = "getrandom-custom")]
+pub fn getrandom_custom(dest: &mut [u8]) -> Result<(), getrandom::Error> {
+    fill(dest).map_err(|Unspecified| getrandom::Error::from(GETRANDOM_ERROR_CODE))
+}
+
+// The `getrandom::Error` code reported when *AWS-LC* fails to generate random bytes.
+#[cfg(feature = "getrandom-custom")]
+const GETRANDOM_ERROR_CODE: core::num::NonZeroU32 =
+    match core::num::NonZeroU32::new(getrandom::Error::CUSTOM_START) {
+        Some(code) => code,
+        None => panic!(),
+    };
+
+/// A CTR-DRBG (NIST SP 800-90A) owned by the caller, seeded directly from the operating
+/// system's entropy source and reseeded only on request or after
+/// `Drbg::RESEED_INTERVAL` generate calls.
+///
+/// *AWS-LC*'s own per-thread generators, used by `SystemRandom` and `rand::fill`, reseed
+/// themselves periodically and when a `fork()` is detected, but cannot be reseeded on demand.
+/// `Drbg` is for callers that need to control reseeding themselves, e.g. a daemon that reseeds
+/// after managing its own process lifecycle, or that needs prediction resistance.
+/// [`Drbg::with_personalization`], [`Drbg::reseed_with_additional_input`] and
+/// [`Drbg::fill_with_additional_input`] provide the optional inputs of NIST SP 800-90A, for
+/// protocols and compliance profiles that prescribe them. The seed material these inputs are
+/// mixed into is zeroized once the generator has absorbed it; the caller's own buffers are left
+/// for the caller to zeroize, e.g. by holding them in a `zeroize::Zeroizing`.
+///
+/// A `Drbg` is not reseeded automatically after `fork()`: a child process must call
+/// [`Drbg::reseed`] before using a `Drbg` it inherited, or it will produce the same output as
+/// its parent.
+///
+/// A `Drbg` is not the FIPS-approved DRBG of the *AWS-LC* FIPS module, and it is not available
+/// with the `fips` feature: the module only exposes its DRBG through `RAND_bytes`, which takes
+/// no personalization string or additional input and cannot be reseeded on request. With the
+/// `fips` feature, [`SystemRandom`] draws from the module's approved DRBG.
+///
+// # FIPS
+// Not available with the `fips` feature: the FIPS module does not export its CTR-DRBG, and the
+// seed material would not be obtained through the FIPS-approved entropy source, so neither a
+// `Drbg` nor its personalization string or additional input is a FIPS-approved service. Use
+// `SystemRandom` when FIPS compliance is required.
+#[cfg(not(feature = "fips"))]
+pub struct Drbg {
+    // `CTR_DRBG_STATE` is not thread-safe.
+    state: Mutex<DrbgState>,
+}
+
+#[cfg(not(feature = "fips"))]
+struct DrbgState {
+    drbg: LcPtr<CTR_DRBG_STATE>,
+    generate_calls: u32,
+}
+
+// Safety: the `CTR_DRBG_STATE` is only accessed while holding the lock.
+#[cfg(not(feature = "fips"))]
+unsafe impl Send for DrbgState {}
+
+#[cfg(not(feature = "fips"))]
+impl Drbg {
+    /// The number of generate calls after which a `Drbg` reseeds itself. This is the same
+    /// interval *AWS-LC* uses for its per-thread generators.
+    pub const RESEED_INTERVAL: u32 = 4096;
+
+    /// The maximum length in bytes of a personalization string or of additional input.
+    pub const MAX_INPUT_LEN: usize = CTR_DRBG_ENTROPY_LEN as usize;
+
+    /// Creates a new `Drbg` seeded from the operating system's entropy source.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the generator could not be instantiated.
+    pub fn new() -> Result<Self, Unspecified> {
+        Self::with_personalization(&[])
+    }
+
+    /// Creates a new `Drbg` seeded from the operating system's entropy source and
+    /// `personalization`, a string that distinguishes this instance from others
+    /// (NIST SP 800-90A section 8.7.1), e.g. a device or protocol identifier.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `personalization` is longer than `Drbg::MAX_INPUT_LEN` bytes,
+    /// or if the generator could not be instantiated.
+    pub fn with_personalization(personalization: &[u8]) -> Result<Self, Unspecified> {
+        Ok(Self {
+            state: Mutex::new(DrbgState::new(personalization)?),
+        })
+    }
+
+    /// Reseeds the generator with fresh entropy from the operating system.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the generator could not be reseeded.
+    pub fn reseed(&self) -> Result<(), Unspecified> {
+        self.reseed_with_additional_input(&[])
+    }
+
+    /// Reseeds the generator with fresh entropy from the operating system and
+    /// `additional_input` (NIST SP 800-90A section 9.2).
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `additional_input` is longer than `Drbg::MAX_INPUT_LEN` bytes,
+    /// or if the generator could not be reseeded.
+    pub fn reseed_with_additional_input(&self, additional_input: &[u8]) -> Result<(), Unspecified> {
+        let mut state = self.state.lock().map_err(|_| Unspecified)?;
+        state.reseed(additional_input)
+    }
+
+    /// Fills `dest`, mixing `additional_input` into the generator first (NIST SP 800-90A
+    /// section 9.3). Requests longer than a single CTR-DRBG generate call are split, and
+    /// `additional_input` is passed to each of the calls.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `additional_input` is longer than `Drbg::MAX_INPUT_LEN` bytes,
+    /// or if unable to fill `dest`.
+    pub fn fill_with_additional_input(
+        &self,
+        dest: &mut [u8],
+        additional_input: &[u8],
+    ) -> Result<(), Unspecified> {
+        let mut state = self.state.lock().map_err(|_| Unspecified)?;
+        state.fill(dest, additional_input)
+    }
+
+    /// Reseeds the generator with fresh entropy from the operating system and then fills
+    /// `dest`, so that the output cannot be predicted even from a compromise of the generator's
+    /// previous state (prediction resistance, NIST SP 800-90A section 8.8).
+    ///
+    /// # Errors
+    /// `error::Unspecified` if unable to fill `dest`.
+    pub fn fill_prediction_resistant(&self, dest: &mut [u8]) -> Result<(), Unspecified> {
+        let mut state = self.state.lock().map_err(|_| Unspecified)?;
+        for chunk in dest.chunks_mut(CTR_DRBG_MAX_GENERATE_LENGTH as usize) {
+            state.reseed(&[])?;
+            state.generate(chunk, &[])?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "fips"))]
+impl DrbgState {
+    fn new(personalization: &[u8]) -> Result<Self, Unspecified> {
+        let seed = seed_material(personalization)?;
+        let drbg = LcPtr::new(unsafe { CTR_DRBG_new(seed.as_ptr(), null(), 0) })
+            .map_err(|()| Unspecified)?;
+        Ok(Self {
+            drbg,
+            generate_calls: 0,
+        })
+    }
+
+    fn fill(&mut self, dest: &mut [u8], additional_input: &[u8]) -> Result<(), Unspecified> {
+        for chunk in dest.chunks_mut(CTR_DRBG_MAX_GENERATE_LENGTH as usize) {
+            self.generate(chunk, additional_input)?;
+        }
+        Ok(())
+    }
+
+    fn reseed(&mut self, additional_input: &[u8]) -> Result<(), Unspecified> {
+        let seed = seed_material(additional_input)?;
+        if 1 != unsafe { CTR_DRBG_reseed(*self.drbg, seed.as_ptr(), null(), 0) } {
+            return Err(Unspecified);
+        }
+        self.generate_calls = 0;
+        Ok(())
+    }
+
+    fn generate(&mut self, dest: &mut [u8], additional_input: &[u8]) -> Result<(), Unspecified> {
+        if additional_input.len() > Drbg::MAX_INPUT_LEN {
+            return Err(Unspecified);
+        }
+        if self.generate_calls >= Drbg::RESEED_INTERVAL {
+            self.reseed(&[])?;
+        }
+        self.generate_calls += 1;
+        if 1 != unsafe {
+            CTR_DRBG_generate(
+                *self.drbg,
+                dest.as_mut_ptr(),
+                dest.len(),
+                additional_input.as_ptr(),
+                additional_input.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+}
+
+// `CTR_DRBG_new` and `CTR_DRBG_reseed` XOR the personalization string or additional input into
+// the entropy they are given. Doing so here instead keeps the combined seed material, which
+// contains the caller's input, in a buffer that is zeroized once the generator has absorbed it.
+#[cfg(not(feature = "fips"))]
+fn seed_material(input: &[u8]) -> Result<Zeroizing<[u8; Drbg::MAX_INPUT_LEN]>, Unspecified> {
+    if input.len() > Drbg::MAX_INPUT_LEN {
+        return Err(Unspecified);
+    }
+    let mut seed = system_entropy();
+    for (seed_byte, input_byte) in seed.iter_mut().zip(input) {
+        *seed_byte ^= input_byte;
+    }
+    Ok(seed)
+}
+
+// The seed is zeroized once the generator has absorbed it.
+#[cfg(not(feature = "fips"))]
+fn system_entropy() -> Zeroizing<[u8; CTR_DRBG_ENTROPY_LEN as usize]> {
+    let mut entropy = Zeroizing::new([0u8; CTR_DRBG_ENTROPY_LEN as usize]);
+    // Aborts if the operating system cannot provide entropy.
+    unsafe { RAND_get_system_entropy_for_custom_prng(entropy.as_mut_ptr(), entropy.len()) };
+    entropy
+}
+
+#[cfg(not(feature = "fips"))]
+impl Debug for Drbg {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Drbg").finish_non_exhaustive()
+    }
+}
+
+#[cfg(not(feature = "fips"))]
+impl sealed::SecureRandom for Drbg {
+    fn fill_impl(&self, dest: &mut [u8]) -> Result<(), Unspecified> {
+        let mut state = self.state.lock().map_err(|_| Unspecified)?;
+        state.fill(dest, &[])
+    }
+}
+
+/// A CTR-DRBG owned by a single thread, for workloads such as packet processing that generate
+/// many nonces and must avoid any cross-thread synchronization.
+///
+/// `SystemRandom` uses *AWS-LC*'s per-thread generators, but with the `fips` feature every call
+/// also takes a process-wide read lock. A `ThreadRandom` is neither `Send` nor `Sync`, so it
+/// never needs a lock; create one on each thread that needs it.
+///
+/// A `ThreadRandom` is seeded from the operating system's entropy source when it is created and
+/// reseeds itself from it after [`Drbg::RESEED_INTERVAL`] generate calls, and on the first call
+/// in the child of a `fork()`, which a handler registered with `pthread_atfork` signals without
+/// a system call on each fill. It can also be reseeded explicitly with
+/// [`ThreadRandom::reseed`].
+///
+/// ```
+/// use aws_lc_rs::rand::{self, ThreadRandom};
+///
+/// let rng = ThreadRandom::new().unwrap();
+/// let nonce: [u8; 12] = rand::generate(&rng).unwrap().expose();
+/// ```
+///
+// # FIPS
+// Not available with the `fips` feature, like `Drbg`. Use `SystemRandom` when FIPS compliance is
+// required.
+#[cfg(not(feature = "fips"))]
+pub struct ThreadRandom {
+    state: RefCell<DrbgState>,
+    fork_generation: Cell<usize>,
+    // Not `Send` or `Sync`.
+    _thread_bound: PhantomData<*const ()>,
+}
+
+// Incremented in the child of every `fork()` once a `ThreadRandom` has been created.
+#[cfg(not(feature = "fips"))]
+static FORK_GENERATION: AtomicUsize = AtomicUsize::new(0);
+#[cfg(not(feature = "fips"))]
+static FORK_HANDLER: Once = Once::new();
+#[cfg(not(feature = "fips"))]
+static FORK_HANDLER_REGISTERED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(all(unix, not(feature = "fips")))]
+extern "C" {
+    fn pthread_atfork(
+        prepare: Option<unsafe extern "C" fn()>,
+        parent: Option<unsafe extern "C" fn()>,
+        child: Option<unsafe extern "C" fn()>,
+    ) -> std::os::raw::c_int;
+}
+
+#[cfg(all(unix, not(feature = "fips")))]
+unsafe extern "C" fn increment_fork_generation() {
+    FORK_GENERATION.fetch_add(1, Ordering::Relaxed);
+}
+
+// Registers the handler that counts forks, returning whether it is in place. There is nothing
+// to register where processes cannot fork.
+#[cfg(not(feature = "fips"))]
+fn register_fork_handler() -> bool {
+    FORK_HANDLER.call_once(|| {
+        #[cfg(unix)]
+        let registered =
+            0 == unsafe { pthread_atfork(None, None, Some(increment_fork_generation)) };
+        #[cfg(not(unix))]
+        let registered = true;
+        FORK_HANDLER_REGISTERED.store(registered, Ordering::Release);
+    });
+    FORK_HANDLER_REGISTERED.load(Ordering::Acquire)
+}
+
+#[cfg(not(feature = "fips"))]
+impl ThreadRandom {
+    /// Creates a new `ThreadRandom` seeded from the operating system's entropy source.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the generator could not be instantiated, or if forks could not
+    /// be detected.
+    pub fn new() -> Result<Self, Unspecified> {
+        if !register_fork_handler() {
+            return Err(Unspecified);
+        }
+        Ok(Self {
+            state: RefCell::new(DrbgState::new(&[])?),
+            fork_generation: Cell::new(FORK_GENERATION.load(Ordering::Relaxed)),
+            _thread_bound: PhantomData,
+        })
+    }
+
+    /// Reseeds the generator with fresh entropy from the operating system.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the generator could not be reseeded.
+    pub fn reseed(&self) -> Result<(), Unspecified> {
+        self.state
+            .try_borrow_mut()
+            .map_err(|_| Unspecified)?
+            .reseed(&[])
+    }
+}
+
+#[cfg(not(feature = "fips"))]
+impl Debug for ThreadRandom {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ThreadRandom").finish_non_exhaustive()
+    }
+}
+
+#[cfg(not(feature = "fips"))]
+impl sealed::SecureRandom for ThreadRandom {
+    fn fill_impl(&self, dest: &mut [u8]) -> Result<(), Unspecified> {
+        let mut state = self.state.try_borrow_mut().map_err(|_| Unspecified)?;
+        // The fork handler runs on the forking thread, the only one in the child.
+        let fork_generation = FORK_GENERATION.load(Ordering::Relaxed);
+        if fork_generation != self.fork_generation.get() {
+            state.reseed(&[])?;
+            self.fork_generation.set(fork_generation);
+        }
+        state.fill(dest, &[])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::rand;
     use core::array::IntoIter;
 
-    use crate::rand::{generate, SecureRandom, SystemRandom};
+    use crate::rand::{generate, random_range, random_u32, random_u64, SecureRandom, SystemRandom};
+    #[cfg(not(feature = "fips"))]
+    use crate::rand::{Drbg, ThreadRandom, FORK_GENERATION};
+    use crate::test::rand::{FixedByteRandom, FixedSliceSequenceRandom};
+    use core::cell::UnsafeCell;
+    use core::mem::MaybeUninit;
 
     #[test]
     fn test_secure_random_fill() {
@@ -204,6 +777,213 @@ mod tests {
         println!("Mean: {mean} Variance: {variance}");
     }
 
+    #[test]
+    fn test_fill_uninit() {
+        let mut buffer = [MaybeUninit::<u8>::uninit(); 173];
+        let random_array: [u8; 173] = rand::fill_uninit(&mut buffer).unwrap().try_into().unwrap();
+        let (mean, variance) = mean_variance(&mut random_array.into_iter()).unwrap();
+        assert!((106f64..150f64).contains(&mean), "Mean: {mean}");
+        assert!(variance > 8f64);
+
+        let mut buffer = [MaybeUninit::<u8>::uninit(); 173];
+        let rng = SystemRandom::new();
+        let random_array: [u8; 173] = rng.fill_uninit(&mut buffer).unwrap().try_into().unwrap();
+        let (mean, variance) = mean_variance(&mut random_array.into_iter()).unwrap();
+        assert!((106f64..150f64).contains(&mean), "Mean: {mean}");
+        assert!(variance > 8f64);
+
+        // Other implementations fill the zeroed buffer.
+        let mut buffer = [MaybeUninit::<u8>::uninit(); 7];
+        let rng = FixedByteRandom { byte: 0x5a };
+        assert_eq!(&[0x5a; 7], rng.fill_uninit(&mut buffer).unwrap());
+    }
+
+    #[test]
+    fn test_random_integers() {
+        let rng = FixedByteRandom { byte: 0xa5 };
+        assert_eq!(0xa5a5_a5a5, random_u32(&rng).unwrap());
+        assert_eq!(0xa5a5_a5a5_a5a5_a5a5, random_u64(&rng).unwrap());
+
+        let rng = SystemRandom::new();
+        let values: Vec<u64> = (0..64).map(|_| random_u64(&rng).unwrap()).collect();
+        assert!(values.iter().any(|v| *v != values[0]));
+    }
+
+    #[test]
+    fn test_random_range() {
+        let rng = SystemRandom::new();
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            let value = random_range(&rng, 1..7).unwrap();
+            assert!((1..7).contains(&value));
+            seen[usize::try_from(value - 1).unwrap()] = true;
+        }
+        assert_eq!([true; 6], seen);
+
+        assert_eq!(42, random_range(&rng, 42..43).unwrap());
+        assert!(random_range(&rng, 0..u64::MAX).unwrap() < u64::MAX);
+        assert!(random_range(&rng, 7..7).is_err());
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 7..1;
+        assert!(random_range(&rng, reversed).is_err());
+
+        // Out-of-range candidates are rejected rather than reduced: with a span of 5 the
+        // candidates are masked to 3 bits, so 7 and 6 are rejected and 2 is accepted.
+        let rng = FixedSliceSequenceRandom {
+            bytes: &[
+                &7u64.to_ne_bytes(),
+                &6u64.to_ne_bytes(),
+                &2u64.to_ne_bytes(),
+            ],
+            current: UnsafeCell::new(0),
+        };
+        assert_eq!(12, random_range(&rng, 10..15).unwrap());
+        drop(rng);
+
+        // A generator that never produces an acceptable candidate is reported as an error.
+        let rng = FixedByteRandom { byte: 0xff };
+        assert!(random_range(&rng, 0..5).is_err());
+    }
+
+    #[cfg(not(feature = "fips"))]
+    #[test]
+    fn test_drbg() {
+        let drbg = Drbg::new().unwrap();
+        let mut random_array = [0u8; 173];
+        drbg.fill(&mut random_array).unwrap();
+        let (mean, variance) = mean_variance(&mut random_array.into_iter()).unwrap();
+        assert!((106f64..150f64).contains(&mean), "Mean: {mean}");
+        assert!(variance > 8f64);
+
+        let mut other_array = [0u8; 173];
+        drbg.fill_prediction_resistant(&mut other_array).unwrap();
+        assert_ne!(random_array, other_array);
+        drbg.reseed().unwrap();
+        drbg.fill(&mut other_array).unwrap();
+        assert_ne!(random_array, other_array);
+
+        // Requests longer than a single CTR-DRBG generate call are split.
+        let mut large = vec![0u8; 3 * 65536 + 1];
+        drbg.fill(&mut large).unwrap();
+        drbg.fill_prediction_resistant(&mut large).unwrap();
+        assert!(large[65536..].iter().any(|b| *b != 0));
+
+        // Generate calls past the reseed interval are still served.
+        for _ in 0..=Drbg::RESEED_INTERVAL {
+            drbg.fill(&mut random_array[..1]).unwrap();
+        }
+
+        crate::test::compile_time_assert_send::<Drbg>();
+        crate::test::compile_time_assert_sync::<Drbg>();
+    }
+
+    #[cfg(not(feature = "fips"))]
+    #[test]
+    fn test_drbg_personalization_and_additional_input() {
+        let max_input = [0x5au8; Drbg::MAX_INPUT_LEN];
+        let too_long = [0x5au8; Drbg::MAX_INPUT_LEN + 1];
+        assert!(Drbg::with_personalization(&too_long).is_err());
+
+        let drbg = Drbg::with_personalization(b"aws-lc-rs test device").unwrap();
+        let mut random_array = [0u8; 173];
+        drbg.fill_with_additional_input(&mut random_array, &max_input)
+            .unwrap();
+        let (mean, variance) = mean_variance(&mut random_array.into_iter()).unwrap();
+        assert!((106f64..150f64).contains(&mean), "Mean: {mean}");
+        assert!(variance > 8f64);
+
+        drbg.reseed_with_additional_input(b"reseed").unwrap();
+        let mut other_array = [0u8; 173];
+        drbg.fill(&mut other_array).unwrap();
+        assert_ne!(random_array, other_array);
+
+        assert!(drbg.reseed_with_additional_input(&too_long).is_err());
+        assert!(drbg
+            .fill_with_additional_input(&mut other_array, &too_long)
+            .is_err());
+
+        let mut large = vec![0u8; 2 * 65536 + 1];
+        drbg.fill_with_additional_input(&mut large, b"large")
+            .unwrap();
+        assert!(large[65536..].iter().any(|b| *b != 0));
+    }
+
+    #[cfg(feature = "rand-core")]
+    #[test]
+    fn test_rand_core_adapter() {
+        use crate::rand::RandCoreAdapter;
+        use rand_core::{CryptoRngCore, RngCore};
+
+        fn fill_token(rng: &mut dyn CryptoRngCore) -> [u8; 173] {
+            let mut token = [0u8; 173];
+            rng.fill_bytes(&mut token);
+            token
+        }
+
+        let mut rng = RandCoreAdapter::new();
+        let random_array = fill_token(&mut rng);
+        let (mean, variance) = mean_variance(&mut random_array.into_iter()).unwrap();
+        assert!((106f64..150f64).contains(&mean), "Mean: {mean}");
+        assert!(variance > 8f64);
+
+        let mut other_array = [0u8; 173];
+        rng.try_fill_bytes(&mut other_array).unwrap();
+        assert_ne!(random_array, other_array);
+
+        let values: Vec<u64> = (0..64).map(|_| rng.next_u64()).collect();
+        assert!(values.iter().any(|v| *v != values[0]));
+        let values: Vec<u32> = (0..64).map(|_| rng.next_u32()).collect();
+        assert!(values.iter().any(|v| *v != values[0]));
+    }
+
+    #[cfg(feature = "getrandom-custom")]
+    #[test]
+    fn test_getrandom_custom() {
+        let mut random_array = [0u8; 173];
+        rand::getrandom_custom(&mut random_array).unwrap();
+        let (mean, variance) = mean_variance(&mut random_array.into_iter()).unwrap();
+        assert!((106f64..150f64).contains(&mean), "Mean: {mean}");
+        assert!(variance > 8f64);
+    }
+
+    #[cfg(not(feature = "fips"))]
+    #[test]
+    fn test_thread_random() {
+        let rng = ThreadRandom::new().unwrap();
+        let mut random_array = [0u8; 173];
+        rng.fill(&mut random_array).unwrap();
+        let (mean, variance) = mean_variance(&mut random_array.into_iter()).unwrap();
+        assert!((106f64..150f64).contains(&mean), "Mean: {mean}");
+        assert!(variance > 8f64);
+
+        let mut other_array = [0u8; 173];
+        rng.reseed().unwrap();
+        rng.fill(&mut other_array).unwrap();
+        assert_ne!(random_array, other_array);
+
+        // Each thread has its own generator.
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                std::thread::spawn(|| {
+                    let rng = ThreadRandom::new().unwrap();
+                    let mut nonces = [[0u8; 12]; 1000];
+                    for nonce in &mut nonces {
+                        rng.fill(nonce).unwrap();
+                    }
+                    nonces[999]
+                })
+            })
+            .collect();
+        let nonces: Vec<[u8; 12]> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert!(nonces.iter().skip(1).all(|nonce| *nonce != nonces[0]));
+
+        // A fork, as counted by the handler, is noticed on the next fill.
+        let generation = FORK_GENERATION.fetch_add(1, core::sync::atomic::Ordering::Relaxed) + 1;
+        assert_ne!(generation, rng.fork_generation.get());
+        rng.fill(&mut other_array).unwrap();
+        assert_eq!(generation, rng.fork_generation.get());
+    }
+
     fn mean_variance<T: Into<f64>, const N: usize>(
         iterable: &mut IntoIter<T, N>,
     ) -> Option<(f64, f64)> {
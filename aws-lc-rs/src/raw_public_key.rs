@@ -0,0 +1,272 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Raw public keys, as used by TLS in place of certificates ([RFC 7250]).
+//!
+//! A [`RawPublicKey`] is a DER-encoded (X.509) `SubjectPublicKeyInfo` without a certificate,
+//! as sent in the TLS `Certificate` message when the `RawPublicKey` certificate type is
+//! negotiated, and used as the identity of IoT devices. Since nothing vouches for the key, the
+//! peer authenticates it out of band, e.g. by comparing it, or its
+//! [`RawPublicKey::fingerprint`], with a pinned value.
+//!
+//! A `RawPublicKey` is created from any public key that can be serialized as a
+//! `SubjectPublicKeyInfo`, including those of `signature::EcdsaKeyPair`,
+//! `signature::Ed25519KeyPair`, `signature::RsaKeyPair` and `agreement::PrivateKey`, and is
+//! converted back with [`RawPublicKey::signature_public_key`] or
+//! [`RawPublicKey::agreement_public_key`].
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! # #[cfg(not(feature = "strict-fips"))]
+//! # {
+//! use aws_lc_rs::raw_public_key::RawPublicKey;
+//! use aws_lc_rs::rand::SystemRandom;
+//! use aws_lc_rs::signature::{self, Ed25519KeyPair, KeyPair, PublicKeyType};
+//!
+//! let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())?;
+//! let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref())?;
+//! let raw_public_key = RawPublicKey::new(key_pair.public_key())?;
+//! let certificate_entry = raw_public_key.to_tls();
+//!
+//! let raw_public_key = RawPublicKey::from_tls(&certificate_entry)?;
+//! let public_key = raw_public_key.signature_public_key()?;
+//! assert_eq!(PublicKeyType::Ed25519, public_key.key_type());
+//!
+//! let message = b"hello, world";
+//! public_key.verify(&signature::ED25519, message, key_pair.sign(message).as_ref())?;
+//! # }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [RFC 7250]: https://tools.ietf.org/html/rfc7250
+
+use crate::agreement;
+use crate::digest::{self, Digest};
+use crate::encoding::{AsDer, PublicKeyX509Der};
+use crate::error::{KeyRejected, Unspecified};
+use crate::hex;
+use crate::signature::{self, parse_spki};
+use core::fmt::{self, Debug, Formatter};
+
+/// The value of the `RawPublicKey` TLS certificate type, as sent in the
+/// `client_certificate_type` and `server_certificate_type` extensions.
+pub const TLS_CERTIFICATE_TYPE: u8 = 2;
+
+// TLS encodes the `SubjectPublicKeyInfo` with a 24-bit length.
+const TLS_LENGTH_LEN: usize = 3;
+const TLS_MAX_LEN: usize = (1 << 24) - 1;
+
+/// A raw public key: a DER-encoded (X.509) `SubjectPublicKeyInfo`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RawPublicKey {
+    spki: Box<[u8]>,
+}
+
+impl RawPublicKey {
+    /// Creates a raw public key from `public_key`'s `SubjectPublicKeyInfo`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `public_key` cannot be serialized, e.g. for FFDHE keys.
+    pub fn new<K>(public_key: &K) -> Result<Self, Unspecified>
+    where
+        K: AsDer<PublicKeyX509Der<'static>>,
+    {
+        Ok(Self {
+            spki: public_key.as_der()?.as_ref().into(),
+        })
+    }
+
+    /// Parses a DER-encoded (X.509) `SubjectPublicKeyInfo` structure.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `spki` is not a `SubjectPublicKeyInfo`, or its key type is not
+    /// supported.
+    pub fn from_der(spki: &[u8]) -> Result<Self, KeyRejected> {
+        parse_spki(spki)?;
+        Ok(Self { spki: spki.into() })
+    }
+
+    /// Parses the `ASN1_subjectPublicKeyInfo` field of a TLS `Certificate` message (RFC 7250
+    /// Section 3), i.e. the `SubjectPublicKeyInfo` prefixed with its 24-bit length.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the length does not match `input`'s, or as for `from_der`.
+    pub fn from_tls(input: &[u8]) -> Result<Self, KeyRejected> {
+        if input.len() < TLS_LENGTH_LEN {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        let (len, spki) = input.split_at(TLS_LENGTH_LEN);
+        let len = len
+            .iter()
+            .fold(0usize, |len, byte| (len << 8) | usize::from(*byte));
+        if len == 0 || len != spki.len() {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        Self::from_der(spki)
+    }
+
+    /// Serializes the key as the `ASN1_subjectPublicKeyInfo` field of a TLS `Certificate`
+    /// message (RFC 7250 Section 3), i.e. the `SubjectPublicKeyInfo` prefixed with its 24-bit
+    /// length.
+    #[must_use]
+    pub fn to_tls(&self) -> Vec<u8> {
+        // `SubjectPublicKeyInfo`s are far shorter than 16 MiB.
+        debug_assert!(self.spki.len() <= TLS_MAX_LEN);
+        let len = self.spki.len().to_be_bytes();
+        let mut output = Vec::with_capacity(TLS_LENGTH_LEN + self.spki.len());
+        output.extend_from_slice(&len[len.len() - TLS_LENGTH_LEN..]);
+        output.extend_from_slice(&self.spki);
+        output
+    }
+
+    /// The key as a public key for signature verification.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the key is not an ECDSA, Ed25519 or RSA key.
+    pub fn signature_public_key(&self) -> Result<signature::PublicKey, KeyRejected> {
+        signature::PublicKey::from_der(&self.spki)
+    }
+
+    /// The key as a public key for key agreement with `algorithm`.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the key is not for `algorithm`.
+    pub fn agreement_public_key(
+        &self,
+        algorithm: &'static agreement::Algorithm,
+    ) -> Result<agreement::PublicKey, KeyRejected> {
+        agreement::PublicKey::from_der(algorithm, &self.spki)
+    }
+
+    /// The `algorithm` hash of the key. Its SHA-256 form is the [RFC 7469] pin of the key, as
+    /// given by `x509::Certificate::spki_fingerprint` for a certificate with the same key.
+    ///
+    /// [RFC 7469]: https://tools.ietf.org/html/rfc7469#section-2.4
+    #[must_use]
+    pub fn fingerprint(&self, algorithm: &'static digest::Algorithm) -> Digest {
+        digest::digest(algorithm, &self.spki)
+    }
+}
+
+impl AsRef<[u8]> for RawPublicKey {
+    /// The DER encoding of the `SubjectPublicKeyInfo`.
+    fn as_ref(&self) -> &[u8] {
+        &self.spki
+    }
+}
+
+impl AsDer<PublicKeyX509Der<'static>> for RawPublicKey {
+    /// Provides the key as a DER-encoded (X.509) `SubjectPublicKeyInfo` structure. These are the
+    /// same bytes as `as_ref`.
+    fn as_der(&self) -> Result<PublicKeyX509Der<'static>, Unspecified> {
+        Ok(PublicKeyX509Der::new(self.spki.to_vec()))
+    }
+}
+
+impl Debug for RawPublicKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&format!("RawPublicKey(\"{}\")", hex::encode(&self.spki)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RawPublicKey;
+    use crate::encoding::{AsDer, PublicKeyX509Der};
+    use crate::signature::{self, EcdsaKeyPair, KeyPair, PublicKeyType, RsaKeyPair};
+    #[cfg(not(feature = "strict-fips"))]
+    use crate::{
+        agreement::{self, PrivateKey},
+        digest,
+        signature::Ed25519KeyPair,
+        test,
+    };
+
+    #[test]
+    fn test_signature_keys() {
+        let key_pair = EcdsaKeyPair::generate(&signature::ECDSA_P256_SHA256_ASN1_SIGNING).unwrap();
+        let raw_public_key = RawPublicKey::new(key_pair.public_key()).unwrap();
+        let public_key = raw_public_key.signature_public_key().unwrap();
+        assert_eq!(PublicKeyType::EcdsaP256, public_key.key_type());
+        assert_eq!(key_pair.public_key().as_ref(), public_key.as_ref());
+
+        #[cfg(not(feature = "strict-fips"))]
+        {
+            let key_pair = Ed25519KeyPair::from_seed_unchecked(&[7; 32]).unwrap();
+            let raw_public_key = RawPublicKey::new(key_pair.public_key()).unwrap();
+            // The `SubjectPublicKeyInfo` of an Ed25519 key is a fixed prefix followed by the key.
+            assert_eq!(
+                test::from_dirty_hex("302a300506032b6570032100"),
+                raw_public_key.as_ref()[..12]
+            );
+            assert_eq!(
+                key_pair.public_key().as_ref(),
+                &raw_public_key.as_ref()[12..]
+            );
+            let public_key = raw_public_key.signature_public_key().unwrap();
+            assert_eq!(PublicKeyType::Ed25519, public_key.key_type());
+        }
+
+        let key_pair =
+            RsaKeyPair::from_pkcs8(include_bytes!("../tests/data/rsa_test_private_key_2048.p8"))
+                .unwrap();
+        let raw_public_key = RawPublicKey::new(key_pair.public_key()).unwrap();
+        let public_key = raw_public_key.signature_public_key().unwrap();
+        assert_eq!(PublicKeyType::Rsa, public_key.key_type());
+        assert_eq!(key_pair.public_key().as_ref(), public_key.as_ref());
+        assert_eq!(
+            raw_public_key,
+            RawPublicKey::from_der(
+                AsDer::<PublicKeyX509Der>::as_der(&raw_public_key)
+                    .unwrap()
+                    .as_ref()
+            )
+            .unwrap()
+        );
+    }
+
+    #[cfg(not(feature = "strict-fips"))]
+    #[test]
+    fn test_agreement_keys() {
+        let private_key = PrivateKey::generate(&agreement::X25519).unwrap();
+        let raw_public_key = RawPublicKey::new(&private_key.compute_public_key().unwrap()).unwrap();
+        let public_key = raw_public_key
+            .agreement_public_key(&agreement::X25519)
+            .unwrap();
+        assert_eq!(
+            private_key.compute_public_key().unwrap().as_ref(),
+            public_key.as_ref()
+        );
+        assert!(raw_public_key
+            .agreement_public_key(&agreement::ECDH_P256)
+            .is_err());
+        assert!(raw_public_key.signature_public_key().is_err());
+    }
+
+    #[cfg(not(feature = "strict-fips"))]
+    #[test]
+    fn test_tls() {
+        let key_pair = Ed25519KeyPair::from_seed_unchecked(&[7; 32]).unwrap();
+        let raw_public_key = RawPublicKey::new(key_pair.public_key()).unwrap();
+        let tls = raw_public_key.to_tls();
+        assert_eq!([0, 0, 44], tls[..3]);
+        assert_eq!(raw_public_key.as_ref(), &tls[3..]);
+        assert_eq!(raw_public_key, RawPublicKey::from_tls(&tls).unwrap());
+        assert_eq!(
+            digest::digest(&digest::SHA256, &tls[3..]).as_ref(),
+            raw_public_key.fingerprint(&digest::SHA256).as_ref()
+        );
+
+        assert!(RawPublicKey::from_tls(&[]).is_err());
+        assert!(RawPublicKey::from_tls(&[0, 0, 0]).is_err());
+        assert!(RawPublicKey::from_tls(&tls[..tls.len() - 1]).is_err());
+        let mut trailing = tls.clone();
+        trailing.push(0);
+        assert!(RawPublicKey::from_tls(&trailing).is_err());
+        assert!(RawPublicKey::from_der(&tls).is_err());
+    }
+}
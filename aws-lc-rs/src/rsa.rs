@@ -67,20 +67,29 @@
 
 mod encoding;
 mod encryption;
+mod generation;
 pub(crate) mod key;
 pub(crate) mod signature;
 
 #[allow(clippy::module_name_repetitions)]
-pub use self::signature::RsaParameters;
+pub use self::signature::{RsaParameters, RsaPssSaltLength, RsaVerificationContext};
 pub use self::{
     encryption::{
         EncryptionAlgorithmId, OaepAlgorithm, OaepPrivateDecryptingKey, OaepPublicEncryptingKey,
-        PrivateDecryptingKey, PublicEncryptingKey, OAEP_SHA1_MGF1SHA1, OAEP_SHA256_MGF1SHA256,
-        OAEP_SHA384_MGF1SHA384, OAEP_SHA512_MGF1SHA512,
+        PrivateDecryptingKey, PublicEncryptingKey, OAEP_SHA1_MGF1SHA1, OAEP_SHA256_MGF1SHA1,
+        OAEP_SHA256_MGF1SHA256, OAEP_SHA384_MGF1SHA1, OAEP_SHA384_MGF1SHA384, OAEP_SHA512_MGF1SHA1,
+        OAEP_SHA512_MGF1SHA512,
     },
-    key::{KeyPair, KeySize, PublicKey, PublicKeyComponents},
+    generation::KeyPairGeneration,
+    key::{KeyPair, KeyPairComponents, KeySize, PublicKey, PublicKeyComponents, RsaSigningContext},
 };
 
+#[cfg(feature = "legacy-pkcs1-encryption")]
+pub use self::encryption::{Pkcs1PrivateDecryptingKey, Pkcs1PublicEncryptingKey};
+
+#[cfg(feature = "rsa-key-components")]
+pub use self::key::PrivateKeyComponents;
+
 pub(crate) use self::signature::RsaVerificationAlgorithmId;
 
 #[cfg(test)]
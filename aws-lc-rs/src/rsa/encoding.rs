@@ -50,13 +50,16 @@ pub(in crate::rsa) mod pkcs8 {
 /// PKCS #1: RSA Cryptography Specifications Version 2.2
 pub(in crate::rsa) mod rfc8017 {
     use crate::{
+        cbb::LcCBB,
         cbs,
+        encoding::RsaPrivateKeyRfc8017Der,
         error::{KeyRejected, Unspecified},
         ptr::{DetachableLcPtr, LcPtr},
+        rsa::key::key_size_bytes,
     };
     use aws_lc::{
-        EVP_PKEY_assign_RSA, EVP_PKEY_new, RSA_parse_private_key, RSA_public_key_from_bytes,
-        RSA_public_key_to_bytes, EVP_PKEY,
+        EVP_PKEY_assign_RSA, EVP_PKEY_new, RSA_marshal_private_key, RSA_parse_private_key,
+        RSA_public_key_from_bytes, RSA_public_key_to_bytes, EVP_PKEY,
     };
     use std::ptr::null_mut;
 
@@ -99,6 +102,21 @@ pub(in crate::rsa) mod rfc8017 {
         Ok(pkey)
     }
 
+    /// DER encode a RSA private key to `RSAPrivateKey` structure.
+    pub(in crate::rsa) fn encode_private_key_der(
+        key: &LcPtr<EVP_PKEY>,
+    ) -> Result<RsaPrivateKeyRfc8017Der<'static>, Unspecified> {
+        // The modulus and the private exponent dominate, with the CRT parameters adding about as
+        // much again.
+        let mut der = LcCBB::new(key_size_bytes(key) * 5);
+
+        if 1 != unsafe { RSA_marshal_private_key(der.as_mut_ptr(), *key.get_rsa()?.as_const()) } {
+            return Err(Unspecified);
+        }
+
+        Ok(RsaPrivateKeyRfc8017Der::from(der.into_buffer()?))
+    }
+
     /// Decodes a DER encoded `RSAPrivateKey` structure.
     #[inline]
     pub(in crate::rsa) fn decode_private_key_der(
@@ -16,9 +16,15 @@ use aws_lc::{
 use core::{fmt::Debug, mem::size_of_val, ptr::null_mut};
 use mirai_annotations::verify_unreachable;
 
+#[cfg(feature = "legacy-pkcs1-encryption")]
+mod pkcs1;
+
+#[cfg(feature = "legacy-pkcs1-encryption")]
+pub use self::pkcs1::{Pkcs1PrivateDecryptingKey, Pkcs1PublicEncryptingKey};
+
 use super::{
     encoding,
-    key::{generate_rsa_key, is_rsa_key, key_size_bits, key_size_bytes},
+    key::{generate_rsa_key, is_rsa_key, key_size_bits, key_size_bytes, RSA_F4},
     KeySize,
 };
 
@@ -50,6 +56,29 @@ pub const OAEP_SHA512_MGF1SHA512: OaepAlgorithm = OaepAlgorithm {
     mgf1_hash_fn: EVP_sha512,
 };
 
+/// RSA-OAEP with SHA256 Hash and SHA1 MGF1
+///
+/// This is the default of Java's `OAEPWithSHA-256AndMGF1Padding` and of some HSMs.
+pub const OAEP_SHA256_MGF1SHA1: OaepAlgorithm = OaepAlgorithm {
+    id: EncryptionAlgorithmId::OaepSha256Mgf1sha1,
+    oaep_hash_fn: EVP_sha256,
+    mgf1_hash_fn: EVP_sha1,
+};
+
+/// RSA-OAEP with SHA384 Hash and SHA1 MGF1
+pub const OAEP_SHA384_MGF1SHA1: OaepAlgorithm = OaepAlgorithm {
+    id: EncryptionAlgorithmId::OaepSha384Mgf1sha1,
+    oaep_hash_fn: EVP_sha384,
+    mgf1_hash_fn: EVP_sha1,
+};
+
+/// RSA-OAEP with SHA512 Hash and SHA1 MGF1
+pub const OAEP_SHA512_MGF1SHA1: OaepAlgorithm = OaepAlgorithm {
+    id: EncryptionAlgorithmId::OaepSha512Mgf1sha1,
+    oaep_hash_fn: EVP_sha512,
+    mgf1_hash_fn: EVP_sha1,
+};
+
 /// RSA Encryption Algorithm Identifier
 #[allow(clippy::module_name_repetitions)]
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -66,6 +95,15 @@ pub enum EncryptionAlgorithmId {
 
     /// RSA-OAEP with SHA512 Hash and SHA512 MGF1
     OaepSha512Mgf1sha512,
+
+    /// RSA-OAEP with SHA256 Hash and SHA1 MGF1
+    OaepSha256Mgf1sha1,
+
+    /// RSA-OAEP with SHA384 Hash and SHA1 MGF1
+    OaepSha384Mgf1sha1,
+
+    /// RSA-OAEP with SHA512 Hash and SHA1 MGF1
+    OaepSha512Mgf1sha1,
 }
 
 type OaepHashFn = unsafe extern "C" fn() -> *const EVP_MD;
@@ -132,7 +170,7 @@ impl PrivateDecryptingKey {
     /// # Errors
     /// * `Unspecified` for any error that occurs during the generation of the RSA keypair.
     pub fn generate(size: KeySize) -> Result<Self, Unspecified> {
-        let key = generate_rsa_key(size.bits(), false)?;
+        let key = generate_rsa_key(size.bits(), RSA_F4, false)?;
         Self::new(key)
     }
 
@@ -147,7 +185,7 @@ impl PrivateDecryptingKey {
     /// * `Unspecified`: Any key generation failure.
     #[cfg(feature = "fips")]
     pub fn generate_fips(size: KeySize) -> Result<Self, Unspecified> {
-        let key = generate_rsa_key(size.bits(), true)?;
+        let key = generate_rsa_key(size.bits(), RSA_F4, true)?;
         Self::new(key)
     }
 
@@ -281,8 +319,12 @@ impl OaepPublicEncryptingKey {
     /// The provided length of `plaintext` must be at most [`Self::max_plaintext_size`].
     ///
     /// # Sizing `output`
-    /// For `OAEP_SHA1_MGF1SHA1`, `OAEP_SHA256_MGF1SHA256`, `OAEP_SHA384_MGF1SHA384`, `OAEP_SHA512_MGF1SHA512` The
-    /// length of `output` must be greater then or equal to [`Self::ciphertext_size`].
+    /// The length of `output` must be greater then or equal to [`Self::ciphertext_size`].
+    ///
+    /// # Label
+    /// `label` is the OAEP label `L` (RFC 8017 Section 7.1.1), which binds the ciphertext to a
+    /// context, such as the label some TPM and CMS profiles require. `None` is the same as an
+    /// empty label. The same label must be given to [`OaepPrivateDecryptingKey::decrypt`].
     ///
     /// # Errors
     /// * `Unspecified` for any error that occurs while encrypting `plaintext`.
@@ -341,13 +383,16 @@ impl OaepPublicEncryptingKey {
         #[allow(unreachable_patterns)]
         let hash_len: usize = match algorithm.id() {
             EncryptionAlgorithmId::OaepSha1Mgf1sha1 => 20,
-            EncryptionAlgorithmId::OaepSha256Mgf1sha256 => 32,
-            EncryptionAlgorithmId::OaepSha384Mgf1sha384 => 48,
-            EncryptionAlgorithmId::OaepSha512Mgf1sha512 => 64,
+            EncryptionAlgorithmId::OaepSha256Mgf1sha256
+            | EncryptionAlgorithmId::OaepSha256Mgf1sha1 => 32,
+            EncryptionAlgorithmId::OaepSha384Mgf1sha384
+            | EncryptionAlgorithmId::OaepSha384Mgf1sha1 => 48,
+            EncryptionAlgorithmId::OaepSha512Mgf1sha512
+            | EncryptionAlgorithmId::OaepSha512Mgf1sha1 => 64,
             _ => verify_unreachable!(),
         };
 
-        // The RSA-OAEP algorithms we support use the hashing algorithm for the hash and mgf1 functions.
+        // The overhead depends on the OAEP hash function, not on the MGF1 hash function.
         self.key_size_bytes() - 2 * hash_len - 2
     }
 
@@ -385,11 +430,17 @@ impl OaepPrivateDecryptingKey {
     /// The provided length of `ciphertext` must be [`Self::key_size_bytes`].
     ///
     /// # Sizing `output`
-    /// For `OAEP_SHA1_MGF1SHA1`, `OAEP_SHA256_MGF1SHA256`, `OAEP_SHA384_MGF1SHA384`, `OAEP_SHA512_MGF1SHA512`. The
-    /// length of `output` must be greater then or equal to [`Self::min_output_size`].
+    /// The length of `output` must be greater then or equal to [`Self::min_output_size`].
+    ///
+    /// # Label
+    /// `label` must be the OAEP label the ciphertext was encrypted with; `None` is the same as an
+    /// empty label. A mismatched label is indistinguishable from any other decryption failure,
+    /// so check the label first when decryption of a ciphertext from another implementation
+    /// unexpectedly fails.
     ///
     /// # Errors
-    /// * `Unspecified` for any error that occurs while decrypting `ciphertext`.
+    /// * `Unspecified` for any error that occurs while decrypting `ciphertext`, including a
+    ///   mismatched `label`.
     pub fn decrypt<'plaintext>(
         &self,
         algorithm: &'static OaepAlgorithm,
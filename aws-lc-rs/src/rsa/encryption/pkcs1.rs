@@ -0,0 +1,333 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use super::{PrivateDecryptingKey, PublicEncryptingKey};
+use crate::digest;
+use crate::error::Unspecified;
+use crate::hmac;
+use crate::ptr::{ConstPointer, LcPtr};
+use aws_lc::{
+    BN_bn2bin_padded, EVP_PKEY_CTX_new, EVP_PKEY_CTX_set_rsa_padding, EVP_PKEY_decrypt,
+    EVP_PKEY_decrypt_init, EVP_PKEY_encrypt, EVP_PKEY_encrypt_init, RSA_get0_d, EVP_PKEY,
+    EVP_PKEY_CTX, RSA_NO_PADDING, RSA_PKCS1_PADDING,
+};
+use core::fmt::Debug;
+use core::ptr::null_mut;
+use std::os::raw::c_int;
+use zeroize::Zeroizing;
+
+// The encryption block is `0x00 || 0x02 || PS || 0x00 || M`, where the padding string `PS`
+// has at least 8 nonzero bytes (RFC 8017 Section 7.2.1).
+const MIN_PADDING_LEN: usize = 11;
+const MIN_PS_LEN: usize = 8;
+
+/// An RSA PKCS#1 v1.5 public key for encryption.
+///
+/// PKCS#1 v1.5 encryption is vulnerable to padding oracle attacks, and is provided only for
+/// interoperability with legacy systems. Use [`super::OaepPublicEncryptingKey`] instead.
+pub struct Pkcs1PublicEncryptingKey {
+    public_key: PublicEncryptingKey,
+}
+
+impl Pkcs1PublicEncryptingKey {
+    /// Constructs a `Pkcs1PublicEncryptingKey` from a `PublicEncryptingKey`.
+    /// # Errors
+    /// * `Unspecified`: Any error that occurs while attempting to construct an RSA PKCS#1 v1.5
+    ///   public key.
+    pub fn new(public_key: PublicEncryptingKey) -> Result<Self, Unspecified> {
+        Ok(Self { public_key })
+    }
+
+    /// Encrypts the contents in `plaintext` and writes the corresponding ciphertext to `ciphertext`.
+    /// Returns the subslice of `ciphertext` containing the ciphertext output.
+    ///
+    /// # Max Plaintext Length
+    /// The provided length of `plaintext` must be at most [`Self::max_plaintext_size`].
+    ///
+    /// # Sizing `output`
+    /// The length of `output` must be greater then or equal to [`Self::ciphertext_size`].
+    ///
+    /// # Errors
+    /// * `Unspecified` for any error that occurs while encrypting `plaintext`.
+    pub fn encrypt<'ciphertext>(
+        &self,
+        plaintext: &[u8],
+        ciphertext: &'ciphertext mut [u8],
+    ) -> Result<&'ciphertext mut [u8], Unspecified> {
+        let pkey_ctx = new_pkey_ctx(&self.public_key.0, RSA_PKCS1_PADDING, true)?;
+
+        let mut out_len = ciphertext.len();
+        if 1 != unsafe {
+            EVP_PKEY_encrypt(
+                *pkey_ctx,
+                ciphertext.as_mut_ptr(),
+                &mut out_len,
+                plaintext.as_ptr(),
+                plaintext.len(),
+            )
+        } {
+            return Err(Unspecified);
+        };
+
+        Ok(&mut ciphertext[..out_len])
+    }
+
+    /// Returns the RSA key size in bytes.
+    #[must_use]
+    pub fn key_size_bytes(&self) -> usize {
+        self.public_key.key_size_bytes()
+    }
+
+    /// Returns the RSA key size in bits.
+    #[must_use]
+    pub fn key_size_bits(&self) -> usize {
+        self.public_key.key_size_bits()
+    }
+
+    /// Returns the max plaintext that could be encrypted using this key.
+    #[must_use]
+    pub fn max_plaintext_size(&self) -> usize {
+        self.key_size_bytes() - MIN_PADDING_LEN
+    }
+
+    /// Returns the max ciphertext size that will be output by `Self::encrypt`.
+    #[must_use]
+    pub fn ciphertext_size(&self) -> usize {
+        self.key_size_bytes()
+    }
+}
+
+impl Debug for Pkcs1PublicEncryptingKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Pkcs1PublicEncryptingKey")
+            .finish_non_exhaustive()
+    }
+}
+
+/// An RSA PKCS#1 v1.5 private key for decryption.
+///
+/// Decryption uses implicit rejection, as described in
+/// [draft-irtf-cfrg-rsa-guidance Section 7.2] and implemented by OpenSSL 3.2 and later: a
+/// ciphertext with invalid padding decrypts, in constant time, to a pseudorandom message derived
+/// from the ciphertext and the private key, rather than failing. This prevents Bleichenbacher's
+/// attack, which relies on distinguishing valid from invalid padding, as long as the caller
+/// treats the plaintext the same way in both cases, e.g. by using it as a key whose use fails
+/// later. The plaintext must therefore be authenticated by the protocol.
+///
+/// PKCS#1 v1.5 encryption is provided only for interoperability with legacy systems. Use
+/// [`super::OaepPrivateDecryptingKey`] instead.
+///
+/// [draft-irtf-cfrg-rsa-guidance Section 7.2]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-rsa-guidance#section-7.2
+pub struct Pkcs1PrivateDecryptingKey {
+    private_key: PrivateDecryptingKey,
+}
+
+impl Pkcs1PrivateDecryptingKey {
+    /// Constructs a `Pkcs1PrivateDecryptingKey` from a `PrivateDecryptingKey`.
+    /// # Errors
+    /// * `Unspecified`: Any error that occurs while attempting to construct an RSA PKCS#1 v1.5
+    ///   private key.
+    pub fn new(private_key: PrivateDecryptingKey) -> Result<Self, Unspecified> {
+        Ok(Self { private_key })
+    }
+
+    /// Decrypts the contents in `ciphertext` and writes the corresponding plaintext to `plaintext`.
+    /// Returns the subslice of `plaintext` containing the plaintext output.
+    ///
+    /// If the padding of the decrypted `ciphertext` is invalid, the plaintext is a pseudorandom
+    /// message instead; see [`Pkcs1PrivateDecryptingKey`].
+    ///
+    /// # Max Ciphertext Length
+    /// The provided length of `ciphertext` must be [`Self::key_size_bytes`].
+    ///
+    /// # Sizing `output`
+    /// The length of `output` must be greater then or equal to [`Self::min_output_size`].
+    ///
+    /// # Errors
+    /// * `Unspecified` if `ciphertext` is not the size of the key or is not less than its
+    ///   modulus, or `plaintext` is too small. Invalid padding is not an error.
+    pub fn decrypt<'plaintext>(
+        &self,
+        ciphertext: &[u8],
+        plaintext: &'plaintext mut [u8],
+    ) -> Result<&'plaintext mut [u8], Unspecified> {
+        let key_size = self.key_size_bytes();
+        if ciphertext.len() != key_size || plaintext.len() < self.min_output_size() {
+            return Err(Unspecified);
+        }
+
+        let pkey_ctx = new_pkey_ctx(&self.private_key.0, RSA_NO_PADDING, false)?;
+        let mut encoded = Zeroizing::new(vec![0u8; key_size]);
+        let mut out_len = encoded.len();
+        if 1 != unsafe {
+            EVP_PKEY_decrypt(
+                *pkey_ctx,
+                encoded.as_mut_ptr(),
+                &mut out_len,
+                ciphertext.as_ptr(),
+                ciphertext.len(),
+            )
+        } || out_len != key_size
+        {
+            return Err(Unspecified);
+        }
+
+        let synthetic = self.synthetic_message(ciphertext)?;
+        let (good, message_len) = check_padding(&encoded);
+        let len = select(good, message_len, synthetic.len());
+        // Both messages are right-aligned in blocks of `key_size` bytes, so the plaintext is
+        // selected without branching on, or indexing by, the padding's validity.
+        let offset = key_size - synthetic.len();
+        let mut selected = Zeroizing::new(vec![0u8; key_size]);
+        for (i, byte) in selected.iter_mut().enumerate() {
+            let synthetic_byte = if i >= offset {
+                synthetic[i - offset]
+            } else {
+                0
+            };
+            // Only the low byte of the selection is kept.
+            #[allow(clippy::cast_possible_truncation)]
+            {
+                *byte = select(good, usize::from(encoded[i]), usize::from(synthetic_byte)) as u8;
+            }
+        }
+        plaintext[..len].copy_from_slice(&selected[key_size - len..]);
+        Ok(&mut plaintext[..len])
+    }
+
+    // The message that replaces the plaintext of a ciphertext with invalid padding.
+    fn synthetic_message(&self, ciphertext: &[u8]) -> Result<Zeroizing<Vec<u8>>, Unspecified> {
+        let key_size = self.key_size_bytes();
+
+        // The key derivation key: the HMAC, keyed with a hash of the private exponent, of the
+        // ciphertext.
+        let rsa = self.private_key.0.get_rsa()?;
+        let d = ConstPointer::new(unsafe { RSA_get0_d(*rsa) })?;
+        let mut d_bytes = Zeroizing::new(vec![0u8; key_size]);
+        if 1 != unsafe { BN_bn2bin_padded(d_bytes.as_mut_ptr(), d_bytes.len(), *d) } {
+            return Err(Unspecified);
+        }
+        let mut d_hash = digest::try_digest(&digest::SHA256, &d_bytes)?;
+        drop(d_bytes);
+        let d_key = hmac::Key::try_new(hmac::HMAC_SHA256, d_hash.as_ref());
+        d_hash.zeroize();
+        let mut kdk = hmac::sign(&d_key?, ciphertext);
+        let kdk_key = hmac::Key::try_new(hmac::HMAC_SHA256, kdk.as_ref());
+        kdk.zeroize();
+        let kdk = kdk_key?;
+
+        let mut message = prf(&kdk, b"message", key_size)?;
+        let candidate_lengths = prf(&kdk, b"length", 256)?;
+
+        // The last of the candidate lengths, masked to the bit length of the maximum offset of
+        // the zero byte that ends the padding string, that is less than it.
+        let max_separator_offset = key_size - 2 - MIN_PS_LEN;
+        let mask = usize::MAX >> max_separator_offset.leading_zeros();
+        let mut len = 0;
+        for candidate in candidate_lengths.chunks_exact(2) {
+            let candidate = usize::from(u16::from_be_bytes([candidate[0], candidate[1]])) & mask;
+            len = select(less_than(candidate, max_separator_offset), candidate, len);
+        }
+
+        // `message` wipes the unused part of the pseudorandom output when dropped.
+        Ok(Zeroizing::new(message.split_off(key_size - len)))
+    }
+
+    /// Returns the RSA key size in bytes.
+    #[must_use]
+    pub fn key_size_bytes(&self) -> usize {
+        self.private_key.key_size_bytes()
+    }
+
+    /// Returns the RSA key size in bits.
+    #[must_use]
+    pub fn key_size_bits(&self) -> usize {
+        self.private_key.key_size_bits()
+    }
+
+    /// Returns the minimum plaintext buffer size required for `Self::decrypt`.
+    #[must_use]
+    pub fn min_output_size(&self) -> usize {
+        self.key_size_bytes() - MIN_PADDING_LEN
+    }
+}
+
+impl Debug for Pkcs1PrivateDecryptingKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Pkcs1PrivateDecryptingKey")
+            .finish_non_exhaustive()
+    }
+}
+
+fn new_pkey_ctx(
+    evp_pkey: &LcPtr<EVP_PKEY>,
+    padding: c_int,
+    encrypt: bool,
+) -> Result<LcPtr<EVP_PKEY_CTX>, Unspecified> {
+    let pkey_ctx = LcPtr::new(unsafe { EVP_PKEY_CTX_new(**evp_pkey, null_mut()) })?;
+    let init = if encrypt {
+        unsafe { EVP_PKEY_encrypt_init(*pkey_ctx) }
+    } else {
+        unsafe { EVP_PKEY_decrypt_init(*pkey_ctx) }
+    };
+    if 1 != init || 1 != unsafe { EVP_PKEY_CTX_set_rsa_padding(*pkey_ctx, padding) } {
+        return Err(Unspecified);
+    }
+    Ok(pkey_ctx)
+}
+
+// Checks the padding of the encryption block `encoded` in constant time, and returns a mask
+// that is all ones if it is valid, along with the length of the message it encodes.
+fn check_padding(encoded: &[u8]) -> (usize, usize) {
+    let mut good = is_zero(usize::from(encoded[0])) & is_zero(usize::from(encoded[1]) ^ 2);
+
+    // The index of the first zero byte after the block type.
+    let mut zero_index = 0;
+    let mut looking_for_index = usize::MAX;
+    for (i, byte) in encoded.iter().enumerate().skip(2) {
+        let equals_zero = is_zero(usize::from(*byte));
+        zero_index = select(looking_for_index & equals_zero, i, zero_index);
+        looking_for_index &= !equals_zero;
+    }
+    good &= !looking_for_index;
+    good &= !less_than(zero_index, 2 + MIN_PS_LEN);
+
+    let message_len = encoded.len() - zero_index - 1;
+    (good, select(good, message_len, 0))
+}
+
+// The pseudorandom function of draft-irtf-cfrg-rsa-guidance Section 7.2: the concatenation of
+// `HMAC(key, I || label || bit_len)` for a 16-bit counter `I`.
+fn prf(key: &hmac::Key, label: &[u8], len: usize) -> Result<Zeroizing<Vec<u8>>, Unspecified> {
+    let bit_len = u16::try_from(len * 8)?.to_be_bytes();
+    // The capacity fits every block, so the output is never reallocated and left behind.
+    let mut output = Zeroizing::new(Vec::with_capacity(len + digest::SHA256_OUTPUT_LEN));
+    let mut counter = 0u16;
+    while output.len() < len {
+        let mut ctx = hmac::Context::with_key(key);
+        ctx.update(&counter.to_be_bytes());
+        ctx.update(label);
+        ctx.update(&bit_len);
+        let mut block = ctx.sign();
+        output.extend_from_slice(block.as_ref());
+        block.zeroize();
+        counter += 1;
+    }
+    output.truncate(len);
+    Ok(output)
+}
+
+// All ones if `x` is zero, and zero otherwise.
+fn is_zero(x: usize) -> usize {
+    ((!x & x.wrapping_sub(1)) >> (usize::BITS - 1)).wrapping_neg()
+}
+
+// All ones if `a < b`, and zero otherwise, for `a` and `b` less than `usize::MAX / 2`.
+fn less_than(a: usize, b: usize) -> usize {
+    (a.wrapping_sub(b) >> (usize::BITS - 1)).wrapping_neg()
+}
+
+// `a` if `mask` is all ones, and `b` if it is zero.
+fn select(mask: usize, a: usize, b: usize) -> usize {
+    (mask & a) | (!mask & b)
+}
@@ -0,0 +1,178 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use super::key::{generate_rsa_key_with_callback, KeyPair, KeySize, RSA_F4};
+use crate::error::Unspecified;
+use crate::ptr::LcPtr;
+use aws_lc::{BN_GENCB_get_arg, BN_GENCB_new, BN_GENCB_set, BN_GENCB};
+use core::fmt::{self, Debug, Formatter};
+use std::os::raw::{c_int, c_void};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+// The `BN_GENCB` event that RSA key generation reports after finding each prime.
+const PRIME_FOUND: c_int = 3;
+// The `BN_GENCB` event reported after generating each prime candidate.
+const CANDIDATE_GENERATED: c_int = 0;
+
+/// A RSA key pair being generated on a background thread, started by
+/// [`KeyPair::generate_in_background`].
+///
+/// Dropping it cancels the generation.
+pub struct KeyPairGeneration {
+    progress: Arc<Progress>,
+    thread: Option<JoinHandle<Result<KeyPair, Unspecified>>>,
+}
+
+#[derive(Default)]
+struct Progress {
+    cancelled: AtomicBool,
+    candidates: AtomicUsize,
+    primes: AtomicUsize,
+}
+
+impl KeyPairGeneration {
+    pub(super) fn spawn(size: KeySize) -> Result<Self, Unspecified> {
+        let progress = Arc::new(Progress::default());
+        let thread = thread::Builder::new()
+            .name("aws-lc-rs RSA key generation".to_string())
+            .spawn({
+                let progress = Arc::clone(&progress);
+                move || generate(size, &progress)
+            })
+            .map_err(|_| Unspecified)?;
+        Ok(Self {
+            progress,
+            thread: Some(thread),
+        })
+    }
+
+    /// The number of prime candidates generated so far. Generation typically takes several
+    /// hundred candidates, but the number varies widely.
+    #[must_use]
+    pub fn candidates_generated(&self) -> usize {
+        self.progress.candidates.load(Ordering::Relaxed)
+    }
+
+    /// The number of the key's two primes found so far.
+    #[must_use]
+    pub fn primes_found(&self) -> usize {
+        self.progress.primes.load(Ordering::Relaxed)
+    }
+
+    /// Returns true if the generation has finished, successfully or not, and [`Self::wait`]
+    /// would not block.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.thread.as_ref().map_or(true, JoinHandle::is_finished)
+    }
+
+    /// Cancels the generation, which stops at its next progress report. [`Self::wait`] then
+    /// returns an error, unless the generation had already finished.
+    pub fn cancel(&self) {
+        self.progress.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Waits for the generation to finish and returns the key pair.
+    ///
+    /// # Errors
+    /// * `Unspecified`: If the generation was cancelled, or for any key generation failure.
+    pub fn wait(mut self) -> Result<KeyPair, Unspecified> {
+        self.thread
+            .take()
+            .ok_or(Unspecified)?
+            .join()
+            .map_err(|_| Unspecified)?
+    }
+}
+
+impl Drop for KeyPairGeneration {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+impl Debug for KeyPairGeneration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyPairGeneration")
+            .field("candidates_generated", &self.candidates_generated())
+            .field("primes_found", &self.primes_found())
+            .field("is_finished", &self.is_finished())
+            .finish()
+    }
+}
+
+fn generate(size: KeySize, progress: &Arc<Progress>) -> Result<KeyPair, Unspecified> {
+    let callback = LcPtr::<BN_GENCB>::new(unsafe { BN_GENCB_new() })?;
+    // `progress` outlives `callback`, which is only used during generation.
+    unsafe {
+        BN_GENCB_set(
+            *callback,
+            Some(report_progress),
+            Arc::as_ptr(progress) as *mut c_void,
+        );
+    }
+    let evp_pkey = generate_rsa_key_with_callback(size.bits(), RSA_F4, false, *callback)?;
+    Ok(KeyPair::new(evp_pkey)?)
+}
+
+unsafe extern "C" fn report_progress(event: c_int, _n: c_int, callback: *mut BN_GENCB) -> c_int {
+    let progress = &*BN_GENCB_get_arg(callback).cast::<Progress>();
+    match event {
+        CANDIDATE_GENERATED => {
+            progress.candidates.fetch_add(1, Ordering::Relaxed);
+        }
+        PRIME_FOUND => {
+            progress.primes.fetch_add(1, Ordering::Relaxed);
+        }
+        _ => {}
+    }
+    c_int::from(!progress.cancelled.load(Ordering::Relaxed))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rsa::{KeyPair, KeySize};
+    use crate::signature::{self, KeyPair as _, UnparsedPublicKey};
+    use crate::test;
+
+    #[test]
+    fn test_generate_in_background() {
+        test::compile_time_assert_send::<crate::rsa::KeyPairGeneration>();
+
+        let generation = KeyPair::generate_in_background(KeySize::Rsa2048).unwrap();
+        let key_pair = generation.wait().unwrap();
+        assert_eq!(256, key_pair.public_modulus_len());
+
+        let rng = crate::rand::SystemRandom::new();
+        let mut sig = vec![0u8; key_pair.public_modulus_len()];
+        key_pair
+            .sign(&signature::RSA_PKCS1_SHA256, &rng, b"message", &mut sig)
+            .unwrap();
+        UnparsedPublicKey::new(
+            &signature::RSA_PKCS1_2048_8192_SHA256,
+            key_pair.public_key().as_ref(),
+        )
+        .verify(b"message", &sig)
+        .unwrap();
+    }
+
+    #[test]
+    fn test_generate_in_background_progress() {
+        let generation = KeyPair::generate_in_background(KeySize::Rsa2048).unwrap();
+        while !generation.is_finished() {
+            std::thread::yield_now();
+        }
+        assert_eq!(2, generation.primes_found());
+        assert!(generation.candidates_generated() >= 2);
+        generation.wait().unwrap();
+    }
+
+    #[test]
+    fn test_generate_in_background_cancel() {
+        let generation = KeyPair::generate_in_background(KeySize::Rsa8192).unwrap();
+        generation.cancel();
+        assert!(generation.wait().is_err());
+    }
+}
@@ -3,34 +3,53 @@
 // Modifications copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
+use super::generation::KeyPairGeneration;
 use super::{
     encoding,
-    signature::{compute_rsa_signature, RsaEncoding, RsaPadding},
+    signature::{compute_rsa_signature, configure_rsa_digest_pkey_ctx, RsaEncoding, RsaPadding},
     RsaParameters,
 };
 #[cfg(feature = "ring-io")]
 use crate::io;
-#[cfg(feature = "ring-io")]
-use crate::ptr::ConstPointer;
+#[cfg(feature = "jwk")]
+use crate::jwk::Jwk;
+#[cfg(feature = "ssh")]
+use crate::{der, ssh};
 use crate::{
-    digest::{self},
-    encoding::{AsDer, Pkcs8V1Der},
+    digest::{self, digest_ctx::DigestContext},
+    encoding::{
+        AsDer, Pkcs8V1Der, PublicKeyX509Der, RsaPrivateKeyRfc8017Der, RsaPublicKeyRfc8017Der,
+    },
     error::{KeyRejected, Unspecified},
     fips::indicator_check,
-    hex,
-    ptr::{DetachableLcPtr, LcPtr, Pointer},
+    hex, policy,
+    ptr::{ConstPointer, DetachableLcPtr, LcPtr, Pointer},
     rand,
     sealed::Sealed,
+    signature::{DigestSigner, Signer},
 };
-#[cfg(feature = "fips")]
-use aws_lc::RSA_check_fips;
+#[cfg(any(feature = "ssh", feature = "fips"))]
+use aws_lc::BN_dup;
+#[cfg(not(feature = "fips"))]
+use aws_lc::RSA_new_private_key;
 use aws_lc::{
-    EVP_DigestSignInit, EVP_PKEY_assign_RSA, EVP_PKEY_bits, EVP_PKEY_new, EVP_PKEY_size,
-    RSA_generate_key_ex, RSA_generate_key_fips, RSA_new, RSA_set0_key, RSA_size, BIGNUM, EVP_PKEY,
-    EVP_PKEY_CTX,
+    BN_CTX_new, BN_new, BN_primality_test, BN_prime_checks_for_validation, BN_sub,
+    EVP_DigestSignFinal, EVP_DigestSignInit, EVP_DigestSignUpdate, EVP_PKEY_CTX_new,
+    EVP_PKEY_assign_RSA, EVP_PKEY_bits, EVP_PKEY_new, EVP_PKEY_sign, EVP_PKEY_sign_init,
+    EVP_PKEY_size, RSA_check_fips, RSA_generate_key_ex, RSA_generate_key_fips, RSA_get0_d,
+    RSA_get0_e, RSA_get0_n, RSA_get0_p, RSA_get0_q, RSA_new, RSA_set0_key, RSA_size, BIGNUM,
+    BN_GENCB, EVP_PKEY, EVP_PKEY_CTX, RSA,
 };
-#[cfg(feature = "ring-io")]
-use aws_lc::{RSA_get0_e, RSA_get0_n};
+#[cfg(feature = "ssh")]
+use aws_lc::{BN_div, BN_sub_word, BN_CTX};
+#[cfg(feature = "fips")]
+use aws_lc::{RSA_check_key, RSA_set0_crt_params, RSA_set0_factors};
+#[cfg(any(feature = "jwk", feature = "ssh", feature = "rsa-key-components"))]
+use aws_lc::{RSA_get0_crt_params, RSA_get0_factors, RSA_get0_key};
+#[cfg(feature = "unstable")]
+use aws_lc::{RSA_sign_raw, RSA_verify_raw, RSA_NO_PADDING};
+#[cfg(any(feature = "jwk", feature = "ssh", feature = "rsa-key-components"))]
+use core::ptr::null;
 use core::{
     fmt::{self, Debug, Formatter},
     ptr::null_mut,
@@ -107,7 +126,7 @@ unsafe impl Send for KeyPair {}
 unsafe impl Sync for KeyPair {}
 
 impl KeyPair {
-    fn new(evp_pkey: LcPtr<EVP_PKEY>) -> Result<Self, KeyRejected> {
+    pub(super) fn new(evp_pkey: LcPtr<EVP_PKEY>) -> Result<Self, KeyRejected> {
         KeyPair::validate_private_key(&evp_pkey)?;
         let serialized_public_key = unsafe { PublicKey::new(&evp_pkey)? };
         Ok(KeyPair {
@@ -116,15 +135,45 @@ impl KeyPair {
         })
     }
 
+    #[cfg(feature = "x509")]
+    pub(crate) fn evp_pkey(&self) -> &LcPtr<EVP_PKEY> {
+        &self.evp_pkey
+    }
+
     /// Generate a RSA `KeyPair` of the specified key-strength.
     ///
     /// # Errors
     /// * `Unspecified`: Any key generation failure.
     pub fn generate(size: KeySize) -> Result<Self, Unspecified> {
-        let private_key = generate_rsa_key(size.bits(), false)?;
+        Self::generate_with_exponent(size, RSA_F4)
+    }
+
+    /// Generate a RSA `KeyPair` of the specified key-strength with the given public exponent.
+    ///
+    /// [`KeyPair::generate`] uses a public exponent of 65537, which should be preferred unless
+    /// interoperability requires otherwise.
+    ///
+    /// # Errors
+    /// * `Unspecified`: If `public_exponent` is even or less than 3, or for any key generation
+    ///   failure.
+    pub fn generate_with_exponent(
+        size: KeySize,
+        public_exponent: u32,
+    ) -> Result<Self, Unspecified> {
+        let private_key = generate_rsa_key(size.bits(), public_exponent, false)?;
         Ok(Self::new(private_key)?)
     }
 
+    /// Starts generating a RSA `KeyPair` of the specified key-strength on a background thread,
+    /// as [`KeyPair::generate`] does. The returned [`KeyPairGeneration`] reports progress and
+    /// can cancel the generation, which can take seconds for larger keys.
+    ///
+    /// # Errors
+    /// * `Unspecified`: If the thread cannot be spawned.
+    pub fn generate_in_background(size: KeySize) -> Result<KeyPairGeneration, Unspecified> {
+        KeyPairGeneration::spawn(size)
+    }
+
     /// Generate a RSA `KeyPair` of the specified key-strength.
     ///
     /// Supports the following key sizes:
@@ -136,7 +185,7 @@ impl KeyPair {
     /// * `Unspecified`: Any key generation failure.
     #[cfg(feature = "fips")]
     pub fn generate_fips(size: KeySize) -> Result<Self, Unspecified> {
-        let private_key = generate_rsa_key(size.bits(), true)?;
+        let private_key = generate_rsa_key(size.bits(), RSA_F4, true)?;
         Ok(Self::new(private_key)?)
     }
 
@@ -169,6 +218,208 @@ impl KeyPair {
         Self::new(key)
     }
 
+    /// Serializes the key pair as a DER-encoded `RSAPrivateKey` structure (RFC 8017), the
+    /// format read by `KeyPair::from_der`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    pub fn to_der(&self) -> Result<RsaPrivateKeyRfc8017Der<'static>, Unspecified> {
+        encoding::rfc8017::encode_private_key_der(&self.evp_pkey)
+    }
+
+    /// Constructs a key pair from an `RSA` JSON Web Key (RFC 7518, section 6.3) with the private
+    /// key members `d`, `p`, `q`, `dp`, `dq` and `qi`.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the key's `kty` is not `RSA`, a member is missing or malformed,
+    /// the key has more than two primes (`oth`), or the key is otherwise unacceptable.
+    #[cfg(feature = "jwk")]
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, KeyRejected> {
+        jwk.expect("RSA", None)?;
+        if jwk.contains("oth") {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        let mut components = Vec::with_capacity(8);
+        for name in ["n", "e", "d", "p", "q", "dp", "dq", "qi"] {
+            let mut bytes = jwk.bytes(name)?;
+            let bn = LcPtr::<BIGNUM>::try_from(bytes.as_slice());
+            bytes.zeroize();
+            components.push(bn.map_err(|()| KeyRejected::unexpected_error())?);
+        }
+        Self::from_bignums(&components)
+    }
+
+    /// Constructs a key pair from its public and private components, e.g. from a JSON Web Key
+    /// with private key members or from a system that stores the CRT parameters separately.
+    ///
+    /// The components must be consistent: `n = p * q`, `d` must be an inverse of `e`, and
+    /// `dP`, `dQ` and `qInv` must be the CRT parameters of `d`, `p` and `q`.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `n` or `e` is empty or has leading zeros, if the components are
+    /// inconsistent, or if the key is otherwise unacceptable.
+    pub fn from_components<Public, Private>(
+        components: &KeyPairComponents<Public, Private>,
+    ) -> Result<Self, KeyRejected>
+    where
+        Public: AsRef<[u8]> + Debug,
+        Private: AsRef<[u8]>,
+    {
+        let public_key = &components.public_key;
+        let (n, e) = (public_key.n.as_ref(), public_key.e.as_ref());
+        if n.first().map_or(true, |&b| b == 0) || e.first().map_or(true, |&b| b == 0) {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        let mut bignums = Vec::with_capacity(8);
+        for bytes in [
+            n,
+            e,
+            components.d.as_ref(),
+            components.p.as_ref(),
+            components.q.as_ref(),
+            components.dP.as_ref(),
+            components.dQ.as_ref(),
+            components.qInv.as_ref(),
+        ] {
+            bignums.push(LcPtr::<BIGNUM>::try_from(bytes)?);
+        }
+        Self::from_bignums(&bignums)
+    }
+
+    // Constructs a key pair from `n`, `e`, `d`, `p`, `q`, `dp`, `dq` and `qi`, in that order.
+    fn from_bignums(components: &[LcPtr<BIGNUM>]) -> Result<Self, KeyRejected> {
+        let rsa = unsafe { new_private_key(components) }
+            .map_err(|()| KeyRejected::inconsistent_components())?;
+        let evp_pkey = LcPtr::new(unsafe { EVP_PKEY_new() })?;
+        if 1 != unsafe { EVP_PKEY_assign_RSA(*evp_pkey, *rsa) } {
+            return Err(KeyRejected::unexpected_error());
+        }
+        rsa.detach();
+
+        Self::new(evp_pkey)
+    }
+
+    /// Serializes the key pair as an `RSA` JSON Web Key (RFC 7518, section 6.3), including the
+    /// private key members.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    #[cfg(feature = "jwk")]
+    #[allow(clippy::many_single_char_names)]
+    pub fn to_jwk(&self) -> Result<Jwk, Unspecified> {
+        let [n, e, d, p, q, dp, dq, qi] = self.raw_components()?;
+
+        let mut jwk = Jwk::new("RSA");
+        for (name, bn) in [
+            ("n", n),
+            ("e", e),
+            ("d", d),
+            ("p", p),
+            ("q", q),
+            ("dp", dp),
+            ("dq", dq),
+            ("qi", qi),
+        ] {
+            let mut bytes = ConstPointer::new(bn)?.to_be_bytes();
+            jwk.set_bytes(name, &bytes);
+            bytes.zeroize();
+        }
+        Ok(jwk)
+    }
+
+    // Returns `n`, `e`, `d`, `p`, `q`, `dp`, `dq` and `qi`, in that order.
+    #[cfg(any(feature = "jwk", feature = "ssh", feature = "rsa-key-components"))]
+    #[allow(clippy::many_single_char_names)]
+    fn raw_components(&self) -> Result<[*const BIGNUM; 8], Unspecified> {
+        let rsa = self.evp_pkey.get_rsa()?;
+        let mut components = [null::<BIGNUM>(); 8];
+        let [n, e, d, p, q, dp, dq, qi] = &mut components;
+        unsafe {
+            RSA_get0_key(*rsa, n, e, d);
+            RSA_get0_factors(*rsa, p, q);
+            RSA_get0_crt_params(*rsa, dp, dq, qi);
+        }
+        Ok(components)
+    }
+
+    /// Exports the key pair's public and private components, e.g. to serialize it in a format
+    /// this crate does not write or to import it into an HSM.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    #[cfg(feature = "rsa-key-components")]
+    #[allow(clippy::many_single_char_names)]
+    pub fn private_key_components(&self) -> Result<PrivateKeyComponents, Unspecified> {
+        let [n, e, d, p, q, dp, dq, qi] = self.raw_components()?;
+        Ok(PrivateKeyComponents {
+            n: ConstPointer::new(n)?.to_be_bytes(),
+            e: ConstPointer::new(e)?.to_be_bytes(),
+            d: ConstPointer::new(d)?.to_be_bytes(),
+            p: ConstPointer::new(p)?.to_be_bytes(),
+            q: ConstPointer::new(q)?.to_be_bytes(),
+            dp: ConstPointer::new(dp)?.to_be_bytes(),
+            dq: ConstPointer::new(dq)?.to_be_bytes(),
+            qinv: ConstPointer::new(qi)?.to_be_bytes(),
+        })
+    }
+
+    /// Constructs a key pair from an `ssh-rsa` OpenSSH private key.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the key is not an `ssh-rsa` key, or is otherwise unacceptable.
+    #[cfg(feature = "ssh")]
+    #[allow(clippy::many_single_char_names)]
+    pub fn from_openssh(key: &ssh::PrivateKey) -> Result<Self, KeyRejected> {
+        let (n, e, d, p, q, qi) = match key.fields()? {
+            (ssh::PublicKeyFields::Rsa { e, n }, ssh::PrivateKeyFields::Rsa { d, iqmp, p, q }) => {
+                (n, e, d, p, q, iqmp)
+            }
+            _ => return Err(KeyRejected::wrong_algorithm()),
+        };
+        let mut components = Vec::with_capacity(8);
+        for bytes in [n, e, d, p, q] {
+            components.push(LcPtr::<BIGNUM>::try_from(bytes)?);
+        }
+        // OpenSSH keys omit the CRT exponents `d mod (p - 1)` and `d mod (q - 1)`.
+        let bn_ctx = LcPtr::new(unsafe { BN_CTX_new() })?;
+        let dp = crt_exponent(&components[2], &components[3], &bn_ctx)?;
+        let dq = crt_exponent(&components[2], &components[4], &bn_ctx)?;
+        components.push(dp);
+        components.push(dq);
+        components.push(LcPtr::<BIGNUM>::try_from(qi)?);
+        Self::from_bignums(&components)
+    }
+
+    /// Serializes the key pair as an `ssh-rsa` OpenSSH private key, with an empty comment.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    #[cfg(feature = "ssh")]
+    #[allow(clippy::many_single_char_names)]
+    pub fn to_openssh(&self) -> Result<ssh::PrivateKey, Unspecified> {
+        let [n, e, d, p, q, _, _, qi] = self.raw_components()?;
+        let n = ConstPointer::new(n)?.to_be_bytes();
+        let e = ConstPointer::new(e)?.to_be_bytes();
+        let mut d = ConstPointer::new(d)?.to_be_bytes();
+        let mut p = ConstPointer::new(p)?.to_be_bytes();
+        let mut q = ConstPointer::new(q)?.to_be_bytes();
+        let mut qi = ConstPointer::new(qi)?.to_be_bytes();
+        let key = ssh::PrivateKey::new(
+            &ssh::PublicKeyFields::Rsa { e: &e, n: &n },
+            &ssh::PrivateKeyFields::Rsa {
+                d: &d,
+                iqmp: &qi,
+                p: &p,
+                q: &q,
+            },
+        );
+        d.zeroize();
+        p.zeroize();
+        q.zeroize();
+        qi.zeroize();
+        key
+    }
+
     /// Returns a boolean indicator if this RSA key is an approved FIPS 140-3 key.
     #[cfg(feature = "fips")]
     #[must_use]
@@ -176,6 +427,107 @@ impl KeyPair {
         is_valid_fips_key(&self.evp_pkey)
     }
 
+    /// Validates the key pair as specified by FIPS 186-5 and SP 800-89, e.g. before using key
+    /// material imported from elsewhere.
+    ///
+    /// This checks that the components are consistent, that `p` and `q` are prime (with a
+    /// false positive rate of at most 2^-128), that `e` is odd and between 2^16 and 2^256, that
+    /// `|p - q| > 2^(nlen/2 - 100)` and `d > 2^(nlen/2)`, and that the key passes a signing
+    /// pairwise consistency test. It takes much longer than constructing the key pair.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the key pair fails any of the checks.
+    pub fn validate(&self) -> Result<(), KeyRejected> {
+        let rsa = self.evp_pkey.get_rsa()?;
+        // Checks the consistency of the components, the range of `e`, and that `n` is a
+        // composite without small factors, and performs the pairwise consistency test.
+        if 1 != unsafe { RSA_check_fips(*rsa) } {
+            return Err(KeyRejected::inconsistent_components());
+        }
+
+        let (p, q, d) = unsafe {
+            (
+                ConstPointer::new(RSA_get0_p(*rsa))?,
+                ConstPointer::new(RSA_get0_q(*rsa))?,
+                ConstPointer::new(RSA_get0_d(*rsa))?,
+            )
+        };
+        let bn_ctx = LcPtr::new(unsafe { BN_CTX_new() })?;
+        for prime in [&p, &q] {
+            let mut is_probably_prime = 0;
+            if 1 != unsafe {
+                BN_primality_test(
+                    &mut is_probably_prime,
+                    **prime,
+                    BN_prime_checks_for_validation,
+                    *bn_ctx,
+                    1,
+                    null_mut(),
+                )
+            } {
+                return Err(KeyRejected::unexpected_error());
+            }
+            if is_probably_prime != 1 {
+                return Err(KeyRejected::invalid_component());
+            }
+        }
+
+        // FIPS 186-5 Appendix A.1.3 and Section 5.1.
+        let half_bits = u32::try_from(key_size_bits(&self.evp_pkey) / 2)
+            .map_err(|_| KeyRejected::unexpected_error())?;
+        let difference = LcPtr::new(unsafe { BN_new() })?;
+        if 1 != unsafe { BN_sub(*difference, *p, *q) } {
+            return Err(KeyRejected::unexpected_error());
+        }
+        if difference.as_const().num_bits() <= half_bits - 100 || d.num_bits() <= half_bits {
+            return Err(KeyRejected::invalid_component());
+        }
+        Ok(())
+    }
+
+    /// Runs a signing pairwise consistency test (PCT) on the key pair: signs a test digest with
+    /// the private key and verifies the signature with the public key.
+    ///
+    /// FIPS key generation already runs this test, and fails rather than return a key pair that
+    /// does not pass it. Calling it again gives high-assurance deployments evidence that the
+    /// check ran, e.g. to log it or to refuse provisioning a key.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the key pair fails the test.
+    pub fn pairwise_consistency_test(&self) -> Result<(), Unspecified> {
+        let rsa = self.evp_pkey.get_rsa()?;
+        // Also performs the basic consistency checks of `RSA_check_key`.
+        if 1 != unsafe { RSA_check_fips(*rsa) } {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+
+    // Performs the raw RSA private key operation, `input^d mod n`, with blinding.
+    #[cfg(feature = "unstable")]
+    pub(crate) fn private_transform<'output>(
+        &self,
+        input: &[u8],
+        output: &'output mut [u8],
+    ) -> Result<&'output mut [u8], Unspecified> {
+        let rsa = self.evp_pkey.get_rsa()?;
+        let mut out_len = 0;
+        if 1 != unsafe {
+            RSA_sign_raw(
+                *rsa,
+                &mut out_len,
+                output.as_mut_ptr(),
+                output.len(),
+                input.as_ptr(),
+                input.len(),
+                RSA_NO_PADDING,
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok(&mut output[..out_len])
+    }
+
     fn validate_private_key(key: &LcPtr<EVP_PKEY>) -> Result<(), KeyRejected> {
         if !is_rsa_key(key) {
             return Err(KeyRejected::unspecified());
@@ -214,9 +566,106 @@ impl KeyPair {
         msg: &[u8],
         signature: &mut [u8],
     ) -> Result<(), Unspecified> {
+        let mut md_ctx = self.digest_sign_init(padding_alg)?;
+
+        let max_len = super::signature::get_signature_length(&mut md_ctx)?;
+
+        debug_assert!(signature.len() >= max_len);
+
+        let computed_signature = compute_rsa_signature(&mut md_ctx, msg, signature)?;
+
+        debug_assert!(computed_signature.len() >= signature.len());
+
+        Ok(())
+    }
+
+    /// Returns the length in bytes of the key pair's public modulus.
+    ///
+    /// A signature has the same length as the public modulus.
+    #[must_use]
+    pub fn public_modulus_len(&self) -> usize {
+        // This was already validated to be an RSA key so this can't fail
+        match self.evp_pkey.get_rsa() {
+            Ok(rsa) => {
+                // https://github.com/awslabs/aws-lc/blob/main/include/openssl/rsa.h#L99
+                unsafe { RSA_size(*rsa) as usize }
+            }
+            Err(_) => verify_unreachable!(),
+        }
+    }
+
+    /// Begins a multi-part signing operation using `padding_alg`, allowing the message to be
+    /// provided incrementally via [`RsaSigningContext::update`] rather than buffered in memory.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on error.
+    pub fn sign_context(
+        &self,
+        padding_alg: &'static dyn RsaEncoding,
+    ) -> Result<RsaSigningContext<'_>, Unspecified> {
+        Ok(RsaSigningContext {
+            key_pair: self,
+            md_ctx: self.digest_sign_init(padding_alg)?,
+        })
+    }
+
+    // # FIPS
+    // Signing a precomputed digest is not a FIPS-approved service.
+    /// Signs a precomputed message `digest`, for callers that have already hashed the message
+    /// with the digest algorithm from `padding_alg`. The digest is padded using the padding
+    /// algorithm from `padding_alg` and the signature is written into `signature`;
+    /// `signature`'s length must be exactly the length returned by `public_modulus_len()`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on error, or if `digest` or `signature` has the wrong length.
+    pub fn sign_digest(
+        &self,
+        padding_alg: &'static dyn RsaEncoding,
+        digest: &[u8],
+        signature: &mut [u8],
+    ) -> Result<(), Unspecified> {
+        let encoding = padding_alg.encoding();
+        let digest_alg = encoding.digest_algorithm();
+        if digest.len() != digest_alg.output_len() || signature.len() != self.public_modulus_len() {
+            return Err(Unspecified);
+        }
+        self.enforce_signing_policy(digest_alg)?;
+
+        let pctx = LcPtr::new(unsafe { EVP_PKEY_CTX_new(*self.evp_pkey, null_mut()) })?;
+        if 1 != unsafe { EVP_PKEY_sign_init(*pctx) } {
+            return Err(Unspecified);
+        }
+        configure_rsa_digest_pkey_ctx(
+            &pctx,
+            digest_alg,
+            encoding.padding(),
+            encoding.pss_salt_length().signing_salt_len(),
+        )?;
+
+        let mut out_sig_len = signature.len();
+        if 1 != indicator_check!(unsafe {
+            EVP_PKEY_sign(
+                *pctx,
+                signature.as_mut_ptr(),
+                &mut out_sig_len,
+                digest.as_ptr(),
+                digest.len(),
+            )
+        }) {
+            return Err(Unspecified);
+        }
+
+        Ok(())
+    }
+
+    fn digest_sign_init(
+        &self,
+        padding_alg: &'static dyn RsaEncoding,
+    ) -> Result<DigestContext, Unspecified> {
         let encoding = padding_alg.encoding();
+        self.enforce_signing_policy(encoding.digest_algorithm())?;
 
-        let mut md_ctx = digest::digest_ctx::DigestContext::new_uninit();
+        let mut md_ctx = DigestContext::new_uninit();
         let mut pctx = null_mut::<EVP_PKEY_CTX>();
         let digest = digest::match_digest_type(&encoding.digest_algorithm().id);
 
@@ -235,33 +684,82 @@ impl KeyPair {
         if let RsaPadding::RSA_PKCS1_PSS_PADDING = encoding.padding() {
             // AWS-LC owns pctx, check for null and then immediately detach so we don't drop it.
             let pctx = DetachableLcPtr::new(pctx)?.detach();
-            super::signature::configure_rsa_pkcs1_pss_padding(pctx)?;
+            super::signature::configure_rsa_pkcs1_pss_padding(
+                pctx,
+                encoding.pss_salt_length().signing_salt_len(),
+            )?;
         }
 
-        let max_len = super::signature::get_signature_length(&mut md_ctx)?;
+        Ok(md_ctx)
+    }
 
-        debug_assert!(signature.len() >= max_len);
+    // Checks the installed policy against signing with this key and `digest_algorithm`.
+    pub(crate) fn enforce_signing_policy(
+        &self,
+        digest_algorithm: &'static digest::Algorithm,
+    ) -> Result<(), Unspecified> {
+        policy::enforce_digest(digest_algorithm)?;
+        policy::enforce_rsa_modulus_bits(u32::try_from(key_size_bits(&self.evp_pkey))?)?;
+        Ok(())
+    }
+}
 
-        let computed_signature = compute_rsa_signature(&mut md_ctx, msg, signature)?;
+/// A multi-part RSA signing operation, created by [`KeyPair::sign_context`].
+#[allow(clippy::module_name_repetitions)]
+pub struct RsaSigningContext<'a> {
+    key_pair: &'a KeyPair,
+    md_ctx: DigestContext,
+}
 
-        debug_assert!(computed_signature.len() >= signature.len());
+impl Debug for RsaSigningContext<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("RsaSigningContext")
+            .field("key_pair", self.key_pair)
+            .finish_non_exhaustive()
+    }
+}
 
+impl RsaSigningContext<'_> {
+    /// Updates the signing operation with the next part of the message.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on error.
+    pub fn update(&mut self, data: &[u8]) -> Result<(), Unspecified> {
+        if 1 != unsafe {
+            EVP_DigestSignUpdate(self.md_ctx.as_mut_ptr(), data.as_ptr().cast(), data.len())
+        } {
+            return Err(Unspecified);
+        }
         Ok(())
     }
 
-    /// Returns the length in bytes of the key pair's public modulus.
+    // # FIPS
+    // The following conditions must be met:
+    // * RSA Key Sizes: 2048, 3072, 4096
+    // * Digest Algorithms: SHA256, SHA384, SHA512
+    /// Completes the signing operation, writing the signature of all data provided via
+    /// [`Self::update`] into `signature`. `signature`'s length must be exactly the length
+    /// returned by [`KeyPair::public_modulus_len`].
     ///
-    /// A signature has the same length as the public modulus.
-    #[must_use]
-    pub fn public_modulus_len(&self) -> usize {
-        // This was already validated to be an RSA key so this can't fail
-        match self.evp_pkey.get_rsa() {
-            Ok(rsa) => {
-                // https://github.com/awslabs/aws-lc/blob/main/include/openssl/rsa.h#L99
-                unsafe { RSA_size(*rsa) as usize }
-            }
-            Err(_) => verify_unreachable!(),
+    /// # Errors
+    /// `error::Unspecified` on error, or if `signature` has the wrong length.
+    pub fn finish(mut self, signature: &mut [u8]) -> Result<(), Unspecified> {
+        if signature.len() != self.key_pair.public_modulus_len() {
+            return Err(Unspecified);
+        }
+
+        let mut out_sig_len = signature.len();
+        if 1 != indicator_check!(unsafe {
+            EVP_DigestSignFinal(
+                self.md_ctx.as_mut_ptr(),
+                signature.as_mut_ptr(),
+                &mut out_sig_len,
+            )
+        }) {
+            return Err(Unspecified);
         }
+
+        Ok(())
     }
 }
 
@@ -290,6 +788,191 @@ impl AsDer<Pkcs8V1Der<'static>> for KeyPair {
     }
 }
 
+/// The public and private components of an RSA key pair, exported by
+/// [`KeyPair::private_key_components`].
+///
+/// Each component is encoded in big-endian bytes without leading zeros. The private components
+/// are zeroized when this is dropped.
+#[cfg(feature = "rsa-key-components")]
+pub struct PrivateKeyComponents {
+    n: Vec<u8>,
+    e: Vec<u8>,
+    d: Vec<u8>,
+    p: Vec<u8>,
+    q: Vec<u8>,
+    dp: Vec<u8>,
+    dq: Vec<u8>,
+    qinv: Vec<u8>,
+}
+
+#[cfg(feature = "rsa-key-components")]
+impl PrivateKeyComponents {
+    /// The public modulus (n).
+    #[must_use]
+    pub fn n(&self) -> &[u8] {
+        &self.n
+    }
+
+    /// The public exponent (e).
+    #[must_use]
+    pub fn e(&self) -> &[u8] {
+        &self.e
+    }
+
+    /// The private exponent (d).
+    #[must_use]
+    pub fn d(&self) -> &[u8] {
+        &self.d
+    }
+
+    /// The first prime factor (p).
+    #[must_use]
+    pub fn p(&self) -> &[u8] {
+        &self.p
+    }
+
+    /// The second prime factor (q).
+    #[must_use]
+    pub fn q(&self) -> &[u8] {
+        &self.q
+    }
+
+    /// The first CRT exponent, `d mod (p - 1)`.
+    #[must_use]
+    pub fn dp(&self) -> &[u8] {
+        &self.dp
+    }
+
+    /// The second CRT exponent, `d mod (q - 1)`.
+    #[must_use]
+    pub fn dq(&self) -> &[u8] {
+        &self.dq
+    }
+
+    /// The CRT coefficient, `q^-1 mod p`.
+    #[must_use]
+    pub fn qinv(&self) -> &[u8] {
+        &self.qinv
+    }
+}
+
+#[cfg(feature = "rsa-key-components")]
+impl Drop for PrivateKeyComponents {
+    fn drop(&mut self) {
+        self.d.zeroize();
+        self.p.zeroize();
+        self.q.zeroize();
+        self.dp.zeroize();
+        self.dq.zeroize();
+        self.qinv.zeroize();
+    }
+}
+
+#[cfg(feature = "rsa-key-components")]
+impl Debug for PrivateKeyComponents {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RsaPrivateKeyComponents")
+            .field("n", &hex::encode(&self.n))
+            .field("e", &hex::encode(&self.e))
+            .finish_non_exhaustive()
+    }
+}
+
+/// The public and private components of an RSA key pair, for
+/// [`KeyPair::from_components`].
+///
+/// Each component is encoded in big-endian bytes.
+#[allow(clippy::module_name_repetitions, non_snake_case)]
+#[derive(Clone, Copy)]
+pub struct KeyPairComponents<Public, Private = Public>
+where
+    Public: AsRef<[u8]> + Debug,
+    Private: AsRef<[u8]>,
+{
+    /// The public key components.
+    pub public_key: PublicKeyComponents<Public>,
+    /// The private exponent.
+    pub d: Private,
+    /// The first prime factor of `n`.
+    pub p: Private,
+    /// The second prime factor of `n`.
+    pub q: Private,
+    /// The first CRT exponent, `d mod (p - 1)`.
+    pub dP: Private,
+    /// The second CRT exponent, `d mod (q - 1)`.
+    pub dQ: Private,
+    /// The CRT coefficient, `q^-1 mod p`.
+    pub qInv: Private,
+}
+
+impl<Public, Private> Debug for KeyPairComponents<Public, Private>
+where
+    Public: AsRef<[u8]> + Debug,
+    Private: AsRef<[u8]>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Debug-printing the private components would leak them.
+        f.debug_struct("RsaKeyPairComponents")
+            .field("public_key", &self.public_key)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An RSA key pair paired with the signature encoding it signs with, for use as a
+/// [`crate::signature::Signer`].
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug)]
+pub struct RsaSigner {
+    key_pair: KeyPair,
+    encoding: &'static dyn RsaEncoding,
+}
+
+impl RsaSigner {
+    /// Constructs a signer that signs with `key_pair` using `encoding`.
+    #[must_use]
+    pub fn new(key_pair: KeyPair, encoding: &'static dyn RsaEncoding) -> Self {
+        Self { key_pair, encoding }
+    }
+
+    /// The key pair used for signing.
+    #[must_use]
+    pub fn key_pair(&self) -> &KeyPair {
+        &self.key_pair
+    }
+}
+
+impl Signer for RsaSigner {
+    fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>, Unspecified> {
+        let mut signature = vec![0u8; self.key_pair.public_modulus_len()];
+        self.key_pair.sign(
+            self.encoding,
+            &rand::SystemRandom::new(),
+            message,
+            &mut signature,
+        )?;
+        Ok(signature)
+    }
+
+    #[inline]
+    fn public_key_bytes(&self) -> &[u8] {
+        self.key_pair.serialized_public_key.as_ref()
+    }
+}
+
+impl DigestSigner for RsaSigner {
+    fn sign_digest(&self, digest: &[u8]) -> Result<Vec<u8>, Unspecified> {
+        let mut signature = vec![0u8; self.key_pair.public_modulus_len()];
+        self.key_pair
+            .sign_digest(self.encoding, digest, &mut signature)?;
+        Ok(signature)
+    }
+
+    #[inline]
+    fn public_key_bytes(&self) -> &[u8] {
+        self.key_pair.serialized_public_key.as_ref()
+    }
+}
+
 /// A serialized RSA public key.
 #[derive(Clone)]
 #[allow(clippy::module_name_repetitions)]
@@ -312,7 +995,7 @@ impl Drop for PublicKey {
 }
 
 impl PublicKey {
-    pub(super) unsafe fn new(evp_pkey: &LcPtr<EVP_PKEY>) -> Result<Self, Unspecified> {
+    pub(crate) unsafe fn new(evp_pkey: &LcPtr<EVP_PKEY>) -> Result<Self, Unspecified> {
         let key = encoding::rfc8017::encode_public_key_der(evp_pkey)?;
         #[cfg(feature = "ring-io")]
         {
@@ -333,6 +1016,34 @@ impl PublicKey {
     }
 }
 
+impl PublicKey {
+    // Performs the raw RSA public key operation, `input^e mod n`.
+    #[cfg(feature = "unstable")]
+    pub(crate) fn public_transform<'output>(
+        &self,
+        input: &[u8],
+        output: &'output mut [u8],
+    ) -> Result<&'output mut [u8], Unspecified> {
+        let evp_pkey = encoding::rfc8017::decode_public_key_der(&self.key)?;
+        let rsa = evp_pkey.get_rsa()?;
+        let mut out_len = 0;
+        if 1 != unsafe {
+            RSA_verify_raw(
+                *rsa,
+                &mut out_len,
+                output.as_mut_ptr(),
+                output.len(),
+                input.as_ptr(),
+                input.len(),
+                RSA_NO_PADDING,
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok(&mut output[..out_len])
+    }
+}
+
 impl Debug for PublicKey {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
         f.write_str(&format!(
@@ -348,6 +1059,60 @@ impl AsRef<[u8]> for PublicKey {
     }
 }
 
+impl PublicKey {
+    /// Provides the public key as an `RSA` JSON Web Key (RFC 7518, section 6.3.1).
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    #[cfg(feature = "jwk")]
+    pub fn to_jwk(&self) -> Result<Jwk, Unspecified> {
+        let evp_pkey = encoding::rfc8017::decode_public_key_der(&self.key)?;
+        let rsa = evp_pkey.get_rsa()?;
+        let mut jwk = Jwk::new("RSA");
+        jwk.set_bytes(
+            "n",
+            &ConstPointer::new(unsafe { RSA_get0_n(*rsa) })?.to_be_bytes(),
+        );
+        jwk.set_bytes(
+            "e",
+            &ConstPointer::new(unsafe { RSA_get0_e(*rsa) })?.to_be_bytes(),
+        );
+        Ok(jwk)
+    }
+
+    /// Provides the public key as an `ssh-rsa` OpenSSH public key, with an empty comment.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    #[cfg(feature = "ssh")]
+    pub fn to_openssh(&self) -> Result<ssh::PublicKey, Unspecified> {
+        let mut key = der::Reader::new(&self.key).read_sequence()?;
+        let n = key.read_unsigned_integer()?;
+        let e = key.read_unsigned_integer()?;
+        key.finish()?;
+        Ok(ssh::PublicKey::new(&ssh::PublicKeyFields::Rsa { e, n }))
+    }
+}
+
+impl AsDer<RsaPublicKeyRfc8017Der<'static>> for PublicKey {
+    /// Provides the public key as a DER-encoded `RSAPublicKey` structure (RFC 8017). These are
+    /// the same bytes as `as_ref`.
+    fn as_der(&self) -> Result<RsaPublicKeyRfc8017Der<'static>, Unspecified> {
+        Ok(RsaPublicKeyRfc8017Der::new(self.key.to_vec()))
+    }
+}
+
+impl AsDer<PublicKeyX509Der<'static>> for PublicKey {
+    /// Provides the public key as a DER-encoded (X.509) `SubjectPublicKeyInfo` structure.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if serialization failed.
+    fn as_der(&self) -> Result<PublicKeyX509Der<'static>, Unspecified> {
+        let evp_pkey = encoding::rfc8017::decode_public_key_der(&self.key)?;
+        encoding::rfc5280::encode_public_key_der(&evp_pkey)
+    }
+}
+
 #[cfg(feature = "ring-io")]
 impl PublicKey {
     /// The public modulus (n).
@@ -394,12 +1159,31 @@ impl<B: AsRef<[u8]> + Debug> Debug for PublicKeyComponents<B> {
 
 impl<B: Copy + AsRef<[u8]> + Debug> Copy for PublicKeyComponents<B> {}
 
+impl<B> TryFrom<&PublicKey> for PublicKeyComponents<B>
+where
+    B: AsRef<[u8]> + Debug + FromIterator<u8>,
+{
+    type Error = Unspecified;
+
+    /// Provides the components of `public_key`.
+    fn try_from(public_key: &PublicKey) -> Result<Self, Self::Error> {
+        let evp_pkey = encoding::rfc8017::decode_public_key_der(&public_key.key)?;
+        let rsa = evp_pkey.get_rsa()?;
+        let n = ConstPointer::new(unsafe { RSA_get0_n(*rsa) })?.to_be_bytes();
+        let e = ConstPointer::new(unsafe { RSA_get0_e(*rsa) })?.to_be_bytes();
+        Ok(PublicKeyComponents {
+            n: n.into_iter().collect(),
+            e: e.into_iter().collect(),
+        })
+    }
+}
+
 impl<B> PublicKeyComponents<B>
 where
     B: AsRef<[u8]> + Debug,
 {
     #[inline]
-    unsafe fn build_rsa(&self) -> Result<LcPtr<EVP_PKEY>, ()> {
+    pub(crate) unsafe fn build_rsa(&self) -> Result<LcPtr<EVP_PKEY>, ()> {
         let n_bytes = self.n.as_ref();
         if n_bytes.is_empty() || n_bytes[0] == 0u8 {
             return Err(());
@@ -428,6 +1212,15 @@ where
         Ok(pkey)
     }
 
+    /// The public key as a DER-encoded `RSAPublicKey` structure (RFC 8017).
+    #[cfg(any(feature = "jwk", feature = "ssh"))]
+    pub(crate) fn to_der(&self) -> Result<Box<[u8]>, Unspecified> {
+        unsafe {
+            let rsa = self.build_rsa()?;
+            encoding::rfc8017::encode_public_key_der(&rsa)
+        }
+    }
+
     /// Verifies that `signature` is a valid signature of `message` using `self`
     /// as the public key. `params` determine what algorithm parameters
     /// (padding, digest algorithm, key length range, etc.) are used in the
@@ -446,6 +1239,7 @@ where
             super::signature::verify_rsa_signature(
                 params.digest_algorithm(),
                 params.padding(),
+                params.pss_salt_length(),
                 &rsa,
                 message,
                 signature,
@@ -455,20 +1249,134 @@ where
     }
 }
 
-pub(super) fn generate_rsa_key(size: c_int, fips: bool) -> Result<LcPtr<EVP_PKEY>, Unspecified> {
+#[cfg(feature = "jwk")]
+impl PublicKeyComponents<Vec<u8>> {
+    /// Constructs the public key components from the `n` and `e` members of an `RSA` JSON Web Key
+    /// (RFC 7518, section 6.3.1).
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the key's `kty` is not `RSA`, or a member is missing or malformed.
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, KeyRejected> {
+        jwk.expect("RSA", None)?;
+        let n = jwk.bytes("n")?;
+        let e = jwk.bytes("e")?;
+        // RFC 7518 requires the minimum number of octets, as does `PublicKeyComponents`.
+        if n.first().map_or(true, |&b| b == 0) || e.first().map_or(true, |&b| b == 0) {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        Ok(PublicKeyComponents { n, e })
+    }
+}
+
+// Creates a private key from copies of `n`, `e`, `d`, `p`, `q`, `dp`, `dq` and `qi`, in that
+// order, failing if they are inconsistent.
+#[cfg(not(feature = "fips"))]
+unsafe fn new_private_key(components: &[LcPtr<BIGNUM>]) -> Result<DetachableLcPtr<RSA>, ()> {
+    DetachableLcPtr::new(RSA_new_private_key(
+        *components[0],
+        *components[1],
+        *components[2],
+        *components[3],
+        *components[4],
+        *components[5],
+        *components[6],
+        *components[7],
+    ))
+}
+
+// The FIPS module lacks `RSA_new_private_key`, so the key is assembled from copies of the
+// components and then checked, as `RSA_new_private_key` does.
+#[cfg(feature = "fips")]
+#[allow(clippy::many_single_char_names)]
+unsafe fn new_private_key(components: &[LcPtr<BIGNUM>]) -> Result<DetachableLcPtr<RSA>, ()> {
+    let rsa = DetachableLcPtr::new(RSA_new())?;
+    let mut copies = Vec::with_capacity(components.len());
+    for component in components {
+        copies.push(DetachableLcPtr::new(BN_dup(**component))?);
+    }
+    let [n, e, d, p, q, dp, dq, qi] = <[_; 8]>::try_from(copies).map_err(|_| ())?;
+    // The key takes ownership of the copies.
+    if 1 != RSA_set0_key(*rsa, *n, *e, *d) {
+        return Err(());
+    }
+    n.detach();
+    e.detach();
+    d.detach();
+    if 1 != RSA_set0_factors(*rsa, *p, *q) {
+        return Err(());
+    }
+    p.detach();
+    q.detach();
+    if 1 != RSA_set0_crt_params(*rsa, *dp, *dq, *qi) {
+        return Err(());
+    }
+    dp.detach();
+    dq.detach();
+    qi.detach();
+    if 1 != RSA_check_key(*rsa) {
+        return Err(());
+    }
+    Ok(rsa)
+}
+
+// Computes the CRT exponent `d mod (prime - 1)`.
+#[cfg(feature = "ssh")]
+fn crt_exponent(
+    d: &LcPtr<BIGNUM>,
+    prime: &LcPtr<BIGNUM>,
+    bn_ctx: &LcPtr<BN_CTX>,
+) -> Result<LcPtr<BIGNUM>, KeyRejected> {
+    unsafe {
+        let prime_minus_one = LcPtr::new(BN_dup(**prime))?;
+        let exponent = LcPtr::new(BN_new())?;
+        if 1 != BN_sub_word(*prime_minus_one, 1)
+            || 1 != BN_div(null_mut(), *exponent, **d, *prime_minus_one, **bn_ctx)
+        {
+            return Err(KeyRejected::inconsistent_components());
+        }
+        Ok(exponent)
+    }
+}
+
+/// The default RSA public exponent, 65537.
+pub(super) const RSA_F4: u32 = 65537;
+
+pub(super) fn generate_rsa_key(
+    size: c_int,
+    public_exponent: u32,
+    fips: bool,
+) -> Result<LcPtr<EVP_PKEY>, Unspecified> {
+    generate_rsa_key_with_callback(size, public_exponent, fips, null_mut())
+}
+
+// Generates a key as `generate_rsa_key` does, reporting progress to `callback`, which may be
+// null, and aborting if it returns zero.
+pub(super) fn generate_rsa_key_with_callback(
+    size: c_int,
+    public_exponent: u32,
+    fips: bool,
+    callback: *mut BN_GENCB,
+) -> Result<LcPtr<EVP_PKEY>, Unspecified> {
     // We explicitly don't use `EVP_PKEY_keygen`, as it will force usage of either the FIPS or non-FIPS
     // keygen function based on the whether the build of AWS-LC had FIPS enbaled. Rather we delegate to the desired
     // generation function.
 
-    const RSA_F4: u64 = 65537;
+    // The public exponent must be odd and greater than one.
+    if public_exponent < 3 || public_exponent % 2 == 0 {
+        return Err(Unspecified);
+    }
 
     let rsa = DetachableLcPtr::new(unsafe { RSA_new() })?;
 
     if 1 != if fips {
-        indicator_check!(unsafe { RSA_generate_key_fips(*rsa, size, null_mut()) })
+        // `RSA_generate_key_fips` always uses `RSA_F4`.
+        if public_exponent != RSA_F4 {
+            return Err(Unspecified);
+        }
+        indicator_check!(unsafe { RSA_generate_key_fips(*rsa, size, callback) })
     } else {
-        let e: LcPtr<BIGNUM> = RSA_F4.try_into()?;
-        unsafe { RSA_generate_key_ex(*rsa, size, *e, null_mut()) }
+        let e: LcPtr<BIGNUM> = u64::from(public_exponent).try_into()?;
+        unsafe { RSA_generate_key_ex(*rsa, size, *e, callback) }
     } {
         return Err(Unspecified);
     }
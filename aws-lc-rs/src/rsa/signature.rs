@@ -5,22 +5,26 @@ use std::{
     fmt::{self, Debug, Formatter},
     mem::MaybeUninit,
     ops::RangeInclusive,
+    os::raw::c_int,
     ptr::{null, null_mut},
 };
 
 use aws_lc::{
-    EVP_DigestSign, EVP_DigestVerify, EVP_DigestVerifyInit, EVP_PKEY_CTX_set_rsa_padding,
-    EVP_PKEY_CTX_set_rsa_pss_saltlen, EVP_PKEY_get0_RSA, RSA_bits, RSA_get0_n, EVP_PKEY,
-    EVP_PKEY_CTX, RSA_PKCS1_PSS_PADDING, RSA_PSS_SALTLEN_DIGEST,
+    EVP_DigestSign, EVP_DigestVerify, EVP_DigestVerifyFinal, EVP_DigestVerifyInit,
+    EVP_DigestVerifyUpdate, EVP_PKEY_CTX_new, EVP_PKEY_CTX_set_rsa_padding,
+    EVP_PKEY_CTX_set_rsa_pss_saltlen, EVP_PKEY_CTX_set_signature_md, EVP_PKEY_get0_RSA,
+    EVP_PKEY_verify, EVP_PKEY_verify_init, RSA_bits, RSA_get0_n, EVP_PKEY, EVP_PKEY_CTX,
+    RSA_PKCS1_PSS_PADDING, RSA_PSS_SALTLEN_DIGEST,
 };
 
 use crate::{
     digest::{self, digest_ctx::DigestContext},
     error::Unspecified,
     fips::indicator_check,
+    policy,
     ptr::{ConstPointer, DetachableLcPtr, LcPtr, Pointer},
     sealed::Sealed,
-    signature::VerificationAlgorithm,
+    signature::{DigestVerificationAlgorithm, VerificationAlgorithm},
 };
 
 #[cfg(feature = "ring-sig-verify")]
@@ -36,12 +40,52 @@ pub enum RsaPadding {
     RSA_PKCS1_PSS_PADDING,
 }
 
+/// The salt length used for RSA-PSS signing and verification.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(clippy::module_name_repetitions)]
+pub enum RsaPssSaltLength {
+    /// The salt is the same length as the message digest. This is the salt length used by the
+    /// predefined `RSA_PSS_*` algorithms.
+    Digest,
+
+    /// The salt is the longest that the key's modulus allows.
+    Maximum,
+
+    /// The salt is exactly the given number of bytes.
+    Bytes(u16),
+}
+
+impl RsaPssSaltLength {
+    pub(super) fn signing_salt_len(self) -> c_int {
+        match self {
+            Self::Digest => RSA_PSS_SALTLEN_DIGEST,
+            // AWS-LC picks the maximal salt length when signing with -2.
+            Self::Maximum => -2,
+            Self::Bytes(len) => c_int::from(len),
+        }
+    }
+
+    fn verification_salt_len(self, digest: &'static digest::Algorithm, n_bits: u32) -> c_int {
+        match self {
+            // AWS-LC accepts any salt length when verifying with -2, so the maximal length is
+            // computed from the modulus instead (RFC 8017 Section 9.1.1).
+            Self::Maximum => {
+                let em_len = (n_bits as usize + 6) / 8;
+                c_int::try_from(em_len.saturating_sub(digest.output_len + 2)).unwrap_or(c_int::MAX)
+            }
+            _ => self.signing_salt_len(),
+        }
+    }
+}
+
 /// Parameters for RSA verification.
 pub struct RsaParameters(
     &'static digest::Algorithm,
     &'static RsaPadding,
     RangeInclusive<u32>,
     &'static RsaVerificationAlgorithmId,
+    RsaPssSaltLength,
 );
 
 impl RsaParameters {
@@ -59,6 +103,11 @@ impl RsaParameters {
     pub(crate) fn bit_size_range(&self) -> &RangeInclusive<u32> {
         &self.2
     }
+
+    #[inline]
+    pub(crate) fn pss_salt_length(&self) -> RsaPssSaltLength {
+        self.4
+    }
 }
 
 impl VerificationAlgorithm for RsaParameters {
@@ -86,6 +135,7 @@ impl VerificationAlgorithm for RsaParameters {
         verify_rsa_signature(
             self.digest_algorithm(),
             self.padding(),
+            self.pss_salt_length(),
             &evp_pkey,
             msg,
             signature,
@@ -94,6 +144,21 @@ impl VerificationAlgorithm for RsaParameters {
     }
 }
 
+impl DigestVerificationAlgorithm for RsaParameters {
+    fn digest_algorithm(&self) -> &'static digest::Algorithm {
+        self.0
+    }
+
+    fn verify_digest(
+        &self,
+        public_key: &[u8],
+        digest: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Unspecified> {
+        RsaParameters::verify_digest(self, public_key, digest, signature)
+    }
+}
+
 impl Sealed for RsaParameters {}
 
 impl Debug for RsaParameters {
@@ -109,7 +174,100 @@ impl RsaParameters {
         range: RangeInclusive<u32>,
         verification_alg: &'static RsaVerificationAlgorithmId,
     ) -> Self {
-        Self(digest_alg, padding, range, verification_alg)
+        Self(
+            digest_alg,
+            padding,
+            range,
+            verification_alg,
+            RsaPssSaltLength::Digest,
+        )
+    }
+
+    /// Verification of signatures using RSA keys of 2048-8192 bits, PSS padding with the given
+    /// salt length, and `digest_alg` as both the message digest and the MGF1 digest.
+    ///
+    /// Use [`RsaPssSaltLength::Digest`] with the predefined `RSA_PSS_2048_8192_*` algorithms
+    /// instead where possible.
+    #[must_use]
+    pub const fn pss(
+        digest_alg: &'static digest::Algorithm,
+        salt_length: RsaPssSaltLength,
+    ) -> Self {
+        Self(
+            digest_alg,
+            &RsaPadding::RSA_PKCS1_PSS_PADDING,
+            2048..=8192,
+            &RsaVerificationAlgorithmId::RSA_PSS_2048_8192,
+            salt_length,
+        )
+    }
+
+    /// Begins a multi-part verification of a signature made by `public_key`, a DER-encoded
+    /// `RSAPublicKey` structure (RFC 8017), allowing the message to be provided incrementally via
+    /// [`RsaVerificationContext::update`] rather than buffered in memory.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `public_key` cannot be parsed or its size is not supported by
+    /// these parameters.
+    pub fn verify_context(&self, public_key: &[u8]) -> Result<RsaVerificationContext, Unspecified> {
+        let evp_pkey = encoding::rfc8017::decode_public_key_der(public_key)?;
+        Ok(RsaVerificationContext {
+            md_ctx: rsa_digest_verify_init(
+                self.digest_algorithm(),
+                self.padding(),
+                self.pss_salt_length(),
+                &evp_pkey,
+                self.bit_size_range(),
+            )?,
+        })
+    }
+
+    /// Verifies `signature` over a precomputed message `digest`, for callers that have already
+    /// hashed the message with these parameters' digest algorithm. `public_key` is a DER-encoded
+    /// `RSAPublicKey` structure (RFC 8017).
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `digest` has the wrong length for the digest algorithm, if
+    /// `public_key` cannot be parsed or has an unsupported size, or if the signature is invalid.
+    pub fn verify_digest(
+        &self,
+        public_key: &[u8],
+        digest: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Unspecified> {
+        if digest.len() != self.digest_algorithm().output_len() {
+            return Err(Unspecified);
+        }
+        let evp_pkey = encoding::rfc8017::decode_public_key_der(public_key)?;
+        let n_bits = checked_modulus_bits(&evp_pkey, self.bit_size_range())?;
+        policy::enforce_digest(self.digest_algorithm())?;
+        policy::enforce_rsa_modulus_bits(n_bits)?;
+
+        let pctx = LcPtr::new(unsafe { EVP_PKEY_CTX_new(*evp_pkey, null_mut()) })?;
+        if 1 != unsafe { EVP_PKEY_verify_init(*pctx) } {
+            return Err(Unspecified);
+        }
+        configure_rsa_digest_pkey_ctx(
+            &pctx,
+            self.digest_algorithm(),
+            self.padding(),
+            self.pss_salt_length()
+                .verification_salt_len(self.digest_algorithm(), n_bits),
+        )?;
+
+        if 1 != indicator_check!(unsafe {
+            EVP_PKEY_verify(
+                *pctx,
+                signature.as_ptr(),
+                signature.len(),
+                digest.as_ptr(),
+                digest.len(),
+            )
+        }) {
+            return Err(Unspecified);
+        }
+
+        Ok(())
     }
 
     /// Parses a DER-encoded `RSAPublicKey` structure (RFC 8017) to determine its size in bits.
@@ -148,6 +306,7 @@ pub(crate) enum RsaVerificationAlgorithmId {
     RSA_PSS_2048_8192_SHA256,
     RSA_PSS_2048_8192_SHA384,
     RSA_PSS_2048_8192_SHA512,
+    RSA_PSS_2048_8192,
 }
 
 #[derive(Debug)]
@@ -159,13 +318,16 @@ pub(crate) enum RsaSigningAlgorithmId {
     RSA_PKCS1_SHA256,
     RSA_PKCS1_SHA384,
     RSA_PKCS1_SHA512,
+    RSA_PSS,
 }
 
+/// An RSA signature encoding: the digest algorithm and padding used when signing.
 #[allow(clippy::module_name_repetitions)]
 pub struct RsaSignatureEncoding(
     &'static digest::Algorithm,
     &'static RsaPadding,
     &'static RsaSigningAlgorithmId,
+    RsaPssSaltLength,
 );
 
 impl RsaSignatureEncoding {
@@ -174,7 +336,26 @@ impl RsaSignatureEncoding {
         padding: &'static RsaPadding,
         sig_alg: &'static RsaSigningAlgorithmId,
     ) -> Self {
-        Self(digest_alg, padding, sig_alg)
+        Self(digest_alg, padding, sig_alg, RsaPssSaltLength::Digest)
+    }
+
+    /// PSS padding with the given salt length, using `digest_alg` as both the message digest and
+    /// the MGF1 digest.
+    ///
+    /// The result must be stored in a `static` to be passed to [`crate::signature::RsaKeyPair::sign`].
+    /// Signatures produced with a non-default salt length are verified with
+    /// [`RsaParameters::pss`].
+    #[must_use]
+    pub const fn pss(
+        digest_alg: &'static digest::Algorithm,
+        salt_length: RsaPssSaltLength,
+    ) -> Self {
+        Self(
+            digest_alg,
+            &RsaPadding::RSA_PKCS1_PSS_PADDING,
+            &RsaSigningAlgorithmId::RSA_PSS,
+            salt_length,
+        )
     }
 
     #[inline]
@@ -186,6 +367,11 @@ impl RsaSignatureEncoding {
     pub(super) fn padding(&self) -> &'static RsaPadding {
         self.1
     }
+
+    #[inline]
+    pub(super) fn pss_salt_length(&self) -> RsaPssSaltLength {
+        self.3
+    }
 }
 
 impl Sealed for RsaSignatureEncoding {}
@@ -235,32 +421,94 @@ pub(super) fn compute_rsa_signature<'a>(
 }
 
 #[inline]
-pub(crate) fn configure_rsa_pkcs1_pss_padding(pctx: *mut EVP_PKEY_CTX) -> Result<(), ()> {
+pub(crate) fn configure_rsa_pkcs1_pss_padding(
+    pctx: *mut EVP_PKEY_CTX,
+    salt_len: c_int,
+) -> Result<(), ()> {
     if 1 != unsafe { EVP_PKEY_CTX_set_rsa_padding(pctx, RSA_PKCS1_PSS_PADDING) } {
         return Err(());
     };
-    if 1 != unsafe { EVP_PKEY_CTX_set_rsa_pss_saltlen(pctx, RSA_PSS_SALTLEN_DIGEST) } {
+    if 1 != unsafe { EVP_PKEY_CTX_set_rsa_pss_saltlen(pctx, salt_len) } {
         return Err(());
     };
     Ok(())
 }
 
+fn checked_modulus_bits(
+    public_key: &LcPtr<EVP_PKEY>,
+    allowed_bit_size: &RangeInclusive<u32>,
+) -> Result<u32, Unspecified> {
+    let rsa = DetachableLcPtr::new(unsafe { EVP_PKEY_get0_RSA(**public_key) })?;
+    let n = ConstPointer::new(unsafe { RSA_get0_n(rsa.detach()) })?;
+    let n_bits = n.num_bits();
+    if !allowed_bit_size.contains(&n_bits) {
+        return Err(Unspecified);
+    }
+    Ok(n_bits)
+}
+
+/// Configures `pctx`, initialized for signing or verification, to operate on digests computed
+/// with `algorithm` using the given padding.
+pub(crate) fn configure_rsa_digest_pkey_ctx(
+    pctx: &LcPtr<EVP_PKEY_CTX>,
+    algorithm: &'static digest::Algorithm,
+    padding: &'static RsaPadding,
+    pss_salt_len: c_int,
+) -> Result<(), Unspecified> {
+    let digest = digest::match_digest_type(&algorithm.id);
+    if 1 != unsafe { EVP_PKEY_CTX_set_signature_md(**pctx, *digest) } {
+        return Err(Unspecified);
+    }
+    if let RsaPadding::RSA_PKCS1_PSS_PADDING = padding {
+        configure_rsa_pkcs1_pss_padding(**pctx, pss_salt_len)?;
+    }
+    Ok(())
+}
+
 #[inline]
 pub(crate) fn verify_rsa_signature(
     algorithm: &'static digest::Algorithm,
     padding: &'static RsaPadding,
+    pss_salt_length: RsaPssSaltLength,
     public_key: &LcPtr<EVP_PKEY>,
     msg: &[u8],
     signature: &[u8],
     allowed_bit_size: &RangeInclusive<u32>,
 ) -> Result<(), Unspecified> {
-    let rsa = DetachableLcPtr::new(unsafe { EVP_PKEY_get0_RSA(**public_key) })?;
-    let n = ConstPointer::new(unsafe { RSA_get0_n(rsa.detach()) })?;
-    let n_bits = n.num_bits();
-    if !allowed_bit_size.contains(&n_bits) {
+    let mut md_ctx = rsa_digest_verify_init(
+        algorithm,
+        padding,
+        pss_salt_length,
+        public_key,
+        allowed_bit_size,
+    )?;
+
+    if 1 != indicator_check!(unsafe {
+        EVP_DigestVerify(
+            md_ctx.as_mut_ptr(),
+            signature.as_ptr(),
+            signature.len(),
+            msg.as_ptr(),
+            msg.len(),
+        )
+    }) {
         return Err(Unspecified);
     }
 
+    Ok(())
+}
+
+fn rsa_digest_verify_init(
+    algorithm: &'static digest::Algorithm,
+    padding: &'static RsaPadding,
+    pss_salt_length: RsaPssSaltLength,
+    public_key: &LcPtr<EVP_PKEY>,
+    allowed_bit_size: &RangeInclusive<u32>,
+) -> Result<DigestContext, Unspecified> {
+    let n_bits = checked_modulus_bits(public_key, allowed_bit_size)?;
+    policy::enforce_digest(algorithm)?;
+    policy::enforce_rsa_modulus_bits(n_bits)?;
+
     let mut md_ctx = DigestContext::new_uninit();
     let digest = digest::match_digest_type(&algorithm.id);
 
@@ -281,22 +529,60 @@ pub(crate) fn verify_rsa_signature(
     if let RsaPadding::RSA_PKCS1_PSS_PADDING = padding {
         // AWS-LC owns pctx, check for null and then immediately detach so we don't drop it.
         let pctx = DetachableLcPtr::new(pctx)?.detach();
-        configure_rsa_pkcs1_pss_padding(pctx)?;
+        configure_rsa_pkcs1_pss_padding(
+            pctx,
+            pss_salt_length.verification_salt_len(algorithm, n_bits),
+        )?;
     }
 
-    if 1 != indicator_check!(unsafe {
-        EVP_DigestVerify(
-            md_ctx.as_mut_ptr(),
-            signature.as_ptr(),
-            signature.len(),
-            msg.as_ptr(),
-            msg.len(),
-        )
-    }) {
-        return Err(Unspecified);
+    Ok(md_ctx)
+}
+
+/// A multi-part RSA signature verification, created by [`RsaParameters::verify_context`].
+#[allow(clippy::module_name_repetitions)]
+pub struct RsaVerificationContext {
+    md_ctx: DigestContext,
+}
+
+impl Debug for RsaVerificationContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RsaVerificationContext")
+            .finish_non_exhaustive()
     }
+}
 
-    Ok(())
+impl RsaVerificationContext {
+    /// Updates the verification with the next part of the message.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on error.
+    pub fn update(&mut self, data: &[u8]) -> Result<(), Unspecified> {
+        if 1 != unsafe {
+            EVP_DigestVerifyUpdate(self.md_ctx.as_mut_ptr(), data.as_ptr().cast(), data.len())
+        } {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+
+    /// Completes the verification, checking that `signature` is valid for all data provided via
+    /// [`Self::update`].
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the signature is invalid.
+    pub fn finish(mut self, signature: &[u8]) -> Result<(), Unspecified> {
+        if 1 != indicator_check!(unsafe {
+            EVP_DigestVerifyFinal(
+                self.md_ctx.as_mut_ptr(),
+                signature.as_ptr(),
+                signature.len(),
+            )
+        }) {
+            return Err(Unspecified);
+        }
+
+        Ok(())
+    }
 }
 
 #[inline]
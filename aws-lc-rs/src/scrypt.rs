@@ -0,0 +1,257 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! scrypt derivation and verification.
+//!
+//! Use `derive` to derive scrypt outputs. Use `verify` to verify secret
+//! against previously-derived outputs.
+//!
+//! scrypt is a memory-hard password-based key derivation function specified in
+//! [RFC 7914]. Unlike PBKDF2, the cost of computing scrypt is dominated by the
+//! amount of memory required, which makes large-scale hardware-accelerated
+//! guessing attacks more expensive.
+//!
+//! scrypt is not a FIPS approved algorithm. Use [`crate::pbkdf2`] if FIPS
+//! compliance is required.
+//!
+//! [RFC 7914]: https://tools.ietf.org/html/rfc7914
+//!
+//! # Example
+//!
+//! ```
+//! use aws_lc_rs::{rand, scrypt};
+//! use aws_lc_rs::rand::SecureRandom;
+//!
+//! let params = scrypt::Params::new(15, 8, 1)?;
+//!
+//! let mut salt = [0u8; 16];
+//! rand::SystemRandom::new().fill(&mut salt)?;
+//!
+//! let mut to_store = [0u8; 32];
+//! scrypt::derive(&params, &salt, b"@74d7]404j|W}6u", &mut to_store)?;
+//!
+//! // An attempt to log in with the wrong password fails.
+//! assert!(scrypt::verify(&params, &salt, b"wrong password", &to_store).is_err());
+//!
+//! // An attempt to log in with the right password succeeds.
+//! assert!(scrypt::verify(&params, &salt, b"@74d7]404j|W}6u", &to_store).is_ok());
+//!
+//! # Ok::<(), aws_lc_rs::error::Unspecified>(())
+//! ```
+
+use crate::constant_time;
+use crate::error::Unspecified;
+use aws_lc::EVP_PBE_scrypt;
+use zeroize::Zeroize;
+
+/// The maximum value of `p * r` permitted by RFC 7914.
+const MAX_PR: u64 = (1 << 30) - 1;
+
+/// The largest supported `log_n`.
+const MAX_LOG_N: u8 = 32;
+
+/// The size of an scrypt block, in bytes, for `r = 1`.
+const BLOCK_LEN: u64 = 128;
+
+/// scrypt cost parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Params {
+    log_n: u8,
+    r: u32,
+    p: u32,
+}
+
+impl Params {
+    /// Constructs scrypt parameters.
+    ///
+    /// | Parameter | RFC 7914 Term
+    /// |-----------|-------------------------------------------------
+    /// | `log_n`   | log2(N) (CPU/Memory cost parameter)
+    /// | `r`       | r (block size)
+    /// | `p`       | p (parallelization parameter)
+    ///
+    /// Computing scrypt with these parameters requires approximately
+    /// `128 * r * (2^log_n + p)` bytes of memory.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if:
+    /// * `log_n` is zero or greater than 32,
+    /// * `r` or `p` is zero,
+    /// * `p * r >= 2^30`, or
+    /// * `2^log_n >= 2^(16 * r)`.
+    pub fn new(log_n: u8, r: u32, p: u32) -> Result<Self, Unspecified> {
+        if log_n == 0 || log_n > MAX_LOG_N || r == 0 || p == 0 {
+            return Err(Unspecified);
+        }
+        if u64::from(p) * u64::from(r) > MAX_PR {
+            return Err(Unspecified);
+        }
+        if u64::from(r) * 16 <= u64::from(log_n) {
+            return Err(Unspecified);
+        }
+        Ok(Self { log_n, r, p })
+    }
+
+    /// The base-2 logarithm of the CPU/Memory cost parameter `N`.
+    #[inline]
+    #[must_use]
+    pub fn log_n(&self) -> u8 {
+        self.log_n
+    }
+
+    /// The block size parameter `r`.
+    #[inline]
+    #[must_use]
+    pub fn r(&self) -> u32 {
+        self.r
+    }
+
+    /// The parallelization parameter `p`.
+    #[inline]
+    #[must_use]
+    pub fn p(&self) -> u32 {
+        self.p
+    }
+
+    fn n(&self) -> u64 {
+        1 << self.log_n
+    }
+
+    /// The number of bytes of memory needed to compute scrypt with these
+    /// parameters.
+    pub(crate) fn max_mem(&self) -> Result<usize, Unspecified> {
+        let blocks = self.n() + u64::from(self.p) + 1;
+        let mem = blocks
+            .checked_mul(BLOCK_LEN * u64::from(self.r))
+            .ok_or(Unspecified)?;
+        usize::try_from(mem).map_err(|_| Unspecified)
+    }
+}
+
+impl Default for Params {
+    /// The parameters recommended for interactive logins: `log_n = 17`, `r = 8`,
+    /// and `p = 1`. Computing scrypt with these parameters requires 128 MiB of
+    /// memory.
+    fn default() -> Self {
+        Self {
+            log_n: 17,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+/// Fills `out` with the key derived using scrypt with the given inputs.
+///
+/// Do not use `derive` as part of verifying a secret; use `verify` instead, to
+/// minimize the effectiveness of timing attacks.
+///
+/// | Parameter   | RFC 7914 Term
+/// |-------------|-------------------------------------------
+/// | `params`    | N, r, p (cost parameters)
+/// | `salt`      | S (salt)
+/// | `secret`    | P (passphrase)
+/// | `out`       | DK (derived key)
+/// | `out.len()` | dkLen (derived key length)
+///
+/// # Errors
+/// `error::Unspecified` if `out` is empty, if the memory required by `params`
+/// cannot be allocated, or if an unexpected error occurs.
+pub fn derive(
+    params: &Params,
+    salt: &[u8],
+    secret: &[u8],
+    out: &mut [u8],
+) -> Result<(), Unspecified> {
+    if out.is_empty() {
+        return Err(Unspecified);
+    }
+    let max_mem = params.max_mem()?;
+
+    if 1 != unsafe {
+        EVP_PBE_scrypt(
+            secret.as_ptr().cast(),
+            secret.len(),
+            salt.as_ptr(),
+            salt.len(),
+            params.n(),
+            u64::from(params.r),
+            u64::from(params.p),
+            max_mem,
+            out.as_mut_ptr(),
+            out.len(),
+        )
+    } {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
+/// Verifies that a previously-derived (e.g., using `derive`) scrypt value
+/// matches the scrypt value derived from the other inputs.
+///
+/// The comparison is done in constant time to prevent timing attacks. The
+/// comparison will fail if `previously_derived` is empty (has a length of
+/// zero).
+///
+/// | Parameter                  | RFC 7914 Term
+/// |----------------------------|--------------------------------------------
+/// | `params`                   | N, r, p (cost parameters)
+/// | `salt`                     | S (salt)
+/// | `secret`                   | P (passphrase)
+/// | `previously_derived`       | DK (derived key)
+/// | `previously_derived.len()` | dkLen (derived key length)
+///
+/// # Errors
+/// `error::Unspecified` if the inputs were not verified.
+pub fn verify(
+    params: &Params,
+    salt: &[u8],
+    secret: &[u8],
+    previously_derived: &[u8],
+) -> Result<(), Unspecified> {
+    if previously_derived.is_empty() {
+        return Err(Unspecified);
+    }
+
+    let mut derived_buf = vec![0u8; previously_derived.len()];
+
+    derive(params, salt, secret, &mut derived_buf)?;
+
+    let result = constant_time::verify_slices_are_equal(&derived_buf, previously_derived);
+    derived_buf.zeroize();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scrypt;
+
+    #[test]
+    fn scrypt_params() {
+        assert!(scrypt::Params::new(0, 8, 1).is_err());
+        assert!(scrypt::Params::new(33, 8, 1).is_err());
+        assert!(scrypt::Params::new(14, 0, 1).is_err());
+        assert!(scrypt::Params::new(14, 8, 0).is_err());
+        assert!(scrypt::Params::new(14, 1 << 15, 1 << 15).is_err());
+        // N must be less than 2^(16 * r)
+        assert!(scrypt::Params::new(16, 1, 1).is_err());
+        assert!(scrypt::Params::new(15, 1, 1).is_ok());
+
+        let params = scrypt::Params::new(14, 8, 2).unwrap();
+        assert_eq!(14, params.log_n());
+        assert_eq!(8, params.r());
+        assert_eq!(2, params.p());
+        assert_eq!(Ok(128 * 8 * ((1 << 14) + 3)), params.max_mem());
+
+        let params = scrypt::Params::default();
+        assert_eq!((17, 8, 1), (params.log_n(), params.r(), params.p()));
+    }
+
+    #[test]
+    fn scrypt_empty_output() {
+        let params = scrypt::Params::new(4, 1, 1).unwrap();
+        assert!(scrypt::derive(&params, b"salt", b"password", &mut []).is_err());
+        assert!(scrypt::verify(&params, b"salt", b"password", &[]).is_err());
+    }
+}
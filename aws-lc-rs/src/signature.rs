@@ -34,7 +34,8 @@
 //!
 //! The public key is encoding in uncompressed form using the
 //! Octet-String-to-Elliptic-Curve-Point algorithm in
-//! [SEC 1: Elliptic Curve Cryptography, Version 2.0].
+//! [SEC 1: Elliptic Curve Cryptography, Version 2.0]. The compressed form is
+//! also accepted during verification.
 //!
 //! During verification, the public key is validated using the ECC Partial
 //! Public-Key Validation Routine from Section 5.6.2.3.3 of
@@ -53,7 +54,8 @@
 //!
 //! The public key is encoding in uncompressed form using the
 //! Octet-String-to-Elliptic-Curve-Point algorithm in
-//! [SEC 1: Elliptic Curve Cryptography, Version 2.0].
+//! [SEC 1: Elliptic Curve Cryptography, Version 2.0]. The compressed form is
+//! also accepted during verification.
 //!
 //! During verification, the public key is validated using the ECC Partial
 //! Public-Key Validation Routine from Section 5.6.2.3.3 of
@@ -96,6 +98,10 @@
 //! Additionally, the entire salt is randomly generated separately for each
 //! signature using the secure random number generator passed to `sign()`.
 //!
+//! Other salt lengths, e.g. as required by some code-signing formats, can be
+//! used by constructing a `static` [`RsaSignatureEncoding::pss`] for signing
+//! and [`RsaParameters::pss`] for verification.
+//!
 //!
 //! [SEC 1: Elliptic Curve Cryptography, Version 2.0]:
 //!     http://www.secg.org/sec1-v2.pdf
@@ -123,6 +129,9 @@
 //!     signature::{self, KeyPair},
 //! };
 //!
+//! # #[cfg(feature = "strict-fips")]
+//! # fn main() {}
+//! # #[cfg(not(feature = "strict-fips"))]
 //! fn main() -> Result<(), aws_lc_rs::error::Unspecified> {
 //!     // Generate a key pair in PKCS#8 (v1) format.
 //!     let rng = rand::SystemRandom::new();
@@ -245,25 +254,45 @@ use core::fmt::{Debug, Formatter};
 use untrusted::Input;
 
 pub use crate::rsa::{
-    signature::RsaEncoding, KeyPair as RsaKeyPair, PublicKey as RsaSubjectPublicKey,
-    PublicKeyComponents as RsaPublicKeyComponents, RsaParameters,
+    key::RsaSigner,
+    signature::{RsaEncoding, RsaSignatureEncoding},
+    KeyPair as RsaKeyPair, KeyPairComponents as RsaKeyPairComponents,
+    PublicKey as RsaSubjectPublicKey, PublicKeyComponents as RsaPublicKeyComponents, RsaParameters,
+    RsaPssSaltLength, RsaSigningContext, RsaVerificationContext,
 };
 
-use crate::rsa::{
-    signature::{RsaSignatureEncoding, RsaSigningAlgorithmId},
-    RsaVerificationAlgorithmId,
-};
+use crate::rsa::{signature::RsaSigningAlgorithmId, RsaVerificationAlgorithmId};
 
-pub use crate::ec::key_pair::{EcdsaKeyPair, PrivateKey as EcdsaPrivateKey};
+#[cfg(feature = "legacy-dsa")]
+pub use crate::dsa::{
+    DsaVerificationAlgorithm, DSA_SHA1_FOR_LEGACY_USE_ONLY, DSA_SHA224_FOR_LEGACY_USE_ONLY,
+    DSA_SHA256_FOR_LEGACY_USE_ONLY,
+};
+pub use crate::ec::key_pair::{EcdsaKeyPair, EcdsaSigningContext, PrivateKey as EcdsaPrivateKey};
+pub use crate::ec::low_s::EcdsaLowSVerificationAlgorithm;
 use crate::ec::EcdsaSignatureFormat;
 pub use crate::ec::{
-    EcdsaSigningAlgorithm, EcdsaVerificationAlgorithm, PublicKey as EcdsaPublicKey,
+    EcdsaSigningAlgorithm, EcdsaVerificationAlgorithm, EcdsaVerificationContext,
+    PublicKey as EcdsaPublicKey,
 };
+#[cfg(not(feature = "strict-fips"))]
 pub use crate::ed25519::{
-    Ed25519KeyPair, EdDSAParameters, Seed as Ed25519Seed, ED25519_PUBLIC_KEY_LEN,
+    Ed25519KeyPair, Ed25519VerificationAlgorithm, Ed25519VerificationPolicy, EdDSAParameters,
+    Seed as Ed25519Seed, ED25519_PUBLIC_KEY_LEN,
 };
+use crate::encoding::{AsDer, PublicKeyX509Der};
+#[cfg(feature = "jwk")]
+use crate::jwk::Jwk;
+use crate::ptr::{ConstPointer, LcPtr};
 use crate::rsa;
-use crate::{digest, ec, error, hex, sealed};
+#[cfg(feature = "ssh")]
+use crate::ssh;
+use crate::{cbs, digest, ec, error, hex, sealed};
+use aws_lc::{
+    CBS_len, EC_GROUP_get_curve_name, EC_KEY_get0_group, EVP_PKEY_get0_EC_KEY,
+    EVP_parse_public_key, NID_X9_62_prime256v1, NID_secp384r1, NID_secp521r1, EVP_PKEY,
+    EVP_PKEY_EC, EVP_PKEY_RSA, EVP_PKEY_RSA_PSS,
+};
 
 /// The longest signature is an ASN.1 P-384 signature where *r* and *s* are of
 /// maximum length with the leading high bit set on each. Then each component
@@ -310,6 +339,107 @@ pub trait KeyPair: Debug + Send + Sized + Sync {
     fn public_key(&self) -> &Self::PublicKey;
 }
 
+/// A key pair that signs messages with a fixed signature algorithm.
+///
+/// `Signer` is object safe, so protocol code can sign with a `&dyn Signer` whose algorithm was
+/// selected at runtime. It is implemented by [`Ed25519KeyPair`], [`EcdsaKeyPair`] and
+/// [`RsaSigner`].
+pub trait Signer: Debug + Send + Sync {
+    /// Signs `message`, returning the signature in the encoding of the key pair's algorithm.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if signing fails.
+    fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>, error::Unspecified>;
+
+    /// The public key, encoded as expected by the corresponding verification algorithm.
+    fn public_key_bytes(&self) -> &[u8];
+}
+
+/// A public key that verifies signatures with a fixed signature algorithm.
+///
+/// `Verifier` is object safe, so protocol code can verify with a `&dyn Verifier` whose
+/// algorithm was selected at runtime. It is implemented by [`UnparsedPublicKey`].
+pub trait Verifier: Debug + Send + Sync {
+    /// Verifies that `signature` is a valid signature of `message`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the signature is not valid.
+    fn verify_message(&self, message: &[u8], signature: &[u8]) -> Result<(), error::Unspecified>;
+}
+
+/// A private key that signs precomputed message digests.
+///
+/// Implement `DigestSigner` for keys whose private-key operation happens outside of this
+/// library, e.g. in a KMS, a PKCS#11 token or a TPM, and wrap them in a [`DelegatedSigner`] to
+/// sign messages through the same [`Signer`] interface as local keys. It is also implemented by
+/// [`EcdsaKeyPair`] and [`RsaSigner`].
+pub trait DigestSigner: Debug + Send + Sync {
+    /// Signs the message digest `digest`, returning the signature in the encoding expected by
+    /// the key's verification algorithm.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if signing fails.
+    fn sign_digest(&self, digest: &[u8]) -> Result<Vec<u8>, error::Unspecified>;
+
+    /// The public key, encoded as expected by the key's verification algorithm.
+    fn public_key_bytes(&self) -> &[u8];
+}
+
+/// A signature verification algorithm that can verify signatures over precomputed message
+/// digests. It is implemented by [`EcdsaVerificationAlgorithm`] and [`RsaParameters`].
+pub trait DigestVerificationAlgorithm: VerificationAlgorithm {
+    /// The digest algorithm that messages are hashed with before signing.
+    fn digest_algorithm(&self) -> &'static digest::Algorithm;
+
+    /// Verifies `signature` over the message digest `digest` with the public key `public_key`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the signature is not valid.
+    fn verify_digest(
+        &self,
+        public_key: &[u8],
+        digest: &[u8],
+        signature: &[u8],
+    ) -> Result<(), error::Unspecified>;
+}
+
+/// A [`Signer`] that digests messages locally and delegates the private-key operation to a
+/// [`DigestSigner`].
+///
+/// Every signature returned by the delegate is verified against its public key before it is
+/// returned, so a misbehaving or misconfigured external key cannot produce invalid signatures.
+#[derive(Debug)]
+pub struct DelegatedSigner<K: DigestSigner> {
+    key: K,
+    algorithm: &'static dyn DigestVerificationAlgorithm,
+}
+
+impl<K: DigestSigner> DelegatedSigner<K> {
+    /// Constructs a signer that signs with `key`, whose signatures are verified by `algorithm`.
+    pub fn new(key: K, algorithm: &'static dyn DigestVerificationAlgorithm) -> Self {
+        Self { key, algorithm }
+    }
+
+    /// The key that signatures are delegated to.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<K: DigestSigner> Signer for DelegatedSigner<K> {
+    fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>, error::Unspecified> {
+        let digest = digest::try_digest(self.algorithm.digest_algorithm(), message)?;
+        let signature = self.key.sign_digest(digest.as_ref())?;
+        self.algorithm
+            .verify_digest(self.key.public_key_bytes(), digest.as_ref(), &signature)?;
+        Ok(signature)
+    }
+
+    fn public_key_bytes(&self) -> &[u8] {
+        self.key.public_key_bytes()
+    }
+}
+
 /// A signature verification algorithm.
 pub trait VerificationAlgorithm: Debug + Sync + sealed::Sealed {
     /// Verify the signature `signature` of message `msg` with the public key
@@ -399,6 +529,272 @@ impl<B: AsRef<[u8]>> UnparsedPublicKey<B> {
     }
 }
 
+#[cfg(feature = "jwk")]
+impl UnparsedPublicKey<Vec<u8>> {
+    /// Construct a new `UnparsedPublicKey` from the public members of a JSON Web Key:
+    /// * `EC` (RFC 7518, section 6.2.1) keys are used in the uncompressed form, for
+    ///   `EcdsaVerificationAlgorithm`s.
+    /// * `OKP` (RFC 8037) keys with the `crv` `Ed25519` are used for `ED25519`.
+    /// * `RSA` (RFC 7518, section 6.3.1) keys are used as a DER-encoded `RSAPublicKey`, for
+    ///   `RsaParameters`.
+    ///
+    /// The key's `alg`, if any, is not checked against `algorithm`; as with `new()`, a key that
+    /// does not suit `algorithm` fails verification.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the key type is not supported, or a member is missing or malformed.
+    pub fn from_jwk(
+        algorithm: &'static dyn VerificationAlgorithm,
+        jwk: &Jwk,
+    ) -> Result<Self, error::KeyRejected> {
+        let bytes = match jwk.kty() {
+            "EC" => {
+                let x = jwk.bytes("x")?;
+                let y = jwk.bytes("y")?;
+                if x.is_empty() || x.len() != y.len() {
+                    return Err(error::KeyRejected::invalid_encoding());
+                }
+                let mut bytes = Vec::with_capacity(1 + x.len() + y.len());
+                bytes.push(0x04);
+                bytes.extend_from_slice(&x);
+                bytes.extend_from_slice(&y);
+                bytes
+            }
+            #[cfg(not(feature = "strict-fips"))]
+            "OKP" => {
+                jwk.expect("OKP", Some("Ed25519"))?;
+                jwk.bytes("x")?
+            }
+            "RSA" => RsaPublicKeyComponents::from_jwk(jwk)?.to_der()?.into_vec(),
+            _ => return Err(error::KeyRejected::wrong_algorithm()),
+        };
+        Ok(Self::new(algorithm, bytes))
+    }
+}
+
+#[cfg(feature = "ssh")]
+impl UnparsedPublicKey<Vec<u8>> {
+    /// Construct a new `UnparsedPublicKey` from an OpenSSH public key:
+    /// * `ecdsa-sha2-*` (RFC 5656) keys are used in the uncompressed form, for
+    ///   `EcdsaVerificationAlgorithm`s.
+    /// * `ssh-ed25519` keys are used for `ED25519`.
+    /// * `ssh-rsa` keys are used as a DER-encoded `RSAPublicKey`, for `RsaParameters`.
+    ///
+    /// As with `new()`, a key that does not suit `algorithm` fails verification.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` on internal error.
+    pub fn from_openssh(
+        algorithm: &'static dyn VerificationAlgorithm,
+        key: &ssh::PublicKey,
+    ) -> Result<Self, error::KeyRejected> {
+        let bytes = match key.fields()? {
+            ssh::PublicKeyFields::Ecdsa { public_key, .. } => public_key.to_vec(),
+            #[cfg(not(feature = "strict-fips"))]
+            ssh::PublicKeyFields::Ed25519 { public_key } => public_key.to_vec(),
+            #[cfg(feature = "strict-fips")]
+            ssh::PublicKeyFields::Ed25519 { .. } => {
+                return Err(error::KeyRejected::wrong_algorithm())
+            }
+            ssh::PublicKeyFields::Rsa { e, n } => {
+                RsaPublicKeyComponents { n, e }.to_der()?.into_vec()
+            }
+        };
+        Ok(Self::new(algorithm, bytes))
+    }
+}
+
+impl<B: AsRef<[u8]> + Send + Sync> Verifier for UnparsedPublicKey<B> {
+    #[inline]
+    fn verify_message(&self, message: &[u8], signature: &[u8]) -> Result<(), error::Unspecified> {
+        self.verify(message, signature)
+    }
+}
+
+/// The type of a [`PublicKey`], as identified by the `AlgorithmIdentifier` of its
+/// `SubjectPublicKeyInfo`.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PublicKeyType {
+    /// An ECDSA key on P-256, for the `ECDSA_P256_*` algorithms.
+    EcdsaP256,
+    /// An ECDSA key on P-384, for the `ECDSA_P384_*` algorithms.
+    EcdsaP384,
+    /// An ECDSA key on P-521, for the `ECDSA_P521_*` algorithms.
+    EcdsaP521,
+    /// An ECDSA key on secp256k1, for the `ECDSA_P256K1_*` algorithms.
+    #[cfg(not(feature = "strict-fips"))]
+    EcdsaP256K1,
+    /// An RSA (`rsaEncryption`) key, for the `RSA_PKCS1_*` and `RSA_PSS_*` algorithms.
+    ///
+    /// `id-RSASSA-PSS` keys are not supported.
+    Rsa,
+    /// An Ed25519 key, for `ED25519`.
+    #[cfg(not(feature = "strict-fips"))]
+    Ed25519,
+    /// A DSA key, for the `DSA_*_FOR_LEGACY_USE_ONLY` algorithms.
+    #[cfg(feature = "legacy-dsa")]
+    Dsa,
+}
+
+/// A public key parsed from a DER-encoded (X.509) `SubjectPublicKeyInfo` structure, whose type is
+/// determined by the structure's `AlgorithmIdentifier`.
+///
+/// Unlike [`UnparsedPublicKey`], the key is parsed and validated up front, and its
+/// [`PublicKeyType`] can be used to select a compatible verification algorithm at runtime, e.g.
+/// from the signature algorithm of a certificate or a protocol message.
+///
+/// ```rust
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use aws_lc_rs::encoding::{AsDer, PublicKeyX509Der};
+/// use aws_lc_rs::signature::{self, KeyPair, PublicKey, PublicKeyType};
+///
+/// let key_pair = signature::EcdsaKeyPair::generate(&signature::ECDSA_P256_SHA256_ASN1_SIGNING)?;
+/// let message = b"hello, world";
+/// let signature = key_pair.sign(&aws_lc_rs::rand::SystemRandom::new(), message)?;
+/// let spki = AsDer::<PublicKeyX509Der>::as_der(key_pair.public_key())?;
+///
+/// let public_key = PublicKey::from_der(spki.as_ref())?;
+/// let algorithm: &'static dyn signature::VerificationAlgorithm = match public_key.key_type() {
+///     PublicKeyType::EcdsaP256 => &signature::ECDSA_P256_SHA256_ASN1,
+///     PublicKeyType::EcdsaP384 => &signature::ECDSA_P384_SHA384_ASN1,
+/// #   #[cfg(not(feature = "strict-fips"))]
+///     PublicKeyType::Ed25519 => &signature::ED25519,
+///     _ => return Err("unsupported key type".into()),
+/// };
+/// public_key.verify(algorithm, message, signature.as_ref())?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct PublicKey {
+    key_type: PublicKeyType,
+    bytes: Box<[u8]>,
+    der: Box<[u8]>,
+}
+
+impl PublicKey {
+    /// Parses a DER-encoded (X.509) `SubjectPublicKeyInfo` structure.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `spki` is not a `SubjectPublicKeyInfo`, if its key type is not
+    /// supported, or if the key is otherwise not acceptable.
+    pub fn from_der(spki: &[u8]) -> Result<Self, error::KeyRejected> {
+        let evp_pkey = parse_spki(spki)?;
+        let (key_type, bytes) = match evp_pkey.id() {
+            EVP_PKEY_EC => {
+                let ec_key = ConstPointer::new(unsafe { EVP_PKEY_get0_EC_KEY(*evp_pkey) })?;
+                let ec_group = ConstPointer::new(unsafe { EC_KEY_get0_group(*ec_key) })?;
+                let curve_nid = unsafe { EC_GROUP_get_curve_name(*ec_group) };
+                let key_type = [
+                    (NID_X9_62_prime256v1, PublicKeyType::EcdsaP256),
+                    (NID_secp384r1, PublicKeyType::EcdsaP384),
+                    (NID_secp521r1, PublicKeyType::EcdsaP521),
+                    #[cfg(not(feature = "strict-fips"))]
+                    (aws_lc::NID_secp256k1, PublicKeyType::EcdsaP256K1),
+                ]
+                .into_iter()
+                .find_map(|(nid, key_type)| (nid == curve_nid).then_some(key_type))
+                .ok_or_else(error::KeyRejected::wrong_algorithm)?;
+                let mut buffer = [0u8; ec::PUBLIC_KEY_MAX_LEN];
+                let len = unsafe { ec::marshal_ec_public_key_to_buffer(&mut buffer, &ec_key)? };
+                (key_type, buffer[..len].into())
+            }
+            EVP_PKEY_RSA => {
+                let public_key = unsafe { rsa::PublicKey::new(&evp_pkey)? };
+                (PublicKeyType::Rsa, public_key.as_ref().into())
+            }
+            #[cfg(not(feature = "strict-fips"))]
+            aws_lc::EVP_PKEY_ED25519 => {
+                let mut buffer = [0u8; ED25519_PUBLIC_KEY_LEN];
+                let mut len = buffer.len();
+                if 1 != unsafe {
+                    aws_lc::EVP_PKEY_get_raw_public_key(*evp_pkey, buffer.as_mut_ptr(), &mut len)
+                } {
+                    return Err(error::KeyRejected::unexpected_error());
+                }
+                (PublicKeyType::Ed25519, buffer[..len].into())
+            }
+            // An `id-RSASSA-PSS` key may restrict the PSS parameters it can be used with, which the
+            // verification algorithms cannot enforce, so such keys are rejected rather than
+            // treated as `rsaEncryption` keys.
+            EVP_PKEY_RSA_PSS => return Err(error::KeyRejected::wrong_algorithm()),
+            // The DSA verification algorithms consume the `SubjectPublicKeyInfo` itself.
+            #[cfg(feature = "legacy-dsa")]
+            aws_lc::EVP_PKEY_DSA => (PublicKeyType::Dsa, spki.into()),
+            _ => return Err(error::KeyRejected::wrong_algorithm()),
+        };
+
+        Ok(Self {
+            key_type,
+            bytes,
+            der: spki.into(),
+        })
+    }
+
+    /// The type of the key.
+    #[must_use]
+    pub fn key_type(&self) -> PublicKeyType {
+        self.key_type
+    }
+
+    /// Verifies that `signature` is a valid signature of `message` using `algorithm`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the signature is not valid, including when `algorithm` is not
+    /// compatible with the key's type.
+    pub fn verify(
+        &self,
+        algorithm: &'static dyn VerificationAlgorithm,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), error::Unspecified> {
+        algorithm.verify_sig(&self.bytes, message, signature)
+    }
+
+    #[cfg(feature = "x509")]
+    pub(crate) fn evp_pkey(&self) -> Result<LcPtr<EVP_PKEY>, error::Unspecified> {
+        Ok(parse_spki(&self.der)?)
+    }
+}
+
+pub(crate) fn parse_spki(spki: &[u8]) -> Result<LcPtr<EVP_PKEY>, error::KeyRejected> {
+    let mut cbs = unsafe { cbs::build_CBS(spki) };
+    let evp_pkey = LcPtr::new(unsafe { EVP_parse_public_key(&mut cbs) })
+        .map_err(|()| error::KeyRejected::invalid_encoding())?;
+    if 0 != unsafe { CBS_len(&cbs) } {
+        return Err(error::KeyRejected::invalid_encoding());
+    }
+    Ok(evp_pkey)
+}
+
+impl AsRef<[u8]> for PublicKey {
+    /// The public key, encoded as expected by the verification algorithms for its type: the
+    /// uncompressed point for ECDSA, a DER-encoded `RSAPublicKey` for RSA, and the raw public
+    /// key for Ed25519.
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl AsDer<PublicKeyX509Der<'static>> for PublicKey {
+    /// The `SubjectPublicKeyInfo` structure the key was parsed from.
+    fn as_der(&self) -> Result<PublicKeyX509Der<'static>, error::Unspecified> {
+        Ok(PublicKeyX509Der::new(self.der.to_vec()))
+    }
+}
+
+impl Debug for PublicKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&format!(
+            "PublicKey {{ key_type: {:?}, bytes: \"{}\" }}",
+            self.key_type,
+            hex::encode(&self.bytes)
+        ))
+    }
+}
+
 /// Verification of signatures using RSA keys of 1024-8192 bits, PKCS#1.5 padding, and SHA-1.
 pub static RSA_PKCS1_1024_8192_SHA1_FOR_LEGACY_USE_ONLY: RsaParameters = RsaParameters::new(
     &digest::SHA1_FOR_LEGACY_USE_ONLY,
@@ -593,6 +989,7 @@ pub static ECDSA_P521_SHA3_512_FIXED: EcdsaVerificationAlgorithm = EcdsaVerifica
 };
 
 /// Verification of fixed-length (PKCS#11 style) ECDSA signatures using the P-256K1 curve and SHA-256.
+#[cfg(not(feature = "strict-fips"))]
 pub static ECDSA_P256K1_SHA256_FIXED: EcdsaVerificationAlgorithm = EcdsaVerificationAlgorithm {
     id: &ec::AlgorithmID::ECDSA_P256K1,
     digest: &digest::SHA256,
@@ -600,6 +997,7 @@ pub static ECDSA_P256K1_SHA256_FIXED: EcdsaVerificationAlgorithm = EcdsaVerifica
 };
 
 /// Verification of fixed-length (PKCS#11 style) ECDSA signatures using the P-256K1 curve and SHA3-256.
+#[cfg(not(feature = "strict-fips"))]
 pub static ECDSA_P256K1_SHA3_256_FIXED: EcdsaVerificationAlgorithm = EcdsaVerificationAlgorithm {
     id: &ec::AlgorithmID::ECDSA_P256K1,
     digest: &digest::SHA3_256,
@@ -684,6 +1082,7 @@ pub static ECDSA_P521_SHA3_512_ASN1: EcdsaVerificationAlgorithm = EcdsaVerificat
 };
 
 /// Verification of ASN.1 DER-encoded ECDSA signatures using the P-256K1 curve and SHA-256.
+#[cfg(not(feature = "strict-fips"))]
 pub static ECDSA_P256K1_SHA256_ASN1: EcdsaVerificationAlgorithm = EcdsaVerificationAlgorithm {
     id: &ec::AlgorithmID::ECDSA_P256K1,
     digest: &digest::SHA256,
@@ -691,6 +1090,7 @@ pub static ECDSA_P256K1_SHA256_ASN1: EcdsaVerificationAlgorithm = EcdsaVerificat
 };
 
 /// Verification of ASN.1 DER-encoded ECDSA signatures using the P-256K1 curve and SHA3-256.
+#[cfg(not(feature = "strict-fips"))]
 pub static ECDSA_P256K1_SHA3_256_ASN1: EcdsaVerificationAlgorithm = EcdsaVerificationAlgorithm {
     id: &ec::AlgorithmID::ECDSA_P256K1,
     digest: &digest::SHA3_256,
@@ -739,10 +1139,12 @@ pub static ECDSA_P521_SHA3_512_FIXED_SIGNING: EcdsaSigningAlgorithm =
     EcdsaSigningAlgorithm(&ECDSA_P521_SHA3_512_FIXED);
 
 /// Signing of fixed-length (PKCS#11 style) ECDSA signatures using the P-256K1 curve and SHA-256.
+#[cfg(not(feature = "strict-fips"))]
 pub static ECDSA_P256K1_SHA256_FIXED_SIGNING: EcdsaSigningAlgorithm =
     EcdsaSigningAlgorithm(&ECDSA_P256K1_SHA256_FIXED);
 
 /// Signing of fixed-length (PKCS#11 style) ECDSA signatures using the P-256K1 curve and SHA3-256.
+#[cfg(not(feature = "strict-fips"))]
 pub static ECDSA_P256K1_SHA3_256_FIXED_SIGNING: EcdsaSigningAlgorithm =
     EcdsaSigningAlgorithm(&ECDSA_P256K1_SHA3_256_FIXED);
 
@@ -788,26 +1190,143 @@ pub static ECDSA_P521_SHA3_512_ASN1_SIGNING: EcdsaSigningAlgorithm =
     EcdsaSigningAlgorithm(&ECDSA_P521_SHA3_512_ASN1);
 
 /// Signing of ASN.1 DER-encoded ECDSA signatures using the P-256K1 curve and SHA-256.
+#[cfg(not(feature = "strict-fips"))]
 pub static ECDSA_P256K1_SHA256_ASN1_SIGNING: EcdsaSigningAlgorithm =
     EcdsaSigningAlgorithm(&ECDSA_P256K1_SHA256_ASN1);
 
 /// Signing of ASN.1 DER-encoded ECDSA signatures using the P-256K1 curve and SHA3-256.
+#[cfg(not(feature = "strict-fips"))]
 pub static ECDSA_P256K1_SHA3_256_ASN1_SIGNING: EcdsaSigningAlgorithm =
     EcdsaSigningAlgorithm(&ECDSA_P256K1_SHA3_256_ASN1);
 
 /// Verification of Ed25519 signatures.
+#[cfg(not(feature = "strict-fips"))]
 pub static ED25519: EdDSAParameters = EdDSAParameters {};
 
+/// Verification of Ed25519 signatures, rejecting non-canonically encoded and small-order public
+/// keys and `R` components.
+#[cfg(not(feature = "strict-fips"))]
+pub static ED25519_STRICT: Ed25519VerificationAlgorithm =
+    Ed25519VerificationAlgorithm::with_policy(Ed25519VerificationPolicy::Strict);
+
 #[cfg(test)]
 mod tests {
+    #[cfg(not(feature = "strict-fips"))]
     use regex::Regex;
 
-    use crate::rand::{generate, SystemRandom};
-    use crate::signature::{UnparsedPublicKey, ED25519};
+    #[cfg(not(feature = "strict-fips"))]
+    use crate::rand::generate;
+    use crate::rand::SystemRandom;
+    use crate::signature::UnparsedPublicKey;
+    #[cfg(not(feature = "strict-fips"))]
+    use crate::signature::ED25519;
 
     #[cfg(feature = "fips")]
     mod fips;
 
+    #[cfg(not(feature = "strict-fips"))]
+    #[test]
+    fn test_public_key_from_der() {
+        use crate::encoding::{AsDer, PublicKeyX509Der};
+        use crate::rsa::{KeySize, PrivateDecryptingKey};
+        use crate::signature::{self, KeyPair, PublicKey, PublicKeyType};
+
+        let rng = SystemRandom::new();
+        let message = b"hello, world";
+
+        for (alg, verification_alg, key_type) in [
+            (
+                &signature::ECDSA_P256_SHA256_ASN1_SIGNING,
+                &signature::ECDSA_P256_SHA256_ASN1,
+                PublicKeyType::EcdsaP256,
+            ),
+            (
+                &signature::ECDSA_P384_SHA384_ASN1_SIGNING,
+                &signature::ECDSA_P384_SHA384_ASN1,
+                PublicKeyType::EcdsaP384,
+            ),
+            (
+                &signature::ECDSA_P521_SHA512_ASN1_SIGNING,
+                &signature::ECDSA_P521_SHA512_ASN1,
+                PublicKeyType::EcdsaP521,
+            ),
+            #[cfg(not(feature = "strict-fips"))]
+            (
+                &signature::ECDSA_P256K1_SHA256_ASN1_SIGNING,
+                &signature::ECDSA_P256K1_SHA256_ASN1,
+                PublicKeyType::EcdsaP256K1,
+            ),
+        ] {
+            let key_pair = signature::EcdsaKeyPair::generate(alg).unwrap();
+            let spki = AsDer::<PublicKeyX509Der>::as_der(key_pair.public_key()).unwrap();
+            let public_key = PublicKey::from_der(spki.as_ref()).unwrap();
+            assert_eq!(key_type, public_key.key_type());
+            assert_eq!(key_pair.public_key().as_ref(), public_key.as_ref());
+            assert_eq!(spki.as_ref(), public_key.as_der().unwrap().as_ref());
+            let signature = key_pair.sign(&rng, message).unwrap();
+            assert!(public_key
+                .verify(verification_alg, message, signature.as_ref())
+                .is_ok());
+            assert!(public_key
+                .verify(&ED25519, message, signature.as_ref())
+                .is_err());
+        }
+
+        let key_pair = signature::Ed25519KeyPair::from_seed_unchecked(&[7u8; 32]).unwrap();
+        let mut spki = crate::hex::decode("302a300506032b6570032100").unwrap();
+        spki.extend_from_slice(key_pair.public_key().as_ref());
+        let public_key = PublicKey::from_der(&spki).unwrap();
+        assert_eq!(PublicKeyType::Ed25519, public_key.key_type());
+        let signature = key_pair.sign(message);
+        assert!(public_key
+            .verify(&ED25519, message, signature.as_ref())
+            .is_ok());
+
+        let private_key = PrivateDecryptingKey::generate(KeySize::Rsa2048).unwrap();
+        let spki = AsDer::<PublicKeyX509Der>::as_der(&private_key.public_key()).unwrap();
+        let public_key = PublicKey::from_der(spki.as_ref()).unwrap();
+        assert_eq!(PublicKeyType::Rsa, public_key.key_type());
+        let key_pair = signature::RsaKeyPair::from_pkcs8(
+            AsDer::<crate::encoding::Pkcs8V1Der>::as_der(&private_key)
+                .unwrap()
+                .as_ref(),
+        )
+        .unwrap();
+        assert_eq!(key_pair.public_key().as_ref(), public_key.as_ref());
+        let mut signature = vec![0u8; key_pair.public_modulus_len()];
+        key_pair
+            .sign(&signature::RSA_PSS_SHA256, &rng, message, &mut signature)
+            .unwrap();
+        assert!(public_key
+            .verify(&signature::RSA_PSS_2048_8192_SHA256, message, &signature)
+            .is_ok());
+
+        // Trailing data, truncation and unsupported key types (X25519) are rejected.
+        let mut trailing = spki.as_ref().to_vec();
+        trailing.push(0);
+        assert!(PublicKey::from_der(&trailing).is_err());
+        assert!(PublicKey::from_der(&spki.as_ref()[..spki.as_ref().len() - 1]).is_err());
+        let x25519 = crate::hex::decode(
+            "302a300506032b656e032100de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f",
+        )
+        .unwrap();
+        assert!(PublicKey::from_der(&x25519).is_err());
+
+        // `id-RSASSA-PSS` keys, with and without PSS parameters, are rejected rather than
+        // treated as `rsaEncryption` keys.
+        for rsa_pss in [
+            "30819d300b06092a864886f70d01010a03818d0030818902818100e23879a168c698663cf5c0f0c80f3357434f538637ddc7d2d96045787e93b989eaf0649959d4b3840e932d095d59626bea54fd7f649eed339c1d1a6e4a8f53e50e641ce20007f3dc4482a2e983afaf2cd2ee9e3c71f90c8dd3a975bfae501488851535c10c9891a77a9b7c3c52e05f7201079509c5cc0dd970794553fc600d170203010001",
+            "3081b0301e06092a864886f70d01010a3011a00f300d0609608648016503040201050003818d0030818902818100e3bc1c1b2cd30a0b93aa5824770816b91eb62d101bf205d995a0ad70c3de9b2aff63bae056e03f153dbd570c7e3e21ab7f64a920c5304f004de1fcd012a7ac191649028a663365df07292cdfa47e145b1815314b61836ec8b5987e322875391b2c0ac4271c5ed0472e4110a10945b16d57b4343c16f4fa9a5dcc26c8b961eb690203010001",
+        ] {
+            let spki = crate::hex::decode(rsa_pss).unwrap();
+            assert_eq!(
+                Err(crate::error::KeyRejected::wrong_algorithm()),
+                PublicKey::from_der(&spki).map(|_| ())
+            );
+        }
+    }
+
+    #[cfg(not(feature = "strict-fips"))]
     #[test]
     fn test_unparsed_public_key() {
         let random_pubkey: [u8; 32] = generate(&SystemRandom::new()).unwrap().expose();
@@ -824,4 +1343,124 @@ mod tests {
 
         assert!(pubkey_re.is_match(&unparsed_pubkey_debug));
     }
+
+    #[cfg(not(feature = "strict-fips"))]
+    #[test]
+    fn test_signer_verifier() {
+        use crate::rsa::KeySize;
+        use crate::signature::{
+            self, EcdsaKeyPair, Ed25519KeyPair, RsaKeyPair, RsaSigner, Signer,
+            VerificationAlgorithm, Verifier,
+        };
+
+        let rng = SystemRandom::new();
+        let ed25519 =
+            Ed25519KeyPair::from_pkcs8(Ed25519KeyPair::generate_pkcs8(&rng).unwrap().as_ref())
+                .unwrap();
+        let ecdsa_alg = &signature::ECDSA_P384_SHA384_ASN1_SIGNING;
+        let ecdsa = EcdsaKeyPair::from_pkcs8(
+            ecdsa_alg,
+            EcdsaKeyPair::generate_pkcs8(ecdsa_alg, &rng)
+                .unwrap()
+                .as_ref(),
+        )
+        .unwrap();
+        let rsa = RsaSigner::new(
+            RsaKeyPair::generate(KeySize::Rsa2048).unwrap(),
+            &signature::RSA_PSS_SHA256,
+        );
+
+        let signers: [(&dyn Signer, &'static dyn VerificationAlgorithm); 3] = [
+            (&ed25519, &ED25519),
+            (&ecdsa, &signature::ECDSA_P384_SHA384_ASN1),
+            (&rsa, &signature::RSA_PSS_2048_8192_SHA256),
+        ];
+        for (signer, verification_alg) in signers {
+            let sig = signer.sign_message(b"message").unwrap();
+            let public_key = UnparsedPublicKey::new(verification_alg, signer.public_key_bytes());
+            let verifier: &dyn Verifier = &public_key;
+            assert!(verifier.verify_message(b"message", &sig).is_ok());
+            assert!(verifier.verify_message(b"other message", &sig).is_err());
+        }
+    }
+
+    #[test]
+    fn test_delegated_signer() {
+        use crate::error::Unspecified;
+        use crate::rsa::KeySize;
+        use crate::signature::{
+            self, DelegatedSigner, DigestSigner, EcdsaKeyPair, KeyPair, RsaKeyPair, RsaSigner,
+            Signer, VerificationAlgorithm,
+        };
+
+        // Stands in for a key held by an external service, which can only sign digests.
+        #[derive(Debug)]
+        struct RemoteKey {
+            key_pair: EcdsaKeyPair,
+            faulty: bool,
+        }
+
+        impl DigestSigner for RemoteKey {
+            fn sign_digest(&self, digest: &[u8]) -> Result<Vec<u8>, Unspecified> {
+                let mut sig = self.key_pair.sign_digest(digest)?.as_ref().to_vec();
+                if self.faulty {
+                    sig[10] ^= 1;
+                }
+                Ok(sig)
+            }
+
+            fn public_key_bytes(&self) -> &[u8] {
+                self.key_pair.public_key().as_ref()
+            }
+        }
+
+        let rng = SystemRandom::new();
+        let ecdsa_alg = &signature::ECDSA_P256_SHA256_FIXED_SIGNING;
+        let new_key_pair = || {
+            EcdsaKeyPair::from_pkcs8(
+                ecdsa_alg,
+                EcdsaKeyPair::generate_pkcs8(ecdsa_alg, &rng)
+                    .unwrap()
+                    .as_ref(),
+            )
+            .unwrap()
+        };
+
+        let remote = DelegatedSigner::new(
+            RemoteKey {
+                key_pair: new_key_pair(),
+                faulty: false,
+            },
+            &signature::ECDSA_P256_SHA256_FIXED,
+        );
+        let faulty = DelegatedSigner::new(
+            RemoteKey {
+                key_pair: new_key_pair(),
+                faulty: true,
+            },
+            &signature::ECDSA_P256_SHA256_FIXED,
+        );
+        let local_ecdsa = DelegatedSigner::new(new_key_pair(), &signature::ECDSA_P256_SHA256_FIXED);
+        let local_rsa = DelegatedSigner::new(
+            RsaSigner::new(
+                RsaKeyPair::generate(KeySize::Rsa2048).unwrap(),
+                &signature::RSA_PKCS1_SHA384,
+            ),
+            &signature::RSA_PKCS1_2048_8192_SHA384,
+        );
+
+        let signers: [(&dyn Signer, &'static dyn VerificationAlgorithm); 3] = [
+            (&remote, &signature::ECDSA_P256_SHA256_FIXED),
+            (&local_ecdsa, &signature::ECDSA_P256_SHA256_FIXED),
+            (&local_rsa, &signature::RSA_PKCS1_2048_8192_SHA384),
+        ];
+        for (signer, verification_alg) in signers {
+            let sig = signer.sign_message(b"message").unwrap();
+            UnparsedPublicKey::new(verification_alg, signer.public_key_bytes())
+                .verify(b"message", &sig)
+                .unwrap();
+        }
+
+        assert!(faulty.sign_message(b"message").is_err());
+    }
 }
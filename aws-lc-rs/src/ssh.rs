@@ -0,0 +1,821 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! OpenSSH public and private key formats.
+//!
+//! A [`PublicKey`] is parsed from, or serialized to, a line of an OpenSSH public key file such
+//! as `id_ed25519.pub`, or the key blob ([RFC 4253 Section 6.6]) used by the SSH protocol and
+//! SSH agents. A [`PrivateKey`] is parsed from, or serialized to, the `openssh-key-v1` format
+//! written by `ssh-keygen`. Conversions to and from *aws-lc-rs* key types are provided by the
+//! key types themselves:
+//!
+//! | Key type                                                          | Conversions                                                                                     |
+//! |-------------------------------------------------------------------|-------------------------------------------------------------------------------------------------|
+//! | `ssh-ed25519`                                                     | `Ed25519KeyPair::{from_openssh, to_openssh}`, the key pair's `public_key().to_openssh()`        |
+//! | `ecdsa-sha2-nistp256`, `ecdsa-sha2-nistp384`, `ecdsa-sha2-nistp521` | `EcdsaKeyPair::{from_openssh, to_openssh}`, `EcdsaPublicKey::to_openssh`                        |
+//! | `ssh-rsa`                                                         | `RsaKeyPair::{from_openssh, to_openssh}`, `RsaSubjectPublicKey::to_openssh`                     |
+//!
+//! Public keys of any of these types can be used for verification with
+//! `signature::UnparsedPublicKey::from_openssh`.
+//!
+//! Private keys encrypted with a passphrase are not supported; `ssh-keygen -p -N ""` removes
+//! the passphrase.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! # #[cfg(not(feature = "strict-fips"))]
+//! # {
+//! use aws_lc_rs::signature::{self, Ed25519KeyPair, KeyPair};
+//! use aws_lc_rs::ssh;
+//!
+//! let private_key = ssh::PrivateKey::parse(include_str!("../tests/data/ssh_test_ed25519"))?;
+//! let key_pair = Ed25519KeyPair::from_openssh(&private_key)?;
+//!
+//! let mut public_key = key_pair.public_key().to_openssh();
+//! public_key.set_comment(private_key.comment());
+//! let authorized_key = public_key.to_string();
+//! assert_eq!(
+//!     include_str!("../tests/data/ssh_test_ed25519.pub").trim_end(),
+//!     authorized_key
+//! );
+//!
+//! let public_key = ssh::PublicKey::parse(&authorized_key)?;
+//! let public_key = signature::UnparsedPublicKey::from_openssh(&signature::ED25519, &public_key)?;
+//! let message = b"hello, world";
+//! public_key.verify(message, key_pair.sign(message).as_ref())?;
+//! # }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [RFC 4253 Section 6.6]: https://tools.ietf.org/html/rfc4253#section-6.6
+
+use crate::digest;
+use crate::encoding::pem;
+use crate::error::{KeyRejected, Unspecified};
+use crate::rand;
+use core::fmt::{self, Debug, Display, Formatter};
+use zeroize::Zeroize;
+
+const MAGIC: &[u8] = b"openssh-key-v1\0";
+const PEM_LABEL: &str = "OPENSSH PRIVATE KEY";
+// `ssh-keygen` wraps the base64 encoding at 70 characters.
+const PEM_LINE_LEN: usize = 70;
+// The block size of the `none` cipher, to which the private section is padded.
+const BLOCK_LEN: usize = 8;
+
+const ED25519: &str = "ssh-ed25519";
+const ED25519_KEY_LEN: usize = 32;
+const RSA: &str = "ssh-rsa";
+const ECDSA_PREFIX: &str = "ecdsa-sha2-";
+// The curves of `ecdsa-sha2-*` keys (RFC 5656 Section 10.1), with the length of their field
+// elements.
+const ECDSA_CURVES: [(&str, usize); 3] = [("nistp256", 32), ("nistp384", 48), ("nistp521", 66)];
+
+/// An OpenSSH public key, with its comment.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    blob: Box<[u8]>,
+    comment: String,
+}
+
+impl PublicKey {
+    /// Parses a line of an OpenSSH public key file, i.e. the key type, the base64-encoded key
+    /// blob and an optional comment, separated by whitespace. `authorized_keys` lines that start
+    /// with options are not supported.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `line` is malformed, the key type does not match the blob, or the
+    /// key type is not supported.
+    pub fn parse(line: &str) -> Result<Self, KeyRejected> {
+        let (key_type, rest) = line
+            .trim()
+            .split_once(char::is_whitespace)
+            .ok_or_else(KeyRejected::invalid_encoding)?;
+        let rest = rest.trim_start();
+        let (base64, comment) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let mut public_key = Self::from_blob(&pem::base64_decode(base64.as_bytes())?)?;
+        if public_key.key_type() != key_type {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        comment.trim().clone_into(&mut public_key.comment);
+        Ok(public_key)
+    }
+
+    /// Parses a key blob, as used in the SSH protocol and by SSH agents.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `blob` is malformed or the key type is not supported.
+    pub fn from_blob(blob: &[u8]) -> Result<Self, KeyRejected> {
+        let mut input = Reader::new(blob);
+        read_public_key(&mut input)?;
+        input.finish()?;
+        Ok(Self {
+            blob: blob.into(),
+            comment: String::new(),
+        })
+    }
+
+    /// The key type, e.g. `ssh-ed25519`.
+    #[must_use]
+    pub fn key_type(&self) -> &str {
+        // Every `PublicKey` has a valid blob, which starts with the key type.
+        Reader::new(&self.blob).read_str().unwrap_or_default()
+    }
+
+    /// The comment, which is often the key owner's `user@host`. It is empty if there is none.
+    #[must_use]
+    pub fn comment(&self) -> &str {
+        &self.comment
+    }
+
+    /// Sets the comment.
+    pub fn set_comment(&mut self, comment: &str) {
+        comment.clone_into(&mut self.comment);
+    }
+
+    /// The SHA-256 fingerprint of the key, as displayed by `ssh-keygen -l`, e.g.
+    /// `SHA256:chwZqSVjkEC5gvC8AMzSVs6aBAEG03p+P4V6uvde6tc`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` on internal error.
+    pub fn fingerprint(&self) -> Result<String, Unspecified> {
+        let digest = digest::digest_unchecked(&digest::SHA256, &self.blob);
+        let base64 = pem::base64_encode(digest.as_ref())?;
+        Ok(format!("SHA256:{}", base64.trim_end_matches('=')))
+    }
+
+    pub(crate) fn new(fields: &PublicKeyFields<'_>) -> Self {
+        let mut blob = Writer::default();
+        write_public_key(&mut blob, fields);
+        Self {
+            blob: blob.0.into(),
+            comment: String::new(),
+        }
+    }
+
+    pub(crate) fn fields(&self) -> Result<PublicKeyFields<'_>, KeyRejected> {
+        read_public_key(&mut Reader::new(&self.blob))
+    }
+}
+
+impl AsRef<[u8]> for PublicKey {
+    /// The key blob.
+    fn as_ref(&self) -> &[u8] {
+        &self.blob
+    }
+}
+
+impl Display for PublicKey {
+    /// Formats the key as a line of an OpenSSH public key file, without a trailing newline.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let base64 = pem::base64_encode(&self.blob).map_err(|Unspecified| fmt::Error)?;
+        write!(f, "{} {}", self.key_type(), base64)?;
+        if !self.comment.is_empty() {
+            write!(f, " {}", self.comment)?;
+        }
+        Ok(())
+    }
+}
+
+#[allow(clippy::missing_fields_in_debug)]
+impl Debug for PublicKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PublicKey")
+            .field("key_type", &self.key_type())
+            .field("fingerprint", &self.fingerprint().unwrap_or_default())
+            .field("comment", &self.comment)
+            .finish()
+    }
+}
+
+/// An unencrypted OpenSSH private key in the `openssh-key-v1` format, with its comment.
+///
+/// The key material is zeroized when the `PrivateKey` is dropped.
+#[derive(Clone)]
+pub struct PrivateKey {
+    public_key: PublicKey,
+    // The key-specific fields of the private section.
+    private: Box<[u8]>,
+    // The value of the private section's check integers.
+    check: u32,
+}
+
+impl PrivateKey {
+    /// Parses an `OPENSSH PRIVATE KEY` PEM document, as written by `ssh-keygen`.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `pem` is malformed, the key is encrypted, the document has more
+    /// than one key, or the key type is not supported.
+    pub fn parse(pem: &str) -> Result<Self, KeyRejected> {
+        let mut bytes = pem::decode(PEM_LABEL, pem)?;
+        let private_key = Self::from_bytes(&bytes);
+        bytes.zeroize();
+        private_key
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, KeyRejected> {
+        let mut input = Reader::new(
+            bytes
+                .strip_prefix(MAGIC)
+                .ok_or_else(KeyRejected::invalid_encoding)?,
+        );
+        let cipher_name = input.read_string()?;
+        let kdf_name = input.read_string()?;
+        let kdf_options = input.read_string()?;
+        if cipher_name != b"none" || kdf_name != b"none" || !kdf_options.is_empty() {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        if input.read_u32()? != 1 {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        let mut public_key = PublicKey::from_blob(input.read_string()?)?;
+        let mut private = Reader::new(input.read_string()?);
+        input.finish()?;
+
+        if private.0.len() % BLOCK_LEN != 0 {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        // The check integers detect a wrong passphrase, and are random but equal.
+        let check = private.read_u32()?;
+        if private.read_u32()? != check || private.read_str()? != public_key.key_type() {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        let fields = private.0;
+        read_private_key(&mut private, &public_key.fields()?)?;
+        let fields = &fields[..fields.len() - private.0.len()];
+        private.read_str()?.clone_into(&mut public_key.comment);
+        // The padding is 1, 2, 3, ..., up to the block size.
+        if private.0.len() >= BLOCK_LEN
+            || !private
+                .0
+                .iter()
+                .enumerate()
+                .all(|(i, b)| usize::from(*b) == i + 1)
+        {
+            return Err(KeyRejected::invalid_encoding());
+        }
+
+        Ok(Self {
+            public_key,
+            private: fields.into(),
+            check,
+        })
+    }
+
+    /// Serializes the key as an `OPENSSH PRIVATE KEY` PEM document, without encryption.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the key is too large to encode.
+    pub fn to_pem(&self) -> Result<String, Unspecified> {
+        let mut private = Writer::default();
+        private.write_u32(self.check);
+        private.write_u32(self.check);
+        private.write_string(self.public_key.key_type().as_bytes());
+        private.0.extend_from_slice(&self.private);
+        private.write_string(self.public_key.comment.as_bytes());
+        let padding_len = (BLOCK_LEN - private.0.len() % BLOCK_LEN) % BLOCK_LEN;
+        private.0.extend((1u8..).take(padding_len));
+
+        let mut bytes = Writer(MAGIC.to_vec());
+        bytes.write_string(b"none");
+        bytes.write_string(b"none");
+        bytes.write_string(b"");
+        bytes.write_u32(1);
+        bytes.write_string(&self.public_key.blob);
+        bytes.write_string(&private.0);
+        private.0.zeroize();
+        let pem = pem::encode_with_line_len(PEM_LABEL, &bytes.0, PEM_LINE_LEN);
+        bytes.0.zeroize();
+        pem
+    }
+
+    /// The public key, whose comment is the private key's.
+    #[must_use]
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// The comment, which is often the key owner's `user@host`. It is empty if there is none.
+    #[must_use]
+    pub fn comment(&self) -> &str {
+        &self.public_key.comment
+    }
+
+    /// Sets the comment.
+    pub fn set_comment(&mut self, comment: &str) {
+        comment.clone_into(&mut self.public_key.comment);
+    }
+
+    pub(crate) fn new(
+        public_fields: &PublicKeyFields<'_>,
+        private_fields: &PrivateKeyFields<'_>,
+    ) -> Result<Self, Unspecified> {
+        let mut check = [0u8; 4];
+        rand::fill(&mut check)?;
+        let mut private = Writer::default();
+        write_private_key(&mut private, public_fields, private_fields);
+        let private_key = Self {
+            public_key: PublicKey::new(public_fields),
+            private: private.0.as_slice().into(),
+            check: u32::from_be_bytes(check),
+        };
+        private.0.zeroize();
+        Ok(private_key)
+    }
+
+    pub(crate) fn fields(
+        &self,
+    ) -> Result<(PublicKeyFields<'_>, PrivateKeyFields<'_>), KeyRejected> {
+        let public_fields = self.public_key.fields()?;
+        let private_fields = read_private_key(&mut Reader::new(&self.private), &public_fields)?;
+        Ok((public_fields, private_fields))
+    }
+}
+
+impl Drop for PrivateKey {
+    fn drop(&mut self) {
+        self.private.zeroize();
+    }
+}
+
+impl Debug for PrivateKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrivateKey")
+            .field("key_type", &self.public_key.key_type())
+            .field("comment", &self.public_key.comment)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The fields of a public key. Integers are big-endian, without leading zeros.
+pub(crate) enum PublicKeyFields<'a> {
+    Ed25519 {
+        public_key: &'a [u8],
+    },
+    Ecdsa {
+        // The curve's name, e.g. `nistp256`.
+        curve: &'a str,
+        // The uncompressed point.
+        public_key: &'a [u8],
+    },
+    Rsa {
+        e: &'a [u8],
+        n: &'a [u8],
+    },
+}
+
+/// The fields of a private key, other than those of its public key. Integers are big-endian,
+/// without leading zeros.
+pub(crate) enum PrivateKeyFields<'a> {
+    Ed25519 {
+        seed: &'a [u8],
+    },
+    Ecdsa {
+        private_key: &'a [u8],
+    },
+    Rsa {
+        d: &'a [u8],
+        iqmp: &'a [u8],
+        p: &'a [u8],
+        q: &'a [u8],
+    },
+}
+
+// The length of the field elements of the `ecdsa-sha2-*` curve named `curve`.
+fn ecdsa_field_len(curve: &str) -> Option<usize> {
+    ECDSA_CURVES
+        .iter()
+        .find_map(|(name, field_len)| (*name == curve).then_some(*field_len))
+}
+
+// Reads a key blob.
+fn read_public_key<'a>(key: &mut Reader<'a>) -> Result<PublicKeyFields<'a>, KeyRejected> {
+    let key_type = key.read_str()?;
+    if key_type == ED25519 {
+        let public_key = key.read_string()?;
+        if public_key.len() != ED25519_KEY_LEN {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        Ok(PublicKeyFields::Ed25519 { public_key })
+    } else if key_type == RSA {
+        let e = key.read_mpint()?;
+        let n = key.read_mpint()?;
+        Ok(PublicKeyFields::Rsa { e, n })
+    } else if let Some(curve) = key_type.strip_prefix(ECDSA_PREFIX) {
+        let field_len = ecdsa_field_len(curve).ok_or_else(KeyRejected::wrong_algorithm)?;
+        if key.read_str()? != curve {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        let public_key = key.read_string()?;
+        if public_key.len() != 1 + 2 * field_len || public_key[0] != 0x04 {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        Ok(PublicKeyFields::Ecdsa { curve, public_key })
+    } else {
+        Err(KeyRejected::wrong_algorithm())
+    }
+}
+
+fn write_public_key(key: &mut Writer, fields: &PublicKeyFields<'_>) {
+    match fields {
+        PublicKeyFields::Ed25519 { public_key } => {
+            key.write_string(ED25519.as_bytes());
+            key.write_string(public_key);
+        }
+        PublicKeyFields::Ecdsa { curve, public_key } => {
+            key.write_string(format!("{ECDSA_PREFIX}{curve}").as_bytes());
+            key.write_string(curve.as_bytes());
+            key.write_string(public_key);
+        }
+        PublicKeyFields::Rsa { e, n } => {
+            key.write_string(RSA.as_bytes());
+            key.write_mpint(e);
+            key.write_mpint(n);
+        }
+    }
+}
+
+// Reads the key-specific fields of a private section, which repeat the public key.
+fn read_private_key<'a>(
+    key: &mut Reader<'a>,
+    public_fields: &PublicKeyFields<'_>,
+) -> Result<PrivateKeyFields<'a>, KeyRejected> {
+    let private_fields = match public_fields {
+        PublicKeyFields::Ed25519 { public_key } => {
+            if key.read_string()? != *public_key {
+                return Err(KeyRejected::inconsistent_components());
+            }
+            // The private key is the seed followed by the public key.
+            let private_key = key.read_string()?;
+            if private_key.len() != 2 * ED25519_KEY_LEN {
+                return Err(KeyRejected::invalid_encoding());
+            }
+            let (seed, public_key_copy) = private_key.split_at(ED25519_KEY_LEN);
+            if public_key_copy != *public_key {
+                return Err(KeyRejected::inconsistent_components());
+            }
+            PrivateKeyFields::Ed25519 { seed }
+        }
+        PublicKeyFields::Ecdsa { curve, public_key } => {
+            if key.read_str()? != *curve || key.read_string()? != *public_key {
+                return Err(KeyRejected::inconsistent_components());
+            }
+            let private_key = key.read_mpint()?;
+            if private_key.len() > (public_key.len() - 1) / 2 {
+                return Err(KeyRejected::invalid_encoding());
+            }
+            PrivateKeyFields::Ecdsa { private_key }
+        }
+        PublicKeyFields::Rsa { e, n } => {
+            if key.read_mpint()? != *n || key.read_mpint()? != *e {
+                return Err(KeyRejected::inconsistent_components());
+            }
+            PrivateKeyFields::Rsa {
+                d: key.read_mpint()?,
+                iqmp: key.read_mpint()?,
+                p: key.read_mpint()?,
+                q: key.read_mpint()?,
+            }
+        }
+    };
+    Ok(private_fields)
+}
+
+fn write_private_key(
+    key: &mut Writer,
+    public_fields: &PublicKeyFields<'_>,
+    private_fields: &PrivateKeyFields<'_>,
+) {
+    match (public_fields, private_fields) {
+        (PublicKeyFields::Ed25519 { public_key }, PrivateKeyFields::Ed25519 { seed }) => {
+            key.write_string(public_key);
+            key.write_u32_len(seed.len() + public_key.len());
+            key.0.extend_from_slice(seed);
+            key.0.extend_from_slice(public_key);
+        }
+        (PublicKeyFields::Ecdsa { curve, public_key }, PrivateKeyFields::Ecdsa { private_key }) => {
+            key.write_string(curve.as_bytes());
+            key.write_string(public_key);
+            key.write_mpint(private_key);
+        }
+        (PublicKeyFields::Rsa { e, n }, PrivateKeyFields::Rsa { d, iqmp, p, q }) => {
+            for integer in [n, e, d, iqmp, p, q] {
+                key.write_mpint(integer);
+            }
+        }
+        _ => unreachable!("the private key does not match the public key"),
+    }
+}
+
+// Reads the encoding of RFC 4251 Section 5.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn new(input: &'a [u8]) -> Self {
+        Self(input)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, KeyRejected> {
+        if self.0.len() < 4 {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        let (value, rest) = self.0.split_at(4);
+        self.0 = rest;
+        // DON'T PANIC: `value` has four bytes.
+        Ok(u32::from_be_bytes(value.try_into().unwrap()))
+    }
+
+    fn read_string(&mut self) -> Result<&'a [u8], KeyRejected> {
+        let len = usize::try_from(self.read_u32()?)?;
+        if self.0.len() < len {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        let (value, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(value)
+    }
+
+    fn read_str(&mut self) -> Result<&'a str, KeyRejected> {
+        core::str::from_utf8(self.read_string()?).map_err(|_| KeyRejected::invalid_encoding())
+    }
+
+    // Reads a non-negative `mpint`, and returns its value without the leading zero byte.
+    fn read_mpint(&mut self) -> Result<&'a [u8], KeyRejected> {
+        match self.read_string()? {
+            [0, rest @ ..] if rest.first().map_or(false, |b| b & 0x80 != 0) => Ok(rest),
+            // Negative and non-minimal encodings.
+            [b, ..] if *b == 0 || b & 0x80 != 0 => Err(KeyRejected::invalid_encoding()),
+            value => Ok(value),
+        }
+    }
+
+    fn finish(&self) -> Result<(), KeyRejected> {
+        if !self.0.is_empty() {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        Ok(())
+    }
+}
+
+// Writes the encoding of RFC 4251 Section 5.
+#[derive(Default)]
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn write_u32(&mut self, value: u32) {
+        self.0.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn write_u32_len(&mut self, len: usize) {
+        // DON'T PANIC: keys are far smaller than 4 GiB.
+        self.write_u32(u32::try_from(len).unwrap());
+    }
+
+    fn write_string(&mut self, value: &[u8]) {
+        self.write_u32_len(value.len());
+        self.0.extend_from_slice(value);
+    }
+
+    // Writes a non-negative `mpint`, given its big-endian value.
+    fn write_mpint(&mut self, value: &[u8]) {
+        let start = value.iter().position(|b| *b != 0).unwrap_or(value.len());
+        let value = &value[start..];
+        if value.first().map_or(false, |b| b & 0x80 != 0) {
+            self.write_u32_len(value.len() + 1);
+            self.0.push(0);
+            self.0.extend_from_slice(value);
+        } else {
+            self.write_string(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PrivateKey, PublicKey, Writer, BLOCK_LEN, MAGIC};
+    #[cfg(not(feature = "strict-fips"))]
+    use crate::signature::Ed25519KeyPair;
+    use crate::signature::{self, EcdsaKeyPair, KeyPair, RsaKeyPair};
+
+    const ED25519: &str = include_str!("../tests/data/ssh_test_ed25519");
+    const ED25519_PUB: &str = include_str!("../tests/data/ssh_test_ed25519.pub");
+    const P256: &str = include_str!("../tests/data/ssh_test_p256");
+    const P256_PUB: &str = include_str!("../tests/data/ssh_test_p256.pub");
+    const P384: &str = include_str!("../tests/data/ssh_test_p384");
+    const P384_PUB: &str = include_str!("../tests/data/ssh_test_p384.pub");
+    const P521: &str = include_str!("../tests/data/ssh_test_p521");
+    const P521_PUB: &str = include_str!("../tests/data/ssh_test_p521.pub");
+    const RSA: &str = include_str!("../tests/data/ssh_test_rsa");
+    const RSA_PUB: &str = include_str!("../tests/data/ssh_test_rsa.pub");
+    const ENCRYPTED: &str = include_str!("../tests/data/ssh_test_enc");
+
+    #[test]
+    fn test_public_key() {
+        let public_key = PublicKey::parse(ED25519_PUB).unwrap();
+        assert_eq!("ssh-ed25519", public_key.key_type());
+        assert_eq!("test@ed25519", public_key.comment());
+        assert_eq!(
+            "SHA256:chwZqSVjkEC5gvC8AMzSVs6aBAEG03p+P4V6uvde6tc",
+            public_key.fingerprint().unwrap()
+        );
+        assert_eq!(ED25519_PUB.trim_end(), public_key.to_string());
+        assert_eq!(
+            public_key.as_ref(),
+            PublicKey::from_blob(public_key.as_ref()).unwrap().as_ref()
+        );
+
+        for (line, key_type, comment) in [
+            (P256_PUB, "ecdsa-sha2-nistp256", "test@p256"),
+            (P384_PUB, "ecdsa-sha2-nistp384", ""),
+            (P521_PUB, "ecdsa-sha2-nistp521", "test@p521"),
+            (RSA_PUB, "ssh-rsa", "test@rsa"),
+        ] {
+            let public_key = PublicKey::parse(line).unwrap();
+            assert_eq!(key_type, public_key.key_type());
+            assert_eq!(comment, public_key.comment());
+            assert_eq!(line.trim_end(), public_key.to_string());
+        }
+
+        let mut public_key = PublicKey::parse("ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIFwDc1WexKVHwtqrXu7UkbPD4HJ94N1dQKnMucYXIg9g  two  words ").unwrap();
+        assert_eq!("two  words", public_key.comment());
+        public_key.set_comment("");
+        assert_eq!(
+            "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIFwDc1WexKVHwtqrXu7UkbPD4HJ94N1dQKnMucYXIg9g",
+            public_key.to_string()
+        );
+    }
+
+    #[test]
+    fn test_public_key_errors() {
+        let blob = PublicKey::parse(ED25519_PUB).unwrap().as_ref().to_vec();
+        for line in [
+            "",
+            "ssh-ed25519",
+            "ssh-ed25519 AAAA*",
+            // The key type does not match the blob's.
+            "ssh-rsa AAAAC3NzaC1lZDI1NTE5AAAAIFwDc1WexKVHwtqrXu7UkbPD4HJ94N1dQKnMucYXIg9g",
+            // A DSA key.
+            "ssh-dss AAAAB3NzaC1kc3MAAAABAQAAAAECAAAAAQMAAAABBA==",
+            // Options.
+            "no-pty ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIFwDc1WexKVHwtqrXu7UkbPD4HJ94N1dQKnMucYXIg9g",
+        ] {
+            assert!(PublicKey::parse(line).is_err(), "{line}");
+        }
+        assert!(PublicKey::from_blob(&blob[..blob.len() - 1]).is_err());
+        let mut trailing = blob.clone();
+        trailing.push(0);
+        assert!(PublicKey::from_blob(&trailing).is_err());
+
+        // An RSA key with a negative exponent.
+        let mut rsa = PublicKey::parse(RSA_PUB).unwrap().as_ref().to_vec();
+        assert_eq!(&[0, 0, 0, 3, 1, 0, 1], &rsa[11..18]);
+        rsa[15] = 0x81;
+        assert!(PublicKey::from_blob(&rsa).is_err());
+    }
+
+    #[test]
+    fn test_private_key() {
+        for (pem, line) in [
+            (ED25519, ED25519_PUB),
+            (P256, P256_PUB),
+            (P384, P384_PUB),
+            (P521, P521_PUB),
+            (RSA, RSA_PUB),
+        ] {
+            let private_key = PrivateKey::parse(pem).unwrap();
+            assert_eq!(&PublicKey::parse(line).unwrap(), private_key.public_key());
+            assert_eq!(pem, private_key.to_pem().unwrap());
+        }
+        let mut private_key = PrivateKey::parse(P256).unwrap();
+        assert_eq!("test@p256", private_key.comment());
+        private_key.set_comment("renamed");
+        let private_key = PrivateKey::parse(&private_key.to_pem().unwrap()).unwrap();
+        assert_eq!("renamed", private_key.public_key().comment());
+        assert!(!format!("{private_key:?}").contains("AAAA"));
+    }
+
+    #[test]
+    fn test_private_key_errors() {
+        assert!(PrivateKey::parse(ENCRYPTED).is_err());
+        assert!(PrivateKey::parse(ED25519_PUB).is_err());
+        assert!(PrivateKey::parse(&ED25519.replace("OPENSSH", "RSA")).is_err());
+
+        // The copy of the public key in the private section no longer matches.
+        let mut tampered = PrivateKey::parse(ED25519).unwrap();
+        tampered.private[4] ^= 1;
+        assert!(PrivateKey::parse(&tampered.to_pem().unwrap()).is_err());
+
+        // Padding of a block or more is rejected, including padding longer than 255 bytes.
+        let private_key = PrivateKey::parse(ED25519).unwrap();
+        let mut private = Writer::default();
+        private.write_u32(private_key.check);
+        private.write_u32(private_key.check);
+        private.write_string(private_key.public_key.key_type().as_bytes());
+        private.0.extend_from_slice(&private_key.private);
+        private.write_string(private_key.public_key.comment.as_bytes());
+        private.0.extend(1..=255u8);
+        while private.0.len() % BLOCK_LEN != 0 {
+            private.0.push(0);
+        }
+        let mut bytes = Writer(MAGIC.to_vec());
+        bytes.write_string(b"none");
+        bytes.write_string(b"none");
+        bytes.write_string(b"");
+        bytes.write_u32(1);
+        bytes.write_string(&private_key.public_key.blob);
+        bytes.write_string(&private.0);
+        assert!(PrivateKey::from_bytes(&bytes.0).is_err());
+    }
+
+    #[test]
+    fn test_key_pairs() {
+        let message = b"hello, world";
+
+        #[cfg(not(feature = "strict-fips"))]
+        {
+            let key_pair =
+                Ed25519KeyPair::from_openssh(&PrivateKey::parse(ED25519).unwrap()).unwrap();
+            let mut private_key = key_pair.to_openssh().unwrap();
+            private_key.set_comment("test@ed25519");
+            assert_eq!(
+                &PublicKey::parse(ED25519_PUB).unwrap(),
+                private_key.public_key()
+            );
+            let public_key = signature::UnparsedPublicKey::from_openssh(
+                &signature::ED25519,
+                &key_pair.public_key().to_openssh(),
+            )
+            .unwrap();
+            public_key
+                .verify(message, key_pair.sign(message).as_ref())
+                .unwrap();
+            assert!(Ed25519KeyPair::from_openssh(&PrivateKey::parse(P256).unwrap()).is_err());
+        }
+
+        let rng = crate::rand::SystemRandom::new();
+        for (pem, line, signing, verification) in [
+            (
+                P256,
+                P256_PUB,
+                &signature::ECDSA_P256_SHA256_ASN1_SIGNING,
+                &signature::ECDSA_P256_SHA256_ASN1,
+            ),
+            (
+                P384,
+                P384_PUB,
+                &signature::ECDSA_P384_SHA384_ASN1_SIGNING,
+                &signature::ECDSA_P384_SHA384_ASN1,
+            ),
+            (
+                P521,
+                P521_PUB,
+                &signature::ECDSA_P521_SHA512_ASN1_SIGNING,
+                &signature::ECDSA_P521_SHA512_ASN1,
+            ),
+        ] {
+            let private_key = PrivateKey::parse(pem).unwrap();
+            let key_pair = EcdsaKeyPair::from_openssh(signing, &private_key).unwrap();
+            let mut public_key = key_pair.public_key().to_openssh().unwrap();
+            public_key.set_comment(private_key.comment());
+            assert_eq!(line.trim_end(), public_key.to_string());
+            let mut exported = key_pair.to_openssh().unwrap();
+            exported.set_comment(private_key.comment());
+            assert_eq!(&*private_key.private, &*exported.private);
+
+            let public_key =
+                signature::UnparsedPublicKey::from_openssh(verification, &public_key).unwrap();
+            public_key
+                .verify(message, key_pair.sign(&rng, message).unwrap().as_ref())
+                .unwrap();
+        }
+        assert!(EcdsaKeyPair::from_openssh(
+            &signature::ECDSA_P384_SHA384_ASN1_SIGNING,
+            &PrivateKey::parse(P256).unwrap()
+        )
+        .is_err());
+
+        let private_key = PrivateKey::parse(RSA).unwrap();
+        let key_pair = RsaKeyPair::from_openssh(&private_key).unwrap();
+        let mut public_key = key_pair.public_key().to_openssh().unwrap();
+        public_key.set_comment(private_key.comment());
+        assert_eq!(RSA_PUB.trim_end(), public_key.to_string());
+        let exported = key_pair.to_openssh().unwrap();
+        assert_eq!(&*private_key.private, &*exported.private);
+        let mut signature = vec![0; key_pair.public_modulus_len()];
+        key_pair
+            .sign(&signature::RSA_PKCS1_SHA256, &rng, message, &mut signature)
+            .unwrap();
+        signature::UnparsedPublicKey::from_openssh(
+            &signature::RSA_PKCS1_2048_8192_SHA256,
+            &public_key,
+        )
+        .unwrap()
+        .verify(message, &signature)
+        .unwrap();
+        assert!(RsaKeyPair::from_openssh(&PrivateKey::parse(ED25519).unwrap()).is_err());
+    }
+}
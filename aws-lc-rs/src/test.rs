@@ -486,6 +486,81 @@ pub mod rand {
             assert_eq!(unsafe { *self.current.get() }, self.bytes.len());
         }
     }
+
+    /// A deterministic `SecureRandom` whose output is fully determined by the seed it was
+    /// created with. **Never use it outside of tests.**
+    ///
+    /// The output is the stream `SHA-256(SHA-256(seed) || counter)` for `counter = 0, 1, ...`,
+    /// with `counter` encoded as a big-endian `u64`. The stream is shared by all calls to
+    /// `fill()`, so the bytes produced only depend on the seed and on the total number of bytes
+    /// requested before, not on how the requests were split.
+    ///
+    /// Only operations that draw their randomness from the `SecureRandom` they are given, such
+    /// as `rand::generate` or a protocol's own nonce generation, become reproducible. Operations
+    /// that accept an `rng` only for API compatibility, like ECDSA signing, still use *AWS-LC*'s
+    /// internal generator.
+    #[cfg(feature = "test-utils")]
+    pub struct DeterministicRandom {
+        key: [u8; 32],
+        state: std::sync::Mutex<DeterministicRandomState>,
+    }
+
+    #[cfg(feature = "test-utils")]
+    struct DeterministicRandomState {
+        counter: u64,
+        block: [u8; 32],
+        used: usize,
+    }
+
+    #[cfg(feature = "test-utils")]
+    impl DeterministicRandom {
+        /// Creates a generator whose output is determined by `seed`.
+        #[must_use]
+        pub fn new(seed: &[u8]) -> Self {
+            let mut key = [0u8; 32];
+            key.copy_from_slice(crate::digest::digest(&crate::digest::SHA256, seed).as_ref());
+            Self {
+                key,
+                state: std::sync::Mutex::new(DeterministicRandomState {
+                    counter: 0,
+                    block: [0u8; 32],
+                    used: 32,
+                }),
+            }
+        }
+    }
+
+    #[cfg(feature = "test-utils")]
+    impl core::fmt::Debug for DeterministicRandom {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.debug_struct("DeterministicRandom")
+                .finish_non_exhaustive()
+        }
+    }
+
+    #[cfg(feature = "test-utils")]
+    impl crate::rand::sealed::SecureRandom for DeterministicRandom {
+        fn fill_impl(&self, dest: &mut [u8]) -> Result<(), error::Unspecified> {
+            let mut state = self.state.lock().map_err(|_| error::Unspecified)?;
+            let mut dest = dest;
+            while !dest.is_empty() {
+                if state.used == state.block.len() {
+                    let mut ctx = crate::digest::Context::new(&crate::digest::SHA256);
+                    ctx.update(&self.key);
+                    ctx.update(&state.counter.to_be_bytes());
+                    state.block.copy_from_slice(ctx.finish().as_ref());
+                    state.counter = state.counter.checked_add(1).ok_or(error::Unspecified)?;
+                    state.used = 0;
+                }
+                let n = core::cmp::min(dest.len(), state.block.len() - state.used);
+                let (head, tail) = dest.split_at_mut(n);
+                head.copy_from_slice(&state.block[state.used..state.used + n]);
+                state.used += n;
+                dest = tail;
+            }
+            Ok(())
+        }
+    }
 }
 
 #[cfg(test)]
@@ -545,6 +620,41 @@ mod tests {
         let _: Result<(), error::Unspecified> = fbr.fill_impl(&mut bs_one);
     }
 
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn deterministic_random() {
+        use crate::digest;
+        use crate::test::rand::DeterministicRandom;
+
+        let rng = DeterministicRandom::new(b"seed");
+        let mut first = [0u8; 40];
+        rng.fill_impl(&mut first).expect("fill");
+
+        let mut key_input = Vec::from(digest::digest(&digest::SHA256, b"seed").as_ref());
+        key_input.extend_from_slice(&0u64.to_be_bytes());
+        assert_eq!(
+            digest::digest(&digest::SHA256, &key_input).as_ref(),
+            &first[..32]
+        );
+
+        // The output does not depend on how the requests are split.
+        let rng = DeterministicRandom::new(b"seed");
+        let mut split = [0u8; 40];
+        rng.fill_impl(&mut split[..3]).expect("fill");
+        rng.fill_impl(&mut split[3..35]).expect("fill");
+        rng.fill_impl(&mut split[35..]).expect("fill");
+        assert_eq!(first, split);
+
+        let rng = DeterministicRandom::new(b"other seed");
+        let mut other = [0u8; 40];
+        rng.fill_impl(&mut other).expect("fill");
+        assert_ne!(first, other);
+
+        let rng = DeterministicRandom::new(b"seed");
+        let generated: [u8; 16] = crate::rand::generate(&rng).unwrap().expose();
+        assert_eq!(&first[..16], &generated);
+    }
+
     // TODO: This test is causing a thread panic which prevents capture with should_panic
     // #[test]
     // #[should_panic]
@@ -24,6 +24,37 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! The TLS 1.2 key block used to derive the record protection keys is computed from the master
+//! secret:
+//!
+//! ```rust
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::tls_prf::{Secret, P_SHA384};
+//!
+//! let pre_master_secret = &[42; 48]; // Value is established during key exchange
+//! let client_random = &[1; 32];
+//! let server_random = &[2; 32];
+//!
+//! let master_secret = Secret::new(&P_SHA384, pre_master_secret)?.derive_with_seed_concatination(
+//!     b"master secret",
+//!     client_random,
+//!     server_random,
+//!     48,
+//! )?;
+//!
+//! // Two 32 byte AES-256-GCM keys and two 4 byte implicit nonces.
+//! let key_block = Secret::new(master_secret.algorithm(), master_secret.as_ref())?
+//!     .derive_with_seed_concatination(b"key expansion", server_random, client_random, 72)?;
+//!
+//! let (client_write_key, rest) = key_block.as_ref().split_at(32);
+//! let (server_write_key, rest) = rest.split_at(32);
+//! let (client_write_iv, server_write_iv) = rest.split_at(4);
+//! # Ok(())
+//! # }
+//! ```
 
 use core::fmt::Debug;
 
@@ -43,6 +74,14 @@ impl Debug for Algorithm {
     }
 }
 
+impl PartialEq for Algorithm {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for Algorithm {}
+
 /// SHA-256 `P_hash` algorithm
 pub const P_SHA256: Algorithm = Algorithm(AlgorithmID::SHA256);
 
@@ -73,6 +112,12 @@ impl Secret {
         Ok(Self { algorithm, secret })
     }
 
+    /// The `P_hash` algorithm associated with this secret.
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        self.algorithm
+    }
+
     /// Calculates `len` bytes of TLS PRF using the configured [`Algorithm`], and returns [`Secret`] of length `len`.
     /// See [RFC5246](https://datatracker.ietf.org/doc/html/rfc5246#section-5)
     ///
@@ -189,6 +234,7 @@ mod tests {
     use alloc::ffi::CString;
 
     use super::{Secret, P_SHA256, P_SHA384, P_SHA512};
+    use crate::test::from_hex;
 
     #[cfg(feature = "fips")]
     mod fips;
@@ -359,6 +405,40 @@ mod tests {
         assert_eq!(EXPECT, output.as_ref());
     }
 
+    #[test]
+    fn master_secret_key_block() {
+        let client_random = &[1u8; 32];
+        let server_random = &[2u8; 32];
+
+        let master_secret = Secret::new(&P_SHA384, &[42u8; 48])
+            .expect("secret created")
+            .derive_with_seed_concatination(b"master secret", client_random, server_random, 48)
+            .expect("derive successful");
+        assert_eq!(&P_SHA384, master_secret.algorithm());
+        assert_eq!(
+            from_hex(
+                "946669393167fe2d222c002c39ce78855166d86cbf0c8c4cd10265d474e28afb\
+                 ad1b756c9cd04cdb89bf88df13f3bfc4"
+            )
+            .unwrap(),
+            master_secret.as_ref()
+        );
+
+        let key_block = Secret::new(master_secret.algorithm(), master_secret.as_ref())
+            .expect("secret created")
+            .derive_with_seed_concatination(b"key expansion", server_random, client_random, 72)
+            .expect("derive successful");
+        assert_eq!(
+            from_hex(
+                "b00427fa127382cececeec42e5be844482f0e1b27c418ed7ad9fa548637c9ff0\
+                 29d10fc6ac387dcf63a0c80b1b275ea18be7d1ad73a97c3c9283974ac5f6078f\
+                 2308df360468482b"
+            )
+            .unwrap(),
+            key_block.as_ref()
+        );
+    }
+
     #[test]
     fn try_into_array() {
         let secret = Secret::new(&P_SHA256, &[42u8; 32]).expect("secret creation to succeed");
@@ -9,4 +9,9 @@
 //! Features contained within this module, or child modules are subject to changes, relocation,
 //! or removal across minor releases, and thus are not subject to semantic versioning policies.
 
+// Only provides X25519, which is not approved for use in FIPS mode.
+#[cfg(not(feature = "strict-fips"))]
+pub mod agreement;
+pub mod kdf;
 pub mod kem;
+pub mod rsa;
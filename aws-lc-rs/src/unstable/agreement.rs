@@ -0,0 +1,81 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Low-level key agreement primitives.
+//!
+//! # ⚠️ Warning
+//! These functions perform no validation of their inputs or outputs. Most users should use
+//! [`crate::agreement`] instead, which rejects the all-zero X25519 output.
+
+use aws_lc::{X25519_public_from_private, X25519};
+
+/// The length of an X25519 scalar, u-coordinate and output.
+pub const X25519_LEN: usize = 32;
+
+/// The X25519 function from [RFC 7748 section 5].
+///
+/// `scalar` is decoded as described in the RFC, which includes clamping it. The result is
+/// returned even if it is all zeros, which happens when `u` is a point of low order; callers
+/// that need to reject such points must check for this themselves.
+///
+/// [RFC 7748 section 5]: https://tools.ietf.org/html/rfc7748#section-5
+#[must_use]
+pub fn x25519(scalar: &[u8; X25519_LEN], u: &[u8; X25519_LEN]) -> [u8; X25519_LEN] {
+    let mut out = [0u8; X25519_LEN];
+    // `X25519` only returns 0 to report an all-zero output, which has already been written.
+    let _ = unsafe { X25519(out.as_mut_ptr(), scalar.as_ptr(), u.as_ptr()) };
+    out
+}
+
+/// The X25519 function from [RFC 7748 section 5] applied to the base point, i.e. the public
+/// key for `scalar`.
+///
+/// [RFC 7748 section 5]: https://tools.ietf.org/html/rfc7748#section-5
+#[must_use]
+pub fn x25519_base_point(scalar: &[u8; X25519_LEN]) -> [u8; X25519_LEN] {
+    let mut out = [0u8; X25519_LEN];
+    unsafe { X25519_public_from_private(out.as_mut_ptr(), scalar.as_ptr()) };
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{x25519, x25519_base_point, X25519_LEN};
+    use crate::test::from_dirty_hex;
+
+    fn bytes(hex: &str) -> [u8; X25519_LEN] {
+        from_dirty_hex(hex).try_into().unwrap()
+    }
+
+    #[test]
+    fn test_x25519_rfc7748() {
+        // RFC 7748 section 5.2.
+        assert_eq!(
+            x25519(
+                &bytes("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4"),
+                &bytes("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c"),
+            ),
+            bytes("c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552")
+        );
+
+        // RFC 7748 section 6.1.
+        let alice_private =
+            bytes("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+        let bob_private = bytes("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+        let alice_public = x25519_base_point(&alice_private);
+        let bob_public = x25519_base_point(&bob_private);
+        assert_eq!(
+            alice_public,
+            bytes("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")
+        );
+        let shared = bytes("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+        assert_eq!(x25519(&alice_private, &bob_public), shared);
+        assert_eq!(x25519(&bob_private, &alice_public), shared);
+    }
+
+    #[test]
+    fn test_x25519_low_order() {
+        let scalar = [0x42u8; X25519_LEN];
+        assert_eq!(x25519(&scalar, &[0u8; X25519_LEN]), [0u8; X25519_LEN]);
+    }
+}
@@ -0,0 +1,313 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Unstable Key Derivation Functions (KDFs).
+//!
+//! # ⚠️ Warning
+//! Algorithms contained in this module are subject to changes, relocation,
+//! or removal across minor releases, and thus are not subject to semantic versioning policies.
+//!
+//! # Key-based Key Derivation Function (KBKDF) in Counter Mode
+//!
+//! [`kbkdf_ctr_hmac`] and [`kbkdf_ctr_cmac`] implement the KDF in Counter Mode specified in
+//! [NIST SP 800-108r1-upd1](https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-108r1-upd1.pdf),
+//! using HMAC or AES-CMAC as the pseudorandom function (PRF).
+//!
+//! ## Example
+//!
+//! ```rust
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::error::Unspecified;
+//! use aws_lc_rs::unstable::kdf::{
+//!     get_kbkdf_ctr_hmac_algorithm, kbkdf_ctr_hmac, KbkdfCtrHmacAlgorithmId,
+//! };
+//!
+//! let algorithm =
+//!     get_kbkdf_ctr_hmac_algorithm(KbkdfCtrHmacAlgorithmId::Sha256).ok_or(Unspecified)?;
+//!
+//! let key_derivation_key = [42u8; 32];
+//! let info = b"key diversification context";
+//!
+//! let mut derived_key = [0u8; 32];
+//! kbkdf_ctr_hmac(algorithm, &key_derivation_key, info, &mut derived_key)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Single-step Key Derivation Function (SSKDF)
+//!
+//! [`sskdf_digest`] and [`sskdf_hmac`] implement the One-Step Key Derivation specified in
+//! [NIST SP 800-56Cr2](https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-56Cr2.pdf),
+//! using a hash function or HMAC as the auxiliary function. It is typically used to derive keys
+//! from the shared secret established by a key-agreement scheme such as ECDH.
+//!
+//! The SSKDF functions are not available when the `fips` feature is enabled, as the FIPS
+//! module does not provide them.
+//!
+//! ## Example
+//!
+//! ```rust
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! # #[cfg(not(feature = "fips"))]
+//! # {
+//! use aws_lc_rs::error::Unspecified;
+//! use aws_lc_rs::unstable::kdf::{get_sskdf_hmac_algorithm, sskdf_hmac, SskdfHmacAlgorithmId};
+//!
+//! let algorithm = get_sskdf_hmac_algorithm(SskdfHmacAlgorithmId::Sha256).ok_or(Unspecified)?;
+//!
+//! let shared_secret = [42u8; 32];
+//! let fixed_info = b"algorithm id || party u info || party v info";
+//! let salt = [7u8; 32];
+//!
+//! let mut derived_key = [0u8; 32];
+//! sskdf_hmac(algorithm, &shared_secret, fixed_info, &salt, &mut derived_key)?;
+//! # }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Two-Step Key Derivation Function
+//!
+//! [`two_step_kdf`] implements the extraction-then-expansion key derivation specified in
+//! Section 5 of [NIST SP 800-56Cr2](https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-56Cr2.pdf),
+//! using HMAC or AES-CMAC for both the extraction and the expansion step.
+//!
+//! KMAC is not offered, as *AWS-LC* does not implement it (nor the cSHAKE function it is built
+//! on). AES-CMAC, the other MAC that SP 800-56Cr2 approves for extraction, is offered in its
+//! place.
+//!
+//! # ANSI X9.63 Key Derivation Function
+//!
+//! [`x963_kdf`] implements the key derivation function specified in Section 3.6.1 of
+//! [SEC 1](https://www.secg.org/sec1-v2.pdf), as used by ECIES.
+//!
+//! # SRTP Key Derivation Function
+//!
+//! [`srtp_kdf`] implements the AES-CM key derivation specified in Section 4.3 of
+//! [RFC 3711](https://www.rfc-editor.org/rfc/rfc3711), used to derive the SRTP and SRTCP
+//! session keys from the master key and master salt negotiated by DTLS-SRTP.
+//!
+//! # Generic Key Derivation
+//!
+//! The [`KeyDerivation`] trait allows protocol implementations to be generic over the KDF
+//! required by a cipher suite.
+//!
+//! ```rust
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::error::Unspecified;
+//! use aws_lc_rs::hkdf;
+//! use aws_lc_rs::unstable::kdf::{
+//!     get_kbkdf_ctr_hmac_algorithm, KbkdfCtrHmacAlgorithmId, KeyDerivation,
+//! };
+//!
+//! fn derive_traffic_key<K: KeyDerivation + ?Sized>(
+//!     kdf: &K,
+//!     shared_secret: &[u8],
+//! ) -> Result<[u8; 16], Unspecified> {
+//!     let mut key = [0u8; 16];
+//!     kdf.derive(shared_secret, b"traffic key", &mut key)?;
+//!     Ok(key)
+//! }
+//!
+//! let shared_secret = [42u8; 32];
+//!
+//! let hkdf_key = derive_traffic_key(&hkdf::HKDF_SHA256, &shared_secret)?;
+//!
+//! let kbkdf = get_kbkdf_ctr_hmac_algorithm(KbkdfCtrHmacAlgorithmId::Sha256).ok_or(Unspecified)?;
+//! let kbkdf_key = derive_traffic_key(kbkdf, &shared_secret)?;
+//!
+//! assert_ne!(hkdf_key, kbkdf_key);
+//! # Ok(())
+//! # }
+//! ```
+
+mod kbkdf;
+mod srtp;
+#[cfg(not(feature = "fips"))]
+mod sskdf;
+mod two_step;
+mod x963;
+
+use crate::error::Unspecified;
+use crate::hkdf;
+
+pub use kbkdf::{
+    get_kbkdf_ctr_cmac_algorithm, get_kbkdf_ctr_hmac_algorithm, kbkdf_ctr_cmac, kbkdf_ctr_hmac,
+    KbkdfCtrCmacAlgorithm, KbkdfCtrCmacAlgorithmId, KbkdfCtrHmacAlgorithm, KbkdfCtrHmacAlgorithmId,
+};
+pub use srtp::{
+    get_srtp_kdf_algorithm, srtp_kdf, SrtpKdfAlgorithm, SrtpKdfAlgorithmId, SrtpKdfLabel,
+    SRTP_MASTER_SALT_LEN,
+};
+#[cfg(not(feature = "fips"))]
+pub use sskdf::{
+    get_sskdf_digest_algorithm, get_sskdf_hmac_algorithm, sskdf_digest, sskdf_hmac,
+    SskdfDigestAlgorithm, SskdfDigestAlgorithmId, SskdfHmacAlgorithm, SskdfHmacAlgorithmId,
+};
+pub use two_step::{
+    get_two_step_kdf_algorithm, two_step_kdf, TwoStepKdfAlgorithm, TwoStepKdfAlgorithmId,
+};
+pub use x963::{get_x963_kdf_algorithm, x963_kdf, X963KdfAlgorithm, X963KdfAlgorithmId};
+
+/// A key derivation function that derives keying material from a secret and
+/// context-specific info.
+pub trait KeyDerivation {
+    /// Fills `output` with keying material derived from `secret` and `info`.
+    ///
+    /// # Errors
+    /// `Unspecified` if the inputs are not valid for the KDF, if `output` is
+    /// empty, or if the requested output length is not supported.
+    fn derive(&self, secret: &[u8], info: &[u8], output: &mut [u8]) -> Result<(), Unspecified>;
+}
+
+/// HKDF-Extract with a salt of `HashLen` zero bytes, followed by HKDF-Expand.
+impl KeyDerivation for hkdf::Algorithm {
+    fn derive(&self, secret: &[u8], info: &[u8], output: &mut [u8]) -> Result<(), Unspecified> {
+        hkdf::Salt::new(*self, &[]).derive(secret, info, output)
+    }
+}
+
+/// HKDF-Extract using this salt, followed by HKDF-Expand.
+impl KeyDerivation for hkdf::Salt {
+    fn derive(&self, secret: &[u8], info: &[u8], output: &mut [u8]) -> Result<(), Unspecified> {
+        if output.is_empty() {
+            return Err(Unspecified);
+        }
+        self.extract(secret)
+            .expand(&[info], OutputLen(output.len()))?
+            .fill(output)
+    }
+}
+
+struct OutputLen(usize);
+
+impl hkdf::KeyType for OutputLen {
+    fn len(&self) -> usize {
+        self.0
+    }
+}
+
+impl KeyDerivation for KbkdfCtrHmacAlgorithm {
+    fn derive(&self, secret: &[u8], info: &[u8], output: &mut [u8]) -> Result<(), Unspecified> {
+        kbkdf_ctr_hmac(self, secret, info, output)
+    }
+}
+
+impl KeyDerivation for KbkdfCtrCmacAlgorithm {
+    fn derive(&self, secret: &[u8], info: &[u8], output: &mut [u8]) -> Result<(), Unspecified> {
+        kbkdf_ctr_cmac(self, secret, info, output)
+    }
+}
+
+#[cfg(not(feature = "fips"))]
+impl KeyDerivation for SskdfDigestAlgorithm {
+    fn derive(&self, secret: &[u8], info: &[u8], output: &mut [u8]) -> Result<(), Unspecified> {
+        sskdf_digest(self, secret, info, output)
+    }
+}
+
+/// SSKDF with the default salt specified in SP 800-56C.
+#[cfg(not(feature = "fips"))]
+impl KeyDerivation for SskdfHmacAlgorithm {
+    fn derive(&self, secret: &[u8], info: &[u8], output: &mut [u8]) -> Result<(), Unspecified> {
+        sskdf_hmac(self, secret, info, &[], output)
+    }
+}
+
+impl KeyDerivation for X963KdfAlgorithm {
+    fn derive(&self, secret: &[u8], info: &[u8], output: &mut [u8]) -> Result<(), Unspecified> {
+        x963_kdf(self, secret, info, output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::Unspecified;
+    use crate::hkdf;
+    use crate::unstable::kdf::{
+        get_kbkdf_ctr_cmac_algorithm, get_kbkdf_ctr_hmac_algorithm, get_x963_kdf_algorithm,
+        kbkdf_ctr_hmac, x963_kdf, KbkdfCtrCmacAlgorithmId, KbkdfCtrHmacAlgorithmId, KeyDerivation,
+        X963KdfAlgorithmId,
+    };
+    #[cfg(not(feature = "fips"))]
+    use crate::unstable::kdf::{
+        get_sskdf_digest_algorithm, get_sskdf_hmac_algorithm, sskdf_hmac, SskdfDigestAlgorithmId,
+        SskdfHmacAlgorithmId,
+    };
+
+    const SECRET: &[u8] = &[42u8; 32];
+    const INFO: &[u8] = b"key derivation info";
+
+    fn derive(kdf: &dyn KeyDerivation, output_len: usize) -> Result<Vec<u8>, Unspecified> {
+        let mut output = vec![0u8; output_len];
+        kdf.derive(SECRET, INFO, &mut output)?;
+        Ok(output)
+    }
+
+    #[test]
+    fn key_derivation_hkdf() {
+        let mut expected = [0u8; 42];
+        hkdf::Salt::new(hkdf::HKDF_SHA256, &[0u8; 32])
+            .extract(SECRET)
+            .expand(&[INFO], hkdf::HKDF_SHA256)
+            .unwrap()
+            .fill(&mut expected[..32])
+            .unwrap();
+
+        let output = derive(&hkdf::HKDF_SHA256, 42).unwrap();
+        assert_eq!(&expected[..32], &output[..32]);
+
+        let salt = hkdf::Salt::new(hkdf::HKDF_SHA256, &[]);
+        assert_eq!(output, derive(&salt, 42).unwrap());
+
+        let salt = hkdf::Salt::new(hkdf::HKDF_SHA256, b"salt");
+        assert_ne!(output, derive(&salt, 42).unwrap());
+
+        assert!(derive(&hkdf::HKDF_SHA256, 255 * 32 + 1).is_err());
+    }
+
+    #[test]
+    fn key_derivation_matches_functions() {
+        let kbkdf = get_kbkdf_ctr_hmac_algorithm(KbkdfCtrHmacAlgorithmId::Sha256).unwrap();
+        let mut expected = [0u8; 42];
+        kbkdf_ctr_hmac(kbkdf, SECRET, INFO, &mut expected).unwrap();
+        assert_eq!(expected.as_slice(), derive(kbkdf, 42).unwrap());
+
+        #[cfg(not(feature = "fips"))]
+        {
+            let sskdf = get_sskdf_hmac_algorithm(SskdfHmacAlgorithmId::Sha256).unwrap();
+            sskdf_hmac(sskdf, SECRET, INFO, &[], &mut expected).unwrap();
+            assert_eq!(expected.as_slice(), derive(sskdf, 42).unwrap());
+        }
+
+        let x963 = get_x963_kdf_algorithm(X963KdfAlgorithmId::Sha256).unwrap();
+        x963_kdf(x963, SECRET, INFO, &mut expected).unwrap();
+        assert_eq!(expected.as_slice(), derive(x963, 42).unwrap());
+
+        #[allow(unused_mut)]
+        let mut kdfs: Vec<&dyn KeyDerivation> = vec![
+            &hkdf::HKDF_SHA384,
+            get_kbkdf_ctr_hmac_algorithm(KbkdfCtrHmacAlgorithmId::Sha384).unwrap(),
+            get_kbkdf_ctr_cmac_algorithm(KbkdfCtrCmacAlgorithmId::Aes256).unwrap(),
+            get_x963_kdf_algorithm(X963KdfAlgorithmId::Sha384).unwrap(),
+        ];
+        #[cfg(not(feature = "fips"))]
+        {
+            kdfs.push(get_sskdf_digest_algorithm(SskdfDigestAlgorithmId::Sha384).unwrap());
+            kdfs.push(get_sskdf_hmac_algorithm(SskdfHmacAlgorithmId::Sha384).unwrap());
+        }
+        let outputs: Vec<Vec<u8>> = kdfs.iter().map(|kdf| derive(*kdf, 64).unwrap()).collect();
+        for (i, a) in outputs.iter().enumerate() {
+            for b in &outputs[i + 1..] {
+                assert_ne!(a, b);
+            }
+            assert!(derive(kdfs[i], 0).is_err());
+        }
+    }
+}
@@ -0,0 +1,492 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use core::fmt::Debug;
+use core::ptr::null_mut;
+
+use crate::cipher::aes::{AES_128_KEY_LEN, AES_256_KEY_LEN};
+use crate::error::Unspecified;
+use crate::fips::indicator_check;
+use crate::hmac;
+use crate::ptr::LcPtr;
+use aws_lc::{
+    CMAC_CTX_new, CMAC_Final, CMAC_Init, CMAC_Update, EVP_aes_128_cbc, EVP_aes_256_cbc, EVP_CIPHER,
+};
+use zeroize::Zeroize;
+
+/// The length of the counter, in bytes, that is prepended to the fixed input
+/// of each PRF invocation.
+const COUNTER_LEN: usize = 4;
+
+/// The length of an AES-CMAC output block, in bytes.
+const CMAC_OUTPUT_LEN: usize = 16;
+
+/// The maximum output length of the PRFs supported by this module.
+const MAX_PRF_OUTPUT_LEN: usize = crate::digest::MAX_OUTPUT_LEN;
+
+/// KBKDF in Counter Mode with HMAC PRF
+pub struct KbkdfCtrHmacAlgorithm {
+    id: KbkdfCtrHmacAlgorithmId,
+}
+
+impl KbkdfCtrHmacAlgorithm {
+    /// Returns the KBKDF Counter HMAC Algorithm Identifier
+    #[must_use]
+    pub fn id(&self) -> KbkdfCtrHmacAlgorithmId {
+        self.id
+    }
+
+    #[must_use]
+    fn hmac_algorithm(&self) -> hmac::Algorithm {
+        match self.id {
+            KbkdfCtrHmacAlgorithmId::Sha224 => hmac::HMAC_SHA224,
+            KbkdfCtrHmacAlgorithmId::Sha256 => hmac::HMAC_SHA256,
+            KbkdfCtrHmacAlgorithmId::Sha384 => hmac::HMAC_SHA384,
+            KbkdfCtrHmacAlgorithmId::Sha512 => hmac::HMAC_SHA512,
+        }
+    }
+}
+
+impl PartialEq for KbkdfCtrHmacAlgorithm {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for KbkdfCtrHmacAlgorithm {}
+
+impl Debug for KbkdfCtrHmacAlgorithm {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.id, f)
+    }
+}
+
+/// KBKDF Counter Mode HMAC Algorithm Identifier
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KbkdfCtrHmacAlgorithmId {
+    /// KBKDF in Counter Mode with HMAC-SHA224
+    Sha224,
+
+    /// KBKDF in Counter Mode with HMAC-SHA256
+    Sha256,
+
+    /// KBKDF in Counter Mode with HMAC-SHA384
+    Sha384,
+
+    /// KBKDF in Counter Mode with HMAC-SHA512
+    Sha512,
+}
+
+const KBKDF_CTR_HMAC_SHA224: KbkdfCtrHmacAlgorithm = KbkdfCtrHmacAlgorithm {
+    id: KbkdfCtrHmacAlgorithmId::Sha224,
+};
+
+const KBKDF_CTR_HMAC_SHA256: KbkdfCtrHmacAlgorithm = KbkdfCtrHmacAlgorithm {
+    id: KbkdfCtrHmacAlgorithmId::Sha256,
+};
+
+const KBKDF_CTR_HMAC_SHA384: KbkdfCtrHmacAlgorithm = KbkdfCtrHmacAlgorithm {
+    id: KbkdfCtrHmacAlgorithmId::Sha384,
+};
+
+const KBKDF_CTR_HMAC_SHA512: KbkdfCtrHmacAlgorithm = KbkdfCtrHmacAlgorithm {
+    id: KbkdfCtrHmacAlgorithmId::Sha512,
+};
+
+/// Retrieve [`KbkdfCtrHmacAlgorithm`] using the [`KbkdfCtrHmacAlgorithmId`] specified by `id`.
+#[must_use]
+pub const fn get_kbkdf_ctr_hmac_algorithm(
+    id: KbkdfCtrHmacAlgorithmId,
+) -> Option<&'static KbkdfCtrHmacAlgorithm> {
+    Some(match id {
+        KbkdfCtrHmacAlgorithmId::Sha224 => &KBKDF_CTR_HMAC_SHA224,
+        KbkdfCtrHmacAlgorithmId::Sha256 => &KBKDF_CTR_HMAC_SHA256,
+        KbkdfCtrHmacAlgorithmId::Sha384 => &KBKDF_CTR_HMAC_SHA384,
+        KbkdfCtrHmacAlgorithmId::Sha512 => &KBKDF_CTR_HMAC_SHA512,
+    })
+}
+
+/// KBKDF in Counter Mode with AES-CMAC PRF
+pub struct KbkdfCtrCmacAlgorithm {
+    id: KbkdfCtrCmacAlgorithmId,
+}
+
+impl KbkdfCtrCmacAlgorithm {
+    /// Returns the KBKDF Counter CMAC Algorithm Identifier
+    #[must_use]
+    pub fn id(&self) -> KbkdfCtrCmacAlgorithmId {
+        self.id
+    }
+
+    /// The length of the key derivation key, in bytes.
+    #[must_use]
+    pub fn key_len(&self) -> usize {
+        match self.id {
+            KbkdfCtrCmacAlgorithmId::Aes128 => AES_128_KEY_LEN,
+            KbkdfCtrCmacAlgorithmId::Aes256 => AES_256_KEY_LEN,
+        }
+    }
+
+    fn cipher(&self) -> *const EVP_CIPHER {
+        unsafe {
+            match self.id {
+                KbkdfCtrCmacAlgorithmId::Aes128 => EVP_aes_128_cbc(),
+                KbkdfCtrCmacAlgorithmId::Aes256 => EVP_aes_256_cbc(),
+            }
+        }
+    }
+}
+
+impl PartialEq for KbkdfCtrCmacAlgorithm {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for KbkdfCtrCmacAlgorithm {}
+
+impl Debug for KbkdfCtrCmacAlgorithm {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.id, f)
+    }
+}
+
+/// KBKDF Counter Mode CMAC Algorithm Identifier
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KbkdfCtrCmacAlgorithmId {
+    /// KBKDF in Counter Mode with AES-128-CMAC
+    Aes128,
+
+    /// KBKDF in Counter Mode with AES-256-CMAC
+    Aes256,
+}
+
+const KBKDF_CTR_CMAC_AES128: KbkdfCtrCmacAlgorithm = KbkdfCtrCmacAlgorithm {
+    id: KbkdfCtrCmacAlgorithmId::Aes128,
+};
+
+const KBKDF_CTR_CMAC_AES256: KbkdfCtrCmacAlgorithm = KbkdfCtrCmacAlgorithm {
+    id: KbkdfCtrCmacAlgorithmId::Aes256,
+};
+
+/// Retrieve [`KbkdfCtrCmacAlgorithm`] using the [`KbkdfCtrCmacAlgorithmId`] specified by `id`.
+#[must_use]
+pub const fn get_kbkdf_ctr_cmac_algorithm(
+    id: KbkdfCtrCmacAlgorithmId,
+) -> Option<&'static KbkdfCtrCmacAlgorithm> {
+    Some(match id {
+        KbkdfCtrCmacAlgorithmId::Aes128 => &KBKDF_CTR_CMAC_AES128,
+        KbkdfCtrCmacAlgorithmId::Aes256 => &KBKDF_CTR_CMAC_AES256,
+    })
+}
+
+/// # Key-based Key Derivation Function (KBKDF) in Counter Mode with HMAC PRF
+///
+/// Each block of output is computed as `HMAC(secret, [i]_32 || info)`, where
+/// `[i]_32` is the 32-bit big-endian encoding of the block counter starting at
+/// one. If the fixed input data required by the protocol includes the
+/// output length `[L]`, it must be encoded by the caller as part of `info`.
+///
+/// ## Input Validation and Defaults
+/// * `output.len() > 0` and `secret.len() > 0`
+/// * The requested `output.len()` must not result in overflowing the 32-bit counter.
+///
+/// ## Implementation Notes
+///
+/// This implementation adheres to the algorithm specified in Section 4.1 of the
+/// NIST Special Publication 800-108 Revision 1 Update 1 published on August
+/// 2022. Using HMAC as the PRF function. In this implementation:
+/// * The counter is 32-bits and is represented in big-endian format
+/// * The counter is placed before the fixed info string
+///
+/// Specification available at <https://doi.org/10.6028/NIST.SP.800-108r1-upd1>
+///
+/// # Errors
+/// `Unspecified` is returned if input validation fails or an unexpected error occurs.
+pub fn kbkdf_ctr_hmac(
+    algorithm: &KbkdfCtrHmacAlgorithm,
+    secret: &[u8],
+    info: &[u8],
+    output: &mut [u8],
+) -> Result<(), Unspecified> {
+    if secret.is_empty() {
+        return Err(Unspecified);
+    }
+    let hmac_algorithm = algorithm.hmac_algorithm();
+    let key = hmac::Key::try_new(hmac_algorithm, secret)?;
+    let prf_output_len = hmac_algorithm.digest_algorithm().output_len;
+    kbkdf_ctr(prf_output_len, output, |counter, block| {
+        let mut ctx = hmac::Context::with_key(&key);
+        ctx.update(counter);
+        ctx.update(info);
+        block.copy_from_slice(ctx.sign().as_ref());
+        Ok(())
+    })
+}
+
+/// # Key-based Key Derivation Function (KBKDF) in Counter Mode with AES-CMAC PRF
+///
+/// Each block of output is computed as `CMAC(secret, [i]_32 || info)`, where
+/// `[i]_32` is the 32-bit big-endian encoding of the block counter starting at
+/// one. If the fixed input data required by the protocol includes the
+/// output length `[L]`, it must be encoded by the caller as part of `info`.
+///
+/// ## Input Validation and Defaults
+/// * `output.len() > 0`
+/// * `secret.len() == algorithm.key_len()`
+/// * The requested `output.len()` must not result in overflowing the 32-bit counter.
+///
+/// ## Implementation Notes
+///
+/// This implementation adheres to the algorithm specified in Section 4.1 of the
+/// NIST Special Publication 800-108 Revision 1 Update 1 published on August
+/// 2022. Using AES-CMAC as the PRF function. In this implementation:
+/// * The counter is 32-bits and is represented in big-endian format
+/// * The counter is placed before the fixed info string
+///
+/// Specification available at <https://doi.org/10.6028/NIST.SP.800-108r1-upd1>
+///
+/// # Errors
+/// `Unspecified` is returned if input validation fails or an unexpected error occurs.
+pub fn kbkdf_ctr_cmac(
+    algorithm: &KbkdfCtrCmacAlgorithm,
+    secret: &[u8],
+    info: &[u8],
+    output: &mut [u8],
+) -> Result<(), Unspecified> {
+    if secret.len() != algorithm.key_len() {
+        return Err(Unspecified);
+    }
+    let ctx = LcPtr::new(unsafe { CMAC_CTX_new() })?;
+    kbkdf_ctr(CMAC_OUTPUT_LEN, output, |counter, block| unsafe {
+        let mut out_len = 0usize;
+        if 1 != CMAC_Init(
+            *ctx,
+            secret.as_ptr().cast(),
+            secret.len(),
+            algorithm.cipher(),
+            null_mut(),
+        ) || 1 != CMAC_Update(*ctx, counter.as_ptr(), counter.len())
+            || 1 != CMAC_Update(*ctx, info.as_ptr(), info.len())
+            || 1 != indicator_check!(CMAC_Final(*ctx, block.as_mut_ptr(), &mut out_len))
+            || out_len != block.len()
+        {
+            return Err(Unspecified);
+        }
+        Ok(())
+    })
+}
+
+fn kbkdf_ctr<F>(prf_output_len: usize, output: &mut [u8], mut prf: F) -> Result<(), Unspecified>
+where
+    F: FnMut(&[u8; COUNTER_LEN], &mut [u8]) -> Result<(), Unspecified>,
+{
+    debug_assert!(prf_output_len <= MAX_PRF_OUTPUT_LEN);
+    if output.is_empty() {
+        return Err(Unspecified);
+    }
+    let blocks = (output.len() + prf_output_len - 1) / prf_output_len;
+    if u32::try_from(blocks).is_err() {
+        return Err(Unspecified);
+    }
+
+    let mut block = [0u8; MAX_PRF_OUTPUT_LEN];
+    let mut result = Ok(());
+    for (i, chunk) in output.chunks_mut(prf_output_len).enumerate() {
+        // `i + 1 <= blocks <= u32::MAX`
+        #[allow(clippy::cast_possible_truncation)]
+        let counter = ((i + 1) as u32).to_be_bytes();
+        result = prf(&counter, &mut block[..prf_output_len]);
+        if result.is_err() {
+            break;
+        }
+        chunk.copy_from_slice(&block[..chunk.len()]);
+    }
+    block.zeroize();
+    if result.is_err() {
+        output.zeroize();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::from_hex;
+    use crate::unstable::kdf::{
+        get_kbkdf_ctr_cmac_algorithm, get_kbkdf_ctr_hmac_algorithm, kbkdf_ctr_cmac, kbkdf_ctr_hmac,
+        KbkdfCtrCmacAlgorithmId, KbkdfCtrHmacAlgorithmId,
+    };
+    use aws_lc::AES_CMAC;
+
+    #[test]
+    fn kbkdf_ctr_hmac_known_answer() {
+        // NIST CAVP KDFCTR_gen.rsp
+        // [PRF=HMAC_SHA256], [CTRLOCATION=BEFORE_FIXED], [RLEN=32_BITS], COUNT=0
+        let secret =
+            from_hex("dd1d91b7d90b2bd3138533ce92b272fbf8a369316aefe242e659cc0ae238afe0").unwrap();
+        let info = from_hex(
+            "01322b96b30acd197979444e468e1c5c6859bf1b1cf951b7e725303e237e46b8\
+             64a145fab25e517b08f8683d0315bb2911d80a0e8aba17f3b413faac",
+        )
+        .unwrap();
+        let expected = from_hex("10621342bfb0fd40046c0e29f2cfdbf0").unwrap();
+
+        let algorithm = get_kbkdf_ctr_hmac_algorithm(KbkdfCtrHmacAlgorithmId::Sha256).unwrap();
+        let mut output = [0u8; 16];
+        kbkdf_ctr_hmac(algorithm, &secret, &info, &mut output).unwrap();
+        assert_eq!(expected.as_slice(), &output);
+    }
+
+    #[test]
+    fn kbkdf_ctr_hmac_rfc8009() {
+        // RFC 8009, Appendix A: KDF-HMAC-SHA2(key, label, k) is SP 800-108 Counter Mode with
+        // a 32-bit counter before the fixed input "label || 0x00 || [k]_32".
+        for (id, key, label, k, expected) in [
+            (
+                KbkdfCtrHmacAlgorithmId::Sha256,
+                "3705d96080c17728a0e800eab6e0d23c",
+                "0000000299",
+                128u32,
+                "b31a018a48f54776f403e9a396325dc3",
+            ),
+            (
+                KbkdfCtrHmacAlgorithmId::Sha256,
+                "3705d96080c17728a0e800eab6e0d23c",
+                "00000002aa",
+                128,
+                "9b197dd1e8c5609d6e67c3e37c62c72e",
+            ),
+            (
+                KbkdfCtrHmacAlgorithmId::Sha256,
+                "3705d96080c17728a0e800eab6e0d23c",
+                "0000000255",
+                128,
+                "9fda0e56ab2d85e1569a688696c26a6c",
+            ),
+            (
+                KbkdfCtrHmacAlgorithmId::Sha384,
+                "6d404d37faf79f9df0d33568d320669800eb4836472ea8a026d16b7182460c52",
+                "0000000299",
+                192,
+                "ef5718be86cc84963d8bbb5031e9f5c4ba41f28faf69e73d",
+            ),
+            (
+                KbkdfCtrHmacAlgorithmId::Sha384,
+                "6d404d37faf79f9df0d33568d320669800eb4836472ea8a026d16b7182460c52",
+                "00000002aa",
+                256,
+                "56ab22bee63d82d7bc5227f6773f8ea7a5eb1c825160c38312980c442e5c7e49",
+            ),
+            (
+                KbkdfCtrHmacAlgorithmId::Sha384,
+                "6d404d37faf79f9df0d33568d320669800eb4836472ea8a026d16b7182460c52",
+                "0000000255",
+                192,
+                "69b16514e3cd8e56b82010d5c73012b622c4d00ffc23ed1f",
+            ),
+        ] {
+            let secret = from_hex(key).unwrap();
+            let mut info = from_hex(label).unwrap();
+            info.push(0);
+            info.extend_from_slice(&k.to_be_bytes());
+            let expected = from_hex(expected).unwrap();
+
+            let algorithm = get_kbkdf_ctr_hmac_algorithm(id).unwrap();
+            let mut output = vec![0u8; expected.len()];
+            kbkdf_ctr_hmac(algorithm, &secret, &info, &mut output).unwrap();
+            assert_eq!(expected, output);
+        }
+    }
+
+    // SP 800-108r1, Section 4.1: K(i) = CMAC(K_IN, [i]_32 || FixedInfo). The PRF is recomputed
+    // with AWS-LC's one-shot AES_CMAC, which is first checked against RFC 4493, Example 2.
+    #[test]
+    fn kbkdf_ctr_cmac_matches_definition() {
+        fn aes_cmac(key: &[u8], message: &[u8]) -> [u8; 16] {
+            let mut out = [0u8; 16];
+            assert_eq!(1, unsafe {
+                AES_CMAC(
+                    out.as_mut_ptr(),
+                    key.as_ptr(),
+                    key.len(),
+                    message.as_ptr(),
+                    message.len(),
+                )
+            });
+            out
+        }
+
+        let rfc4493_key = from_hex("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+        let rfc4493_message = from_hex("6bc1bee22e409f96e93d7e117393172a").unwrap();
+        assert_eq!(
+            from_hex("070a16b46b4d4144f79bdd9dd04a287c").unwrap(),
+            aes_cmac(&rfc4493_key, &rfc4493_message)
+        );
+
+        for (id, secret) in [
+            (KbkdfCtrCmacAlgorithmId::Aes128, rfc4493_key),
+            (
+                KbkdfCtrCmacAlgorithmId::Aes256,
+                from_hex("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4")
+                    .unwrap(),
+            ),
+        ] {
+            let mut expected = Vec::new();
+            for i in 1u32..=3 {
+                let mut message = i.to_be_bytes().to_vec();
+                message.extend_from_slice(&rfc4493_message);
+                expected.extend_from_slice(&aes_cmac(&secret, &message));
+            }
+
+            let algorithm = get_kbkdf_ctr_cmac_algorithm(id).unwrap();
+            let mut output = [0u8; 40];
+            kbkdf_ctr_cmac(algorithm, &secret, &rfc4493_message, &mut output).unwrap();
+            assert_eq!(&expected[..40], &output);
+        }
+    }
+
+    #[test]
+    fn kbkdf_ctr_multi_block() {
+        for id in [
+            KbkdfCtrHmacAlgorithmId::Sha224,
+            KbkdfCtrHmacAlgorithmId::Sha256,
+            KbkdfCtrHmacAlgorithmId::Sha384,
+            KbkdfCtrHmacAlgorithmId::Sha512,
+        ] {
+            let algorithm = get_kbkdf_ctr_hmac_algorithm(id).unwrap();
+            assert_eq!(id, algorithm.id());
+            assert_eq!(format!("{id:?}"), format!("{algorithm:?}"));
+
+            let mut long = [0u8; 200];
+            kbkdf_ctr_hmac(algorithm, &[1u8; 32], b"info", &mut long).unwrap();
+            // A shorter output must be a prefix of a longer output.
+            let mut short = [0u8; 33];
+            kbkdf_ctr_hmac(algorithm, &[1u8; 32], b"info", &mut short).unwrap();
+            assert_eq!(&long[..33], &short);
+
+            assert!(kbkdf_ctr_hmac(algorithm, &[], b"info", &mut short).is_err());
+            assert!(kbkdf_ctr_hmac(algorithm, &[1u8; 32], b"info", &mut []).is_err());
+        }
+
+        for (id, key_len) in [
+            (KbkdfCtrCmacAlgorithmId::Aes128, 16),
+            (KbkdfCtrCmacAlgorithmId::Aes256, 32),
+        ] {
+            let algorithm = get_kbkdf_ctr_cmac_algorithm(id).unwrap();
+            assert_eq!(id, algorithm.id());
+            assert_eq!(key_len, algorithm.key_len());
+
+            let secret = vec![7u8; key_len];
+            let mut long = [0u8; 100];
+            kbkdf_ctr_cmac(algorithm, &secret, b"info", &mut long).unwrap();
+            let mut short = [0u8; 17];
+            kbkdf_ctr_cmac(algorithm, &secret, b"info", &mut short).unwrap();
+            assert_eq!(&long[..17], &short);
+
+            assert!(kbkdf_ctr_cmac(algorithm, &secret[1..], b"info", &mut short).is_err());
+            assert!(kbkdf_ctr_cmac(algorithm, &secret, b"info", &mut []).is_err());
+        }
+    }
+}
@@ -0,0 +1,260 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use core::fmt::Debug;
+
+use crate::cipher::{
+    EncryptingKey, EncryptionContext, UnboundCipherKey, AES_128, AES_128_KEY_LEN, AES_256,
+    AES_256_KEY_LEN,
+};
+use crate::error::Unspecified;
+use crate::iv::FixedLength;
+use zeroize::Zeroize;
+
+/// The length of an SRTP master salt, in bytes.
+pub const SRTP_MASTER_SALT_LEN: usize = 14;
+
+/// The maximum number of bytes that can be derived for a single label.
+///
+/// The AES-CM IV reserves the least significant 16 bits for the block counter.
+const MAX_OUTPUT_LEN: usize = (1 << 16) * 16;
+
+/// The byte offset in the master salt at which the label is applied.
+const LABEL_OFFSET: usize = 7;
+
+/// SRTP AES-CM key derivation function
+pub struct SrtpKdfAlgorithm {
+    id: SrtpKdfAlgorithmId,
+}
+
+impl SrtpKdfAlgorithm {
+    /// Returns the SRTP KDF Algorithm Identifier
+    #[must_use]
+    pub fn id(&self) -> SrtpKdfAlgorithmId {
+        self.id
+    }
+
+    /// The length of the SRTP master key, in bytes.
+    #[must_use]
+    pub fn master_key_len(&self) -> usize {
+        match self.id {
+            SrtpKdfAlgorithmId::Aes128Cm => AES_128_KEY_LEN,
+            SrtpKdfAlgorithmId::Aes256Cm => AES_256_KEY_LEN,
+        }
+    }
+}
+
+impl PartialEq for SrtpKdfAlgorithm {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for SrtpKdfAlgorithm {}
+
+impl Debug for SrtpKdfAlgorithm {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.id, f)
+    }
+}
+
+/// SRTP KDF Algorithm Identifier
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SrtpKdfAlgorithmId {
+    /// AES-128 in Counter Mode, as specified in RFC 3711
+    Aes128Cm,
+
+    /// AES-256 in Counter Mode, as specified in RFC 6188
+    Aes256Cm,
+}
+
+const SRTP_KDF_AES128_CM: SrtpKdfAlgorithm = SrtpKdfAlgorithm {
+    id: SrtpKdfAlgorithmId::Aes128Cm,
+};
+
+const SRTP_KDF_AES256_CM: SrtpKdfAlgorithm = SrtpKdfAlgorithm {
+    id: SrtpKdfAlgorithmId::Aes256Cm,
+};
+
+/// Retrieve [`SrtpKdfAlgorithm`] using the [`SrtpKdfAlgorithmId`] specified by `id`.
+#[must_use]
+pub const fn get_srtp_kdf_algorithm(id: SrtpKdfAlgorithmId) -> Option<&'static SrtpKdfAlgorithm> {
+    Some(match id {
+        SrtpKdfAlgorithmId::Aes128Cm => &SRTP_KDF_AES128_CM,
+        SrtpKdfAlgorithmId::Aes256Cm => &SRTP_KDF_AES256_CM,
+    })
+}
+
+/// The SRTP and SRTCP session key labels defined in RFC 3711 Section 4.3.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SrtpKdfLabel {
+    /// SRTP encryption key (`0x00`)
+    SrtpEncryption,
+
+    /// SRTP authentication key (`0x01`)
+    SrtpAuthentication,
+
+    /// SRTP salting key (`0x02`)
+    SrtpSalt,
+
+    /// SRTCP encryption key (`0x03`)
+    SrtcpEncryption,
+
+    /// SRTCP authentication key (`0x04`)
+    SrtcpAuthentication,
+
+    /// SRTCP salting key (`0x05`)
+    SrtcpSalt,
+}
+
+impl SrtpKdfLabel {
+    fn value(self) -> u8 {
+        match self {
+            SrtpKdfLabel::SrtpEncryption => 0x00,
+            SrtpKdfLabel::SrtpAuthentication => 0x01,
+            SrtpKdfLabel::SrtpSalt => 0x02,
+            SrtpKdfLabel::SrtcpEncryption => 0x03,
+            SrtpKdfLabel::SrtcpAuthentication => 0x04,
+            SrtpKdfLabel::SrtcpSalt => 0x05,
+        }
+    }
+}
+
+/// # SRTP Key Derivation Function
+///
+/// Fills `output` with the session key identified by `label`, derived from
+/// the SRTP master key and master salt.
+///
+/// The key derivation rate is zero, so session keys are derived once per
+/// master key as is the case for DTLS-SRTP (RFC 5764) and WebRTC.
+///
+/// ## Input Validation and Defaults
+/// * `master_key.len() == algorithm.master_key_len()`
+/// * `master_salt.len() == SRTP_MASTER_SALT_LEN`
+/// * `output.len() > 0` and `output.len() <= 2^20`
+///
+/// ## Implementation Notes
+///
+/// This implementation adheres to the AES-CM PRF and key derivation specified in
+/// Section 4.3 of [RFC 3711](https://www.rfc-editor.org/rfc/rfc3711), and its
+/// extension to AES-256 in [RFC 6188](https://www.rfc-editor.org/rfc/rfc6188).
+///
+/// # Errors
+/// `Unspecified` is returned if input validation fails or an unexpected error occurs.
+pub fn srtp_kdf(
+    algorithm: &SrtpKdfAlgorithm,
+    master_key: &[u8],
+    master_salt: &[u8],
+    label: SrtpKdfLabel,
+    output: &mut [u8],
+) -> Result<(), Unspecified> {
+    if master_key.len() != algorithm.master_key_len()
+        || master_salt.len() != SRTP_MASTER_SALT_LEN
+        || output.is_empty()
+        || output.len() > MAX_OUTPUT_LEN
+    {
+        return Err(Unspecified);
+    }
+    let cipher = match algorithm.id {
+        SrtpKdfAlgorithmId::Aes128Cm => &AES_128,
+        SrtpKdfAlgorithmId::Aes256Cm => &AES_256,
+    };
+
+    // x = key_id XOR master_salt, where key_id = label || r and r = 0.
+    // IV = x * 2^16
+    let mut iv = [0u8; 16];
+    iv[..SRTP_MASTER_SALT_LEN].copy_from_slice(master_salt);
+    iv[LABEL_OFFSET] ^= label.value();
+
+    let key = EncryptingKey::ctr(UnboundCipherKey::new(cipher, master_key)?)?;
+    output.fill(0);
+    let result = key.less_safe_encrypt(output, EncryptionContext::Iv128(FixedLength::from(iv)));
+    iv.zeroize();
+    if result.is_err() {
+        output.zeroize();
+    }
+    result.map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::from_hex;
+    use crate::unstable::kdf::{
+        get_srtp_kdf_algorithm, srtp_kdf, SrtpKdfAlgorithmId, SrtpKdfLabel,
+    };
+
+    #[test]
+    fn srtp_kdf_known_answer() {
+        // RFC 3711 Appendix B.3
+        let master_key = from_hex("e1f97a0d3e018be0d64fa32c06de4139").unwrap();
+        let master_salt = from_hex("0ec675ad498afeebb6960b3aabe6").unwrap();
+        let algorithm = get_srtp_kdf_algorithm(SrtpKdfAlgorithmId::Aes128Cm).unwrap();
+
+        for (label, expected) in [
+            (
+                SrtpKdfLabel::SrtpEncryption,
+                "c61e7a93744f39ee10734afe3ff7a087",
+            ),
+            (SrtpKdfLabel::SrtpSalt, "30cbbc08863d8c85d49db34a9ae1"),
+            (
+                SrtpKdfLabel::SrtpAuthentication,
+                "cebe321f6ff7716b6fd4ab49af256a156d38baa4",
+            ),
+        ] {
+            let expected = from_hex(expected).unwrap();
+            let mut output = vec![0u8; expected.len()];
+            srtp_kdf(algorithm, &master_key, &master_salt, label, &mut output).unwrap();
+            assert_eq!(expected, output);
+        }
+    }
+
+    #[test]
+    fn srtp_kdf_input_validation() {
+        for (id, key_len) in [
+            (SrtpKdfAlgorithmId::Aes128Cm, 16),
+            (SrtpKdfAlgorithmId::Aes256Cm, 32),
+        ] {
+            let algorithm = get_srtp_kdf_algorithm(id).unwrap();
+            assert_eq!(id, algorithm.id());
+            assert_eq!(key_len, algorithm.master_key_len());
+            assert_eq!(format!("{id:?}"), format!("{algorithm:?}"));
+
+            let master_key = vec![1u8; key_len];
+            let master_salt = [2u8; 14];
+            let label = SrtpKdfLabel::SrtcpEncryption;
+            let mut output = [0u8; 32];
+            assert!(srtp_kdf(algorithm, &master_key, &master_salt, label, &mut output).is_ok());
+            assert!(srtp_kdf(
+                algorithm,
+                &master_key[1..],
+                &master_salt,
+                label,
+                &mut output
+            )
+            .is_err());
+            assert!(srtp_kdf(
+                algorithm,
+                &master_key,
+                &master_salt[1..],
+                label,
+                &mut output
+            )
+            .is_err());
+            assert!(srtp_kdf(algorithm, &master_key, &master_salt, label, &mut []).is_err());
+
+            // Each label yields an independent key.
+            let mut other = [0u8; 32];
+            srtp_kdf(
+                algorithm,
+                &master_key,
+                &master_salt,
+                SrtpKdfLabel::SrtcpSalt,
+                &mut other,
+            )
+            .unwrap();
+            assert_ne!(output, other);
+        }
+    }
+}
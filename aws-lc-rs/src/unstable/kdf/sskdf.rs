@@ -0,0 +1,357 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use core::fmt::Debug;
+
+use crate::digest::{match_digest_type, AlgorithmID};
+use crate::error::Unspecified;
+use crate::fips::indicator_check;
+use aws_lc::{SSKDF_digest, SSKDF_hmac};
+
+/// SSKDF with digest algorithm
+pub struct SskdfDigestAlgorithm {
+    id: SskdfDigestAlgorithmId,
+}
+
+impl SskdfDigestAlgorithm {
+    /// Returns the SSKDF Digest Algorithm Identifier
+    #[must_use]
+    pub fn id(&self) -> SskdfDigestAlgorithmId {
+        self.id
+    }
+
+    #[must_use]
+    fn digest_algorithm(&self) -> &'static AlgorithmID {
+        match self.id {
+            SskdfDigestAlgorithmId::Sha224 => &AlgorithmID::SHA224,
+            SskdfDigestAlgorithmId::Sha256 => &AlgorithmID::SHA256,
+            SskdfDigestAlgorithmId::Sha384 => &AlgorithmID::SHA384,
+            SskdfDigestAlgorithmId::Sha512 => &AlgorithmID::SHA512,
+        }
+    }
+}
+
+impl PartialEq for SskdfDigestAlgorithm {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for SskdfDigestAlgorithm {}
+
+impl Debug for SskdfDigestAlgorithm {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.id, f)
+    }
+}
+
+/// SSKDF with HMAC algorithm
+pub struct SskdfHmacAlgorithm {
+    id: SskdfHmacAlgorithmId,
+}
+
+impl SskdfHmacAlgorithm {
+    /// Returns the SSKDF HMAC Algorithm Identifier
+    #[must_use]
+    pub fn id(&self) -> SskdfHmacAlgorithmId {
+        self.id
+    }
+
+    #[must_use]
+    fn digest_algorithm(&self) -> &'static AlgorithmID {
+        match self.id {
+            SskdfHmacAlgorithmId::Sha224 => &AlgorithmID::SHA224,
+            SskdfHmacAlgorithmId::Sha256 => &AlgorithmID::SHA256,
+            SskdfHmacAlgorithmId::Sha384 => &AlgorithmID::SHA384,
+            SskdfHmacAlgorithmId::Sha512 => &AlgorithmID::SHA512,
+        }
+    }
+}
+
+impl PartialEq for SskdfHmacAlgorithm {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for SskdfHmacAlgorithm {}
+
+impl Debug for SskdfHmacAlgorithm {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.id, f)
+    }
+}
+
+/// SSKDF with digest algorithm identifiers
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SskdfDigestAlgorithmId {
+    /// SSKDF with SHA224
+    Sha224,
+
+    /// SSKDF with SHA256
+    Sha256,
+
+    /// SSKDF with SHA384
+    Sha384,
+
+    /// SSKDF with SHA512
+    Sha512,
+}
+
+/// SSKDF with HMAC algorithm identifiers
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SskdfHmacAlgorithmId {
+    /// SSKDF with HMAC-SHA224
+    Sha224,
+
+    /// SSKDF with HMAC-SHA256
+    Sha256,
+
+    /// SSKDF with HMAC-SHA384
+    Sha384,
+
+    /// SSKDF with HMAC-SHA512
+    Sha512,
+}
+
+const SSKDF_DIGEST_SHA224: SskdfDigestAlgorithm = SskdfDigestAlgorithm {
+    id: SskdfDigestAlgorithmId::Sha224,
+};
+
+const SSKDF_DIGEST_SHA256: SskdfDigestAlgorithm = SskdfDigestAlgorithm {
+    id: SskdfDigestAlgorithmId::Sha256,
+};
+
+const SSKDF_DIGEST_SHA384: SskdfDigestAlgorithm = SskdfDigestAlgorithm {
+    id: SskdfDigestAlgorithmId::Sha384,
+};
+
+const SSKDF_DIGEST_SHA512: SskdfDigestAlgorithm = SskdfDigestAlgorithm {
+    id: SskdfDigestAlgorithmId::Sha512,
+};
+
+const SSKDF_HMAC_SHA224: SskdfHmacAlgorithm = SskdfHmacAlgorithm {
+    id: SskdfHmacAlgorithmId::Sha224,
+};
+
+const SSKDF_HMAC_SHA256: SskdfHmacAlgorithm = SskdfHmacAlgorithm {
+    id: SskdfHmacAlgorithmId::Sha256,
+};
+
+const SSKDF_HMAC_SHA384: SskdfHmacAlgorithm = SskdfHmacAlgorithm {
+    id: SskdfHmacAlgorithmId::Sha384,
+};
+
+const SSKDF_HMAC_SHA512: SskdfHmacAlgorithm = SskdfHmacAlgorithm {
+    id: SskdfHmacAlgorithmId::Sha512,
+};
+
+/// Retrieve [`SskdfDigestAlgorithm`] using the [`SskdfDigestAlgorithmId`] specified by `id`.
+#[must_use]
+pub const fn get_sskdf_digest_algorithm(
+    id: SskdfDigestAlgorithmId,
+) -> Option<&'static SskdfDigestAlgorithm> {
+    Some(match id {
+        SskdfDigestAlgorithmId::Sha224 => &SSKDF_DIGEST_SHA224,
+        SskdfDigestAlgorithmId::Sha256 => &SSKDF_DIGEST_SHA256,
+        SskdfDigestAlgorithmId::Sha384 => &SSKDF_DIGEST_SHA384,
+        SskdfDigestAlgorithmId::Sha512 => &SSKDF_DIGEST_SHA512,
+    })
+}
+
+/// Retrieve [`SskdfHmacAlgorithm`] using the [`SskdfHmacAlgorithmId`] specified by `id`.
+#[must_use]
+pub const fn get_sskdf_hmac_algorithm(
+    id: SskdfHmacAlgorithmId,
+) -> Option<&'static SskdfHmacAlgorithm> {
+    Some(match id {
+        SskdfHmacAlgorithmId::Sha224 => &SSKDF_HMAC_SHA224,
+        SskdfHmacAlgorithmId::Sha256 => &SSKDF_HMAC_SHA256,
+        SskdfHmacAlgorithmId::Sha384 => &SSKDF_HMAC_SHA384,
+        SskdfHmacAlgorithmId::Sha512 => &SSKDF_HMAC_SHA512,
+    })
+}
+
+/// # Single-step Key Derivation Function (SSKDF) with HMAC PRF
+///
+/// ## Input Validation and Defaults
+/// * `output.len() > 0` and `secret.len() > 0`
+/// * `output.len() <= (2^32 - 1) * H_outputBits`, where `H_outputBits` is the
+///   output length of the HMAC digest.
+/// * If `salt` is empty, the default salt specified in SP 800-56C (an all-zero
+///   byte string of the HMAC block length) is used.
+///
+/// ## Implementation Notes
+///
+/// This implementation adheres to the algorithm specified in Section 4 of the
+/// NIST Special Publication 800-56C Revision 2 published on August 2020.
+/// Using Option 2 for the auxiliary function H.
+///
+/// Specification is available at <https://doi.org/10.6028/NIST.SP.800-56Cr2>
+///
+/// # Errors
+/// `Unspecified` is returned if input validation fails or an unexpected error occurs.
+pub fn sskdf_hmac(
+    algorithm: &SskdfHmacAlgorithm,
+    secret: &[u8],
+    info: &[u8],
+    salt: &[u8],
+    output: &mut [u8],
+) -> Result<(), Unspecified> {
+    let evp_md = match_digest_type(algorithm.digest_algorithm());
+    let out_len = output.len();
+    if 1 != indicator_check!(unsafe {
+        SSKDF_hmac(
+            output.as_mut_ptr(),
+            out_len,
+            *evp_md,
+            secret.as_ptr(),
+            secret.len(),
+            info.as_ptr(),
+            info.len(),
+            salt.as_ptr(),
+            salt.len(),
+        )
+    }) {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
+/// # Single-step Key Derivation Function (SSKDF) with Digest
+///
+/// ## Input Validation and Defaults
+/// * `output.len() > 0` and `secret.len() > 0`
+/// * `output.len() <= (2^32 - 1) * H_outputBits`, where `H_outputBits` is the
+///   output length of the digest.
+///
+/// ## Implementation Notes
+///
+/// This implementation adheres to the algorithm specified in Section 4 of the
+/// NIST Special Publication 800-56C Revision 2 published on August 2020.
+/// Using Option 1 for the auxiliary function H.
+///
+/// Specification is available at <https://doi.org/10.6028/NIST.SP.800-56Cr2>
+///
+/// # Errors
+/// `Unspecified` is returned if input validation fails or an unexpected error occurs.
+pub fn sskdf_digest(
+    algorithm: &SskdfDigestAlgorithm,
+    secret: &[u8],
+    info: &[u8],
+    output: &mut [u8],
+) -> Result<(), Unspecified> {
+    let evp_md = match_digest_type(algorithm.digest_algorithm());
+    let out_len = output.len();
+    if 1 != indicator_check!(unsafe {
+        SSKDF_digest(
+            output.as_mut_ptr(),
+            out_len,
+            *evp_md,
+            secret.as_ptr(),
+            secret.len(),
+            info.as_ptr(),
+            info.len(),
+        )
+    }) {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hmac;
+    use crate::test::from_hex;
+    use crate::unstable::kdf::{
+        get_sskdf_digest_algorithm, get_sskdf_hmac_algorithm, sskdf_digest, sskdf_hmac,
+        SskdfDigestAlgorithmId, SskdfHmacAlgorithmId,
+    };
+
+    const SECRET: &str = "a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90";
+    const INFO: &[u8] = b"sskdf test info";
+    const SALT: &str = "0102030405060708090a0b0c0d0e0f10";
+
+    // RFC 7518, Appendix C: the JWA "ECDH-ES" Concat KDF is the SP 800-56A one-step KDF with
+    // SHA-256 as the auxiliary function.
+    #[test]
+    fn sskdf_digest_known_answer() {
+        let z = [
+            158, 86, 217, 29, 129, 113, 53, 211, 114, 131, 66, 131, 191, 132, 38, 156, 251, 49,
+            110, 163, 218, 128, 106, 72, 246, 218, 167, 121, 140, 254, 144, 196,
+        ];
+        let info = from_hex(
+            "00000007413132384743\
+             4d00000005416c696365\
+             00000003426f6200000080",
+        )
+        .unwrap();
+        let expected = [
+            86, 170, 141, 234, 248, 35, 109, 32, 92, 34, 40, 205, 113, 167, 16, 26,
+        ];
+
+        let algorithm = get_sskdf_digest_algorithm(SskdfDigestAlgorithmId::Sha256).unwrap();
+        let mut output = [0u8; 16];
+        sskdf_digest(algorithm, &z, &info, &mut output).unwrap();
+        assert_eq!(expected, output);
+    }
+
+    // SP 800-56Cr2, Section 4.1, option 2: K(i) = HMAC(salt, [i]_32 || Z || FixedInfo).
+    #[test]
+    fn sskdf_hmac_matches_definition() {
+        let secret = from_hex(SECRET).unwrap();
+        let salt = from_hex(SALT).unwrap();
+
+        let key = hmac::Key::new(hmac::HMAC_SHA256, &salt);
+        let mut expected = Vec::new();
+        for i in 1u32..=2 {
+            let mut ctx = hmac::Context::with_key(&key);
+            ctx.update(&i.to_be_bytes());
+            ctx.update(&secret);
+            ctx.update(INFO);
+            expected.extend_from_slice(ctx.sign().as_ref());
+        }
+
+        let algorithm = get_sskdf_hmac_algorithm(SskdfHmacAlgorithmId::Sha256).unwrap();
+        let mut output = [0u8; 48];
+        sskdf_hmac(algorithm, &secret, INFO, &salt, &mut output).unwrap();
+        assert_eq!(&expected[..48], &output);
+    }
+
+    #[test]
+    fn sskdf_input_validation() {
+        let secret = from_hex(SECRET).unwrap();
+        let salt = from_hex(SALT).unwrap();
+        for id in [
+            SskdfDigestAlgorithmId::Sha224,
+            SskdfDigestAlgorithmId::Sha256,
+            SskdfDigestAlgorithmId::Sha384,
+            SskdfDigestAlgorithmId::Sha512,
+        ] {
+            let algorithm = get_sskdf_digest_algorithm(id).unwrap();
+            assert_eq!(id, algorithm.id());
+            assert_eq!(format!("{id:?}"), format!("{algorithm:?}"));
+            let mut output = [0u8; 80];
+            assert!(sskdf_digest(algorithm, &secret, INFO, &mut output).is_ok());
+            assert!(sskdf_digest(algorithm, &[], INFO, &mut output).is_err());
+            assert!(sskdf_digest(algorithm, &secret, INFO, &mut []).is_err());
+        }
+        for id in [
+            SskdfHmacAlgorithmId::Sha224,
+            SskdfHmacAlgorithmId::Sha256,
+            SskdfHmacAlgorithmId::Sha384,
+            SskdfHmacAlgorithmId::Sha512,
+        ] {
+            let algorithm = get_sskdf_hmac_algorithm(id).unwrap();
+            assert_eq!(id, algorithm.id());
+            assert_eq!(format!("{id:?}"), format!("{algorithm:?}"));
+            let mut output = [0u8; 80];
+            assert!(sskdf_hmac(algorithm, &secret, INFO, &salt, &mut output).is_ok());
+            assert!(sskdf_hmac(algorithm, &[], INFO, &salt, &mut output).is_err());
+            assert!(sskdf_hmac(algorithm, &secret, INFO, &[], &mut output).is_ok());
+            assert!(sskdf_hmac(algorithm, &secret, INFO, &salt, &mut []).is_err());
+        }
+    }
+}
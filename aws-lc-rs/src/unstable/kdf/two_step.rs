@@ -0,0 +1,321 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use core::fmt::Debug;
+
+use crate::cipher::aes::{AES_128_KEY_LEN, AES_256_KEY_LEN};
+use crate::error::Unspecified;
+use crate::hmac;
+use crate::unstable::kdf::kbkdf::{
+    get_kbkdf_ctr_cmac_algorithm, get_kbkdf_ctr_hmac_algorithm, kbkdf_ctr_cmac, kbkdf_ctr_hmac,
+    KbkdfCtrCmacAlgorithm, KbkdfCtrCmacAlgorithmId, KbkdfCtrHmacAlgorithm, KbkdfCtrHmacAlgorithmId,
+};
+use aws_lc::AES_CMAC;
+use zeroize::Zeroize;
+
+/// The length of an AES-CMAC output block, in bytes.
+const CMAC_OUTPUT_LEN: usize = 16;
+
+/// The maximum length of a key-derivation key produced by the extraction step.
+const MAX_KDK_LEN: usize = crate::digest::MAX_OUTPUT_LEN;
+
+/// SP 800-56C Two-Step KDF
+pub struct TwoStepKdfAlgorithm {
+    id: TwoStepKdfAlgorithmId,
+}
+
+impl TwoStepKdfAlgorithm {
+    /// Returns the Two-Step KDF Algorithm Identifier
+    #[must_use]
+    pub fn id(&self) -> TwoStepKdfAlgorithmId {
+        self.id
+    }
+
+    /// The required salt length in bytes, if the algorithm mandates one.
+    ///
+    /// AES-CMAC based variants require the salt to be an AES key of the
+    /// corresponding length. HMAC based variants accept a salt of any length.
+    #[must_use]
+    pub fn salt_len(&self) -> Option<usize> {
+        match self.id {
+            TwoStepKdfAlgorithmId::HmacSha224
+            | TwoStepKdfAlgorithmId::HmacSha256
+            | TwoStepKdfAlgorithmId::HmacSha384
+            | TwoStepKdfAlgorithmId::HmacSha512 => None,
+            TwoStepKdfAlgorithmId::CmacAes128 => Some(AES_128_KEY_LEN),
+            TwoStepKdfAlgorithmId::CmacAes256 => Some(AES_256_KEY_LEN),
+        }
+    }
+}
+
+impl PartialEq for TwoStepKdfAlgorithm {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for TwoStepKdfAlgorithm {}
+
+impl Debug for TwoStepKdfAlgorithm {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.id, f)
+    }
+}
+
+/// SP 800-56C Two-Step KDF Algorithm Identifier
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TwoStepKdfAlgorithmId {
+    /// Two-Step KDF using HMAC-SHA224 for extraction and expansion
+    HmacSha224,
+
+    /// Two-Step KDF using HMAC-SHA256 for extraction and expansion
+    HmacSha256,
+
+    /// Two-Step KDF using HMAC-SHA384 for extraction and expansion
+    HmacSha384,
+
+    /// Two-Step KDF using HMAC-SHA512 for extraction and expansion
+    HmacSha512,
+
+    /// Two-Step KDF using AES-128-CMAC for extraction and expansion
+    CmacAes128,
+
+    /// Two-Step KDF using AES-256-CMAC for extraction, and AES-128-CMAC for expansion
+    CmacAes256,
+}
+
+const TWO_STEP_KDF_HMAC_SHA224: TwoStepKdfAlgorithm = TwoStepKdfAlgorithm {
+    id: TwoStepKdfAlgorithmId::HmacSha224,
+};
+
+const TWO_STEP_KDF_HMAC_SHA256: TwoStepKdfAlgorithm = TwoStepKdfAlgorithm {
+    id: TwoStepKdfAlgorithmId::HmacSha256,
+};
+
+const TWO_STEP_KDF_HMAC_SHA384: TwoStepKdfAlgorithm = TwoStepKdfAlgorithm {
+    id: TwoStepKdfAlgorithmId::HmacSha384,
+};
+
+const TWO_STEP_KDF_HMAC_SHA512: TwoStepKdfAlgorithm = TwoStepKdfAlgorithm {
+    id: TwoStepKdfAlgorithmId::HmacSha512,
+};
+
+const TWO_STEP_KDF_CMAC_AES128: TwoStepKdfAlgorithm = TwoStepKdfAlgorithm {
+    id: TwoStepKdfAlgorithmId::CmacAes128,
+};
+
+const TWO_STEP_KDF_CMAC_AES256: TwoStepKdfAlgorithm = TwoStepKdfAlgorithm {
+    id: TwoStepKdfAlgorithmId::CmacAes256,
+};
+
+/// Retrieve [`TwoStepKdfAlgorithm`] using the [`TwoStepKdfAlgorithmId`] specified by `id`.
+#[must_use]
+pub const fn get_two_step_kdf_algorithm(
+    id: TwoStepKdfAlgorithmId,
+) -> Option<&'static TwoStepKdfAlgorithm> {
+    Some(match id {
+        TwoStepKdfAlgorithmId::HmacSha224 => &TWO_STEP_KDF_HMAC_SHA224,
+        TwoStepKdfAlgorithmId::HmacSha256 => &TWO_STEP_KDF_HMAC_SHA256,
+        TwoStepKdfAlgorithmId::HmacSha384 => &TWO_STEP_KDF_HMAC_SHA384,
+        TwoStepKdfAlgorithmId::HmacSha512 => &TWO_STEP_KDF_HMAC_SHA512,
+        TwoStepKdfAlgorithmId::CmacAes128 => &TWO_STEP_KDF_CMAC_AES128,
+        TwoStepKdfAlgorithmId::CmacAes256 => &TWO_STEP_KDF_CMAC_AES256,
+    })
+}
+
+enum Expansion {
+    Hmac(&'static KbkdfCtrHmacAlgorithm),
+    Cmac(&'static KbkdfCtrCmacAlgorithm),
+}
+
+fn kbkdf_hmac(id: KbkdfCtrHmacAlgorithmId) -> Result<Expansion, Unspecified> {
+    get_kbkdf_ctr_hmac_algorithm(id)
+        .map(Expansion::Hmac)
+        .ok_or(Unspecified)
+}
+
+fn kbkdf_cmac(id: KbkdfCtrCmacAlgorithmId) -> Result<Expansion, Unspecified> {
+    get_kbkdf_ctr_cmac_algorithm(id)
+        .map(Expansion::Cmac)
+        .ok_or(Unspecified)
+}
+
+/// # Two-Step Key Derivation Function (extraction-then-expansion)
+///
+/// The randomness-extraction step computes the key-derivation key
+/// `K_DK = MAC(salt, secret)`. The key-expansion step then derives `output`
+/// from `K_DK` and `info` using the KDF in Counter Mode of SP 800-108r1, with
+/// a 32-bit big-endian counter placed before `info`. See [`super::kbkdf_ctr_hmac`]
+/// and [`super::kbkdf_ctr_cmac`].
+///
+/// KMAC based variants are not supported, as *AWS-LC* does not implement KMAC.
+///
+/// ## Input Validation and Defaults
+/// * `output.len() > 0` and `secret.len() > 0`
+/// * For HMAC based algorithms, if `salt` is empty the default salt specified
+///   in SP 800-56C (an all-zero byte string of the HMAC block length) is used.
+/// * For AES-CMAC based algorithms `salt.len()` must equal
+///   [`TwoStepKdfAlgorithm::salt_len`].
+///
+/// ## Implementation Notes
+///
+/// This implementation adheres to the algorithm specified in Section 5 of the
+/// NIST Special Publication 800-56C Revision 2 published on August 2020.
+/// When AES-CMAC is used for extraction, the 128-bit key-derivation key is
+/// used with AES-128-CMAC in the expansion step.
+///
+/// Specification is available at <https://doi.org/10.6028/NIST.SP.800-56Cr2>
+///
+/// # Errors
+/// `Unspecified` is returned if input validation fails or an unexpected error occurs.
+pub fn two_step_kdf(
+    algorithm: &TwoStepKdfAlgorithm,
+    secret: &[u8],
+    salt: &[u8],
+    info: &[u8],
+    output: &mut [u8],
+) -> Result<(), Unspecified> {
+    if secret.is_empty() || output.is_empty() {
+        return Err(Unspecified);
+    }
+    if let Some(salt_len) = algorithm.salt_len() {
+        if salt.len() != salt_len {
+            return Err(Unspecified);
+        }
+    }
+
+    let (extraction, expansion) = match algorithm.id {
+        TwoStepKdfAlgorithmId::HmacSha224 => (
+            Some(hmac::HMAC_SHA224),
+            kbkdf_hmac(KbkdfCtrHmacAlgorithmId::Sha224)?,
+        ),
+        TwoStepKdfAlgorithmId::HmacSha256 => (
+            Some(hmac::HMAC_SHA256),
+            kbkdf_hmac(KbkdfCtrHmacAlgorithmId::Sha256)?,
+        ),
+        TwoStepKdfAlgorithmId::HmacSha384 => (
+            Some(hmac::HMAC_SHA384),
+            kbkdf_hmac(KbkdfCtrHmacAlgorithmId::Sha384)?,
+        ),
+        TwoStepKdfAlgorithmId::HmacSha512 => (
+            Some(hmac::HMAC_SHA512),
+            kbkdf_hmac(KbkdfCtrHmacAlgorithmId::Sha512)?,
+        ),
+        TwoStepKdfAlgorithmId::CmacAes128 | TwoStepKdfAlgorithmId::CmacAes256 => {
+            (None, kbkdf_cmac(KbkdfCtrCmacAlgorithmId::Aes128)?)
+        }
+    };
+
+    let mut kdk = [0u8; MAX_KDK_LEN];
+    let kdk_len = if let Some(hmac_algorithm) = extraction {
+        let tag = hmac::sign(&hmac::Key::try_new(hmac_algorithm, salt)?, secret);
+        kdk[..tag.as_ref().len()].copy_from_slice(tag.as_ref());
+        tag.as_ref().len()
+    } else {
+        if 1 != unsafe {
+            AES_CMAC(
+                kdk.as_mut_ptr(),
+                salt.as_ptr(),
+                salt.len(),
+                secret.as_ptr(),
+                secret.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        CMAC_OUTPUT_LEN
+    };
+
+    let result = match expansion {
+        Expansion::Hmac(algorithm) => kbkdf_ctr_hmac(algorithm, &kdk[..kdk_len], info, output),
+        Expansion::Cmac(algorithm) => kbkdf_ctr_cmac(algorithm, &kdk[..kdk_len], info, output),
+    };
+    kdk.zeroize();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::from_hex;
+    use crate::unstable::kdf::{
+        get_kbkdf_ctr_cmac_algorithm, get_kbkdf_ctr_hmac_algorithm, get_two_step_kdf_algorithm,
+        kbkdf_ctr_cmac, kbkdf_ctr_hmac, two_step_kdf, KbkdfCtrCmacAlgorithmId,
+        KbkdfCtrHmacAlgorithmId, TwoStepKdfAlgorithmId,
+    };
+
+    const SECRET: &str = "a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90";
+    const INFO: &[u8] = b"two-step test info";
+
+    // SP 800-56Cr2, Section 5.1: K_DK = MAC(salt, Z), followed by SP 800-108 Counter Mode
+    // keyed with K_DK. The inputs are chosen so that the extraction step reproduces a published
+    // MAC test vector: RFC 4231 test case 2, RFC 4493 example 2 and the SP 800-38B AES-256
+    // example with a 16-byte message.
+    #[test]
+    fn two_step_kdf_matches_definition() {
+        let cmac_message = from_hex("6bc1bee22e409f96e93d7e117393172a").unwrap();
+        for (id, salt, secret, kdk) in [
+            (
+                TwoStepKdfAlgorithmId::HmacSha256,
+                b"Jefe".to_vec(),
+                b"what do ya want for nothing?".to_vec(),
+                "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            ),
+            (
+                TwoStepKdfAlgorithmId::CmacAes128,
+                from_hex("2b7e151628aed2a6abf7158809cf4f3c").unwrap(),
+                cmac_message.clone(),
+                "070a16b46b4d4144f79bdd9dd04a287c",
+            ),
+            (
+                TwoStepKdfAlgorithmId::CmacAes256,
+                from_hex("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4")
+                    .unwrap(),
+                cmac_message.clone(),
+                "28a7023f452e8f82bd4bf28d8c37c35c",
+            ),
+        ] {
+            let kdk = from_hex(kdk).unwrap();
+            let mut expected = [0u8; 40];
+            if id == TwoStepKdfAlgorithmId::HmacSha256 {
+                let kbkdf = get_kbkdf_ctr_hmac_algorithm(KbkdfCtrHmacAlgorithmId::Sha256).unwrap();
+                kbkdf_ctr_hmac(kbkdf, &kdk, INFO, &mut expected).unwrap();
+            } else {
+                let kbkdf = get_kbkdf_ctr_cmac_algorithm(KbkdfCtrCmacAlgorithmId::Aes128).unwrap();
+                kbkdf_ctr_cmac(kbkdf, &kdk, INFO, &mut expected).unwrap();
+            }
+
+            let algorithm = get_two_step_kdf_algorithm(id).unwrap();
+            let mut output = [0u8; 40];
+            two_step_kdf(algorithm, &secret, &salt, INFO, &mut output).unwrap();
+            assert_eq!(expected, output);
+        }
+    }
+
+    #[test]
+    fn two_step_kdf_input_validation() {
+        let secret = from_hex(SECRET).unwrap();
+        for id in [
+            TwoStepKdfAlgorithmId::HmacSha224,
+            TwoStepKdfAlgorithmId::HmacSha256,
+            TwoStepKdfAlgorithmId::HmacSha384,
+            TwoStepKdfAlgorithmId::HmacSha512,
+            TwoStepKdfAlgorithmId::CmacAes128,
+            TwoStepKdfAlgorithmId::CmacAes256,
+        ] {
+            let algorithm = get_two_step_kdf_algorithm(id).unwrap();
+            assert_eq!(id, algorithm.id());
+            assert_eq!(format!("{id:?}"), format!("{algorithm:?}"));
+
+            let salt = vec![3u8; algorithm.salt_len().unwrap_or(20)];
+            let mut output = [0u8; 80];
+            assert!(two_step_kdf(algorithm, &secret, &salt, INFO, &mut output).is_ok());
+            assert!(two_step_kdf(algorithm, &[], &salt, INFO, &mut output).is_err());
+            assert!(two_step_kdf(algorithm, &secret, &salt, INFO, &mut []).is_err());
+            assert_eq!(
+                algorithm.salt_len().is_none(),
+                two_step_kdf(algorithm, &secret, &salt[1..], INFO, &mut output).is_ok()
+            );
+        }
+    }
+}
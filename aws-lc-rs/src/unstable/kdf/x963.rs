@@ -0,0 +1,189 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use core::fmt::Debug;
+
+use crate::digest;
+use crate::error::Unspecified;
+
+/// ANSI X9.63 KDF
+pub struct X963KdfAlgorithm {
+    id: X963KdfAlgorithmId,
+}
+
+impl X963KdfAlgorithm {
+    /// Returns the ANSI X9.63 KDF Algorithm Identifier
+    #[must_use]
+    pub fn id(&self) -> X963KdfAlgorithmId {
+        self.id
+    }
+
+    #[must_use]
+    fn digest_algorithm(&self) -> &'static digest::Algorithm {
+        match self.id {
+            X963KdfAlgorithmId::Sha224 => &digest::SHA224,
+            X963KdfAlgorithmId::Sha256 => &digest::SHA256,
+            X963KdfAlgorithmId::Sha384 => &digest::SHA384,
+            X963KdfAlgorithmId::Sha512 => &digest::SHA512,
+        }
+    }
+}
+
+impl PartialEq for X963KdfAlgorithm {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for X963KdfAlgorithm {}
+
+impl Debug for X963KdfAlgorithm {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.id, f)
+    }
+}
+
+/// ANSI X9.63 KDF Algorithm Identifier
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum X963KdfAlgorithmId {
+    /// ANSI X9.63 KDF with SHA224
+    Sha224,
+
+    /// ANSI X9.63 KDF with SHA256
+    Sha256,
+
+    /// ANSI X9.63 KDF with SHA384
+    Sha384,
+
+    /// ANSI X9.63 KDF with SHA512
+    Sha512,
+}
+
+const X963_KDF_SHA224: X963KdfAlgorithm = X963KdfAlgorithm {
+    id: X963KdfAlgorithmId::Sha224,
+};
+
+const X963_KDF_SHA256: X963KdfAlgorithm = X963KdfAlgorithm {
+    id: X963KdfAlgorithmId::Sha256,
+};
+
+const X963_KDF_SHA384: X963KdfAlgorithm = X963KdfAlgorithm {
+    id: X963KdfAlgorithmId::Sha384,
+};
+
+const X963_KDF_SHA512: X963KdfAlgorithm = X963KdfAlgorithm {
+    id: X963KdfAlgorithmId::Sha512,
+};
+
+/// Retrieve [`X963KdfAlgorithm`] using the [`X963KdfAlgorithmId`] specified by `id`.
+#[must_use]
+pub const fn get_x963_kdf_algorithm(id: X963KdfAlgorithmId) -> Option<&'static X963KdfAlgorithm> {
+    Some(match id {
+        X963KdfAlgorithmId::Sha224 => &X963_KDF_SHA224,
+        X963KdfAlgorithmId::Sha256 => &X963_KDF_SHA256,
+        X963KdfAlgorithmId::Sha384 => &X963_KDF_SHA384,
+        X963KdfAlgorithmId::Sha512 => &X963_KDF_SHA512,
+    })
+}
+
+/// # ANSI X9.63 Key Derivation Function
+///
+/// Each block of output is computed as `H(secret || [i]_32 || info)`, where
+/// `[i]_32` is the 32-bit big-endian encoding of the block counter starting at
+/// one. This is the KDF used by the ECIES scheme of SEC 1.
+///
+/// ## Input Validation and Defaults
+/// * `output.len() > 0` and `secret.len() > 0`
+/// * The requested `output.len()` must not result in overflowing the 32-bit counter.
+///
+/// ## Implementation Notes
+///
+/// This implementation adheres to the algorithm specified in Section 3.6.1 of
+/// SEC 1: Elliptic Curve Cryptography, Version 2.0.
+///
+/// Specification is available at <https://www.secg.org/sec1-v2.pdf>
+///
+/// # Errors
+/// `Unspecified` is returned if input validation fails or an unexpected error occurs.
+pub fn x963_kdf(
+    algorithm: &X963KdfAlgorithm,
+    secret: &[u8],
+    info: &[u8],
+    output: &mut [u8],
+) -> Result<(), Unspecified> {
+    if secret.is_empty() || output.is_empty() {
+        return Err(Unspecified);
+    }
+    let digest_algorithm = algorithm.digest_algorithm();
+    let output_len = digest_algorithm.output_len;
+    let blocks = (output.len() + output_len - 1) / output_len;
+    if u32::try_from(blocks).is_err() {
+        return Err(Unspecified);
+    }
+
+    for (i, chunk) in output.chunks_mut(output_len).enumerate() {
+        // `i + 1 <= blocks <= u32::MAX`
+        #[allow(clippy::cast_possible_truncation)]
+        let counter = ((i + 1) as u32).to_be_bytes();
+        let mut ctx = digest::Context::try_new(digest_algorithm)?;
+        ctx.update(secret);
+        ctx.update(&counter);
+        ctx.update(info);
+        let block = ctx.finish();
+        chunk.copy_from_slice(&block.as_ref()[..chunk.len()]);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::from_hex;
+    use crate::unstable::kdf::{get_x963_kdf_algorithm, x963_kdf, X963KdfAlgorithmId};
+
+    #[test]
+    fn x963_kdf_known_answer() {
+        // NIST CAVS 12.0, ansx963_2001.rsp, [SHA-256]
+        for (secret, info, expected) in [
+            (
+                "96c05619d56c328ab95fe84b18264b08725b85e33fd34f08",
+                "",
+                "443024c3dae66b95e6f5670601558f71",
+            ),
+            (
+                "22518b10e70f2a3f243810ae3254139efbee04aa57c7af7d",
+                "75eef81aa3041e33b80971203d2c0c52",
+                "c498af77161cc59f2962b9a713e2b215152d139766ce34a776df11866a69bf2e\
+                 52a13d9c7c6fc878c50c5ea0bc7b00e0da2447cfd874f6cf92f30d0097111485\
+                 500c90c3af8b487872d04685d14c8d1dc8d7fa08beb0ce0ababc11f0bd496269\
+                 142d43525a78e5bc79a17f59676a5706dc54d54d4d1f0bd7e386128ec26afc21",
+            ),
+        ] {
+            let algorithm = get_x963_kdf_algorithm(X963KdfAlgorithmId::Sha256).unwrap();
+            let secret = from_hex(secret).unwrap();
+            let info = from_hex(info).unwrap();
+            let expected = from_hex(expected).unwrap();
+            let mut output = vec![0u8; expected.len()];
+            x963_kdf(algorithm, &secret, &info, &mut output).unwrap();
+            assert_eq!(expected, output);
+        }
+    }
+
+    #[test]
+    fn x963_kdf_input_validation() {
+        for id in [
+            X963KdfAlgorithmId::Sha224,
+            X963KdfAlgorithmId::Sha256,
+            X963KdfAlgorithmId::Sha384,
+            X963KdfAlgorithmId::Sha512,
+        ] {
+            let algorithm = get_x963_kdf_algorithm(id).unwrap();
+            assert_eq!(id, algorithm.id());
+            assert_eq!(format!("{id:?}"), format!("{algorithm:?}"));
+            let mut output = [0u8; 100];
+            assert!(x963_kdf(algorithm, &[1u8; 32], b"info", &mut output).is_ok());
+            assert!(x963_kdf(algorithm, &[], b"info", &mut output).is_err());
+            assert!(x963_kdf(algorithm, &[1u8; 32], b"info", &mut []).is_err());
+        }
+    }
+}
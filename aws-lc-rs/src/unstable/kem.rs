@@ -204,6 +204,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_kem_pairwise_consistency_test() {
+        for algorithm in [&KYBER512_R3, &KYBER768_R3, &KYBER1024_R3] {
+            let priv_key = DecapsulationKey::generate(algorithm).unwrap();
+            priv_key
+                .pairwise_consistency_test()
+                .expect("consistent key");
+        }
+    }
+
     #[test]
     fn test_serialized_kem_e2e() {
         for algorithm in [&KYBER512_R3, &KYBER768_R3, &KYBER1024_R3] {
@@ -0,0 +1,122 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Low-level RSA primitives.
+//!
+//! # ⚠️ Warning
+//! These functions are the raw RSA operations from [RFC 8017 section 5], without any encoding
+//! or padding. They are building blocks for protocols such as RSA blind signatures, and are not
+//! a secure signature or encryption scheme by themselves: inputs must be encoded as the
+//! protocol being implemented requires. Most users should use [`crate::rsa`] or
+//! [`crate::signature`] instead.
+//!
+//! [RFC 8017 section 5]: https://www.rfc-editor.org/rfc/rfc8017#section-5
+
+pub mod blind;
+
+use crate::error::Unspecified;
+use crate::rsa::{KeyPair, PublicKey};
+
+/// The RSASP1 signature primitive, `message^d mod n`, which is also the RSADP decryption
+/// primitive.
+///
+/// `message` is the big-endian representative, which must be exactly
+/// `key_pair.public_modulus_len()` bytes long and less than the modulus. The private key
+/// operation is blinded. Returns the subslice of `output` containing the result, which has the
+/// same length as `message`.
+///
+/// # Errors
+/// `error::Unspecified` if `message` has the wrong length or is not less than the modulus, or
+/// `output` is shorter than the modulus.
+pub fn rsasp1<'output>(
+    key_pair: &KeyPair,
+    message: &[u8],
+    output: &'output mut [u8],
+) -> Result<&'output mut [u8], Unspecified> {
+    key_pair.private_transform(message, output)
+}
+
+/// The RSAVP1 verification primitive, `signature^e mod n`, which is also the RSAEP encryption
+/// primitive.
+///
+/// `signature` is the big-endian representative, which must be exactly as long as the modulus
+/// and less than it. Returns the subslice of `output` containing the result, which has the same
+/// length as `signature`.
+///
+/// # Errors
+/// `error::Unspecified` if `signature` has the wrong length or is not less than the modulus, or
+/// `output` is shorter than the modulus.
+pub fn rsavp1<'output>(
+    public_key: &PublicKey,
+    signature: &[u8],
+    output: &'output mut [u8],
+) -> Result<&'output mut [u8], Unspecified> {
+    public_key.public_transform(signature, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rsasp1, rsavp1};
+    use crate::rand::SystemRandom;
+    use crate::rsa::KeyPair;
+    use crate::signature::{self, KeyPair as _};
+    use crate::{digest, test};
+
+    fn key_pair() -> KeyPair {
+        KeyPair::from_pkcs8(include_bytes!(
+            "../../tests/data/rsa_test_private_key_2048.p8"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rsasp1_matches_pkcs1_signature() {
+        let key_pair = key_pair();
+        let message = b"raw RSA";
+        let mut expected = vec![0u8; key_pair.public_modulus_len()];
+        key_pair
+            .sign(
+                &signature::RSA_PKCS1_SHA256,
+                &SystemRandom::new(),
+                message,
+                &mut expected,
+            )
+            .unwrap();
+
+        // EMSA-PKCS1-v1_5 encoding of the SHA-256 digest (RFC 8017 section 9.2).
+        let digest_info = test::from_dirty_hex("3031300d060960864801650304020105000420");
+        let digest = digest::digest(&digest::SHA256, message);
+        let mut encoded = vec![0x00, 0x01];
+        encoded.resize(key_pair.public_modulus_len() - digest_info.len() - 33, 0xff);
+        encoded.push(0x00);
+        encoded.extend_from_slice(&digest_info);
+        encoded.extend_from_slice(digest.as_ref());
+
+        let mut output = vec![0u8; key_pair.public_modulus_len()];
+        let signature = rsasp1(&key_pair, &encoded, &mut output).unwrap();
+        assert_eq!(expected, signature);
+
+        let mut output = vec![0u8; key_pair.public_modulus_len()];
+        let recovered = rsavp1(key_pair.public_key(), &expected, &mut output).unwrap();
+        assert_eq!(encoded, recovered);
+    }
+
+    #[test]
+    fn test_raw_rsa_rejects_invalid_input() {
+        let key_pair = key_pair();
+        let len = key_pair.public_modulus_len();
+        let mut output = vec![0u8; len];
+
+        // Not less than the modulus.
+        assert!(rsasp1(&key_pair, &vec![0xff; len], &mut output).is_err());
+        assert!(rsavp1(key_pair.public_key(), &vec![0xff; len], &mut output).is_err());
+
+        // Not the length of the modulus.
+        assert!(rsasp1(&key_pair, &[1u8; 32], &mut output).is_err());
+        assert!(rsavp1(key_pair.public_key(), &[1u8; 32], &mut output).is_err());
+
+        // Output too small.
+        let mut output = vec![0u8; len - 1];
+        assert!(rsasp1(&key_pair, &vec![1u8; len], &mut output).is_err());
+    }
+}
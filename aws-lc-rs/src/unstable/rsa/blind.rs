@@ -0,0 +1,481 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! RSA blind signatures, as specified in [RFC 9474], e.g. for Privacy Pass tokens.
+//!
+//! The client prepares a message with [`BlindSignatureAlgorithm::prepare`], blinds it with
+//! [`BlindSignatureAlgorithm::blind`] and sends the blinded message to the server. The server
+//! signs the blinded message with [`blind_sign`] without learning the message. The client then
+//! unblinds the blind signature with [`BlindSignatureAlgorithm::finalize`], which yields an
+//! RSASSA-PSS signature of the prepared message that the server cannot link to the blinded
+//! message.
+//!
+//! # Example
+//!
+//! ```
+//! use aws_lc_rs::rsa::{KeyPair, KeySize, PublicKeyComponents};
+//! use aws_lc_rs::signature::KeyPair as _;
+//! use aws_lc_rs::unstable::rsa::blind::{blind_sign, RSABSSA_SHA384_PSS_RANDOMIZED};
+//!
+//! let key_pair = KeyPair::generate(KeySize::Rsa2048)?;
+//! let public_key = PublicKeyComponents::<Vec<u8>>::try_from(key_pair.public_key())?;
+//! let algorithm = &RSABSSA_SHA384_PSS_RANDOMIZED;
+//!
+//! // The client blinds the message.
+//! let message = algorithm.prepare(b"hello")?;
+//! let (blinded_message, inverse) = algorithm.blind(&public_key, &message)?;
+//!
+//! // The server signs the blinded message.
+//! let blind_signature = blind_sign(&key_pair, &blinded_message)?;
+//!
+//! // The client unblinds the signature.
+//! let signature = algorithm.finalize(&public_key, &message, &blind_signature, &inverse)?;
+//! algorithm.verify(&public_key, &message, &signature)?;
+//! # Ok::<(), aws_lc_rs::error::Unspecified>(())
+//! ```
+//!
+//! [RFC 9474]: https://www.rfc-editor.org/rfc/rfc9474
+
+use crate::digest::{self, match_digest_type};
+use crate::error::Unspecified;
+use crate::ptr::{ConstPointer, LcPtr};
+use crate::rand;
+use crate::rsa::{KeyPair, PublicKeyComponents};
+use crate::signature::KeyPair as _;
+use aws_lc::{
+    BN_CTX_new, BN_bin2bn, BN_bn2bin_padded, BN_gcd, BN_is_one, BN_mod_inverse, BN_mod_mul, BN_new,
+    BN_rand_range_ex, RSA_bits, RSA_get0_n, RSA_padding_add_PKCS1_PSS_mgf1, RSA_size,
+    RSA_verify_pss_mgf1, RSA_verify_raw, BIGNUM, BN_CTX, EVP_PKEY, RSA, RSA_NO_PADDING,
+};
+use core::fmt::{self, Debug, Formatter};
+use core::ptr::{null, null_mut};
+use std::os::raw::c_int;
+
+/// The length of the random prefix that the randomized variants prepend to the message.
+const MESSAGE_RANDOMIZER_LEN: usize = 32;
+
+/// An RSA blind signature variant (RFC 9474 section 5).
+pub struct BlindSignatureAlgorithm {
+    id: AlgorithmId,
+    salt_len: usize,
+    randomized: bool,
+}
+
+/// The identifier of an RSA blind signature variant.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AlgorithmId {
+    /// RSABSSA-SHA384-PSS-Randomized
+    RsabssaSha384PssRandomized,
+    /// RSABSSA-SHA384-PSSZERO-Randomized
+    RsabssaSha384PsszeroRandomized,
+    /// RSABSSA-SHA384-PSS-Deterministic
+    RsabssaSha384PssDeterministic,
+    /// RSABSSA-SHA384-PSSZERO-Deterministic
+    RsabssaSha384PsszeroDeterministic,
+}
+
+/// RSABSSA-SHA384-PSS-Randomized: SHA-384, a 48-byte salt and a randomized message. This is
+/// the variant RFC 9474 recommends.
+pub static RSABSSA_SHA384_PSS_RANDOMIZED: BlindSignatureAlgorithm = BlindSignatureAlgorithm {
+    id: AlgorithmId::RsabssaSha384PssRandomized,
+    salt_len: 48,
+    randomized: true,
+};
+
+/// RSABSSA-SHA384-PSSZERO-Randomized: SHA-384, no salt and a randomized message.
+pub static RSABSSA_SHA384_PSSZERO_RANDOMIZED: BlindSignatureAlgorithm = BlindSignatureAlgorithm {
+    id: AlgorithmId::RsabssaSha384PsszeroRandomized,
+    salt_len: 0,
+    randomized: true,
+};
+
+/// RSABSSA-SHA384-PSS-Deterministic: SHA-384, a 48-byte salt and the message as is.
+pub static RSABSSA_SHA384_PSS_DETERMINISTIC: BlindSignatureAlgorithm = BlindSignatureAlgorithm {
+    id: AlgorithmId::RsabssaSha384PssDeterministic,
+    salt_len: 48,
+    randomized: false,
+};
+
+/// RSABSSA-SHA384-PSSZERO-Deterministic: SHA-384, no salt and the message as is. Its
+/// signatures are deterministic, and only suitable for messages with high entropy.
+pub static RSABSSA_SHA384_PSSZERO_DETERMINISTIC: BlindSignatureAlgorithm =
+    BlindSignatureAlgorithm {
+        id: AlgorithmId::RsabssaSha384PsszeroDeterministic,
+        salt_len: 0,
+        randomized: false,
+    };
+
+impl Debug for BlindSignatureAlgorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.id, f)
+    }
+}
+
+/// The inverse of the blind that [`BlindSignatureAlgorithm::blind`] applied to a message,
+/// which [`BlindSignatureAlgorithm::finalize`] uses to unblind the blind signature.
+///
+/// It must be kept secret, and used for only one message.
+pub struct BlindingInverse(LcPtr<BIGNUM>);
+
+impl Debug for BlindingInverse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("BlindingInverse")
+    }
+}
+
+impl BlindSignatureAlgorithm {
+    /// The identifier of this variant.
+    #[must_use]
+    pub fn id(&self) -> AlgorithmId {
+        self.id
+    }
+
+    /// Prepares `message` for blinding (RFC 9474 section 4.1). The randomized variants prepend
+    /// 32 random bytes; the deterministic variants return `message` as is.
+    ///
+    /// The prepared message is the one to blind, finalize and verify.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if generating the random bytes fails.
+    pub fn prepare(&self, message: &[u8]) -> Result<Vec<u8>, Unspecified> {
+        if !self.randomized {
+            return Ok(message.to_vec());
+        }
+        let mut prepared = vec![0u8; MESSAGE_RANDOMIZER_LEN];
+        rand::fill(&mut prepared)?;
+        prepared.extend_from_slice(message);
+        Ok(prepared)
+    }
+
+    /// Blinds the prepared `message` for the server with `public_key` (RFC 9474 section 4.2).
+    /// Returns the blinded message to send to the server, and the inverse of the blind to
+    /// finalize the server's blind signature with.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `public_key` is invalid or its modulus is not between 2048 and
+    /// 8192 bits, or the message cannot be blinded.
+    #[allow(clippy::many_single_char_names)]
+    pub fn blind<B>(
+        &self,
+        public_key: &PublicKeyComponents<B>,
+        message: &[u8],
+    ) -> Result<(Vec<u8>, BlindingInverse), Unspecified>
+    where
+        B: AsRef<[u8]> + Debug,
+    {
+        let rsa = PublicRsa::new(public_key)?;
+        let k = rsa.modulus_len();
+        let n = rsa.modulus()?;
+        let bn_ctx = LcPtr::<BN_CTX>::new(unsafe { BN_CTX_new() })?;
+
+        let mut encoded = vec![0u8; k];
+        let digest = digest::try_digest(&digest::SHA384, message)?;
+        if 1 != unsafe {
+            RSA_padding_add_PKCS1_PSS_mgf1(
+                *rsa.rsa,
+                encoded.as_mut_ptr(),
+                digest.as_ref().as_ptr(),
+                *match_digest_type(&digest::SHA384.id),
+                null(),
+                c_int::try_from(self.salt_len).map_err(|_| Unspecified)?,
+            )
+        } {
+            return Err(Unspecified);
+        }
+        let m = bignum(&encoded)?;
+
+        // The message must be invertible modulo `n`, which is overwhelmingly likely unless the
+        // key is malicious.
+        let gcd = LcPtr::new(unsafe { BN_new() })?;
+        if 1 != unsafe { BN_gcd(*gcd, *m, *n, *bn_ctx) } || 1 != unsafe { BN_is_one(*gcd) } {
+            return Err(Unspecified);
+        }
+
+        let r = LcPtr::new(unsafe { BN_new() })?;
+        if 1 != unsafe { BN_rand_range_ex(*r, 1, *n) } {
+            return Err(Unspecified);
+        }
+        let inverse = LcPtr::new(unsafe { BN_mod_inverse(null_mut(), *r, *n, *bn_ctx) })?;
+
+        // x = RSAVP1(pk, r), z = m * x mod n
+        let mut x = to_padded_bytes(&r, k)?;
+        rsa.public_transform(&mut x)?;
+        let x = bignum(&x)?;
+        let z = LcPtr::new(unsafe { BN_new() })?;
+        if 1 != unsafe { BN_mod_mul(*z, *m, *x, *n, *bn_ctx) } {
+            return Err(Unspecified);
+        }
+
+        Ok((to_padded_bytes(&z, k)?, BlindingInverse(inverse)))
+    }
+
+    /// Unblinds the server's `blind_signature` of the blinded message that
+    /// [`Self::blind`] returned for the prepared `message` (RFC 9474 section 4.4), and verifies
+    /// the resulting signature.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `blind_signature` does not unblind to a valid signature of
+    /// `message`, e.g. because the server signed with a different key.
+    pub fn finalize<B>(
+        &self,
+        public_key: &PublicKeyComponents<B>,
+        message: &[u8],
+        blind_signature: &[u8],
+        inverse: &BlindingInverse,
+    ) -> Result<Vec<u8>, Unspecified>
+    where
+        B: AsRef<[u8]> + Debug,
+    {
+        let rsa = PublicRsa::new(public_key)?;
+        let k = rsa.modulus_len();
+        if blind_signature.len() != k {
+            return Err(Unspecified);
+        }
+        let n = rsa.modulus()?;
+        let bn_ctx = LcPtr::<BN_CTX>::new(unsafe { BN_CTX_new() })?;
+
+        let z = bignum(blind_signature)?;
+        let s = LcPtr::new(unsafe { BN_new() })?;
+        if 1 != unsafe { BN_mod_mul(*s, *z, *inverse.0, *n, *bn_ctx) } {
+            return Err(Unspecified);
+        }
+        let signature = to_padded_bytes(&s, k)?;
+        self.verify_with(&rsa, message, &signature)?;
+        Ok(signature)
+    }
+
+    /// Verifies `signature` of the prepared `message` (RFC 9474 section 4.5), i.e. as an
+    /// RSASSA-PSS signature with SHA-384 and this variant's salt length.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `public_key` is invalid or the signature is invalid.
+    pub fn verify<B>(
+        &self,
+        public_key: &PublicKeyComponents<B>,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Unspecified>
+    where
+        B: AsRef<[u8]> + Debug,
+    {
+        self.verify_with(&PublicRsa::new(public_key)?, message, signature)
+    }
+
+    fn verify_with(
+        &self,
+        rsa: &PublicRsa,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Unspecified> {
+        let digest = digest::try_digest(&digest::SHA384, message)?;
+        if 1 != unsafe {
+            RSA_verify_pss_mgf1(
+                *rsa.rsa,
+                digest.as_ref().as_ptr(),
+                digest.as_ref().len(),
+                *match_digest_type(&digest::SHA384.id),
+                null(),
+                c_int::try_from(self.salt_len).map_err(|_| Unspecified)?,
+                signature.as_ptr(),
+                signature.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+}
+
+/// Signs a client's `blinded_message` (RFC 9474 section 4.3), without learning the message.
+/// The same for all variants.
+///
+/// # Errors
+/// `error::Unspecified` if `blinded_message` is not the length of the modulus or is not less
+/// than it, or the signature fails to verify.
+pub fn blind_sign(key_pair: &KeyPair, blinded_message: &[u8]) -> Result<Vec<u8>, Unspecified> {
+    let k = key_pair.public_modulus_len();
+    let mut blind_signature = vec![0u8; k];
+    key_pair.private_transform(blinded_message, &mut blind_signature)?;
+
+    // Protects against fault attacks that would reveal the private key.
+    let mut check = vec![0u8; k];
+    let check = key_pair
+        .public_key()
+        .public_transform(&blind_signature, &mut check)?;
+    if check != blinded_message {
+        return Err(Unspecified);
+    }
+    Ok(blind_signature)
+}
+
+// A client's copy of the server's public key.
+struct PublicRsa {
+    rsa: LcPtr<RSA>,
+    // Owns the key that `rsa` refers to.
+    _evp_pkey: LcPtr<EVP_PKEY>,
+}
+
+impl PublicRsa {
+    fn new<B>(public_key: &PublicKeyComponents<B>) -> Result<Self, Unspecified>
+    where
+        B: AsRef<[u8]> + Debug,
+    {
+        let evp_pkey = unsafe { public_key.build_rsa() }?;
+        let rsa = evp_pkey.get_rsa()?;
+        if !(2048..=8192).contains(&unsafe { RSA_bits(*rsa) }) {
+            return Err(Unspecified);
+        }
+        Ok(Self {
+            rsa,
+            _evp_pkey: evp_pkey,
+        })
+    }
+
+    fn modulus_len(&self) -> usize {
+        (unsafe { RSA_size(*self.rsa) }) as usize
+    }
+
+    fn modulus(&self) -> Result<ConstPointer<BIGNUM>, Unspecified> {
+        Ok(ConstPointer::new(unsafe { RSA_get0_n(*self.rsa) })?)
+    }
+
+    // RSAVP1, in place.
+    fn public_transform(&self, input: &mut [u8]) -> Result<(), Unspecified> {
+        let mut out_len = 0;
+        let mut output = vec![0u8; input.len()];
+        if 1 != unsafe {
+            RSA_verify_raw(
+                *self.rsa,
+                &mut out_len,
+                output.as_mut_ptr(),
+                output.len(),
+                input.as_ptr(),
+                input.len(),
+                RSA_NO_PADDING,
+            )
+        } || out_len != input.len()
+        {
+            return Err(Unspecified);
+        }
+        input.copy_from_slice(&output);
+        Ok(())
+    }
+}
+
+fn bignum(bytes: &[u8]) -> Result<LcPtr<BIGNUM>, Unspecified> {
+    Ok(LcPtr::new(unsafe {
+        BN_bin2bn(bytes.as_ptr(), bytes.len(), null_mut())
+    })?)
+}
+
+fn to_padded_bytes(bn: &LcPtr<BIGNUM>, len: usize) -> Result<Vec<u8>, Unspecified> {
+    let mut bytes = vec![0u8; len];
+    if 1 != unsafe { BN_bn2bin_padded(bytes.as_mut_ptr(), len, **bn) } {
+        return Err(Unspecified);
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        blind_sign, BlindSignatureAlgorithm, RSABSSA_SHA384_PSSZERO_DETERMINISTIC,
+        RSABSSA_SHA384_PSSZERO_RANDOMIZED, RSABSSA_SHA384_PSS_DETERMINISTIC,
+        RSABSSA_SHA384_PSS_RANDOMIZED,
+    };
+    use crate::rsa::{KeyPair, PublicKeyComponents};
+    use crate::signature::{self, KeyPair as _, UnparsedPublicKey};
+    use crate::test::from_dirty_hex;
+
+    const ALGORITHMS: [&BlindSignatureAlgorithm; 4] = [
+        &RSABSSA_SHA384_PSS_RANDOMIZED,
+        &RSABSSA_SHA384_PSSZERO_RANDOMIZED,
+        &RSABSSA_SHA384_PSS_DETERMINISTIC,
+        &RSABSSA_SHA384_PSSZERO_DETERMINISTIC,
+    ];
+
+    fn key_pair() -> KeyPair {
+        KeyPair::from_pkcs8(include_bytes!(
+            "../../../tests/data/rsa_test_private_key_2048.p8"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_blind_signature_round_trip() {
+        let key_pair = key_pair();
+        let public_key = PublicKeyComponents::<Vec<u8>>::try_from(key_pair.public_key()).unwrap();
+
+        for algorithm in ALGORITHMS {
+            let message = algorithm.prepare(b"hello blind signatures").unwrap();
+            let randomizer_len = if algorithm.randomized { 32 } else { 0 };
+            assert_eq!(22 + randomizer_len, message.len());
+
+            let (blinded_message, inverse) = algorithm.blind(&public_key, &message).unwrap();
+            assert_eq!(256, blinded_message.len());
+            let blind_signature = blind_sign(&key_pair, &blinded_message).unwrap();
+            let signature = algorithm
+                .finalize(&public_key, &message, &blind_signature, &inverse)
+                .unwrap();
+            algorithm.verify(&public_key, &message, &signature).unwrap();
+            assert!(algorithm
+                .verify(&public_key, b"another message", &signature)
+                .is_err());
+
+            // The signature is an ordinary RSASSA-PSS signature.
+            if algorithm.salt_len == 48 {
+                UnparsedPublicKey::new(
+                    &signature::RSA_PSS_2048_8192_SHA384,
+                    key_pair.public_key().as_ref(),
+                )
+                .verify(&message, &signature)
+                .unwrap();
+            }
+
+            // The blind signature must be unblinded with the inverse of its own blind.
+            let (_, other_inverse) = algorithm.blind(&public_key, &message).unwrap();
+            assert!(algorithm
+                .finalize(&public_key, &message, &blind_signature, &other_inverse)
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_blind_signature_unlinkable() {
+        // Produced with `openssl dgst -sha384 -sign -sigopt rsa_padding_mode:pss
+        // -sigopt rsa_pss_saltlen:0`.
+        const SIGNATURE: &str = "922b92eb1af5e543f05606f7d4c5b993340fb65828aa5f1ce4bff7037255a48c\
+            ff3bc9ed17a345ab9bdcc991d95101522dfdb47effeaffd501a2c6b78d975888af471eaac3f8a884e440f\
+            9e7487fa727a83b4f7ad13d6ee0f869f53e4d35c292295592c6f03b9d29c2e72098af90a8180affd1d5fc\
+            ba969d0c30dad6adcfdf15f7c7c8a1b7fb5c8f9833f7fdc4ad15995a5ec5e593bc94ae8d5b4d535aa8828\
+            73f396af5523fd85a300ca925c877f78d42a067e506ee69327c89ce6144504ab56f224af3cf7577a9ace7\
+            77d8ab5ca429b4882d29865562af3aa9327e28e46c77978b56116e3a9295e61211d2ba5d3529a42517bb7\
+            ea2deb8bb40cce328d36e0f";
+
+        let key_pair = key_pair();
+        let public_key = PublicKeyComponents::<Vec<u8>>::try_from(key_pair.public_key()).unwrap();
+        let algorithm = &RSABSSA_SHA384_PSSZERO_DETERMINISTIC;
+
+        let message = b"hello blind signatures";
+        let (first, first_inverse) = algorithm.blind(&public_key, message).unwrap();
+        let (second, second_inverse) = algorithm.blind(&public_key, message).unwrap();
+        assert_ne!(first, second);
+
+        for (blinded_message, inverse) in [(first, first_inverse), (second, second_inverse)] {
+            let blind_signature = blind_sign(&key_pair, &blinded_message).unwrap();
+            let signature = algorithm
+                .finalize(&public_key, message, &blind_signature, &inverse)
+                .unwrap();
+            assert_eq!(from_dirty_hex(SIGNATURE), signature);
+        }
+    }
+
+    #[test]
+    fn test_blind_sign_rejects_invalid_input() {
+        let key_pair = key_pair();
+        assert!(blind_sign(&key_pair, &[0xff; 256]).is_err());
+        assert!(blind_sign(&key_pair, &[1; 255]).is_err());
+    }
+}
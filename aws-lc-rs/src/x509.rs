@@ -0,0 +1,299 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! X.509 certificates, as described in [RFC 5280].
+//!
+//! # Example
+//!
+//! ```rust
+//! # use std::error::Error;
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::signature::{EcdsaKeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+//! use aws_lc_rs::x509::{Certificate, CertificateBuilder, Name, SubjectAltName};
+//!
+//! let key_pair = EcdsaKeyPair::generate(&ECDSA_P256_SHA256_ASN1_SIGNING)?;
+//! let der = CertificateBuilder::new(Name::builder().common_name("localhost").build()?)
+//!     .subject_alt_name(SubjectAltName::DnsName("localhost".to_string()))
+//!     .self_signed(&key_pair)?;
+//! let certificate = Certificate::from_der(der.as_ref())?;
+//!
+//! assert_eq!("CN=localhost", certificate.subject().to_string());
+//! assert_eq!(Some("localhost"), certificate.subject().common_name());
+//! assert!(certificate
+//!     .subject_alt_names()
+//!     .contains(&SubjectAltName::DnsName("localhost".to_string())));
+//!
+//! assert_eq!(certificate.issuer(), certificate.subject());
+//! certificate.verify_signature(&certificate.public_key()?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [RFC 5280]: https://tools.ietf.org/html/rfc5280
+
+mod builder;
+mod certificate;
+mod crl;
+mod name;
+mod request;
+mod verify;
+
+pub use self::builder::CertificateBuilder;
+pub use self::certificate::{Certificate, Extension, KeyIdentifierMethod, SubjectAltName};
+pub use self::crl::{CertificateRevocationList, RevokedCertificate};
+pub use self::name::{Name, NameBuilder, NameEntry};
+pub use self::request::CertificateRequestBuilder;
+pub use self::verify::{
+    KeyPurpose, RevocationCheck, VerificationError, VerificationOptions, Verifier,
+};
+
+use crate::digest;
+use crate::error::Unspecified;
+use crate::ptr::{DetachableLcPtr, LcPtr};
+use crate::signature::EcdsaKeyPair;
+#[cfg(not(feature = "strict-fips"))]
+use crate::signature::Ed25519KeyPair;
+use crate::{policy, rsa};
+use aws_lc::{
+    ASN1_IA5STRING_new, ASN1_OCTET_STRING_new, ASN1_STRING_get0_data, ASN1_STRING_length,
+    ASN1_STRING_set, ASN1_STRING_to_UTF8, ASN1_TIME_to_posix, EVP_MD_type, EVP_PKEY_bits,
+    EVP_PKEY_id, EVP_PKEY_up_ref, GENERAL_NAMES_new, GENERAL_NAME_new, GENERAL_NAME_set0_value,
+    NID_subject_alt_name, NID_undef, OBJ_find_sigid_algs, OBJ_obj2txt, OBJ_txt2obj,
+    OPENSSL_sk_push, X509V3_EXT_i2d, X509_EXTENSION_create_by_OBJ, ASN1_OBJECT, ASN1_STRING,
+    ASN1_TIME, EVP_MD, EVP_PKEY, EVP_PKEY_RSA, GEN_DIRNAME, GEN_DNS, GEN_EMAIL, GEN_IPADD, GEN_URI,
+    X509_EXTENSION,
+};
+use core::ptr::null_mut;
+use std::ffi::CString;
+use std::net::IpAddr;
+use std::os::raw::{c_char, c_int};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A key pair that signs certificates and certificate signing requests.
+///
+/// ECDSA keys sign with the digest algorithm of their signing algorithm, and RSA keys sign
+/// with PKCS#1 v1.5 padding and SHA-256.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug)]
+pub enum SigningKey<'a> {
+    /// An ECDSA key pair.
+    Ecdsa(&'a EcdsaKeyPair),
+    /// An Ed25519 key pair.
+    #[cfg(not(feature = "strict-fips"))]
+    Ed25519(&'a Ed25519KeyPair),
+    /// An RSA key pair.
+    Rsa(&'a rsa::KeyPair),
+}
+
+impl<'a> From<&'a EcdsaKeyPair> for SigningKey<'a> {
+    fn from(key_pair: &'a EcdsaKeyPair) -> Self {
+        SigningKey::Ecdsa(key_pair)
+    }
+}
+
+#[cfg(not(feature = "strict-fips"))]
+impl<'a> From<&'a Ed25519KeyPair> for SigningKey<'a> {
+    fn from(key_pair: &'a Ed25519KeyPair) -> Self {
+        SigningKey::Ed25519(key_pair)
+    }
+}
+
+impl<'a> From<&'a rsa::KeyPair> for SigningKey<'a> {
+    fn from(key_pair: &'a rsa::KeyPair) -> Self {
+        SigningKey::Rsa(key_pair)
+    }
+}
+
+impl SigningKey<'_> {
+    fn evp_pkey(&self) -> Result<LcPtr<EVP_PKEY>, Unspecified> {
+        let evp_pkey = match self {
+            SigningKey::Ecdsa(key_pair) => key_pair.evp_pkey(),
+            #[cfg(not(feature = "strict-fips"))]
+            SigningKey::Ed25519(key_pair) => return key_pair.evp_pkey(),
+            SigningKey::Rsa(key_pair) => key_pair.evp_pkey(),
+        };
+        if 1 != unsafe { EVP_PKEY_up_ref(**evp_pkey) } {
+            return Err(Unspecified);
+        }
+        Ok(LcPtr::new(**evp_pkey)?)
+    }
+
+    // Checks the installed policy against signing with this key.
+    fn enforce_policy(&self) -> Result<(), Unspecified> {
+        match self {
+            SigningKey::Ecdsa(key_pair) => policy::enforce_digest(key_pair.digest_algorithm())?,
+            #[cfg(not(feature = "strict-fips"))]
+            SigningKey::Ed25519(_) => {}
+            SigningKey::Rsa(key_pair) => key_pair.enforce_signing_policy(&digest::SHA256)?,
+        }
+        Ok(())
+    }
+
+    // Ed25519 signs the message itself, which AWS-LC expresses as a null digest.
+    fn digest(&self) -> *const EVP_MD {
+        match self {
+            SigningKey::Ecdsa(key_pair) => {
+                *digest::match_digest_type(&key_pair.digest_algorithm().id)
+            }
+            #[cfg(not(feature = "strict-fips"))]
+            SigningKey::Ed25519(_) => core::ptr::null(),
+            SigningKey::Rsa(_) => *digest::match_digest_type(&digest::SHA256.id),
+        }
+    }
+}
+
+// Checks the installed policy against verifying a signature made by `public_key` with the
+// signature algorithm `signature_nid`.
+unsafe fn enforce_signature_policy(
+    signature_nid: c_int,
+    public_key: *const EVP_PKEY,
+) -> Result<(), Unspecified> {
+    let mut digest_nid = NID_undef;
+    if 1 == OBJ_find_sigid_algs(signature_nid, &mut digest_nid, null_mut()) {
+        // Digest algorithms that the crate does not implement cannot be denied.
+        if let Some(algorithm) = [
+            &digest::SHA1_FOR_LEGACY_USE_ONLY,
+            &digest::SHA224,
+            &digest::SHA256,
+            &digest::SHA384,
+            &digest::SHA512,
+            &digest::SHA512_256,
+        ]
+        .into_iter()
+        .find(|algorithm| digest_nid == EVP_MD_type(*digest::match_digest_type(&algorithm.id)))
+        {
+            policy::enforce_digest(algorithm)?;
+        }
+    }
+    if EVP_PKEY_RSA == EVP_PKEY_id(public_key) {
+        policy::enforce_rsa_modulus_bits(u32::try_from(EVP_PKEY_bits(public_key))?)?;
+    }
+    Ok(())
+}
+
+// The contents of `string`, which must outlive the returned slice.
+unsafe fn asn1_string_bytes<'a>(string: *const ASN1_STRING) -> &'a [u8] {
+    let data = ASN1_STRING_get0_data(string);
+    match usize::try_from(ASN1_STRING_length(string)) {
+        Ok(len) if !data.is_null() => core::slice::from_raw_parts(data, len),
+        _ => &[],
+    }
+}
+
+unsafe fn asn1_string_to_utf8(string: *const ASN1_STRING) -> Result<String, Unspecified> {
+    let mut out = null_mut::<u8>();
+    let len = usize::try_from(ASN1_STRING_to_UTF8(&mut out, string))?;
+    let out = LcPtr::new(out)?;
+    String::from_utf8(out.as_slice(len).to_vec()).map_err(|_| Unspecified)
+}
+
+// The dotted-decimal form of `object`.
+unsafe fn oid_to_string(object: *const ASN1_OBJECT) -> Result<String, Unspecified> {
+    let mut buffer = [0u8; 80];
+    let len = OBJ_obj2txt(
+        buffer.as_mut_ptr().cast::<c_char>(),
+        c_int::try_from(buffer.len())?,
+        object,
+        1,
+    );
+    let len = usize::try_from(len)?;
+    if len == 0 || len >= buffer.len() {
+        return Err(Unspecified);
+    }
+    String::from_utf8(buffer[..len].to_vec()).map_err(|_| Unspecified)
+}
+
+fn posix_to_system_time(seconds: i64) -> Result<SystemTime, Unspecified> {
+    let offset = Duration::from_secs(seconds.unsigned_abs());
+    if seconds < 0 {
+        UNIX_EPOCH.checked_sub(offset)
+    } else {
+        UNIX_EPOCH.checked_add(offset)
+    }
+    .ok_or(Unspecified)
+}
+
+pub(crate) unsafe fn asn1_time_to_system_time(
+    time: *const ASN1_TIME,
+) -> Result<SystemTime, Unspecified> {
+    let mut seconds = 0;
+    if 1 != ASN1_TIME_to_posix(time, &mut seconds) {
+        return Err(Unspecified);
+    }
+    posix_to_system_time(seconds)
+}
+
+fn system_time_to_posix(time: SystemTime) -> Result<i64, Unspecified> {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(elapsed) => i64::try_from(elapsed.as_secs()),
+        Err(error) => i64::try_from(error.duration().as_secs()).map(|seconds| -seconds),
+    }
+    .map_err(|_| Unspecified)
+}
+
+// An extension with the DER-encoded `value`, identified by the dotted-decimal `oid`.
+fn new_extension(
+    oid: &str,
+    critical: bool,
+    value: &[u8],
+) -> Result<LcPtr<X509_EXTENSION>, Unspecified> {
+    let oid = CString::new(oid).map_err(|_| Unspecified)?;
+    let object = LcPtr::new(unsafe { OBJ_txt2obj(oid.as_ptr(), 1) })?;
+    let data = new_asn1_string(unsafe { ASN1_OCTET_STRING_new() }, value)?;
+    Ok(LcPtr::new(unsafe {
+        X509_EXTENSION_create_by_OBJ(null_mut(), *object, c_int::from(critical), *data)
+    })?)
+}
+
+fn new_subject_alt_name_extension(
+    names: &[SubjectAltName],
+) -> Result<LcPtr<X509_EXTENSION>, Unspecified> {
+    let general_names = LcPtr::new(unsafe { GENERAL_NAMES_new() })?;
+    for name in names {
+        let general_name = DetachableLcPtr::new(unsafe { GENERAL_NAME_new() })?;
+        let (name_type, value) = match name {
+            SubjectAltName::DnsName(value) => (GEN_DNS, new_ia5_string(value)?.detach().cast()),
+            SubjectAltName::Email(value) => (GEN_EMAIL, new_ia5_string(value)?.detach().cast()),
+            SubjectAltName::Uri(value) => (GEN_URI, new_ia5_string(value)?.detach().cast()),
+            SubjectAltName::IpAddress(ip) => {
+                let octets = match ip {
+                    IpAddr::V4(ip) => ip.octets().to_vec(),
+                    IpAddr::V6(ip) => ip.octets().to_vec(),
+                };
+                let value = new_asn1_string(unsafe { ASN1_OCTET_STRING_new() }, &octets)?;
+                (GEN_IPADD, value.detach().cast())
+            }
+            SubjectAltName::DirectoryName(value) => {
+                (GEN_DIRNAME, value.to_x509_name()?.detach().cast())
+            }
+        };
+        // `general_name` takes ownership of `value`.
+        unsafe { GENERAL_NAME_set0_value(*general_name, name_type, value) };
+        if 0 == unsafe { OPENSSL_sk_push((*general_names).cast(), (*general_name).cast()) } {
+            return Err(Unspecified);
+        }
+        general_name.detach();
+    }
+    Ok(LcPtr::new(unsafe {
+        X509V3_EXT_i2d(NID_subject_alt_name, 0, (*general_names).cast())
+    })?)
+}
+
+fn new_ia5_string(value: &str) -> Result<DetachableLcPtr<ASN1_STRING>, Unspecified> {
+    if !value.is_ascii() {
+        return Err(Unspecified);
+    }
+    new_asn1_string(unsafe { ASN1_IA5STRING_new() }, value.as_bytes())
+}
+
+fn new_asn1_string(
+    string: *mut ASN1_STRING,
+    value: &[u8],
+) -> Result<DetachableLcPtr<ASN1_STRING>, Unspecified> {
+    let string = DetachableLcPtr::new(string)?;
+    let len = value.len().try_into()?;
+    if 1 != unsafe { ASN1_STRING_set(*string, value.as_ptr().cast(), len) } {
+        return Err(Unspecified);
+    }
+    Ok(string)
+}
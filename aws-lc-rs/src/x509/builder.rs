@@ -0,0 +1,401 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use super::{
+    new_asn1_string, new_extension, new_subject_alt_name_extension, system_time_to_posix,
+    Certificate, KeyPurpose, Name, SigningKey, SubjectAltName,
+};
+use crate::error::Unspecified;
+use crate::ptr::LcPtr;
+use crate::{digest, rand};
+use aws_lc::{
+    ASN1_BIT_STRING_new, ASN1_BIT_STRING_set_bit, ASN1_OCTET_STRING_new, ASN1_TIME_set_posix,
+    BASIC_CONSTRAINTS_new, BN_bin2bn, BN_to_ASN1_INTEGER, EVP_sha1, NID_basic_constraints,
+    NID_ext_key_usage, NID_key_usage, NID_subject_key_identifier, OBJ_nid2obj, OPENSSL_sk_new_null,
+    OPENSSL_sk_push, X509V3_EXT_i2d, X509_add_ext, X509_new, X509_pubkey_digest,
+    X509_set1_notAfter, X509_set1_notBefore, X509_set_issuer_name, X509_set_pubkey,
+    X509_set_serialNumber, X509_set_subject_name, X509_set_version, X509_sign, ASN1_INTEGER,
+    BASIC_CONSTRAINTS, X509, X509_EXTENSION, X509_VERSION_3,
+};
+use core::ptr::null_mut;
+use std::os::raw::c_int;
+use std::time::{Duration, SystemTime};
+
+// The bits of the `KeyUsage` extension, as numbered in RFC 5280 Section 4.2.1.3.
+const KEY_USAGE_DIGITAL_SIGNATURE: c_int = 0;
+const KEY_USAGE_KEY_CERT_SIGN: c_int = 5;
+const KEY_USAGE_CRL_SIGN: c_int = 6;
+
+// RFC 5280 Section 4.1.2.2 limits serial numbers to 20 octets.
+const SERIAL_NUMBER_MAX_LEN: usize = 20;
+const SERIAL_NUMBER_DEFAULT_LEN: usize = 16;
+
+const DEFAULT_VALIDITY: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+/// Builds and signs an X.509 v3 certificate.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// # #[cfg(not(feature = "strict-fips"))]
+/// # {
+/// use aws_lc_rs::signature::Ed25519KeyPair;
+/// use aws_lc_rs::rand::SystemRandom;
+/// use aws_lc_rs::x509::{CertificateBuilder, KeyPurpose, Name, SubjectAltName};
+///
+/// let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())?;
+/// let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref())?;
+///
+/// let subject = Name::builder().common_name("localhost").build()?;
+/// let certificate = CertificateBuilder::new(subject)
+///     .subject_alt_name(SubjectAltName::DnsName("localhost".to_string()))
+///     .key_purpose(KeyPurpose::ServerAuth)
+///     .self_signed(&key_pair)?;
+/// assert_eq!(certificate.subject(), certificate.issuer());
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct CertificateBuilder {
+    subject: Name,
+    serial_number: Option<Box<[u8]>>,
+    not_before: SystemTime,
+    not_after: SystemTime,
+    subject_alt_names: Vec<SubjectAltName>,
+    ca: bool,
+    key_purposes: Vec<KeyPurpose>,
+    extensions: Vec<(String, bool, Box<[u8]>)>,
+}
+
+impl CertificateBuilder {
+    /// Returns a builder for a certificate for `subject`, valid for one year from now, with a
+    /// random serial number.
+    #[must_use]
+    pub fn new(subject: Name) -> Self {
+        let now = SystemTime::now();
+        Self {
+            subject,
+            serial_number: None,
+            not_before: now,
+            not_after: now + DEFAULT_VALIDITY,
+            subject_alt_names: Vec::new(),
+            ca: false,
+            key_purposes: Vec::new(),
+            extensions: Vec::new(),
+        }
+    }
+
+    /// Sets the big-endian bytes of the serial number, which must be positive and at most 20
+    /// bytes long.
+    #[must_use]
+    pub fn serial_number(mut self, serial_number: &[u8]) -> Self {
+        self.serial_number = Some(serial_number.into());
+        self
+    }
+
+    /// Sets the validity period of the certificate.
+    #[must_use]
+    pub fn validity(mut self, not_before: SystemTime, not_after: SystemTime) -> Self {
+        self.not_before = not_before;
+        self.not_after = not_after;
+        self
+    }
+
+    /// Adds `name` to the certificate's subject alternative names.
+    #[must_use]
+    pub fn subject_alt_name(mut self, name: SubjectAltName) -> Self {
+        self.subject_alt_names.push(name);
+        self
+    }
+
+    /// Marks the certificate as a CA that can issue certificates and CRLs. Otherwise its key
+    /// usage is limited to digital signatures.
+    #[must_use]
+    pub fn ca(mut self, ca: bool) -> Self {
+        self.ca = ca;
+        self
+    }
+
+    /// Adds `purpose` to the certificate's extended key usage.
+    #[must_use]
+    pub fn key_purpose(mut self, purpose: KeyPurpose) -> Self {
+        self.key_purposes.push(purpose);
+        self
+    }
+
+    /// Adds an extension of type `oid`, a dotted-decimal object identifier, with the
+    /// DER-encoded `value`.
+    #[must_use]
+    pub fn extension(mut self, oid: &str, critical: bool, value: &[u8]) -> Self {
+        self.extensions
+            .push((oid.to_string(), critical, value.into()));
+        self
+    }
+
+    /// Creates a certificate for the public key of `key_pair`, signed by `key_pair` itself.
+    ///
+    /// The certificate has basic constraints, key usage and subject key identifier
+    /// extensions, in addition to those added to the builder.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the serial number is not valid, the validity period ends before
+    /// it begins, a subject alternative name or extension cannot be encoded, or signing fails.
+    pub fn self_signed<'a>(
+        &self,
+        key_pair: impl Into<SigningKey<'a>>,
+    ) -> Result<Certificate, Unspecified> {
+        if self.not_after < self.not_before {
+            return Err(Unspecified);
+        }
+        let key_pair = key_pair.into();
+        key_pair.enforce_policy()?;
+        let evp_pkey = key_pair.evp_pkey()?;
+
+        let x509 = LcPtr::new(unsafe { X509_new() })?;
+        let subject = self.subject.to_x509_name()?;
+        let serial_number = self.new_serial_number()?;
+        let not_before = LcPtr::new(unsafe {
+            ASN1_TIME_set_posix(null_mut(), system_time_to_posix(self.not_before)?)
+        })?;
+        let not_after = LcPtr::new(unsafe {
+            ASN1_TIME_set_posix(null_mut(), system_time_to_posix(self.not_after)?)
+        })?;
+        if 1 != unsafe { X509_set_version(*x509, X509_VERSION_3.into()) }
+            || 1 != unsafe { X509_set_serialNumber(*x509, *serial_number) }
+            || 1 != unsafe { X509_set_subject_name(*x509, *subject) }
+            || 1 != unsafe { X509_set_issuer_name(*x509, *subject) }
+            || 1 != unsafe { X509_set1_notBefore(*x509, *not_before) }
+            || 1 != unsafe { X509_set1_notAfter(*x509, *not_after) }
+            || 1 != unsafe { X509_set_pubkey(*x509, *evp_pkey) }
+        {
+            return Err(Unspecified);
+        }
+
+        for extension in self.new_extensions(&x509)? {
+            // The certificate copies the extension.
+            if 1 != unsafe { X509_add_ext(*x509, *extension, -1) } {
+                return Err(Unspecified);
+            }
+        }
+
+        if 0 == unsafe { X509_sign(*x509, *evp_pkey, key_pair.digest()) } {
+            return Err(Unspecified);
+        }
+        Ok(Certificate::from_x509(x509)?)
+    }
+
+    fn new_serial_number(&self) -> Result<LcPtr<ASN1_INTEGER>, Unspecified> {
+        let serial_number = match &self.serial_number {
+            Some(serial_number) => serial_number.clone(),
+            None => {
+                let mut serial_number = [0u8; SERIAL_NUMBER_DEFAULT_LEN];
+                rand::fill(&mut serial_number)?;
+                // Keep the serial number positive and of a fixed length.
+                serial_number[0] = (serial_number[0] & 0x7f) | 0x40;
+                serial_number.into()
+            }
+        };
+        if serial_number.len() > SERIAL_NUMBER_MAX_LEN || serial_number.iter().all(|b| *b == 0) {
+            return Err(Unspecified);
+        }
+        let bn = LcPtr::new(unsafe {
+            BN_bin2bn(serial_number.as_ptr(), serial_number.len(), null_mut())
+        })?;
+        Ok(LcPtr::new(unsafe { BN_to_ASN1_INTEGER(*bn, null_mut()) })?)
+    }
+
+    fn new_extensions(
+        &self,
+        x509: &LcPtr<X509>,
+    ) -> Result<Vec<LcPtr<X509_EXTENSION>>, Unspecified> {
+        let mut extensions = Vec::new();
+
+        let basic_constraints = LcPtr::new(unsafe { BASIC_CONSTRAINTS_new() })?;
+        let fields: *mut BASIC_CONSTRAINTS = *basic_constraints;
+        unsafe { (*fields).ca = c_int::from(self.ca) };
+        extensions.push(LcPtr::new(unsafe {
+            X509V3_EXT_i2d(NID_basic_constraints, 1, (*basic_constraints).cast())
+        })?);
+
+        let key_usage = LcPtr::new(unsafe { ASN1_BIT_STRING_new() })?;
+        let bits: &[c_int] = if self.ca {
+            &[
+                KEY_USAGE_DIGITAL_SIGNATURE,
+                KEY_USAGE_KEY_CERT_SIGN,
+                KEY_USAGE_CRL_SIGN,
+            ]
+        } else {
+            &[KEY_USAGE_DIGITAL_SIGNATURE]
+        };
+        for bit in bits {
+            if 1 != unsafe { ASN1_BIT_STRING_set_bit(*key_usage, *bit, 1) } {
+                return Err(Unspecified);
+            }
+        }
+        extensions.push(LcPtr::new(unsafe {
+            X509V3_EXT_i2d(NID_key_usage, 1, (*key_usage).cast())
+        })?);
+
+        if !self.key_purposes.is_empty() {
+            // The stack borrows the objects, which `OBJ_nid2obj` returns as static values.
+            let purposes = LcPtr::new(unsafe { OPENSSL_sk_new_null() })?;
+            for purpose in &self.key_purposes {
+                let object = unsafe { OBJ_nid2obj(purpose.nid()) };
+                if object.is_null() || 0 == unsafe { OPENSSL_sk_push(*purposes, object.cast()) } {
+                    return Err(Unspecified);
+                }
+            }
+            extensions.push(LcPtr::new(unsafe {
+                X509V3_EXT_i2d(NID_ext_key_usage, 0, (*purposes).cast())
+            })?);
+        }
+
+        // The SHA-1 hash of the public key, as in RFC 5280 Section 4.2.1.2.
+        let mut key_id = [0u8; digest::SHA1_OUTPUT_LEN];
+        let mut key_id_len = 0;
+        if 1 != unsafe {
+            X509_pubkey_digest(**x509, EVP_sha1(), key_id.as_mut_ptr(), &mut key_id_len)
+        } {
+            return Err(Unspecified);
+        }
+        let key_id = new_asn1_string(
+            unsafe { ASN1_OCTET_STRING_new() },
+            &key_id[..usize::try_from(key_id_len)?],
+        )?;
+        extensions.push(LcPtr::new(unsafe {
+            X509V3_EXT_i2d(NID_subject_key_identifier, 0, (*key_id).cast())
+        })?);
+
+        if !self.subject_alt_names.is_empty() {
+            extensions.push(new_subject_alt_name_extension(&self.subject_alt_names)?);
+        }
+        for (oid, critical, value) in &self.extensions {
+            extensions.push(new_extension(oid, *critical, value)?);
+        }
+        Ok(extensions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CertificateBuilder;
+    use crate::rand::SystemRandom;
+    use crate::rsa::{KeyPair, KeySize};
+    #[cfg(not(feature = "strict-fips"))]
+    use crate::signature::Ed25519KeyPair;
+    use crate::signature::{EcdsaKeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+    use crate::x509::{
+        Certificate, KeyPurpose, Name, SigningKey, SubjectAltName, VerificationOptions, Verifier,
+    };
+    use std::net::Ipv4Addr;
+    #[cfg(not(feature = "strict-fips"))]
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_self_signed() {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng).unwrap();
+        let ecdsa =
+            EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref()).unwrap();
+        #[cfg(not(feature = "strict-fips"))]
+        let ed25519 = Ed25519KeyPair::from_seed_unchecked(&[7u8; 32]).unwrap();
+        let rsa = KeyPair::generate(KeySize::Rsa2048).unwrap();
+
+        let subject = Name::builder().common_name("localhost").build().unwrap();
+        let builder = CertificateBuilder::new(subject.clone())
+            .subject_alt_name(SubjectAltName::DnsName("localhost".to_string()))
+            .subject_alt_name(SubjectAltName::IpAddress(Ipv4Addr::LOCALHOST.into()))
+            .key_purpose(KeyPurpose::ServerAuth)
+            .key_purpose(KeyPurpose::ClientAuth);
+
+        for key_pair in [
+            SigningKey::from(&ecdsa),
+            #[cfg(not(feature = "strict-fips"))]
+            SigningKey::from(&ed25519),
+            SigningKey::from(&rsa),
+        ] {
+            let certificate = builder.self_signed(key_pair).unwrap();
+            assert_eq!(3, certificate.version());
+            assert_eq!(&subject, certificate.subject());
+            assert_eq!(&subject, certificate.issuer());
+            assert!(!certificate.is_ca());
+            assert_eq!(16, certificate.serial_number().len());
+            assert_eq!(
+                &[
+                    SubjectAltName::DnsName("localhost".to_string()),
+                    SubjectAltName::IpAddress(Ipv4Addr::LOCALHOST.into()),
+                ],
+                certificate.subject_alt_names()
+            );
+            // Basic constraints, key usage, extended key usage, subject key identifier and
+            // subject alternative names.
+            assert_eq!(5, certificate.extensions().unwrap().len());
+            certificate
+                .verify_signature(&certificate.public_key().unwrap())
+                .unwrap();
+
+            let anchor = Certificate::from_der(certificate.as_ref()).unwrap();
+            let verifier = Verifier::new(&[anchor]).unwrap();
+            let options = VerificationOptions::new()
+                .dns_name("localhost")
+                .purpose(KeyPurpose::ClientAuth);
+            assert_eq!(
+                1,
+                verifier.verify(&certificate, &[], &options).unwrap().len()
+            );
+            let options = VerificationOptions::new().dns_name("example.com");
+            assert!(verifier.verify(&certificate, &[], &options).is_err());
+        }
+    }
+
+    #[cfg(not(feature = "strict-fips"))]
+    #[test]
+    fn test_self_signed_ca() {
+        let ed25519 = Ed25519KeyPair::from_seed_unchecked(&[7u8; 32]).unwrap();
+        let subject = Name::builder().common_name("Test CA").build().unwrap();
+        let not_before = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let not_after = not_before + Duration::from_secs(86400);
+        let certificate = CertificateBuilder::new(subject)
+            .serial_number(&[0x01, 0x02])
+            .validity(not_before, not_after)
+            .ca(true)
+            .extension("1.3.6.1.5.5.7.1.24", false, &[0x30, 0x03, 0x02, 0x01, 0x05])
+            .self_signed(&ed25519)
+            .unwrap();
+        assert!(certificate.is_ca());
+        assert_eq!(&[0x01, 0x02], certificate.serial_number());
+        assert_eq!(not_before, certificate.not_before());
+        assert_eq!(not_after, certificate.not_after());
+        assert_eq!(4, certificate.extensions().unwrap().len());
+    }
+
+    #[cfg(not(feature = "strict-fips"))]
+    #[test]
+    fn test_self_signed_errors() {
+        let ed25519 = Ed25519KeyPair::from_seed_unchecked(&[7u8; 32]).unwrap();
+        let subject = Name::builder().common_name("localhost").build().unwrap();
+        let builder = CertificateBuilder::new(subject);
+        assert!(builder
+            .clone()
+            .serial_number(&[0u8; 2])
+            .self_signed(&ed25519)
+            .is_err());
+        assert!(builder
+            .clone()
+            .serial_number(&[1u8; 21])
+            .self_signed(&ed25519)
+            .is_err());
+        assert!(builder
+            .clone()
+            .validity(UNIX_EPOCH + Duration::from_secs(1), UNIX_EPOCH)
+            .self_signed(&ed25519)
+            .is_err());
+        assert!(builder
+            .subject_alt_name(SubjectAltName::DnsName("exämple.com".to_string()))
+            .self_signed(&ed25519)
+            .is_err());
+    }
+}
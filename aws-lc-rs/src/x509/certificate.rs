@@ -0,0 +1,541 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use super::{
+    asn1_string_bytes, asn1_string_to_utf8, asn1_time_to_system_time, enforce_signature_policy,
+    oid_to_string, Name,
+};
+use crate::der::Reader;
+use crate::digest::{self, Digest};
+use crate::encoding::{AsDer, CertificateDer};
+use crate::error::{KeyRejected, Unspecified};
+use crate::ptr::LcPtr;
+use crate::signature::PublicKey;
+use aws_lc::{
+    d2i_X509, i2d_X509, i2d_X509_PUBKEY, NID_subject_alt_name, OPENSSL_sk_num, OPENSSL_sk_value,
+    X509_EXTENSION_get_critical, X509_EXTENSION_get_data, X509_EXTENSION_get_object,
+    X509_get0_notAfter, X509_get0_notBefore, X509_get0_serialNumber, X509_get0_subject_key_id,
+    X509_get_X509_PUBKEY, X509_get_ext, X509_get_ext_count, X509_get_ext_d2i,
+    X509_get_extension_flags, X509_get_issuer_name, X509_get_signature_nid, X509_get_subject_name,
+    X509_get_version, X509_verify, EXFLAG_CA, EXFLAG_INVALID, GENERAL_NAME, GENERAL_NAMES,
+    GEN_DIRNAME, GEN_DNS, GEN_EMAIL, GEN_IPADD, GEN_URI, OPENSSL_STACK, X509,
+};
+use core::fmt::{self, Debug, Formatter};
+use core::ptr::null_mut;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::os::raw::c_long;
+use std::time::SystemTime;
+
+/// A parsed X.509 certificate.
+pub struct Certificate {
+    x509: LcPtr<X509>,
+    der: Box<[u8]>,
+    subject: Name,
+    issuer: Name,
+    not_before: SystemTime,
+    not_after: SystemTime,
+    subject_alt_names: Vec<SubjectAltName>,
+    subject_public_key_info: Box<[u8]>,
+}
+
+// `X509` is reference counted and its lazily computed fields are protected by a lock.
+unsafe impl Send for Certificate {}
+unsafe impl Sync for Certificate {}
+
+/// An entry of a certificate's subject alternative name extension.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SubjectAltName {
+    /// A DNS name, possibly a wildcard such as `*.example.com`.
+    DnsName(String),
+    /// An IPv4 or IPv6 address.
+    IpAddress(IpAddr),
+    /// An email address.
+    Email(String),
+    /// A URI.
+    Uri(String),
+    /// A distinguished name.
+    DirectoryName(Name),
+}
+
+/// A method of deriving a key identifier, such as the value of the subject key identifier
+/// extension, from a public key.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyIdentifierMethod {
+    /// The SHA-1 hash of the public key, as described in [RFC 5280 Section 4.2.1.2]. Most
+    /// certificate authorities use this method.
+    ///
+    /// [RFC 5280 Section 4.2.1.2]: https://tools.ietf.org/html/rfc5280#section-4.2.1.2
+    Sha1,
+    /// The leftmost 160 bits of the SHA-256 hash of the public key, as described in
+    /// [RFC 7093 Section 2].
+    ///
+    /// [RFC 7093 Section 2]: https://tools.ietf.org/html/rfc7093#section-2
+    Sha256Truncated,
+}
+
+/// A certificate extension.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Extension<'a> {
+    oid: String,
+    critical: bool,
+    value: &'a [u8],
+}
+
+impl Extension<'_> {
+    /// The extension's type, as a dotted-decimal object identifier such as `2.5.29.17`.
+    #[must_use]
+    pub fn oid(&self) -> &str {
+        &self.oid
+    }
+
+    /// Whether the extension is marked critical.
+    #[must_use]
+    pub fn is_critical(&self) -> bool {
+        self.critical
+    }
+
+    /// The DER-encoded value of the extension.
+    #[must_use]
+    pub fn value(&self) -> &[u8] {
+        self.value
+    }
+}
+
+impl Certificate {
+    /// Parses a DER-encoded X.509 certificate.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `der` is not a well-formed certificate, including when one of
+    /// the extensions this type interprets, such as the subject alternative name, is malformed.
+    pub fn from_der(der: &[u8]) -> Result<Self, KeyRejected> {
+        let mut input = der.as_ptr();
+        let len = c_long::try_from(der.len())?;
+        let x509 = LcPtr::new(unsafe { d2i_X509(null_mut(), &mut input, len) })
+            .map_err(|()| KeyRejected::invalid_encoding())?;
+        if input != der.as_ptr().wrapping_add(der.len()) {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        Self::new(x509, der.into())
+    }
+
+    // Takes ownership of `x509`, a reference to an already parsed certificate.
+    pub(crate) fn from_x509(x509: LcPtr<X509>) -> Result<Self, KeyRejected> {
+        let mut der = null_mut::<u8>();
+        let len = usize::try_from(unsafe { i2d_X509(*x509, &mut der) })?;
+        let der = LcPtr::new(der)?;
+        let der = unsafe { der.as_slice(len) }.into();
+        Self::new(x509, der)
+    }
+
+    fn new(x509: LcPtr<X509>, der: Box<[u8]>) -> Result<Self, KeyRejected> {
+        if 0 != unsafe { X509_get_extension_flags(*x509) } & EXFLAG_INVALID as u32 {
+            return Err(KeyRejected::invalid_encoding());
+        }
+
+        unsafe {
+            Ok(Self {
+                subject: Name::from_x509_name(X509_get_subject_name(*x509))?,
+                issuer: Name::from_x509_name(X509_get_issuer_name(*x509))?,
+                not_before: asn1_time_to_system_time(X509_get0_notBefore(*x509))?,
+                not_after: asn1_time_to_system_time(X509_get0_notAfter(*x509))?,
+                subject_alt_names: parse_subject_alt_names(&x509)?,
+                subject_public_key_info: encode_public_key(&x509)?,
+                der,
+                x509,
+            })
+        }
+    }
+
+    pub(crate) fn x509(&self) -> &LcPtr<X509> {
+        &self.x509
+    }
+
+    /// The certificate's version: 1, 2 or 3.
+    #[must_use]
+    pub fn version(&self) -> u8 {
+        // The parser only accepts the version numbers 0, 1 and 2.
+        u8::try_from(unsafe { X509_get_version(*self.x509) } + 1).unwrap_or(u8::MAX)
+    }
+
+    /// The big-endian bytes of the certificate's serial number.
+    #[must_use]
+    pub fn serial_number(&self) -> &[u8] {
+        unsafe { asn1_string_bytes(X509_get0_serialNumber(*self.x509)) }
+    }
+
+    /// The certificate's subject.
+    #[must_use]
+    pub fn subject(&self) -> &Name {
+        &self.subject
+    }
+
+    /// The certificate's issuer.
+    #[must_use]
+    pub fn issuer(&self) -> &Name {
+        &self.issuer
+    }
+
+    /// The start of the certificate's validity period.
+    #[must_use]
+    pub fn not_before(&self) -> SystemTime {
+        self.not_before
+    }
+
+    /// The end of the certificate's validity period.
+    #[must_use]
+    pub fn not_after(&self) -> SystemTime {
+        self.not_after
+    }
+
+    /// Whether `time` is within the certificate's validity period, inclusive.
+    #[must_use]
+    pub fn is_valid_at(&self, time: SystemTime) -> bool {
+        self.not_before <= time && time <= self.not_after
+    }
+
+    /// The entries of the certificate's subject alternative name extension, in the order they
+    /// are encoded. Entries of other types than those of `SubjectAltName` are omitted.
+    #[must_use]
+    pub fn subject_alt_names(&self) -> &[SubjectAltName] {
+        &self.subject_alt_names
+    }
+
+    /// Whether the certificate's basic constraints extension identifies it as a CA.
+    #[must_use]
+    pub fn is_ca(&self) -> bool {
+        0 != unsafe { X509_get_extension_flags(*self.x509) } & EXFLAG_CA as u32
+    }
+
+    /// The certificate's extensions, in the order they are encoded.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if an extension cannot be read.
+    pub fn extensions(&self) -> Result<Vec<Extension<'_>>, Unspecified> {
+        let count = unsafe { X509_get_ext_count(*self.x509) };
+        let mut extensions = Vec::with_capacity(usize::try_from(count)?);
+        for i in 0..count {
+            let extension = unsafe { X509_get_ext(*self.x509, i) };
+            if extension.is_null() {
+                return Err(Unspecified);
+            }
+            unsafe {
+                extensions.push(Extension {
+                    oid: oid_to_string(X509_EXTENSION_get_object(extension))?,
+                    critical: 1 == X509_EXTENSION_get_critical(extension),
+                    value: asn1_string_bytes(X509_EXTENSION_get_data(extension)),
+                });
+            }
+        }
+        Ok(extensions)
+    }
+
+    /// The DER-encoded (X.509) `SubjectPublicKeyInfo` of the certificate.
+    #[must_use]
+    pub fn subject_public_key_info(&self) -> &[u8] {
+        &self.subject_public_key_info
+    }
+
+    /// The value of the certificate's subject key identifier extension, if it has one.
+    #[cfg_attr(not(feature = "cms"), allow(dead_code))]
+    pub(crate) fn subject_key_identifier(&self) -> Option<&[u8]> {
+        let key_id = unsafe { X509_get0_subject_key_id(*self.x509) };
+        if key_id.is_null() {
+            return None;
+        }
+        Some(unsafe { asn1_string_bytes(key_id) })
+    }
+
+    /// The key itself, i.e. the contents of the `subjectPublicKey` BIT STRING of the
+    /// certificate's `SubjectPublicKeyInfo`.
+    pub(crate) fn subject_public_key(&self) -> Result<&[u8], Unspecified> {
+        let mut spki = Reader::new(&self.subject_public_key_info).read_sequence()?;
+        spki.read_sequence()?;
+        spki.read_bit_string()
+    }
+
+    /// The fingerprint, or thumbprint, of the certificate: the `algorithm` hash of its DER
+    /// encoding. Tools usually display SHA-256 fingerprints.
+    #[must_use]
+    pub fn fingerprint(&self, algorithm: &'static digest::Algorithm) -> Digest {
+        digest::digest(algorithm, &self.der)
+    }
+
+    /// The `algorithm` hash of the certificate's `SubjectPublicKeyInfo`. Unlike
+    /// `Certificate::fingerprint`, it does not change when a certificate is renewed with the
+    /// same key; the base64 encoding of its SHA-256 form is an [RFC 7469] pin.
+    ///
+    /// [RFC 7469]: https://tools.ietf.org/html/rfc7469#section-2.4
+    #[must_use]
+    pub fn spki_fingerprint(&self, algorithm: &'static digest::Algorithm) -> Digest {
+        digest::digest(algorithm, &self.subject_public_key_info)
+    }
+
+    /// Derives a key identifier from the certificate's public key with `method`. It equals the
+    /// subject key identifier of certificates for the same key that were issued using
+    /// `method`, and the authority key identifier of certificates they issue.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the certificate's `SubjectPublicKeyInfo` is malformed.
+    pub fn key_identifier(&self, method: KeyIdentifierMethod) -> Result<Vec<u8>, Unspecified> {
+        let key = self.subject_public_key()?;
+        Ok(match method {
+            KeyIdentifierMethod::Sha1 => {
+                digest::digest_unchecked(&digest::SHA1_FOR_LEGACY_USE_ONLY, key)
+                    .as_ref()
+                    .to_vec()
+            }
+            KeyIdentifierMethod::Sha256Truncated => {
+                digest::digest_unchecked(&digest::SHA256, key).as_ref()[..20].to_vec()
+            }
+        })
+    }
+
+    /// The certificate's public key.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the key's type is not supported or the key is not acceptable.
+    pub fn public_key(&self) -> Result<PublicKey, KeyRejected> {
+        PublicKey::from_der(&self.subject_public_key_info)
+    }
+
+    /// Verifies that the certificate's signature was made by `issuer_public_key`.
+    ///
+    /// Only the signature is checked; the certificate's validity period and extensions are not.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the signature is not valid, including when the certificate's
+    /// signature algorithm is not supported or does not match the key's type.
+    pub fn verify_signature(&self, issuer_public_key: &PublicKey) -> Result<(), Unspecified> {
+        let evp_pkey = issuer_public_key.evp_pkey()?;
+        unsafe { enforce_signature_policy(X509_get_signature_nid(*self.x509), *evp_pkey) }?;
+        if 1 != unsafe { X509_verify(*self.x509, *evp_pkey) } {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+}
+
+impl AsRef<[u8]> for Certificate {
+    /// The DER encoding of the certificate.
+    fn as_ref(&self) -> &[u8] {
+        &self.der
+    }
+}
+
+impl AsDer<CertificateDer<'static>> for Certificate {
+    /// Serializes the certificate as DER.
+    fn as_der(&self) -> Result<CertificateDer<'static>, Unspecified> {
+        Ok(CertificateDer::new(self.der.to_vec()))
+    }
+}
+
+impl Debug for Certificate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Certificate")
+            .field("subject", &self.subject)
+            .field("issuer", &self.issuer)
+            .field("not_before", &self.not_before)
+            .field("not_after", &self.not_after)
+            .finish_non_exhaustive()
+    }
+}
+
+unsafe fn encode_public_key(x509: &LcPtr<X509>) -> Result<Box<[u8]>, Unspecified> {
+    let mut der = null_mut::<u8>();
+    let len = usize::try_from(i2d_X509_PUBKEY(X509_get_X509_PUBKEY(**x509), &mut der))?;
+    let der = LcPtr::new(der)?;
+    Ok(der.as_slice(len).into())
+}
+
+unsafe fn parse_subject_alt_names(x509: &LcPtr<X509>) -> Result<Vec<SubjectAltName>, Unspecified> {
+    let mut critical = 0;
+    let names = X509_get_ext_d2i(**x509, NID_subject_alt_name, &mut critical, null_mut());
+    let names = match LcPtr::new(names.cast::<GENERAL_NAMES>()) {
+        Ok(names) => names,
+        // -1 indicates the extension is absent; anything else that it could not be decoded.
+        Err(()) if critical == -1 => return Ok(Vec::new()),
+        Err(()) => return Err(Unspecified),
+    };
+
+    let stack = (*names).cast::<OPENSSL_STACK>();
+    let mut subject_alt_names = Vec::new();
+    for i in 0..OPENSSL_sk_num(stack) {
+        let name = &*OPENSSL_sk_value(stack, i).cast::<GENERAL_NAME>();
+        let subject_alt_name = match name.type_ {
+            GEN_DNS => SubjectAltName::DnsName(asn1_string_to_utf8(name.d.dNSName)?),
+            GEN_EMAIL => SubjectAltName::Email(asn1_string_to_utf8(name.d.rfc822Name)?),
+            GEN_URI => SubjectAltName::Uri(asn1_string_to_utf8(name.d.uniformResourceIdentifier)?),
+            GEN_IPADD => match asn1_string_bytes(name.d.iPAddress) {
+                &[a, b, c, d] => SubjectAltName::IpAddress(Ipv4Addr::new(a, b, c, d).into()),
+                bytes => {
+                    SubjectAltName::IpAddress(Ipv6Addr::from(<[u8; 16]>::try_from(bytes)?).into())
+                }
+            },
+            GEN_DIRNAME => {
+                SubjectAltName::DirectoryName(Name::from_x509_name(name.d.directoryName)?)
+            }
+            _ => continue,
+        };
+        subject_alt_names.push(subject_alt_name);
+    }
+    Ok(subject_alt_names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Certificate, KeyIdentifierMethod, SubjectAltName};
+    use crate::der::Reader;
+    use crate::digest;
+    use crate::encoding::{AsDer, CertificateDer};
+    use crate::hex;
+    use crate::signature;
+    use crate::signature::KeyPair;
+    use std::net::Ipv4Addr;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    const CA: &[u8] = include_bytes!("../../tests/data/x509_test_ca.der");
+    const LEAF: &[u8] = include_bytes!("../../tests/data/x509_test_leaf.der");
+    const LEAF_KEY: &[u8] = include_bytes!("../../tests/data/x509_test_leaf_key.p8");
+
+    #[test]
+    fn test_from_der() {
+        let certificate = Certificate::from_der(LEAF).unwrap();
+        assert_eq!(LEAF, certificate.as_ref());
+        assert_eq!(3, certificate.version());
+        assert_eq!(&[0x10, 0x00], certificate.serial_number());
+        assert_eq!("CN=localhost", certificate.subject().to_string());
+        assert_eq!(Some("localhost"), certificate.subject().common_name());
+        assert_eq!("CN=Test CA", certificate.issuer().to_string());
+        assert_eq!(1, certificate.issuer().entries().len());
+        assert_eq!("2.5.4.3", certificate.issuer().entries()[0].oid());
+        assert_eq!("Test CA", certificate.issuer().entries()[0].value());
+        assert!(!certificate.is_ca());
+
+        let not_before = UNIX_EPOCH + Duration::from_secs(1_792_144_705);
+        let not_after = UNIX_EPOCH + Duration::from_secs(4_945_744_705);
+        assert_eq!(not_before, certificate.not_before());
+        assert_eq!(not_after, certificate.not_after());
+        assert!(certificate.is_valid_at(not_before));
+        assert!(certificate.is_valid_at(not_after));
+        assert!(!certificate.is_valid_at(not_before - Duration::from_secs(1)));
+        assert!(!certificate.is_valid_at(not_after + Duration::from_secs(1)));
+
+        assert_eq!(
+            &[
+                SubjectAltName::DnsName("localhost".to_string()),
+                SubjectAltName::DnsName("*.example.com".to_string()),
+                SubjectAltName::IpAddress(Ipv4Addr::LOCALHOST.into()),
+            ],
+            certificate.subject_alt_names()
+        );
+
+        let extensions = certificate.extensions().unwrap();
+        let oids: Vec<&str> = extensions.iter().map(|e| e.oid()).collect();
+        assert_eq!(
+            vec![
+                "2.5.29.17",
+                "2.5.29.37",
+                "2.5.29.15",
+                "2.5.29.19",
+                "2.5.29.14",
+                "2.5.29.35"
+            ],
+            oids
+        );
+        assert!(!extensions[0].is_critical());
+        assert!(extensions[3].is_critical());
+        // BasicConstraints ::= SEQUENCE { cA BOOLEAN DEFAULT FALSE, ... }
+        assert_eq!(&[0x30, 0x00], extensions[3].value());
+
+        let key_pair = signature::EcdsaKeyPair::from_pkcs8(
+            &signature::ECDSA_P256_SHA256_ASN1_SIGNING,
+            LEAF_KEY,
+        )
+        .unwrap();
+        let public_key = certificate.public_key().unwrap();
+        assert_eq!(key_pair.public_key().as_ref(), public_key.as_ref());
+        assert_eq!(
+            AsDer::<crate::encoding::PublicKeyX509Der>::as_der(key_pair.public_key())
+                .unwrap()
+                .as_ref(),
+            certificate.subject_public_key_info()
+        );
+
+        let der: CertificateDer = certificate.as_der().unwrap();
+        assert_eq!(LEAF, der.as_ref());
+        assert!(format!("{certificate:?}").contains("CN=localhost"));
+    }
+
+    #[test]
+    fn test_fingerprints() {
+        let certificate = Certificate::from_der(LEAF).unwrap();
+        assert_eq!(
+            "88cb9fb5ce189430331a110551225313dc3f2f70420685d7a5fd2bb859729b29",
+            hex::encode(certificate.fingerprint(&digest::SHA256))
+        );
+        assert_eq!(
+            "517ad3036dc75a2f39a3391cb8618511d6e12dfb2dc41a7595cd65f37274fbee",
+            hex::encode(certificate.spki_fingerprint(&digest::SHA256))
+        );
+        assert_eq!(
+            "cd7bc5b21bbd82722e9c001534863a6f5cdc56be",
+            hex::encode(
+                certificate
+                    .key_identifier(KeyIdentifierMethod::Sha256Truncated)
+                    .unwrap()
+            )
+        );
+
+        // The test certificates' key identifiers were derived with SHA-1.
+        for der in [CA, LEAF] {
+            let certificate = Certificate::from_der(der).unwrap();
+            let extensions = certificate.extensions().unwrap();
+            let subject_key_identifier = extensions
+                .iter()
+                .find(|extension| extension.oid() == "2.5.29.14")
+                .unwrap();
+            assert_eq!(
+                Reader::new(subject_key_identifier.value())
+                    .read_octet_string()
+                    .unwrap(),
+                certificate
+                    .key_identifier(KeyIdentifierMethod::Sha1)
+                    .unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let ca = Certificate::from_der(CA).unwrap();
+        let leaf = Certificate::from_der(LEAF).unwrap();
+        assert!(ca.is_ca());
+        assert_eq!(ca.subject(), leaf.issuer());
+        assert!(ca.subject_alt_names().is_empty());
+
+        leaf.verify_signature(&ca.public_key().unwrap()).unwrap();
+        ca.verify_signature(&ca.public_key().unwrap()).unwrap();
+        assert!(leaf.verify_signature(&leaf.public_key().unwrap()).is_err());
+
+        let mut tampered = LEAF.to_vec();
+        let position = tampered.windows(9).position(|w| w == b"localhost").unwrap();
+        tampered[position] = b'L';
+        let tampered = Certificate::from_der(&tampered).unwrap();
+        assert_eq!(Some("Localhost"), tampered.subject().common_name());
+        assert!(tampered
+            .verify_signature(&ca.public_key().unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn test_from_der_errors() {
+        assert!(Certificate::from_der(&[]).is_err());
+        assert!(Certificate::from_der(&LEAF[..LEAF.len() - 1]).is_err());
+        let mut trailing = LEAF.to_vec();
+        trailing.push(0);
+        assert!(Certificate::from_der(&trailing).is_err());
+        assert!(Certificate::from_der(LEAF_KEY).is_err());
+    }
+}
@@ -0,0 +1,252 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use super::{
+    asn1_string_bytes, asn1_time_to_system_time, enforce_signature_policy, Certificate, Name,
+};
+use crate::error::{KeyRejected, Unspecified};
+use crate::ptr::LcPtr;
+use crate::signature::PublicKey;
+use aws_lc::{
+    d2i_X509_CRL, OPENSSL_sk_num, OPENSSL_sk_value, X509_CRL_get0_lastUpdate,
+    X509_CRL_get0_nextUpdate, X509_CRL_get_REVOKED, X509_CRL_get_issuer,
+    X509_CRL_get_signature_nid, X509_CRL_verify, X509_REVOKED_get0_revocationDate,
+    X509_REVOKED_get0_serialNumber, OPENSSL_STACK, X509_CRL, X509_REVOKED,
+};
+use core::fmt::{self, Debug, Formatter};
+use core::ptr::null_mut;
+use std::os::raw::c_long;
+use std::time::SystemTime;
+
+/// A parsed X.509 certificate revocation list (CRL).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use aws_lc_rs::x509::{Certificate, CertificateRevocationList};
+///
+/// let issuer = Certificate::from_der(&std::fs::read("ca.der")?)?;
+/// let certificate = Certificate::from_der(&std::fs::read("server.der")?)?;
+/// let crl = CertificateRevocationList::from_der(&std::fs::read("ca.crl")?)?;
+///
+/// assert_eq!(crl.issuer(), issuer.subject());
+/// crl.verify_signature(&issuer.public_key()?)?;
+/// if crl.is_revoked(&certificate) {
+///     return Err("certificate revoked".into());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct CertificateRevocationList {
+    crl: LcPtr<X509_CRL>,
+    der: Box<[u8]>,
+    issuer: Name,
+    this_update: SystemTime,
+    next_update: Option<SystemTime>,
+    revoked_certificates: Vec<RevokedCertificate>,
+}
+
+// `X509_CRL` is reference counted and its lazily computed fields are protected by a lock.
+unsafe impl Send for CertificateRevocationList {}
+unsafe impl Sync for CertificateRevocationList {}
+
+/// An entry of a certificate revocation list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RevokedCertificate {
+    serial_number: Box<[u8]>,
+    revocation_time: SystemTime,
+}
+
+impl RevokedCertificate {
+    /// The big-endian bytes of the revoked certificate's serial number.
+    #[must_use]
+    pub fn serial_number(&self) -> &[u8] {
+        &self.serial_number
+    }
+
+    /// The time at which the certificate was revoked.
+    #[must_use]
+    pub fn revocation_time(&self) -> SystemTime {
+        self.revocation_time
+    }
+}
+
+impl CertificateRevocationList {
+    /// Parses a DER-encoded X.509 certificate revocation list.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if `der` is not a well-formed certificate revocation list.
+    pub fn from_der(der: &[u8]) -> Result<Self, KeyRejected> {
+        let mut input = der.as_ptr();
+        let len = c_long::try_from(der.len())?;
+        let crl = LcPtr::new(unsafe { d2i_X509_CRL(null_mut(), &mut input, len) })
+            .map_err(|()| KeyRejected::invalid_encoding())?;
+        if input != der.as_ptr().wrapping_add(der.len()) {
+            return Err(KeyRejected::invalid_encoding());
+        }
+
+        unsafe {
+            let next_update = X509_CRL_get0_nextUpdate(*crl);
+            Ok(Self {
+                issuer: Name::from_x509_name(X509_CRL_get_issuer(*crl))?,
+                this_update: asn1_time_to_system_time(X509_CRL_get0_lastUpdate(*crl))?,
+                next_update: if next_update.is_null() {
+                    None
+                } else {
+                    Some(asn1_time_to_system_time(next_update)?)
+                },
+                revoked_certificates: parse_revoked_certificates(&crl)?,
+                der: der.into(),
+                crl,
+            })
+        }
+    }
+
+    pub(crate) fn crl(&self) -> &LcPtr<X509_CRL> {
+        &self.crl
+    }
+
+    /// The CRL's issuer.
+    #[must_use]
+    pub fn issuer(&self) -> &Name {
+        &self.issuer
+    }
+
+    /// The time at which the CRL was issued.
+    #[must_use]
+    pub fn this_update(&self) -> SystemTime {
+        self.this_update
+    }
+
+    /// The time by which the next CRL will be issued, if any.
+    #[must_use]
+    pub fn next_update(&self) -> Option<SystemTime> {
+        self.next_update
+    }
+
+    /// The revoked certificates, in the order they are encoded.
+    #[must_use]
+    pub fn revoked_certificates(&self) -> &[RevokedCertificate] {
+        &self.revoked_certificates
+    }
+
+    /// Whether the CRL lists `certificate`, i.e. the CRL's issuer is the certificate's issuer
+    /// and it lists the certificate's serial number.
+    ///
+    /// The CRL's signature and validity period are not checked; see
+    /// `CertificateRevocationList::verify_signature`, or `Verifier::add_crl` to check
+    /// revocation as part of verifying a chain.
+    #[must_use]
+    pub fn is_revoked(&self, certificate: &Certificate) -> bool {
+        &self.issuer == certificate.issuer()
+            && self
+                .revoked_certificates
+                .iter()
+                .any(|revoked| revoked.serial_number() == certificate.serial_number())
+    }
+
+    /// Verifies that the CRL's signature was made by `issuer_public_key`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the signature is not valid, including when the CRL's signature
+    /// algorithm is not supported or does not match the key's type.
+    pub fn verify_signature(&self, issuer_public_key: &PublicKey) -> Result<(), Unspecified> {
+        let evp_pkey = issuer_public_key.evp_pkey()?;
+        unsafe { enforce_signature_policy(X509_CRL_get_signature_nid(*self.crl), *evp_pkey) }?;
+        if 1 != unsafe { X509_CRL_verify(*self.crl, *evp_pkey) } {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+}
+
+impl AsRef<[u8]> for CertificateRevocationList {
+    /// The DER encoding of the CRL.
+    fn as_ref(&self) -> &[u8] {
+        &self.der
+    }
+}
+
+impl Debug for CertificateRevocationList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CertificateRevocationList")
+            .field("issuer", &self.issuer)
+            .field("this_update", &self.this_update)
+            .field("next_update", &self.next_update)
+            .finish_non_exhaustive()
+    }
+}
+
+unsafe fn parse_revoked_certificates(
+    crl: &LcPtr<X509_CRL>,
+) -> Result<Vec<RevokedCertificate>, Unspecified> {
+    let stack = X509_CRL_get_REVOKED(**crl).cast::<OPENSSL_STACK>();
+    // A CRL without revoked certificates may omit the list.
+    if stack.is_null() {
+        return Ok(Vec::new());
+    }
+    let mut revoked_certificates = Vec::new();
+    for i in 0..OPENSSL_sk_num(stack) {
+        let revoked = OPENSSL_sk_value(stack, i).cast::<X509_REVOKED>();
+        revoked_certificates.push(RevokedCertificate {
+            serial_number: asn1_string_bytes(X509_REVOKED_get0_serialNumber(revoked)).into(),
+            revocation_time: asn1_time_to_system_time(X509_REVOKED_get0_revocationDate(revoked))?,
+        });
+    }
+    Ok(revoked_certificates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CertificateRevocationList;
+    use crate::x509::Certificate;
+
+    const CA: &[u8] = include_bytes!("../../tests/data/x509_test_ca.der");
+    const LEAF: &[u8] = include_bytes!("../../tests/data/x509_test_leaf.der");
+    const CLIENT: &[u8] = include_bytes!("../../tests/data/x509_test_client.der");
+    const CRL: &[u8] = include_bytes!("../../tests/data/x509_test_crl.der");
+    const CRL_EMPTY: &[u8] = include_bytes!("../../tests/data/x509_test_crl_empty.der");
+
+    #[test]
+    fn test_from_der() {
+        let ca = Certificate::from_der(CA).unwrap();
+        let leaf = Certificate::from_der(LEAF).unwrap();
+        let crl = CertificateRevocationList::from_der(CRL).unwrap();
+        assert_eq!(CRL, crl.as_ref());
+        assert_eq!(ca.subject(), crl.issuer());
+        assert!(crl.this_update() < crl.next_update().unwrap());
+        assert_eq!(1, crl.revoked_certificates().len());
+        let revoked = &crl.revoked_certificates()[0];
+        assert_eq!(&[0x10, 0x00], revoked.serial_number());
+        assert_eq!(crl.this_update(), revoked.revocation_time());
+        assert!(crl.is_revoked(&leaf));
+        assert!(!crl.is_revoked(&ca));
+        // The client certificate is issued by another CA.
+        assert!(!crl.is_revoked(&Certificate::from_der(CLIENT).unwrap()));
+
+        let crl = CertificateRevocationList::from_der(CRL_EMPTY).unwrap();
+        assert!(crl.revoked_certificates().is_empty());
+        assert!(!crl.is_revoked(&leaf));
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let ca = Certificate::from_der(CA).unwrap();
+        let leaf = Certificate::from_der(LEAF).unwrap();
+        let crl = CertificateRevocationList::from_der(CRL).unwrap();
+        crl.verify_signature(&ca.public_key().unwrap()).unwrap();
+        assert!(crl.verify_signature(&leaf.public_key().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_from_der_errors() {
+        assert!(CertificateRevocationList::from_der(&[]).is_err());
+        assert!(CertificateRevocationList::from_der(&CRL[..CRL.len() - 1]).is_err());
+        let mut trailing = CRL.to_vec();
+        trailing.push(0);
+        assert!(CertificateRevocationList::from_der(&trailing).is_err());
+        assert!(CertificateRevocationList::from_der(LEAF).is_err());
+    }
+}
@@ -0,0 +1,247 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use super::{asn1_string_to_utf8, oid_to_string};
+use crate::error::Unspecified;
+use crate::ptr::{DetachableLcPtr, LcPtr};
+use aws_lc::{
+    d2i_X509_NAME, i2d_X509_NAME, BIO_mem_contents, BIO_new, BIO_s_mem, OBJ_txt2obj,
+    X509_NAME_ENTRY_get_data, X509_NAME_ENTRY_get_object, X509_NAME_add_entry_by_OBJ,
+    X509_NAME_entry_count, X509_NAME_get_entry, X509_NAME_new, X509_NAME_print_ex,
+    ASN1_STRFLGS_ESC_MSB, MBSTRING_UTF8, X509_NAME, XN_FLAG_RFC2253,
+};
+use core::fmt::{self, Debug, Display, Formatter};
+use core::ptr::null_mut;
+use std::ffi::CString;
+use std::os::raw::c_ulong;
+
+const OID_COMMON_NAME: &str = "2.5.4.3";
+const OID_COUNTRY: &str = "2.5.4.6";
+const OID_LOCALITY: &str = "2.5.4.7";
+const OID_STATE_OR_PROVINCE: &str = "2.5.4.8";
+const OID_ORGANIZATION: &str = "2.5.4.10";
+const OID_ORGANIZATIONAL_UNIT: &str = "2.5.4.11";
+
+/// A distinguished name, such as the subject or issuer of a certificate.
+///
+/// Names are compared by their DER encoding.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Name {
+    der: Box<[u8]>,
+    entries: Vec<NameEntry>,
+    rfc4514: String,
+}
+
+/// An attribute of a distinguished name, such as its common name.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NameEntry {
+    oid: String,
+    value: String,
+}
+
+impl NameEntry {
+    /// The attribute's type, as a dotted-decimal object identifier such as `2.5.4.3`.
+    #[must_use]
+    pub fn oid(&self) -> &str {
+        &self.oid
+    }
+
+    /// The attribute's value.
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+/// Builds a `Name` from its attributes.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use aws_lc_rs::x509::Name;
+///
+/// let name = Name::builder()
+///     .country("US")
+///     .organization("Example")
+///     .common_name("example.com")
+///     .build()?;
+/// assert_eq!("CN=example.com,O=Example,C=US", name.to_string());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct NameBuilder {
+    entries: Vec<NameEntry>,
+}
+
+impl NameBuilder {
+    /// Appends an attribute of type `oid`, a dotted-decimal object identifier, with `value`.
+    #[must_use]
+    pub fn entry(mut self, oid: &str, value: &str) -> Self {
+        self.entries.push(NameEntry {
+            oid: oid.to_string(),
+            value: value.to_string(),
+        });
+        self
+    }
+
+    /// Appends a common name (CN) attribute.
+    #[must_use]
+    pub fn common_name(self, value: &str) -> Self {
+        self.entry(OID_COMMON_NAME, value)
+    }
+
+    /// Appends a country (C) attribute, a two-letter ISO 3166 code.
+    #[must_use]
+    pub fn country(self, value: &str) -> Self {
+        self.entry(OID_COUNTRY, value)
+    }
+
+    /// Appends a locality (L) attribute.
+    #[must_use]
+    pub fn locality(self, value: &str) -> Self {
+        self.entry(OID_LOCALITY, value)
+    }
+
+    /// Appends a state or province (ST) attribute.
+    #[must_use]
+    pub fn state_or_province(self, value: &str) -> Self {
+        self.entry(OID_STATE_OR_PROVINCE, value)
+    }
+
+    /// Appends an organization (O) attribute.
+    #[must_use]
+    pub fn organization(self, value: &str) -> Self {
+        self.entry(OID_ORGANIZATION, value)
+    }
+
+    /// Appends an organizational unit (OU) attribute.
+    #[must_use]
+    pub fn organizational_unit(self, value: &str) -> Self {
+        self.entry(OID_ORGANIZATIONAL_UNIT, value)
+    }
+
+    /// Encodes the name, with its attributes in the order they were appended.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if an object identifier is malformed, or a value is not valid for
+    /// its attribute, e.g. a country code that is not two letters long.
+    pub fn build(&self) -> Result<Name, Unspecified> {
+        let name = LcPtr::new(unsafe { X509_NAME_new() })?;
+        for entry in &self.entries {
+            let oid = CString::new(entry.oid.as_str()).map_err(|_| Unspecified)?;
+            let object = LcPtr::new(unsafe { OBJ_txt2obj(oid.as_ptr(), 1) })?;
+            let len = entry.value.len().try_into()?;
+            if 1 != unsafe {
+                X509_NAME_add_entry_by_OBJ(
+                    *name,
+                    *object,
+                    MBSTRING_UTF8,
+                    entry.value.as_ptr(),
+                    len,
+                    -1,
+                    0,
+                )
+            } {
+                return Err(Unspecified);
+            }
+        }
+        unsafe { Name::from_x509_name(*name) }
+    }
+}
+
+impl Name {
+    /// Returns a builder for a new name.
+    #[must_use]
+    pub fn builder() -> NameBuilder {
+        NameBuilder::default()
+    }
+
+    pub(crate) fn to_x509_name(&self) -> Result<DetachableLcPtr<X509_NAME>, Unspecified> {
+        let mut input = self.der.as_ptr();
+        let len = self.der.len().try_into()?;
+        Ok(DetachableLcPtr::new(unsafe {
+            d2i_X509_NAME(null_mut(), &mut input, len)
+        })?)
+    }
+
+    pub(crate) unsafe fn from_x509_name(name: *mut X509_NAME) -> Result<Self, Unspecified> {
+        let mut der = null_mut::<u8>();
+        let len = usize::try_from(i2d_X509_NAME(name, &mut der))?;
+        let der = LcPtr::new(der)?;
+
+        let count = X509_NAME_entry_count(name);
+        let mut entries = Vec::with_capacity(usize::try_from(count)?);
+        for i in 0..count {
+            let entry = X509_NAME_get_entry(name, i);
+            entries.push(NameEntry {
+                oid: oid_to_string(X509_NAME_ENTRY_get_object(entry))?,
+                value: asn1_string_to_utf8(X509_NAME_ENTRY_get_data(entry))?,
+            });
+        }
+
+        let bio = LcPtr::new(BIO_new(BIO_s_mem()))?;
+        #[allow(clippy::cast_sign_loss)]
+        let flags = (XN_FLAG_RFC2253 & !ASN1_STRFLGS_ESC_MSB) as c_ulong;
+        if X509_NAME_print_ex(*bio, name, 0, flags) < 0 {
+            return Err(Unspecified);
+        }
+        let mut contents = core::ptr::null();
+        let mut contents_len = 0;
+        if 1 != BIO_mem_contents(*bio, &mut contents, &mut contents_len) {
+            return Err(Unspecified);
+        }
+        let rfc4514 = if contents_len == 0 {
+            String::new()
+        } else {
+            String::from_utf8(core::slice::from_raw_parts(contents, contents_len).to_vec())
+                .map_err(|_| Unspecified)?
+        };
+
+        Ok(Self {
+            der: der.as_slice(len).into(),
+            entries,
+            rfc4514,
+        })
+    }
+
+    /// The attributes of the name, in the order they are encoded.
+    #[must_use]
+    pub fn entries(&self) -> &[NameEntry] {
+        &self.entries
+    }
+
+    /// The value of the name's last common name (CN) attribute, if any.
+    #[must_use]
+    pub fn common_name(&self) -> Option<&str> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| entry.oid == OID_COMMON_NAME)
+            .map(NameEntry::value)
+    }
+}
+
+impl AsRef<[u8]> for Name {
+    /// The DER encoding of the name.
+    fn as_ref(&self) -> &[u8] {
+        &self.der
+    }
+}
+
+impl Display for Name {
+    /// Formats the name as described in [RFC 4514], e.g. `CN=localhost,O=Example`.
+    ///
+    /// [RFC 4514]: https://tools.ietf.org/html/rfc4514
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.rfc4514)
+    }
+}
+
+impl Debug for Name {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Name").field(&self.rfc4514).finish()
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use super::{new_extension, new_subject_alt_name_extension, Name, SigningKey, SubjectAltName};
+use crate::encoding::CertificateSigningRequestDer;
+use crate::error::Unspecified;
+use crate::ptr::LcPtr;
+use aws_lc::{
+    i2d_X509_REQ, OPENSSL_sk_new_null, OPENSSL_sk_push, X509_REQ_add_extensions, X509_REQ_new,
+    X509_REQ_set_pubkey, X509_REQ_set_subject_name, X509_REQ_set_version, X509_REQ_sign,
+    X509_REQ_VERSION_1,
+};
+use core::ptr::null_mut;
+
+/// Builds and signs a certificate signing request (CSR), as described in [RFC 2986].
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use aws_lc_rs::rand::SystemRandom;
+/// use aws_lc_rs::signature::{EcdsaKeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+/// use aws_lc_rs::x509::{CertificateRequestBuilder, Name, SubjectAltName};
+///
+/// let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &SystemRandom::new())?;
+/// let key_pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref())?;
+///
+/// let subject = Name::builder().common_name("example.com").build()?;
+/// let csr = CertificateRequestBuilder::new(subject)
+///     .subject_alt_name(SubjectAltName::DnsName("example.com".to_string()))
+///     .subject_alt_name(SubjectAltName::DnsName("www.example.com".to_string()))
+///     .sign(&key_pair)?;
+/// let pem = csr.to_pem()?;
+/// # assert!(pem.starts_with("-----BEGIN CERTIFICATE REQUEST-----"));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [RFC 2986]: https://tools.ietf.org/html/rfc2986
+#[derive(Clone, Debug)]
+pub struct CertificateRequestBuilder {
+    subject: Name,
+    subject_alt_names: Vec<SubjectAltName>,
+    extensions: Vec<(String, bool, Box<[u8]>)>,
+}
+
+impl CertificateRequestBuilder {
+    /// Returns a builder for a request for a certificate for `subject`.
+    #[must_use]
+    pub fn new(subject: Name) -> Self {
+        Self {
+            subject,
+            subject_alt_names: Vec::new(),
+            extensions: Vec::new(),
+        }
+    }
+
+    /// Requests `name` as a subject alternative name of the certificate.
+    #[must_use]
+    pub fn subject_alt_name(mut self, name: SubjectAltName) -> Self {
+        self.subject_alt_names.push(name);
+        self
+    }
+
+    /// Requests an extension of type `oid`, a dotted-decimal object identifier, with the
+    /// DER-encoded `value`.
+    #[must_use]
+    pub fn extension(mut self, oid: &str, critical: bool, value: &[u8]) -> Self {
+        self.extensions
+            .push((oid.to_string(), critical, value.into()));
+        self
+    }
+
+    /// Signs the request with `key_pair`, whose public key it contains.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if a subject alternative name or extension cannot be encoded, e.g.
+    /// a DNS name that is not ASCII, or if signing fails.
+    pub fn sign<'a>(
+        &self,
+        key_pair: impl Into<SigningKey<'a>>,
+    ) -> Result<CertificateSigningRequestDer<'static>, Unspecified> {
+        let key_pair = key_pair.into();
+        key_pair.enforce_policy()?;
+        let evp_pkey = key_pair.evp_pkey()?;
+
+        let request = LcPtr::new(unsafe { X509_REQ_new() })?;
+        let subject = self.subject.to_x509_name()?;
+        if 1 != unsafe { X509_REQ_set_version(*request, X509_REQ_VERSION_1.into()) }
+            || 1 != unsafe { X509_REQ_set_subject_name(*request, *subject) }
+            || 1 != unsafe { X509_REQ_set_pubkey(*request, *evp_pkey) }
+        {
+            return Err(Unspecified);
+        }
+
+        let mut extensions = Vec::new();
+        if !self.subject_alt_names.is_empty() {
+            extensions.push(new_subject_alt_name_extension(&self.subject_alt_names)?);
+        }
+        for (oid, critical, value) in &self.extensions {
+            extensions.push(new_extension(oid, *critical, value)?);
+        }
+        if !extensions.is_empty() {
+            // The stack borrows the extensions, which the request copies.
+            let stack = LcPtr::new(unsafe { OPENSSL_sk_new_null() })?;
+            for extension in &extensions {
+                if 0 == unsafe { OPENSSL_sk_push(*stack, (**extension).cast()) } {
+                    return Err(Unspecified);
+                }
+            }
+            if 1 != unsafe { X509_REQ_add_extensions(*request, (*stack).cast()) } {
+                return Err(Unspecified);
+            }
+        }
+
+        if 0 == unsafe { X509_REQ_sign(*request, *evp_pkey, key_pair.digest()) } {
+            return Err(Unspecified);
+        }
+
+        let mut der = null_mut::<u8>();
+        let len = usize::try_from(unsafe { i2d_X509_REQ(*request, &mut der) })?;
+        let der = LcPtr::new(der)?;
+        Ok(CertificateSigningRequestDer::new(
+            unsafe { der.as_slice(len) }.to_vec(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CertificateRequestBuilder;
+    use crate::ptr::LcPtr;
+    use crate::rand::SystemRandom;
+    use crate::rsa::{KeyPair, KeySize};
+    #[cfg(not(feature = "strict-fips"))]
+    use crate::signature::Ed25519KeyPair;
+    use crate::signature::{EcdsaKeyPair, ECDSA_P384_SHA384_ASN1_SIGNING};
+    use crate::x509::{Name, SigningKey, SubjectAltName};
+    use aws_lc::{
+        d2i_X509_REQ, OPENSSL_sk_pop, X509_REQ_get0_pubkey, X509_REQ_get_extensions,
+        X509_REQ_get_subject_name, X509_REQ_verify, OPENSSL_STACK, X509_EXTENSION,
+    };
+    use core::ptr::null_mut;
+    use std::net::Ipv6Addr;
+
+    // Checks the request's signature, and returns its subject and number of extensions.
+    fn parse(der: &[u8]) -> (Name, usize) {
+        let mut input = der.as_ptr();
+        let request = LcPtr::new(unsafe {
+            d2i_X509_REQ(null_mut(), &mut input, der.len().try_into().unwrap())
+        })
+        .unwrap();
+        assert_eq!(1, unsafe {
+            X509_REQ_verify(*request, X509_REQ_get0_pubkey(*request))
+        });
+        let subject = unsafe { Name::from_x509_name(X509_REQ_get_subject_name(*request)) }.unwrap();
+        let mut count = 0;
+        if let Ok(extensions) =
+            LcPtr::new(unsafe { X509_REQ_get_extensions(*request) }.cast::<OPENSSL_STACK>())
+        {
+            while let Ok(_extension) =
+                LcPtr::new(unsafe { OPENSSL_sk_pop(*extensions) }.cast::<X509_EXTENSION>())
+            {
+                count += 1;
+            }
+        }
+        (subject, count)
+    }
+
+    #[test]
+    fn test_sign() {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P384_SHA384_ASN1_SIGNING, &rng).unwrap();
+        let ecdsa =
+            EcdsaKeyPair::from_pkcs8(&ECDSA_P384_SHA384_ASN1_SIGNING, pkcs8.as_ref()).unwrap();
+        #[cfg(not(feature = "strict-fips"))]
+        let ed25519 = Ed25519KeyPair::from_seed_unchecked(&[7u8; 32]).unwrap();
+        let rsa = KeyPair::generate(KeySize::Rsa2048).unwrap();
+
+        let subject = Name::builder()
+            .country("US")
+            .organization("Example")
+            .common_name("example.com")
+            .build()
+            .unwrap();
+        let builder = CertificateRequestBuilder::new(subject.clone())
+            .subject_alt_name(SubjectAltName::DnsName("example.com".to_string()))
+            .subject_alt_name(SubjectAltName::IpAddress(Ipv6Addr::LOCALHOST.into()))
+            .subject_alt_name(SubjectAltName::DirectoryName(subject.clone()))
+            // id-pe-tlsfeature with status_request, i.e. OCSP must-staple.
+            .extension("1.3.6.1.5.5.7.1.24", false, &[0x30, 0x03, 0x02, 0x01, 0x05]);
+
+        for key_pair in [
+            SigningKey::from(&ecdsa),
+            #[cfg(not(feature = "strict-fips"))]
+            SigningKey::from(&ed25519),
+            SigningKey::from(&rsa),
+        ] {
+            let csr = builder.sign(key_pair).unwrap();
+            assert_eq!((subject.clone(), 2), parse(csr.as_ref()));
+        }
+
+        let csr = CertificateRequestBuilder::new(subject.clone())
+            .sign(&ecdsa)
+            .unwrap();
+        assert_eq!((subject, 0), parse(csr.as_ref()));
+    }
+
+    #[cfg(not(feature = "strict-fips"))]
+    #[test]
+    fn test_sign_errors() {
+        let ed25519 = Ed25519KeyPair::from_seed_unchecked(&[7u8; 32]).unwrap();
+        let subject = Name::builder().common_name("example.com").build().unwrap();
+        assert!(CertificateRequestBuilder::new(subject.clone())
+            .subject_alt_name(SubjectAltName::DnsName("exämple.com".to_string()))
+            .sign(&ed25519)
+            .is_err());
+        assert!(CertificateRequestBuilder::new(subject)
+            .extension("not an oid", false, &[0x05, 0x00])
+            .sign(&ed25519)
+            .is_err());
+        assert!(Name::builder().country("USA").build().is_err());
+        assert!(Name::builder().entry("2.5.4", "x").build().is_ok());
+    }
+}
@@ -0,0 +1,526 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use super::{
+    enforce_signature_policy, system_time_to_posix, Certificate, CertificateRevocationList,
+};
+use crate::error::Unspecified;
+use crate::ptr::LcPtr;
+use aws_lc::{
+    NID_client_auth, NID_email_protect, NID_server_auth, NID_time_stamp, OPENSSL_sk_new_null,
+    OPENSSL_sk_num, OPENSSL_sk_push, OPENSSL_sk_value, X509_STORE_CTX_get0_chain,
+    X509_STORE_CTX_get0_param, X509_STORE_CTX_get_error, X509_STORE_CTX_get_error_depth,
+    X509_STORE_CTX_init, X509_STORE_CTX_new, X509_STORE_add_cert, X509_STORE_add_crl,
+    X509_STORE_new, X509_STORE_set_flags, X509_VERIFY_PARAM_set1_host, X509_VERIFY_PARAM_set1_ip,
+    X509_VERIFY_PARAM_set_flags, X509_VERIFY_PARAM_set_purpose, X509_VERIFY_PARAM_set_time_posix,
+    X509_get0_pubkey, X509_get_signature_nid, X509_up_ref, X509_verify_cert,
+    X509_verify_cert_error_string, OPENSSL_STACK, X509, X509_PURPOSE_SMIME_SIGN,
+    X509_PURPOSE_SSL_CLIENT, X509_PURPOSE_SSL_SERVER, X509_PURPOSE_TIMESTAMP_SIGN, X509_STORE,
+    X509_STORE_CTX, X509_V_ERR_UNSPECIFIED, X509_V_FLAG_CRL_CHECK, X509_V_FLAG_CRL_CHECK_ALL,
+    X509_V_FLAG_PARTIAL_CHAIN,
+};
+use core::fmt::{self, Display, Formatter};
+use std::error::Error;
+use std::ffi::CStr;
+use std::net::IpAddr;
+use std::os::raw::{c_int, c_long, c_ulong};
+use std::time::SystemTime;
+
+/// Verifies certificates against a set of trust anchors.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use aws_lc_rs::signature::{EcdsaKeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+/// use aws_lc_rs::x509::{
+///     Certificate, CertificateBuilder, KeyPurpose, Name, SubjectAltName, VerificationOptions,
+///     Verifier,
+/// };
+///
+/// // A self-signed server certificate, trusted directly.
+/// let key_pair = EcdsaKeyPair::generate(&ECDSA_P256_SHA256_ASN1_SIGNING)?;
+/// let server = CertificateBuilder::new(Name::builder().common_name("localhost").build()?)
+///     .subject_alt_name(SubjectAltName::DnsName("localhost".to_string()))
+///     .key_purpose(KeyPurpose::ServerAuth)
+///     .self_signed(&key_pair)?;
+///
+/// let verifier = Verifier::new(&[Certificate::from_der(server.as_ref())?])?;
+/// let options = VerificationOptions::new()
+///     .dns_name("localhost")
+///     .purpose(KeyPurpose::ServerAuth);
+/// let chain = verifier.verify(&server, &[], &options)?;
+/// assert_eq!(1, chain.len());
+/// # Ok(())
+/// # }
+/// ```
+pub struct Verifier {
+    store: LcPtr<X509_STORE>,
+}
+
+// `X509_STORE` is only modified through `&mut self`, and is safe to share between threads.
+unsafe impl Send for Verifier {}
+unsafe impl Sync for Verifier {}
+
+/// The purpose a certificate is verified for, checked against its extended key usage
+/// extension and those of its issuers.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyPurpose {
+    /// TLS server authentication.
+    ServerAuth,
+    /// TLS client authentication.
+    ClientAuth,
+    /// S/MIME email signing.
+    EmailProtection,
+    /// Time stamping.
+    TimeStamping,
+}
+
+impl KeyPurpose {
+    fn id(self) -> c_int {
+        match self {
+            KeyPurpose::ServerAuth => X509_PURPOSE_SSL_SERVER,
+            KeyPurpose::ClientAuth => X509_PURPOSE_SSL_CLIENT,
+            KeyPurpose::EmailProtection => X509_PURPOSE_SMIME_SIGN,
+            KeyPurpose::TimeStamping => X509_PURPOSE_TIMESTAMP_SIGN,
+        }
+    }
+
+    // The NID of the purpose's extended key usage.
+    pub(super) fn nid(self) -> c_int {
+        match self {
+            KeyPurpose::ServerAuth => NID_server_auth,
+            KeyPurpose::ClientAuth => NID_client_auth,
+            KeyPurpose::EmailProtection => NID_email_protect,
+            KeyPurpose::TimeStamping => NID_time_stamp,
+        }
+    }
+}
+
+/// The certificates of a chain whose revocation status is checked, against the CRLs added
+/// with `Verifier::add_crl`.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RevocationCheck {
+    /// Only the certificate being verified.
+    Leaf,
+    /// Every certificate of the chain.
+    Chain,
+}
+
+impl RevocationCheck {
+    fn flags(self) -> c_int {
+        match self {
+            RevocationCheck::Leaf => X509_V_FLAG_CRL_CHECK,
+            RevocationCheck::Chain => X509_V_FLAG_CRL_CHECK | X509_V_FLAG_CRL_CHECK_ALL,
+        }
+    }
+}
+
+/// Additional checks for `Verifier::verify`.
+#[derive(Clone, Debug, Default)]
+pub struct VerificationOptions {
+    dns_name: Option<String>,
+    ip_address: Option<IpAddr>,
+    purpose: Option<KeyPurpose>,
+    time: Option<SystemTime>,
+    revocation: Option<RevocationCheck>,
+}
+
+impl VerificationOptions {
+    /// Options that only check the chain itself, at the current time.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires the certificate to be valid for the DNS name `dns_name`, matching its
+    /// subject alternative names, including wildcards such as `*.example.com`.
+    #[must_use]
+    pub fn dns_name(mut self, dns_name: &str) -> Self {
+        self.dns_name = Some(dns_name.to_string());
+        self
+    }
+
+    /// Requires the certificate to be valid for the IP address `ip_address`.
+    #[must_use]
+    pub fn ip_address(mut self, ip_address: IpAddr) -> Self {
+        self.ip_address = Some(ip_address);
+        self
+    }
+
+    /// Requires the chain to be valid for `purpose`.
+    #[must_use]
+    pub fn purpose(mut self, purpose: KeyPurpose) -> Self {
+        self.purpose = Some(purpose);
+        self
+    }
+
+    /// Checks the validity periods of the chain at `time` instead of the current time.
+    #[must_use]
+    pub fn time(mut self, time: SystemTime) -> Self {
+        self.time = Some(time);
+        self
+    }
+
+    /// Requires the certificates selected by `revocation` to not be revoked. Each of their
+    /// issuers must have a current CRL added with `Verifier::add_crl`, or verification fails.
+    #[must_use]
+    pub fn revocation(mut self, revocation: RevocationCheck) -> Self {
+        self.revocation = Some(revocation);
+        self
+    }
+}
+
+/// The reason a certificate could not be verified.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerificationError {
+    reason: &'static str,
+    depth: usize,
+}
+
+impl VerificationError {
+    fn new(code: c_int, depth: usize) -> Self {
+        let reason = unsafe { X509_verify_cert_error_string(c_long::from(code)) };
+        let reason = if reason.is_null() {
+            "unknown error"
+        } else {
+            // The returned strings are static.
+            unsafe { CStr::from_ptr(reason) }
+                .to_str()
+                .unwrap_or("unknown error")
+        };
+        Self { reason, depth }
+    }
+
+    fn unspecified() -> Self {
+        Self::new(X509_V_ERR_UNSPECIFIED, 0)
+    }
+
+    fn denied(depth: usize) -> Self {
+        Self {
+            reason: "denied by the installed policy",
+            depth,
+        }
+    }
+
+    /// A description of the failure, such as `certificate has expired`.
+    #[must_use]
+    pub fn reason(&self) -> &'static str {
+        self.reason
+    }
+
+    /// The position in the chain of the certificate that failed verification, where 0 is
+    /// the certificate being verified.
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+}
+
+impl Display for VerificationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (depth {})", self.reason, self.depth)
+    }
+}
+
+impl Error for VerificationError {}
+
+impl From<VerificationError> for Unspecified {
+    fn from(_: VerificationError) -> Self {
+        Unspecified
+    }
+}
+
+impl Verifier {
+    /// Creates a verifier that trusts `trust_anchors`. Trust anchors need not be self-signed,
+    /// so an intermediate CA can be trusted on its own.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the trust store cannot be created.
+    pub fn new(trust_anchors: &[Certificate]) -> Result<Self, Unspecified> {
+        let store = LcPtr::new(unsafe { X509_STORE_new() })?;
+        #[allow(clippy::cast_sign_loss)]
+        if 1 != unsafe { X509_STORE_set_flags(*store, X509_V_FLAG_PARTIAL_CHAIN as c_ulong) } {
+            return Err(Unspecified);
+        }
+        for trust_anchor in trust_anchors {
+            // The store takes its own reference to the certificate.
+            if 1 != unsafe { X509_STORE_add_cert(*store, **trust_anchor.x509()) } {
+                return Err(Unspecified);
+            }
+        }
+        Ok(Self { store })
+    }
+
+    /// Adds `crl` to the CRLs that `VerificationOptions::revocation` checks against. Its
+    /// signature and validity period are checked when it is used.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the CRL cannot be added.
+    pub fn add_crl(&mut self, crl: &CertificateRevocationList) -> Result<(), Unspecified> {
+        // The store takes its own reference to the CRL.
+        if 1 != unsafe { X509_STORE_add_crl(*self.store, **crl.crl()) } {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+
+    /// Builds a chain from `certificate` to one of the trust anchors, using `intermediates`
+    /// as needed, and verifies it with `options`.
+    ///
+    /// Returns the chain, starting with `certificate` and ending with the trust anchor.
+    ///
+    /// # Errors
+    /// `VerificationError` if no valid chain can be built, or the certificate does not satisfy
+    /// `options`.
+    pub fn verify(
+        &self,
+        certificate: &Certificate,
+        intermediates: &[Certificate],
+        options: &VerificationOptions,
+    ) -> Result<Vec<Certificate>, VerificationError> {
+        let untrusted = LcPtr::new(unsafe { OPENSSL_sk_new_null() })
+            .map_err(|()| VerificationError::unspecified())?;
+        for intermediate in intermediates {
+            // The stack doesn't own its elements; `intermediates` outlives it.
+            if 0 == unsafe { OPENSSL_sk_push(*untrusted, (**intermediate.x509()).cast()) } {
+                return Err(VerificationError::unspecified());
+            }
+        }
+
+        let ctx = LcPtr::new(unsafe { X509_STORE_CTX_new() })
+            .map_err(|()| VerificationError::unspecified())?;
+        if 1 != unsafe {
+            X509_STORE_CTX_init(*ctx, *self.store, **certificate.x509(), (*untrusted).cast())
+        } {
+            return Err(VerificationError::unspecified());
+        }
+        unsafe { configure(&ctx, options) }.map_err(|()| VerificationError::unspecified())?;
+
+        if 1 != unsafe { X509_verify_cert(*ctx) } {
+            let code = unsafe { X509_STORE_CTX_get_error(*ctx) };
+            let depth = usize::try_from(unsafe { X509_STORE_CTX_get_error_depth(*ctx) })
+                .unwrap_or_default();
+            return Err(VerificationError::new(code, depth));
+        }
+
+        let chain = unsafe { chain(&ctx) }.map_err(|_| VerificationError::unspecified())?;
+        // AWS-LC verifies the signatures in the chain without consulting the installed policy,
+        // so the certificates it built the chain from are checked against it afterwards.
+        for (depth, pair) in chain.windows(2).enumerate() {
+            let issuer_key = unsafe { X509_get0_pubkey(**pair[1].x509()) };
+            if issuer_key.is_null() {
+                return Err(VerificationError::unspecified());
+            }
+            let signature_nid = unsafe { X509_get_signature_nid(**pair[0].x509()) };
+            if unsafe { enforce_signature_policy(signature_nid, issuer_key) }.is_err() {
+                return Err(VerificationError::denied(depth));
+            }
+        }
+        Ok(chain)
+    }
+}
+
+unsafe fn configure(ctx: &LcPtr<X509_STORE_CTX>, options: &VerificationOptions) -> Result<(), ()> {
+    let param = X509_STORE_CTX_get0_param(**ctx);
+    if let Some(dns_name) = &options.dns_name {
+        if 1 != X509_VERIFY_PARAM_set1_host(param, dns_name.as_ptr().cast(), dns_name.len()) {
+            return Err(());
+        }
+    }
+    if let Some(ip_address) = options.ip_address {
+        let result = match ip_address {
+            IpAddr::V4(ip) => X509_VERIFY_PARAM_set1_ip(param, ip.octets().as_ptr(), 4),
+            IpAddr::V6(ip) => X509_VERIFY_PARAM_set1_ip(param, ip.octets().as_ptr(), 16),
+        };
+        if 1 != result {
+            return Err(());
+        }
+    }
+    if let Some(purpose) = options.purpose {
+        if 1 != X509_VERIFY_PARAM_set_purpose(param, purpose.id()) {
+            return Err(());
+        }
+    }
+    if let Some(time) = options.time {
+        let seconds = system_time_to_posix(time).map_err(|_| ())?;
+        X509_VERIFY_PARAM_set_time_posix(param, seconds);
+    }
+    if let Some(revocation) = options.revocation {
+        #[allow(clippy::cast_sign_loss)]
+        if 1 != X509_VERIFY_PARAM_set_flags(param, revocation.flags() as c_ulong) {
+            return Err(());
+        }
+    }
+    Ok(())
+}
+
+// The chain built by a successful verification.
+unsafe fn chain(ctx: &LcPtr<X509_STORE_CTX>) -> Result<Vec<Certificate>, Unspecified> {
+    let stack = X509_STORE_CTX_get0_chain(**ctx).cast::<OPENSSL_STACK>();
+    if stack.is_null() {
+        return Err(Unspecified);
+    }
+    let mut chain = Vec::new();
+    for i in 0..OPENSSL_sk_num(stack) {
+        let x509 = OPENSSL_sk_value(stack, i).cast::<X509>();
+        if 1 != X509_up_ref(x509) {
+            return Err(Unspecified);
+        }
+        chain.push(Certificate::from_x509(LcPtr::new(x509)?)?);
+    }
+    Ok(chain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KeyPurpose, RevocationCheck, VerificationOptions, Verifier};
+    use crate::x509::{Certificate, CertificateRevocationList};
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    const CA: &[u8] = include_bytes!("../../tests/data/x509_test_ca.der");
+    const LEAF: &[u8] = include_bytes!("../../tests/data/x509_test_leaf.der");
+    const INTERMEDIATE: &[u8] = include_bytes!("../../tests/data/x509_test_intermediate.der");
+    const CLIENT: &[u8] = include_bytes!("../../tests/data/x509_test_client.der");
+    const CRL: &[u8] = include_bytes!("../../tests/data/x509_test_crl.der");
+    const CRL_EMPTY: &[u8] = include_bytes!("../../tests/data/x509_test_crl_empty.der");
+
+    fn verifier() -> Verifier {
+        Verifier::new(&[Certificate::from_der(CA).unwrap()]).unwrap()
+    }
+
+    #[test]
+    fn test_verify() {
+        let leaf = Certificate::from_der(LEAF).unwrap();
+        let verifier = verifier();
+
+        let chain = verifier
+            .verify(&leaf, &[], &VerificationOptions::new())
+            .unwrap();
+        assert_eq!(2, chain.len());
+        assert_eq!(LEAF, chain[0].as_ref());
+        assert_eq!(CA, chain[1].as_ref());
+
+        for options in [
+            VerificationOptions::new().dns_name("localhost"),
+            VerificationOptions::new().dns_name("www.example.com"),
+            VerificationOptions::new().ip_address(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            VerificationOptions::new().purpose(KeyPurpose::ServerAuth),
+        ] {
+            verifier.verify(&leaf, &[], &options).unwrap();
+        }
+
+        for options in [
+            VerificationOptions::new().dns_name("example.com"),
+            VerificationOptions::new().dns_name("a.b.example.com"),
+            VerificationOptions::new().ip_address(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2))),
+            VerificationOptions::new().purpose(KeyPurpose::ClientAuth),
+        ] {
+            let error = verifier.verify(&leaf, &[], &options).unwrap_err();
+            assert_eq!(0, error.depth());
+        }
+    }
+
+    #[test]
+    fn test_verify_time() {
+        let leaf = Certificate::from_der(LEAF).unwrap();
+        let verifier = verifier();
+
+        let time = leaf.not_before() + Duration::from_secs(86400);
+        verifier
+            .verify(&leaf, &[], &VerificationOptions::new().time(time))
+            .unwrap();
+
+        let time = leaf.not_after() + Duration::from_secs(1);
+        let error = verifier
+            .verify(&leaf, &[], &VerificationOptions::new().time(time))
+            .unwrap_err();
+        assert_eq!("certificate has expired", error.reason());
+
+        let error = verifier
+            .verify(&leaf, &[], &VerificationOptions::new().time(UNIX_EPOCH))
+            .unwrap_err();
+        assert_eq!("certificate is not yet valid", error.reason());
+    }
+
+    #[test]
+    fn test_verify_intermediate() {
+        let intermediate = Certificate::from_der(INTERMEDIATE).unwrap();
+        let client = Certificate::from_der(CLIENT).unwrap();
+        let verifier = verifier();
+        let options = VerificationOptions::new()
+            .dns_name("client.example.com")
+            .purpose(KeyPurpose::ClientAuth);
+
+        let chain = verifier
+            .verify(&client, core::slice::from_ref(&intermediate), &options)
+            .unwrap();
+        assert_eq!(3, chain.len());
+        assert_eq!(CLIENT, chain[0].as_ref());
+        assert_eq!(INTERMEDIATE, chain[1].as_ref());
+        assert_eq!(CA, chain[2].as_ref());
+
+        let error = verifier.verify(&client, &[], &options).unwrap_err();
+        assert_eq!("unable to get local issuer certificate", error.reason());
+
+        // Trust anchors need not be self-signed.
+        let verifier = Verifier::new(&[intermediate]).unwrap();
+        let chain = verifier.verify(&client, &[], &options).unwrap();
+        assert_eq!(2, chain.len());
+
+        let verifier = Verifier::new(&[]).unwrap();
+        let error = verifier
+            .verify(&Certificate::from_der(CA).unwrap(), &[], &options)
+            .unwrap_err();
+        assert_eq!("self signed certificate", error.reason());
+    }
+
+    #[test]
+    fn test_verify_revocation() {
+        let leaf = Certificate::from_der(LEAF).unwrap();
+        let intermediate = Certificate::from_der(INTERMEDIATE).unwrap();
+        let client = Certificate::from_der(CLIENT).unwrap();
+        let leaf_options = VerificationOptions::new().revocation(RevocationCheck::Leaf);
+        let chain_options = VerificationOptions::new().revocation(RevocationCheck::Chain);
+
+        // Revocation is only checked when requested, and then requires a CRL.
+        let mut verifier = verifier();
+        verifier
+            .verify(&leaf, &[], &VerificationOptions::new())
+            .unwrap();
+        let error = verifier.verify(&leaf, &[], &leaf_options).unwrap_err();
+        assert_eq!("unable to get certificate CRL", error.reason());
+
+        verifier
+            .add_crl(&CertificateRevocationList::from_der(CRL_EMPTY).unwrap())
+            .unwrap();
+        verifier.verify(&leaf, &[], &leaf_options).unwrap();
+        verifier.verify(&leaf, &[], &chain_options).unwrap();
+
+        // The intermediate's certificate is covered by the CRL, but not the client's.
+        let intermediates = core::slice::from_ref(&intermediate);
+        verifier
+            .verify(&client, intermediates, &VerificationOptions::new())
+            .unwrap();
+        let error = verifier
+            .verify(&client, intermediates, &chain_options)
+            .unwrap_err();
+        assert_eq!("unable to get certificate CRL", error.reason());
+        assert_eq!(0, error.depth());
+
+        let mut verifier = self::verifier();
+        verifier
+            .add_crl(&CertificateRevocationList::from_der(CRL).unwrap())
+            .unwrap();
+        verifier
+            .verify(&leaf, &[], &VerificationOptions::new())
+            .unwrap();
+        let error = verifier.verify(&leaf, &[], &leaf_options).unwrap_err();
+        assert_eq!("certificate revoked", error.reason());
+        assert_eq!(0, error.depth());
+    }
+}
@@ -41,6 +41,7 @@ fn aead_aes_gcm_siv_128() {
     );
 }
 
+#[cfg(not(feature = "strict-fips"))]
 #[test]
 fn aead_chacha20_poly1305() {
     test_aead_all(
@@ -489,6 +490,7 @@ fn test_aead_nonce_sizes() {
 }
 
 #[allow(clippy::range_plus_one, clippy::cast_possible_truncation)]
+#[cfg(not(feature = "strict-fips"))]
 #[test]
 fn aead_chacha20_poly1305_openssh() {
     // TODO: test_aead_key_sizes(...);
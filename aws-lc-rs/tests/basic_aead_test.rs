@@ -5,9 +5,11 @@ extern crate core;
 
 use aws_lc_rs::{aead, error, test};
 
+#[cfg(not(feature = "strict-fips"))]
+use aead::CHACHA20_POLY1305;
 use aead::{
     Aad, Algorithm, BoundKey, Nonce, NonceSequence, OpeningKey, SealingKey, UnboundKey,
-    AES_128_GCM, AES_128_GCM_SIV, AES_256_GCM, AES_256_GCM_SIV, CHACHA20_POLY1305,
+    AES_128_GCM, AES_128_GCM_SIV, AES_256_GCM, AES_256_GCM_SIV,
 };
 use aws_lc_rs::test::from_hex;
 use error::Unspecified;
@@ -111,6 +113,7 @@ fn test_aes_128_gcm_siv() {
     test_aead_append_within(&config, &in_out).unwrap();
 }
 
+#[cfg(not(feature = "strict-fips"))]
 #[test]
 fn test_chacha20_poly1305() {
     let config = AeadConfig::new(
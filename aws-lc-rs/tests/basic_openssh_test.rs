@@ -1,6 +1,8 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
+#![cfg(not(feature = "strict-fips"))]
+
 use aws_lc_rs::test::from_hex;
 
 use aws_lc_rs::aead;
@@ -29,6 +29,7 @@ fn test_quic_aes_256_gcm() {
     assert_eq!(mask.as_ref(), key.new_mask(&sample).unwrap());
 }
 
+#[cfg(not(feature = "strict-fips"))]
 #[test]
 fn test_quic_chacha20() {
     let key_bytes =
@@ -280,6 +280,48 @@ test_i_u_f!(digest_test_i_u_f_sha224, digest::SHA224);
 test_i_u_f!(digest_test_i_u_f_sha256, digest::SHA256);
 test_i_u_f!(digest_test_i_u_f_sha384, digest::SHA384);
 test_i_u_f!(digest_test_i_u_f_sha512, digest::SHA512);
+#[cfg(feature = "legacy-digests")]
+test_i_u_f!(
+    digest_test_i_u_f_ripemd160,
+    digest::RIPEMD160_FOR_LEGACY_USE_ONLY
+);
+
+/// Test vectors from the RIPEMD-160 reference page,
+/// <https://homes.esat.kuleuven.be/~bosselae/ripemd160.html>.
+#[cfg(feature = "legacy-digests")]
+#[test]
+fn digest_ripemd160() {
+    let cases: [(&[u8], &str); 4] = [
+        (b"", "9c1185a5c5e9fc54612808977ee8f548b2258d31"),
+        (b"abc", "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"),
+        (
+            b"message digest",
+            "5d0689ef49d2fae572b881b123a85ffa21595f36",
+        ),
+        (
+            b"abcdefghijklmnopqrstuvwxyz",
+            "f71c27109c692c1b56bbdceb5b9d2865b3708dbc",
+        ),
+    ];
+    for (input, expected) in cases {
+        let expected = test::from_hex(expected).unwrap();
+        let actual = digest::digest(&digest::RIPEMD160_FOR_LEGACY_USE_ONLY, input);
+        assert_eq!(&expected, &actual.as_ref());
+    }
+
+    let mut ctx = digest::Context::new(&digest::RIPEMD160_FOR_LEGACY_USE_ONLY);
+    for _ in 0..1_000 {
+        ctx.update(&[b'a'; 1_000]);
+    }
+    assert_eq!(
+        test::from_hex("52783243c1697bdbe16d37f97f68f08325dc1528").unwrap(),
+        ctx.finish().as_ref()
+    );
+    assert_eq!(
+        "RIPEMD160",
+        &format!("{:?}", digest::RIPEMD160_FOR_LEGACY_USE_ONLY)
+    );
+}
 
 /// See <https://bugzilla.mozilla.org/show_bug.cgi?id=610162/>. This tests the
 /// calculation of 8GB of the byte 123.
@@ -433,3 +475,70 @@ fn digest_test_fmt() {
         &format!("{:?}", digest::digest(&digest::SHA512_256, b"hello, world"))
     );
 }
+
+#[test]
+fn digest_hash_reader() {
+    use std::io::{Cursor, ErrorKind, Read};
+
+    // Returns the data in small chunks, interleaved with interrupted reads.
+    struct ChunkedReader<'a> {
+        data: &'a [u8],
+        interrupt: bool,
+    }
+
+    impl Read for ChunkedReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(ErrorKind::Interrupted.into());
+            }
+            let len = core::cmp::min(core::cmp::min(buf.len(), 7), self.data.len());
+            buf[..len].copy_from_slice(&self.data[..len]);
+            self.data = &self.data[len..];
+            Ok(len)
+        }
+    }
+
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Err(ErrorKind::BrokenPipe.into())
+        }
+    }
+
+    let data: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+
+    for alg in [
+        &digest::SHA1_FOR_LEGACY_USE_ONLY,
+        &digest::SHA256,
+        &digest::SHA384,
+        &digest::SHA512,
+        &digest::SHA3_256,
+    ] {
+        let expected = digest::digest(alg, &data);
+
+        let actual = digest::hash_reader(alg, Cursor::new(&data)).unwrap();
+        assert_eq!(alg, actual.algorithm());
+        assert_eq!(expected.as_ref(), actual.as_ref());
+
+        let actual = digest::hash_reader(
+            alg,
+            ChunkedReader {
+                data: &data[..1_000],
+                interrupt: false,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            digest::digest(alg, &data[..1_000]).as_ref(),
+            actual.as_ref()
+        );
+
+        let actual = digest::hash_reader(alg, std::io::empty()).unwrap();
+        assert_eq!(digest::digest(alg, &[]).as_ref(), actual.as_ref());
+
+        let err = digest::hash_reader(alg, FailingReader).unwrap_err();
+        assert_eq!(ErrorKind::BrokenPipe, err.kind());
+    }
+}
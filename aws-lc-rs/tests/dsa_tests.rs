@@ -0,0 +1,52 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+#![cfg(feature = "legacy-dsa")]
+
+use aws_lc_rs::signature::{self, DsaVerificationAlgorithm, UnparsedPublicKey};
+use aws_lc_rs::{test, test_file};
+
+#[test]
+fn dsa_verify() {
+    test::run(test_file!("data/dsa_tests.txt"), |section, test_case| {
+        assert_eq!(section, "");
+
+        let digest_name = test_case.consume_string("Digest");
+        let alg: &'static DsaVerificationAlgorithm = match digest_name.as_ref() {
+            "SHA1" => &signature::DSA_SHA1_FOR_LEGACY_USE_ONLY,
+            "SHA224" => &signature::DSA_SHA224_FOR_LEGACY_USE_ONLY,
+            "SHA256" => &signature::DSA_SHA256_FOR_LEGACY_USE_ONLY,
+            _ => panic!("Unsupported digest: {digest_name}"),
+        };
+        let public_key = test_case.consume_bytes("Key");
+        let msg = test_case.consume_bytes("Msg");
+        let sig = test_case.consume_bytes("Sig");
+        let expected_result = test_case.consume_string("Result");
+
+        let actual_result = UnparsedPublicKey::new(alg, &public_key).verify(&msg, &sig);
+        assert_eq!(actual_result.is_ok(), expected_result == "P");
+
+        Ok(())
+    });
+}
+
+#[test]
+fn dsa_rejects_non_dsa_keys() {
+    // An Ed25519 SubjectPublicKeyInfo.
+    let public_key = test::from_dirty_hex(
+        "302a300506032b6570032100d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+    );
+    assert!(
+        UnparsedPublicKey::new(&signature::DSA_SHA256_FOR_LEGACY_USE_ONLY, &public_key)
+            .verify(b"", &[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01])
+            .is_err()
+    );
+}
+
+#[test]
+fn dsa_debug() {
+    assert_eq!(
+        "{ DSA_SHA256_FOR_LEGACY_USE_ONLY }",
+        format!("{:?}", signature::DSA_SHA256_FOR_LEGACY_USE_ONLY)
+    );
+}
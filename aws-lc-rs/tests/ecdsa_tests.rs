@@ -5,6 +5,7 @@
 
 use aws_lc_rs::encoding::{AsBigEndian, EcPrivateKeyRfc5915Der};
 use aws_lc_rs::{
+    digest,
     encoding::AsDer,
     rand::SystemRandom,
     signature::{self, EcdsaKeyPair, KeyPair, Signature, UnparsedPublicKey},
@@ -131,9 +132,13 @@ fn ecdsa_generate_pkcs8_test() {
         &signature::ECDSA_P521_SHA512_ASN1_SIGNING,
         &signature::ECDSA_P521_SHA3_512_FIXED_SIGNING,
         &signature::ECDSA_P521_SHA3_512_ASN1_SIGNING,
+        #[cfg(not(feature = "strict-fips"))]
         &signature::ECDSA_P256K1_SHA256_ASN1_SIGNING,
+        #[cfg(not(feature = "strict-fips"))]
         &signature::ECDSA_P256K1_SHA256_FIXED_SIGNING,
+        #[cfg(not(feature = "strict-fips"))]
         &signature::ECDSA_P256K1_SHA3_256_ASN1_SIGNING,
+        #[cfg(not(feature = "strict-fips"))]
         &signature::ECDSA_P256K1_SHA3_256_FIXED_SIGNING,
     ] {
         let pkcs8 = EcdsaKeyPair::generate_pkcs8(alg, &rng).unwrap();
@@ -181,8 +186,12 @@ fn test_signature_ecdsa_verify_asn1(data_file: test::File) {
             ("P-521", "SHA384") => &signature::ECDSA_P521_SHA384_ASN1,
             ("P-521", "SHA512") => &signature::ECDSA_P521_SHA512_ASN1,
             ("P-521", "SHA3-512") => &signature::ECDSA_P521_SHA3_512_ASN1,
+            #[cfg(not(feature = "strict-fips"))]
             ("secp256k1", "SHA256") => &signature::ECDSA_P256K1_SHA256_ASN1,
+            #[cfg(not(feature = "strict-fips"))]
             ("secp256k1", "SHA3-256") => &signature::ECDSA_P256K1_SHA3_256_ASN1,
+            #[cfg(feature = "strict-fips")]
+            ("secp256k1", _) => return Ok(()),
             _ => {
                 panic!("Unsupported curve+digest: {curve_name}+{digest_name}");
             }
@@ -227,8 +236,12 @@ fn test_signature_ecdsa_verify_fixed(data_file: test::File) {
             ("P-521", "SHA384") => &signature::ECDSA_P521_SHA384_FIXED,
             ("P-521", "SHA512") => &signature::ECDSA_P521_SHA512_FIXED,
             ("P-521", "SHA3-512") => &signature::ECDSA_P521_SHA3_512_FIXED,
+            #[cfg(not(feature = "strict-fips"))]
             ("secp256k1", "SHA256") => &signature::ECDSA_P256K1_SHA256_FIXED,
+            #[cfg(not(feature = "strict-fips"))]
             ("secp256k1", "SHA3-256") => &signature::ECDSA_P256K1_SHA3_256_FIXED,
+            #[cfg(feature = "strict-fips")]
+            ("secp256k1", _) => return Ok(()),
             _ => {
                 unrecoverable!("Unsupported curve+digest: {}+{}", curve_name, digest_name);
             }
@@ -337,14 +350,18 @@ fn test_signature_ecdsa_sign_fixed_sign_and_verify(data_file: test::File) {
                 &signature::ECDSA_P521_SHA3_512_FIXED_SIGNING,
                 &signature::ECDSA_P521_SHA3_512_FIXED,
             ),
+            #[cfg(not(feature = "strict-fips"))]
             ("secp256k1", "SHA256") => (
                 &signature::ECDSA_P256K1_SHA256_FIXED_SIGNING,
                 &signature::ECDSA_P256K1_SHA256_FIXED,
             ),
+            #[cfg(not(feature = "strict-fips"))]
             ("secp256k1", "SHA3-256") => (
                 &signature::ECDSA_P256K1_SHA3_256_FIXED_SIGNING,
                 &signature::ECDSA_P256K1_SHA3_256_FIXED,
             ),
+            #[cfg(feature = "strict-fips")]
+            ("secp256k1", _) => return Ok(()),
             _ => {
                 panic!("Unsupported curve+digest: {curve_name}+{digest_name}");
             }
@@ -427,14 +444,18 @@ fn test_signature_ecdsa_sign_asn1(data_file: test::File) {
                 &signature::ECDSA_P521_SHA3_512_ASN1_SIGNING,
                 &signature::ECDSA_P521_SHA3_512_ASN1,
             ),
+            #[cfg(not(feature = "strict-fips"))]
             ("secp256k1", "SHA256") => (
                 &signature::ECDSA_P256K1_SHA256_ASN1_SIGNING,
                 &signature::ECDSA_P256K1_SHA256_ASN1,
             ),
+            #[cfg(not(feature = "strict-fips"))]
             ("secp256k1", "SHA3-256") => (
                 &signature::ECDSA_P256K1_SHA3_256_ASN1_SIGNING,
                 &signature::ECDSA_P256K1_SHA3_256_ASN1,
             ),
+            #[cfg(feature = "strict-fips")]
+            ("secp256k1", _) => return Ok(()),
             _ => {
                 panic!("Unsupported curve+digest: {curve_name}+{digest_name}");
             }
@@ -518,3 +539,113 @@ fn test_private_key() {
         }
     }
 }
+
+#[test]
+fn test_signature_ecdsa_sign_verify_context() {
+    let msg = vec![0xa5u8; 100_000];
+    let rng = SystemRandom::new();
+
+    for (signing_alg, verification_alg) in [
+        (
+            &signature::ECDSA_P256_SHA256_ASN1_SIGNING,
+            &signature::ECDSA_P256_SHA256_ASN1,
+        ),
+        (
+            &signature::ECDSA_P384_SHA384_FIXED_SIGNING,
+            &signature::ECDSA_P384_SHA384_FIXED,
+        ),
+        (
+            &signature::ECDSA_P521_SHA512_FIXED_SIGNING,
+            &signature::ECDSA_P521_SHA512_FIXED,
+        ),
+    ] {
+        let key_pair_doc = EcdsaKeyPair::generate_pkcs8(signing_alg, &rng).unwrap();
+        let key_pair = EcdsaKeyPair::from_pkcs8(signing_alg, key_pair_doc.as_ref()).unwrap();
+        let public_key = key_pair.public_key().as_ref();
+
+        let mut ctx = key_pair.sign_context().unwrap();
+        for chunk in msg.chunks(4096) {
+            ctx.update(chunk).unwrap();
+        }
+        let sig = ctx.finish().unwrap();
+        UnparsedPublicKey::new(verification_alg, public_key)
+            .verify(&msg, sig.as_ref())
+            .unwrap();
+
+        let one_shot_sig = key_pair.sign(&rng, &msg).unwrap();
+        for sig in [&sig, &one_shot_sig] {
+            let mut ctx = verification_alg.verify_context(public_key).unwrap();
+            for chunk in msg.chunks(1000) {
+                ctx.update(chunk).unwrap();
+            }
+            ctx.finish(sig.as_ref()).unwrap();
+        }
+
+        let mut ctx = verification_alg.verify_context(public_key).unwrap();
+        ctx.update(&msg[1..]).unwrap();
+        assert!(ctx.finish(sig.as_ref()).is_err());
+    }
+}
+
+#[test]
+fn test_signature_ecdsa_sign_verify_digest() {
+    let msg = b"precomputed digest";
+    let rng = SystemRandom::new();
+
+    for (signing_alg, verification_alg, digest_alg) in [
+        (
+            &signature::ECDSA_P256_SHA256_FIXED_SIGNING,
+            &signature::ECDSA_P256_SHA256_FIXED,
+            &digest::SHA256,
+        ),
+        (
+            &signature::ECDSA_P384_SHA384_ASN1_SIGNING,
+            &signature::ECDSA_P384_SHA384_ASN1,
+            &digest::SHA384,
+        ),
+        (
+            &signature::ECDSA_P521_SHA3_512_FIXED_SIGNING,
+            &signature::ECDSA_P521_SHA3_512_FIXED,
+            &digest::SHA3_512,
+        ),
+    ] {
+        let key_pair_doc = EcdsaKeyPair::generate_pkcs8(signing_alg, &rng).unwrap();
+        let key_pair = EcdsaKeyPair::from_pkcs8(signing_alg, key_pair_doc.as_ref()).unwrap();
+        let public_key = key_pair.public_key().as_ref();
+        let msg_digest = digest::digest(digest_alg, msg);
+
+        let sig = key_pair.sign_digest(msg_digest.as_ref()).unwrap();
+        UnparsedPublicKey::new(verification_alg, public_key)
+            .verify(msg, sig.as_ref())
+            .unwrap();
+
+        let one_shot_sig = key_pair.sign(&rng, msg).unwrap();
+        verification_alg
+            .verify_digest(public_key, msg_digest.as_ref(), one_shot_sig.as_ref())
+            .unwrap();
+
+        let mut wrong_digest = msg_digest.as_ref().to_vec();
+        wrong_digest[0] ^= 1;
+        assert!(verification_alg
+            .verify_digest(public_key, &wrong_digest, sig.as_ref())
+            .is_err());
+        assert!(verification_alg
+            .verify_digest(public_key, &wrong_digest[1..], sig.as_ref())
+            .is_err());
+        assert!(key_pair.sign_digest(&wrong_digest[1..]).is_err());
+    }
+}
+
+#[test]
+fn ecdsa_pairwise_consistency_test() {
+    for alg in [
+        &signature::ECDSA_P256_SHA256_ASN1_SIGNING,
+        &signature::ECDSA_P384_SHA384_FIXED_SIGNING,
+        &signature::ECDSA_P521_SHA512_ASN1_SIGNING,
+        #[cfg(not(feature = "strict-fips"))]
+        &signature::ECDSA_P256K1_SHA256_FIXED_SIGNING,
+    ] {
+        let key_pair = EcdsaKeyPair::generate(alg).unwrap();
+        key_pair.pairwise_consistency_test().unwrap();
+    }
+}
@@ -3,6 +3,8 @@
 // Modifications copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
+#![cfg(not(feature = "strict-fips"))]
+
 use aws_lc_rs::encoding::{AsBigEndian, Curve25519SeedBin};
 use aws_lc_rs::rand::SystemRandom;
 use aws_lc_rs::{
@@ -38,6 +40,10 @@ fn test_signature_ed25519() {
             let actual_sig = key_pair.sign(&msg);
             assert_eq!(&expected_sig[..], actual_sig.as_ref());
 
+            let key_pair = Ed25519KeyPair::from_seed_unchecked(&seed).unwrap();
+            assert_eq!(&public_key[..], key_pair.public_key().as_ref());
+            assert_eq!(&expected_sig[..], key_pair.sign(&msg).as_ref());
+
             // Test Signature verification.
             test_signature_verification(&public_key, &msg, &expected_sig, Ok(()));
 
@@ -233,3 +239,81 @@ fn test_seed() {
 
     assert_eq!(key_pair_doc.as_ref(), key_pair_copy_doc.as_ref());
 }
+
+#[test]
+fn test_from_seed_unchecked() {
+    let rnd = SystemRandom::new();
+    let key_pair =
+        Ed25519KeyPair::from_pkcs8(Ed25519KeyPair::generate_pkcs8(&rnd).unwrap().as_ref()).unwrap();
+    let seed_buffer: Curve25519SeedBin = key_pair.seed().unwrap().as_be_bytes().unwrap();
+
+    let key_pair_copy = Ed25519KeyPair::from_seed_unchecked(seed_buffer.as_ref()).unwrap();
+    assert_eq!(
+        key_pair.public_key().as_ref(),
+        key_pair_copy.public_key().as_ref()
+    );
+    assert_eq!(
+        key_pair.to_pkcs8().unwrap().as_ref(),
+        key_pair_copy.to_pkcs8().unwrap().as_ref()
+    );
+
+    assert_eq!(
+        key_pair.public_key().as_ref(),
+        &key_pair_copy.public_key().as_bytes()[..]
+    );
+
+    assert!(Ed25519KeyPair::from_seed_unchecked(&seed_buffer.as_ref()[..31]).is_err());
+    assert!(Ed25519KeyPair::from_seed_unchecked(&[0u8; 33]).is_err());
+}
+
+#[test]
+fn test_ed25519_verification_policy() {
+    use signature::{
+        Ed25519VerificationAlgorithm, Ed25519VerificationPolicy, VerificationAlgorithm, ED25519,
+        ED25519_STRICT,
+    };
+
+    let rnd = SystemRandom::new();
+    let key_pair =
+        Ed25519KeyPair::from_pkcs8(Ed25519KeyPair::generate_pkcs8(&rnd).unwrap().as_ref()).unwrap();
+    let msg = b"consensus";
+    let sig = key_pair.sign(msg);
+    let public_key = key_pair.public_key().as_ref();
+
+    assert_eq!(Ed25519VerificationPolicy::Strict, ED25519_STRICT.policy());
+    let permissive =
+        Ed25519VerificationAlgorithm::with_policy(Ed25519VerificationPolicy::Permissive);
+    for alg in [
+        &ED25519 as &dyn VerificationAlgorithm,
+        &permissive,
+        &ED25519_STRICT,
+    ] {
+        assert!(alg.verify_sig(public_key, msg, sig.as_ref()).is_ok());
+        assert!(alg
+            .verify_sig(public_key, msg, &sig.as_ref()[..63])
+            .is_err());
+        assert!(alg
+            .verify_sig(&public_key[..31], msg, sig.as_ref())
+            .is_err());
+    }
+
+    // With the identity as the public key, R = [S]B verifies for any message. Here S = 1, so R
+    // is the base point.
+    let mut forged_sig =
+        test::from_hex("5866666666666666666666666666666666666666666666666666666666666666").unwrap();
+    forged_sig.push(1);
+    forged_sig.resize(64, 0);
+    let identity =
+        test::from_hex("0100000000000000000000000000000000000000000000000000000000000000").unwrap();
+    // p + 1, a non-canonical encoding of the identity.
+    let non_canonical_identity =
+        test::from_hex("eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f").unwrap();
+    assert!(ED25519.verify_sig(&identity, msg, &forged_sig).is_ok());
+    assert!(permissive.verify_sig(&identity, msg, &forged_sig).is_ok());
+    assert!(ED25519_STRICT
+        .verify_sig(&identity, msg, &forged_sig)
+        .is_err());
+    assert!(ED25519_STRICT
+        .verify_sig(&non_canonical_identity, msg, &forged_sig)
+        .is_err());
+}
@@ -0,0 +1,237 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use aws_lc_rs::policy::{self, CryptoPolicy};
+use aws_lc_rs::rand::SystemRandom;
+use aws_lc_rs::signature::{self, EcdsaKeyPair, KeyPair, RsaKeyPair};
+use aws_lc_rs::{digest, hmac};
+
+// The installed policy is process-wide, so this binary checks it from a single test.
+#[test]
+#[allow(clippy::too_many_lines)]
+fn installed_policy_denies_operations() {
+    const MESSAGE: &[u8] = b"message";
+    let rng = SystemRandom::new();
+
+    let rsa_key =
+        RsaKeyPair::from_pkcs8(include_bytes!("data/rsa_test_private_key_2048.p8")).unwrap();
+    let mut rsa_signature = vec![0u8; rsa_key.public_modulus_len()];
+    rsa_key
+        .sign(
+            &signature::RSA_PSS_SHA256,
+            &rng,
+            MESSAGE,
+            &mut rsa_signature,
+        )
+        .unwrap();
+    let rsa_public_key = signature::UnparsedPublicKey::new(
+        &signature::RSA_PSS_2048_8192_SHA256,
+        rsa_key.public_key().as_ref(),
+    );
+
+    let ec_pkcs8 =
+        EcdsaKeyPair::generate_pkcs8(&signature::ECDSA_P384_SHA384_ASN1_SIGNING, &rng).unwrap();
+    let ec_key = EcdsaKeyPair::from_pkcs8(
+        &signature::ECDSA_P384_SHA384_ASN1_SIGNING,
+        ec_pkcs8.as_ref(),
+    )
+    .unwrap();
+    let ec_signature = ec_key.sign(&rng, MESSAGE).unwrap();
+    let ec_public_key = signature::UnparsedPublicKey::new(
+        &signature::ECDSA_P384_SHA384_ASN1,
+        ec_key.public_key().as_ref(),
+    );
+
+    assert!(CryptoPolicy::installed().is_none());
+    rsa_public_key.verify(MESSAGE, &rsa_signature).unwrap();
+    ec_public_key
+        .verify(MESSAGE, ec_signature.as_ref())
+        .unwrap();
+
+    let restrictive = CryptoPolicy::new()
+        .deny_digest(&digest::SHA384)
+        .min_rsa_modulus_bits(3072);
+    restrictive.clone().install();
+    assert_eq!(Some(restrictive), CryptoPolicy::installed());
+    assert!(policy::check_digest(&digest::SHA384).is_err());
+    assert!(policy::check_rsa_modulus_bits(2048).is_err());
+    assert!(rsa_public_key.verify(MESSAGE, &rsa_signature).is_err());
+    assert!(ec_public_key
+        .verify(MESSAGE, ec_signature.as_ref())
+        .is_err());
+    assert!(signature::ECDSA_P384_SHA384_ASN1
+        .verify_context(ec_key.public_key().as_ref())
+        .is_err());
+    assert_eq!(
+        "DigestDenied",
+        policy::checked(|| ec_public_key.verify(MESSAGE, ec_signature.as_ref()))
+            .unwrap_err()
+            .to_string()
+    );
+    assert_eq!(
+        "RsaModulusTooSmall",
+        policy::checked(|| rsa_public_key.verify(MESSAGE, &rsa_signature))
+            .unwrap_err()
+            .to_string()
+    );
+
+    assert!(ec_key.sign(&rng, MESSAGE).is_err());
+    assert!(policy::checked(|| ec_key.sign(&rng, MESSAGE)).is_err());
+    assert!(policy::checked(|| {
+        rsa_key.sign(
+            &signature::RSA_PSS_SHA256,
+            &rng,
+            MESSAGE,
+            &mut rsa_signature,
+        )
+    })
+    .is_err());
+
+    assert!(std::panic::catch_unwind(|| digest::digest(&digest::SHA384, MESSAGE)).is_err());
+    assert_eq!(
+        std::io::ErrorKind::PermissionDenied,
+        digest::hash_reader(&digest::SHA384, MESSAGE)
+            .unwrap_err()
+            .kind()
+    );
+    assert!(digest::digest(&digest::SHA256, MESSAGE).as_ref().len() == 32);
+    assert!(policy::checked(|| hmac::Key::generate(hmac::HMAC_SHA384, &rng)).is_err());
+    assert!(std::panic::catch_unwind(|| hmac::Key::new(hmac::HMAC_SHA384, &[0; 48])).is_err());
+    hmac::Key::generate(hmac::HMAC_SHA256, &rng).unwrap();
+
+    #[cfg(feature = "x509")]
+    {
+        use aws_lc_rs::x509::{Certificate, VerificationOptions, Verifier};
+
+        let ca = Certificate::from_der(include_bytes!("data/x509_test_ca.der")).unwrap();
+        let ca_key = ca.public_key().unwrap();
+        ca.verify_signature(&ca_key).unwrap();
+        CryptoPolicy::new().deny_digest(&digest::SHA256).install();
+        assert_eq!(
+            "DigestDenied",
+            policy::checked(|| ca.verify_signature(&ca_key))
+                .unwrap_err()
+                .to_string()
+        );
+
+        // The client certificate is signed by the intermediate's 2048-bit RSA key.
+        let intermediate =
+            Certificate::from_der(include_bytes!("data/x509_test_intermediate.der")).unwrap();
+        let client = Certificate::from_der(include_bytes!("data/x509_test_client.der")).unwrap();
+        let verifier = Verifier::new(&[ca]).unwrap();
+        CryptoPolicy::new().min_rsa_modulus_bits(4096).install();
+        let error = verifier
+            .verify(
+                &client,
+                core::slice::from_ref(&intermediate),
+                &VerificationOptions::new(),
+            )
+            .unwrap_err();
+        assert_eq!("denied by the installed policy", error.reason());
+        assert_eq!(0, error.depth());
+    }
+
+    // Fallible operations that hash internally fail, rather than panic, when their digest is
+    // denied.
+    policy::uninstall();
+    let recovery_id = (0..4)
+        .find(|&id| {
+            signature::ECDSA_P384_SHA384_ASN1
+                .recover_public_key(MESSAGE, ec_signature.as_ref(), id)
+                .is_ok()
+        })
+        .unwrap();
+    #[cfg(feature = "legacy-pkcs1-encryption")]
+    let (pkcs1_key, pkcs1_ciphertext) = {
+        use aws_lc_rs::rsa::{
+            Pkcs1PrivateDecryptingKey, Pkcs1PublicEncryptingKey, PrivateDecryptingKey,
+        };
+
+        let private_key =
+            PrivateDecryptingKey::from_pkcs8(include_bytes!("data/rsa_test_private_key_2048.p8"))
+                .unwrap();
+        let public_key = Pkcs1PublicEncryptingKey::new(private_key.public_key()).unwrap();
+        let mut ciphertext = vec![0u8; public_key.ciphertext_size()];
+        public_key.encrypt(MESSAGE, &mut ciphertext).unwrap();
+        (
+            Pkcs1PrivateDecryptingKey::new(private_key).unwrap(),
+            ciphertext,
+        )
+    };
+    CryptoPolicy::new()
+        .deny_digest(&digest::SHA256)
+        .deny_digest(&digest::SHA384)
+        .install();
+    assert!(policy::checked(|| {
+        signature::ECDSA_P384_SHA384_ASN1.recover_public_key(
+            MESSAGE,
+            ec_signature.as_ref(),
+            recovery_id,
+        )
+    })
+    .is_err());
+    #[cfg(feature = "legacy-pkcs1-encryption")]
+    {
+        let mut plaintext = vec![0u8; pkcs1_key.min_output_size()];
+        assert!(policy::checked(|| pkcs1_key.decrypt(&pkcs1_ciphertext, &mut plaintext)).is_err());
+    }
+    #[cfg(feature = "unstable")]
+    {
+        use aws_lc_rs::rsa::PublicKeyComponents;
+        use aws_lc_rs::unstable::kdf::{
+            get_kbkdf_ctr_hmac_algorithm, get_two_step_kdf_algorithm, get_x963_kdf_algorithm,
+            kbkdf_ctr_hmac, two_step_kdf, x963_kdf, KbkdfCtrHmacAlgorithmId, TwoStepKdfAlgorithmId,
+            X963KdfAlgorithmId,
+        };
+        use aws_lc_rs::unstable::rsa::blind::RSABSSA_SHA384_PSS_DETERMINISTIC;
+
+        let mut output = [0u8; 32];
+        assert!(policy::checked(|| kbkdf_ctr_hmac(
+            get_kbkdf_ctr_hmac_algorithm(KbkdfCtrHmacAlgorithmId::Sha256).unwrap(),
+            &[1; 32],
+            b"info",
+            &mut output,
+        ))
+        .is_err());
+        assert!(policy::checked(|| two_step_kdf(
+            get_two_step_kdf_algorithm(TwoStepKdfAlgorithmId::HmacSha384).unwrap(),
+            &[1; 32],
+            &[2; 48],
+            b"info",
+            &mut output,
+        ))
+        .is_err());
+        assert!(policy::checked(|| x963_kdf(
+            get_x963_kdf_algorithm(X963KdfAlgorithmId::Sha256).unwrap(),
+            &[1; 32],
+            b"info",
+            &mut output,
+        ))
+        .is_err());
+
+        let rsa_public_key =
+            PublicKeyComponents::<Vec<u8>>::try_from(rsa_key.public_key()).unwrap();
+        assert!(policy::checked(|| {
+            RSABSSA_SHA384_PSS_DETERMINISTIC.blind(&rsa_public_key, MESSAGE)
+        })
+        .is_err());
+        assert!(policy::checked(|| {
+            RSABSSA_SHA384_PSS_DETERMINISTIC.verify(&rsa_public_key, MESSAGE, &rsa_signature)
+        })
+        .is_err());
+    }
+
+    policy::uninstall();
+    assert!(CryptoPolicy::installed().is_none());
+    assert!(policy::check_digest(&digest::SHA384).is_ok());
+    rsa_public_key.verify(MESSAGE, &rsa_signature).unwrap();
+    ec_public_key
+        .verify(MESSAGE, ec_signature.as_ref())
+        .unwrap();
+    // Failures that the policy did not cause are passed through.
+    assert!(
+        policy::checked(|| ec_public_key.verify(b"other message", ec_signature.as_ref()))
+            .unwrap()
+            .is_err()
+    );
+}
@@ -16,6 +16,7 @@ fn quic_aes_256() {
     test_quic(&quic::AES_256, test_file!("data/quic_aes_256_tests.txt"));
 }
 
+#[cfg(not(feature = "strict-fips"))]
 #[test]
 fn quic_chacha20() {
     test_quic(&quic::CHACHA20, test_file!("data/quic_chacha20_tests.txt"));
@@ -0,0 +1,45 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+#![cfg(not(feature = "strict-fips"))]
+
+use aws_lc_rs::{error, scrypt, test, test_file};
+
+/// Test vectors from RFC 7914.
+#[test]
+fn scrypt_tests() {
+    test::run(test_file!("data/scrypt_tests.txt"), |section, test_case| {
+        assert_eq!(section, "");
+        let n = test_case.consume_usize("N");
+        assert!(n.is_power_of_two());
+        #[allow(clippy::cast_possible_truncation)]
+        let log_n = n.trailing_zeros() as u8;
+        #[allow(clippy::cast_possible_truncation)]
+        let r = test_case.consume_usize("r") as u32;
+        #[allow(clippy::cast_possible_truncation)]
+        let p = test_case.consume_usize("p") as u32;
+        let params = scrypt::Params::new(log_n, r, p).unwrap();
+        let secret = test_case.consume_bytes("P");
+        let salt = test_case.consume_bytes("S");
+        let dk = test_case.consume_bytes("DK");
+        let verify_expected_result = test_case.consume_string("Verify");
+        let verify_expected_result = match verify_expected_result.as_str() {
+            "OK" => Ok(()),
+            "Err" => Err(error::Unspecified),
+            _ => panic!("Unsupported value of \"Verify\""),
+        };
+
+        {
+            let mut out = vec![0u8; dk.len()];
+            scrypt::derive(&params, &salt, &secret, &mut out).unwrap();
+            assert_eq!(dk == out, verify_expected_result.is_ok());
+        }
+
+        assert_eq!(
+            scrypt::verify(&params, &salt, &secret, &dk),
+            verify_expected_result
+        );
+
+        Ok(())
+    });
+}